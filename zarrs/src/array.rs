@@ -22,12 +22,14 @@
 //! The documentation for [`Array`] details how to interact with arrays.
 
 mod array_builder;
+mod array_builder_v2;
 mod array_bytes;
 mod array_errors;
 mod array_metadata_options;
 mod array_representation;
 mod bytes_representation;
 mod chunk_cache;
+mod chunk_codecs;
 pub mod chunk_grid;
 pub mod chunk_key_encoding;
 pub mod codec;
@@ -46,6 +48,7 @@ use std::sync::Arc;
 
 pub use self::{
     array_builder::ArrayBuilder,
+    array_builder_v2::ArrayBuilderV2,
     array_bytes::{
         copy_fill_value_into, update_array_bytes, ArrayBytes, ArrayBytesError, RawBytes,
         RawBytesOffsets,
@@ -81,7 +84,12 @@ pub type FillValueMetadata = crate::metadata::v3::array::fill_value::FillValueMe
 
 pub use chunk_cache::array_chunk_cache_ext_sync::ArrayChunkCacheExt;
 pub use chunk_cache::{
-    chunk_cache_lru::*, ChunkCache, ChunkCacheType, ChunkCacheTypeDecoded, ChunkCacheTypeEncoded,
+    cached_array::CachedArray,
+    chunk_cache_diagnostics::{ChunkCacheDiagnostics, ChunkCacheRecord, ChunkCacheSource},
+    chunk_cache_lru::*,
+    chunk_cache_policy::*,
+    chunk_cache_shared::{ChunkCacheDecodedSharedArray, ChunkCacheDecodedSharedSizeLimit},
+    ChunkCache, ChunkCacheType, ChunkCacheTypeDecoded, ChunkCacheTypeEncoded,
 };
 
 #[cfg(feature = "sharding")]
@@ -92,10 +100,18 @@ pub use array_sync_sharded_readable_ext::{ArrayShardedReadableExt, ArrayShardedR
 
 use crate::{
     array_subset::{ArraySubset, IncompatibleDimensionalityError},
-    config::MetadataConvertVersion,
-    metadata::{v2_to_v3::array_metadata_v2_to_v3, v3::AdditionalFields},
+    config::{global_config, MetadataConvertVersion, MustUnderstandLevel},
+    metadata::{
+        v2_to_v3::array_metadata_v2_to_v3,
+        v3::{AdditionalFields, UnsupportedAdditionalFieldError},
+    },
     node::{data_key, NodePath},
-    storage::StoreKey,
+    storage::{StorageError, StoreKey},
+};
+
+use chunk_codecs::{
+    chunk_codecs_from_field, chunk_codecs_to_field, ChunkCodecs, CHUNK_CODECS_FIELD,
+    MAX_CHUNK_CODECS,
 };
 
 /// An ND index to an element in an array.
@@ -145,6 +161,7 @@ pub fn chunk_shape_to_array_shape(chunk_shape: &[std::num::NonZeroU64]) -> Array
 ///  - [`shape`](Array::shape) / [`set_shape`](Array::set_shape)
 ///  - [`attributes`](Array::attributes) / [`attributes_mut`](Array::attributes_mut)
 ///  - [`dimension_names`](Array::dimension_names) / [`set_dimension_names`](Array::set_dimension_names)
+///  - [`chunk_codecs`](Array::chunk_codecs) / [`set_chunk_codecs`](Array::set_chunk_codecs)
 ///
 /// ### `zarrs` Metadata
 /// By default, the `zarrs` version and a link to its source code is written to the `_zarrs` attribute in array metadata when calling [`store_metadata`](Array::store_metadata).
@@ -294,6 +311,14 @@ pub fn chunk_shape_to_array_shape(chunk_shape: &[std::num::NonZeroU64]) -> Array
 ///  - [`ChunkCacheDecodedLruSizeLimitThreadLocal`]: a thread-local decoded chunk cache with a fixed size in bytes (per thread).
 ///  - [`ChunkCacheEncodedLruSizeLimitThreadLocal`]: a thread-local encoded chunk cache with a fixed size in bytes (per thread).
 ///
+/// Chunk caches with a configurable [`ChunkCacheEvictionPolicy`] (LRU, LFU, or MRU) are also provided:
+///  - [`ChunkCacheDecodedPolicyChunkLimit`]/[`ChunkCacheEncodedPolicyChunkLimit`]: a decoded/encoded chunk cache with a fixed chunk capacity.
+///  - [`ChunkCacheDecodedPolicySizeLimit`]/[`ChunkCacheEncodedPolicySizeLimit`]: a decoded/encoded chunk cache with a fixed size in bytes.
+///
+/// A [`ChunkCacheDecodedSharedSizeLimit`] is a decoded chunk cache with a fixed size in bytes that is shared by multiple arrays, such as the arrays opened from a [`Hierarchy`](crate::node::Hierarchy).
+///
+/// An MRU (most recently used) policy avoids thrashing in single-pass sequential scans over more chunks than fit in the cache.
+///
 /// `zarrs` consumers can create custom caches by implementing the [`ChunkCache`] trait.
 ///
 /// Chunk caching is likely to be effective for remote stores where redundant retrieval are costly.
@@ -357,6 +382,8 @@ pub struct Array<TStorage: ?Sized> {
     fill_value: FillValue,
     /// Specifies a list of codecs to be used for encoding and decoding chunks.
     codecs: Arc<CodecChain>,
+    /// Codecs overriding `codecs` for specific chunks.
+    chunk_codecs: ChunkCodecs,
     // /// Optional user defined attributes.
     // attributes: serde_json::Map<String, serde_json::Value>,
     /// An optional list of storage transformers.
@@ -367,6 +394,8 @@ pub struct Array<TStorage: ?Sized> {
     // additional_fields: AdditionalFields,
     /// Metadata used to create the array
     metadata: ArrayMetadata,
+    /// Retrieve chunks sharing a store key once in an array subset retrieve.
+    deduplicate_chunk_reads: bool,
 }
 
 impl<TStorage: ?Sized> Array<TStorage> {
@@ -377,6 +406,8 @@ impl<TStorage: ?Sized> Array<TStorage> {
     /// Returns [`ArrayCreateError`] if:
     ///  - any metadata is invalid or,
     ///  - a plugin (e.g. data type/chunk grid/chunk key encoding/codec/storage transformer) is invalid.
+    ///
+    /// See the [must understand level](crate::config::Config#must-understand-level) configuration for the handling of additional fields.
     pub fn new_with_metadata(
         storage: Arc<TStorage>,
         path: &str,
@@ -384,6 +415,20 @@ impl<TStorage: ?Sized> Array<TStorage> {
     ) -> Result<Self, ArrayCreateError> {
         let path = NodePath::new(path)?;
 
+        let additional_fields = match &metadata {
+            ArrayMetadata::V3(metadata) => &metadata.additional_fields,
+            ArrayMetadata::V2(metadata) => &metadata.additional_fields,
+        };
+        if let Err(err) = validate_additional_fields(additional_fields) {
+            match global_config().must_understand_level() {
+                MustUnderstandLevel::Strict => return Err(err.into()),
+                MustUnderstandLevel::LenientRead => {}
+                MustUnderstandLevel::Permissive => {
+                    log::warn!("array at {path} has an ignored {err}");
+                }
+            }
+        }
+
         // Convert V2 metadata to V3 if it is a compatible subset
         let metadata_v3 = match &metadata {
             ArrayMetadata::V3(v3) => Ok(v3.clone()),
@@ -408,6 +453,14 @@ impl<TStorage: ?Sized> Array<TStorage> {
             CodecChain::from_metadata(&metadata_v3.codecs)
                 .map_err(ArrayCreateError::CodecsCreateError)?,
         );
+        let chunk_codecs = match &metadata {
+            ArrayMetadata::V3(metadata) => metadata.additional_fields.get(CHUNK_CODECS_FIELD),
+            ArrayMetadata::V2(_) => None,
+        }
+        .map(chunk_codecs_from_field)
+        .transpose()
+        .map_err(ArrayCreateError::CodecsCreateError)?
+        .unwrap_or_default();
         let storage_transformers =
             StorageTransformerChain::from_metadata(&metadata_v3.storage_transformers, &path)
                 .map_err(ArrayCreateError::StorageTransformersCreateError)?;
@@ -431,14 +484,45 @@ impl<TStorage: ?Sized> Array<TStorage> {
             chunk_key_encoding,
             fill_value,
             codecs,
+            chunk_codecs,
             // attributes: metadata_v3.attributes,
             // additional_fields: metadata_v3.additional_fields,
             storage_transformers,
             dimension_names: metadata_v3.dimension_names,
             metadata,
+            deduplicate_chunk_reads: false,
         })
     }
 
+    /// Replace the metadata and the fields derived from it with those of `reloaded`.
+    ///
+    /// The storage, path, and settings of this handle (e.g. [`deduplicate_chunk_reads`](Array::deduplicate_chunk_reads)) are preserved.
+    fn replace_metadata(&mut self, reloaded: Self) {
+        let Self {
+            storage: _,
+            path: _,
+            data_type,
+            chunk_grid,
+            chunk_key_encoding,
+            fill_value,
+            codecs,
+            chunk_codecs,
+            storage_transformers,
+            dimension_names,
+            metadata,
+            deduplicate_chunk_reads: _,
+        } = reloaded;
+        self.data_type = data_type;
+        self.chunk_grid = chunk_grid;
+        self.chunk_key_encoding = chunk_key_encoding;
+        self.fill_value = fill_value;
+        self.codecs = codecs;
+        self.chunk_codecs = chunk_codecs;
+        self.storage_transformers = storage_transformers;
+        self.dimension_names = dimension_names;
+        self.metadata = metadata;
+    }
+
     /// Get the node path.
     #[must_use]
     pub const fn path(&self) -> &NodePath {
@@ -485,11 +569,76 @@ impl<TStorage: ?Sized> Array<TStorage> {
     }
 
     /// Get the codecs.
+    ///
+    /// These are the default codecs of the array, see [`chunk_codecs`](Array::chunk_codecs) for the codecs of a specific chunk.
     #[must_use]
     pub fn codecs(&self) -> &CodecChain {
         &self.codecs
     }
 
+    /// Get the codecs of the chunk at `chunk_indices`.
+    ///
+    /// These are the codecs set with [`set_chunk_codecs`](Array::set_chunk_codecs), otherwise the default [`codecs`](Array::codecs) of the array.
+    #[must_use]
+    pub fn chunk_codecs(&self, chunk_indices: &[u64]) -> &Arc<CodecChain> {
+        self.chunk_codecs.get(chunk_indices).unwrap_or(&self.codecs)
+    }
+
+    /// Override the codecs of the chunk at `chunk_indices`, or remove the override if `codecs` is [`None`].
+    ///
+    /// This permits chunks with very different characteristics to be encoded differently (e.g. dense chunks compressed with `blosc`, while sparse chunks use the default codecs).
+    /// Chunks that only contain the fill value are not stored regardless of their codecs (see [`CodecOptions::store_empty_chunks`](crate::array::codec::CodecOptions::store_empty_chunks)).
+    ///
+    /// The override is recorded in the `zarrs_chunk_codecs` additional field of the array metadata, which is annotated with `"must_understand": true` since other Zarr implementations cannot read the overridden chunks.
+    /// To keep the array metadata small, at most 1024 chunks can have an override.
+    /// Reads and writes of the chunk with this array use the override.
+    ///
+    /// Note that this does not re-encode a stored chunk: set the override *before* storing the chunk, and store the metadata with [`store_metadata`](Array::store_metadata) to persist the override.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - `chunk_indices` are invalid or outside of the chunk grid,
+    ///  - the array has Zarr V2 metadata, which cannot record an override, or
+    ///  - the override would exceed the limit on the number of overridden chunks.
+    pub fn set_chunk_codecs(
+        &mut self,
+        chunk_indices: &[u64],
+        codecs: Option<Arc<CodecChain>>,
+    ) -> Result<&mut Self, ArrayError> {
+        if !self
+            .chunk_grid()
+            .chunk_indices_inbounds(chunk_indices, self.shape())
+        {
+            return Err(ArrayError::InvalidChunkGridIndicesError(
+                chunk_indices.to_vec(),
+            ));
+        }
+        let ArrayMetadata::V3(metadata) = &mut self.metadata else {
+            return Err(ArrayError::UnsupportedMethod(
+                "per-chunk codecs are not supported for Zarr V2 arrays".to_string(),
+            ));
+        };
+        if let Some(codecs) = codecs {
+            if self.chunk_codecs.len() >= MAX_CHUNK_CODECS
+                && !self.chunk_codecs.contains_key(chunk_indices)
+            {
+                return Err(ArrayError::ChunkCodecsLimitExceeded(MAX_CHUNK_CODECS));
+            }
+            self.chunk_codecs.insert(chunk_indices.to_vec(), codecs);
+        } else {
+            self.chunk_codecs.remove(chunk_indices);
+        }
+        if self.chunk_codecs.is_empty() {
+            metadata.additional_fields.remove(CHUNK_CODECS_FIELD);
+        } else {
+            metadata.additional_fields.insert(
+                CHUNK_CODECS_FIELD.to_string(),
+                chunk_codecs_to_field(&self.chunk_codecs),
+            );
+        }
+        Ok(self)
+    }
+
     /// Get the chunk grid.
     #[must_use]
     pub const fn chunk_grid(&self) -> &ChunkGrid {
@@ -523,6 +672,23 @@ impl<TStorage: ?Sized> Array<TStorage> {
         self
     }
 
+    /// Return the deduplicate chunk reads setting.
+    #[must_use]
+    pub const fn deduplicate_chunk_reads(&self) -> bool {
+        self.deduplicate_chunk_reads
+    }
+
+    /// Set whether or not an array subset retrieve fetches and decodes chunks that share a store key only once.
+    ///
+    /// Distinct chunk grid indices only share a store key with an unusual chunk key encoding (e.g. one that broadcasts a single stored chunk over the chunk grid).
+    /// If `true`, the store keys of the chunks intersecting the subset are computed up front, and chunks sharing a key and shape are retrieved once and copied into each region of the output.
+    /// This applies to [`retrieve_array_subset`](Array::retrieve_array_subset) and [`retrieve_chunks`](Array::retrieve_chunks) and their variants, including the async variants.
+    /// This setting defaults to `false` and is not stored in the array metadata.
+    pub fn set_deduplicate_chunk_reads(&mut self, deduplicate_chunk_reads: bool) -> &mut Self {
+        self.deduplicate_chunk_reads = deduplicate_chunk_reads;
+        self
+    }
+
     /// Get the attributes.
     #[must_use]
     pub const fn attributes(&self) -> &serde_json::Map<String, serde_json::Value> {
@@ -565,6 +731,12 @@ impl<TStorage: ?Sized> Array<TStorage> {
         &self.metadata
     }
 
+    /// Return the Zarr format version of the array metadata (`2` or `3`).
+    #[must_use]
+    pub fn zarr_format(&self) -> u8 {
+        self.metadata.zarr_format()
+    }
+
     /// Return a new [`ArrayMetadata`] with [`ArrayMetadataOptions`] applied.
     ///
     /// This method is used internally by [`Array::store_metadata`] and [`Array::store_metadata_opt`].
@@ -632,11 +804,51 @@ impl<TStorage: ?Sized> Array<TStorage> {
     }
 
     /// Return the [`StoreKey`] of the chunk at `chunk_indices`.
+    ///
+    /// The key is the array path joined with the chunk key encoding of `chunk_indices` (e.g. `array/c/0/1` with the default encoding, or `array/0.1` with the `v2` encoding).
+    /// This is the exact key used to store and retrieve the chunk.
+    ///
+    /// The key does not depend on the existence of the chunk, and `chunk_indices` are not validated against the chunk grid.
+    /// Out-of-bounds indices, or indices with a different dimensionality to the array, still produce a key.
     #[must_use]
     pub fn chunk_key(&self, chunk_indices: &[u64]) -> StoreKey {
         data_key(self.path(), &self.chunk_key_encoding.encode(chunk_indices))
     }
 
+    /// Return the chunk indices of `key` if it is the key of a chunk of the array.
+    ///
+    /// This is the inverse of [`chunk_key`](Array::chunk_key), for mapping listed store keys to chunk indices.
+    /// The indices are parsed from the digits in the key and verified by encoding them with the chunk key encoding, so the prefix and separator must match.
+    /// Keys that are not chunk keys of the array (e.g. metadata keys, keys of other nodes, or chunk keys with a different encoding or dimensionality) return [`None`].
+    ///
+    /// The indices are not validated against the chunk grid.
+    #[must_use]
+    pub fn chunk_indices_from_key(&self, key: &StoreKey) -> Option<ArrayIndices> {
+        if self.dimensionality() == 0 {
+            // The key of the chunk of a zero-dimensional array has no indices (e.g. `c`, or `0` with the `v2` encoding)
+            return (&self.chunk_key(&[]) == key).then_some(vec![]);
+        }
+        let chunk_indices = self
+            .path()
+            .relative_key(key)?
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|index| !index.is_empty())
+            .map(str::parse::<u64>)
+            .collect::<Result<ArrayIndices, _>>()
+            .ok()?;
+        (chunk_indices.len() == self.dimensionality() && &self.chunk_key(&chunk_indices) == key)
+            .then_some(chunk_indices)
+    }
+
+    /// Return the chunk indices of `key` if it is the key of a chunk within the chunk grid.
+    fn chunk_grid_indices_from_key(&self, key: &StoreKey) -> Option<ArrayIndices> {
+        let chunk_indices = self.chunk_indices_from_key(key)?;
+        let chunk_grid_shape = self.chunk_grid_shape()?;
+        std::iter::zip(&chunk_indices, &chunk_grid_shape)
+            .all(|(i, n)| i < n)
+            .then_some(chunk_indices)
+    }
+
     /// Return the origin of the chunk at `chunk_indices`.
     ///
     /// # Errors
@@ -660,9 +872,102 @@ impl<TStorage: ?Sized> Array<TStorage> {
     }
 
     /// Return an array subset that spans the entire array.
+    ///
+    /// The subset starts at the [origin](chunk_grid::ChunkGridTraits::origin) of the chunk grid, which is usually all zeros.
     #[must_use]
     pub fn subset_all(&self) -> ArraySubset {
-        ArraySubset::new_with_shape(self.shape().to_vec())
+        unsafe {
+            ArraySubset::new_with_start_shape_unchecked(
+                self.chunk_grid().origin(),
+                self.shape().to_vec(),
+            )
+        }
+    }
+
+    /// Return the fill value of chunks created by a partial write with `options`.
+    ///
+    /// This is the [new chunk fill value](codec::CodecOptions::new_chunk_fill_value) if set, otherwise the array fill value.
+    fn new_chunk_fill_value<'a>(
+        &'a self,
+        options: &'a codec::CodecOptions,
+    ) -> Result<&'a FillValue, ArrayError> {
+        match options.new_chunk_fill_value() {
+            Some(fill_value) => {
+                if self
+                    .data_type()
+                    .fixed_size()
+                    .is_some_and(|size| size != fill_value.size())
+                {
+                    Err(data_type::IncompatibleFillValueError::new(
+                        self.data_type().name(),
+                        fill_value.clone(),
+                    )
+                    .into())
+                } else {
+                    Ok(fill_value)
+                }
+            }
+            None => Ok(self.fill_value()),
+        }
+    }
+
+    /// Validate `T` and the decoded size of the array against the [retrieve size limit](crate::config::Config#retrieve-size-limit) for `retrieve_all`.
+    fn retrieve_all_validate<T: Element>(
+        &self,
+        options: &codec::CodecOptions,
+    ) -> Result<(), ArrayError> {
+        T::validate_data_type(self.data_type())?;
+        if let Some(data_type_size) = self.data_type().fixed_size() {
+            let limit = options.retrieve_size_limit();
+            let size = self
+                .shape()
+                .iter()
+                .try_fold(data_type_size as u64, |size, &dim| size.checked_mul(dim))
+                .unwrap_or(u64::MAX);
+            if size > limit {
+                return Err(ArrayError::RetrieveSizeLimitExceeded(size, limit));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate the number of elements of a read with `shape` against the [retrieve element limit](crate::config::Config#retrieve-element-limit).
+    fn retrieve_element_limit_validate(
+        shape: &[u64],
+        options: &codec::CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let limit = options.retrieve_element_limit();
+        let num_elements = shape
+            .iter()
+            .try_fold(1u64, |num_elements, &dim| num_elements.checked_mul(dim))
+            .unwrap_or(u64::MAX);
+        if num_elements > limit {
+            Err(ArrayError::RetrieveElementLimitExceeded(
+                num_elements,
+                limit,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validate the number of elements passed to `store_all` and return the codec options to store them with.
+    ///
+    /// Every chunk is written in full by `store_all`, so chunks are not retrieved before they are updated.
+    fn store_all_options(
+        &self,
+        num_elements: usize,
+        options: &codec::CodecOptions,
+    ) -> Result<codec::CodecOptions, ArrayError> {
+        let expected_num_elements = self.shape().iter().product::<u64>();
+        if num_elements as u64 == expected_num_elements {
+            Ok(options.into_builder().assume_new_chunks(true).build())
+        } else {
+            Err(ArrayError::InvalidDataShape(
+                vec![num_elements],
+                vec![usize::try_from(expected_num_elements).unwrap_or(usize::MAX)],
+            ))
+        }
     }
 
     /// Return the shape of the chunk at `chunk_indices`.
@@ -680,6 +985,88 @@ impl<TStorage: ?Sized> Array<TStorage> {
             .collect())
     }
 
+    /// Group the chunks in `chunks` that share a store key and chunk shape.
+    ///
+    /// Groups are in order of their first chunk in `chunks.indices()`.
+    pub(crate) fn chunk_groups_by_key(
+        &self,
+        chunks: &ArraySubset,
+    ) -> Result<Vec<Vec<ArrayIndices>>, ArrayError> {
+        let mut groups: Vec<Vec<ArrayIndices>> = Vec::new();
+        let mut group_index: std::collections::HashMap<(StoreKey, ArrayShape), usize> =
+            std::collections::HashMap::new();
+        for chunk_indices in &chunks.indices() {
+            let chunk_shape = self.chunk_subset(&chunk_indices)?.shape().to_vec();
+            let key = (self.chunk_key(&chunk_indices), chunk_shape);
+            if let Some(index) = group_index.get(&key) {
+                groups[*index].push(chunk_indices);
+            } else {
+                group_index.insert(key, groups.len());
+                groups.push(vec![chunk_indices]);
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Extract the intersections of `array_subset` with each chunk in `chunk_group` from `chunk_bytes`, the decoded bytes shared by the group.
+    ///
+    /// Each intersection is paired with its subset relative to `array_subset`.
+    pub(crate) fn chunk_group_bytes_and_subsets(
+        &self,
+        array_subset: &ArraySubset,
+        chunk_group: &[ArrayIndices],
+        chunk_bytes: &ArrayBytes<'_>,
+    ) -> Result<Vec<(ArrayBytes<'static>, ArraySubset)>, ArrayError> {
+        chunk_group
+            .iter()
+            .map(|chunk_indices| {
+                let chunk_subset = self.chunk_subset(chunk_indices)?;
+                let chunk_subset_overlap = chunk_subset.overlap(array_subset)?;
+                let bytes = chunk_bytes
+                    .extract_array_subset(
+                        &chunk_subset_overlap.relative_to(chunk_subset.start())?,
+                        chunk_subset.shape(),
+                        self.data_type(),
+                    )?
+                    .into_owned();
+                Ok((
+                    bytes,
+                    chunk_subset_overlap.relative_to(array_subset.start())?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Merge the bytes of `array_subset` from the outputs of [`chunk_group_bytes_and_subsets`](Array::chunk_group_bytes_and_subsets).
+    pub(crate) fn merge_chunk_group_bytes(
+        &self,
+        array_subset: &ArraySubset,
+        chunk_bytes_and_subsets: Vec<(ArrayBytes<'static>, ArraySubset)>,
+    ) -> Result<ArrayBytes<'static>, ArrayError> {
+        match self.data_type().size() {
+            DataTypeSize::Variable => Ok(array_bytes::merge_chunks_vlen(
+                chunk_bytes_and_subsets,
+                array_subset.shape(),
+            )?),
+            DataTypeSize::Fixed(data_type_size) => {
+                let mut output = vec![0; array_subset.num_elements_usize() * data_type_size];
+                {
+                    let output = unsafe_cell_slice::UnsafeCellSlice::new(&mut output);
+                    for (bytes, subset) in chunk_bytes_and_subsets {
+                        array_bytes::update_bytes_flen(
+                            &output,
+                            array_subset.shape(),
+                            &bytes.into_fixed()?,
+                            &subset,
+                            data_type_size,
+                        );
+                    }
+                }
+                Ok(ArrayBytes::from(output))
+            }
+        }
+    }
+
     /// Return the array subset of the chunk at `chunk_indices`.
     ///
     /// # Errors
@@ -697,7 +1084,7 @@ impl<TStorage: ?Sized> Array<TStorage> {
     /// Returns [`ArrayError::InvalidChunkGridIndicesError`] if the `chunk_indices` are incompatible with the chunk grid.
     pub fn chunk_subset_bounded(&self, chunk_indices: &[u64]) -> Result<ArraySubset, ArrayError> {
         let chunk_subset = self.chunk_subset(chunk_indices)?;
-        Ok(unsafe { chunk_subset.bound_unchecked(self.shape()) })
+        Ok(unsafe { chunk_subset.bound_unchecked(&self.subset_all().end_exc()) })
     }
 
     /// Return the array subset of `chunks`.
@@ -724,17 +1111,26 @@ impl<TStorage: ?Sized> Array<TStorage> {
     /// Returns [`ArrayError::InvalidChunkGridIndicesError`] if the `chunk_indices` are incompatible with the chunk grid.
     pub fn chunks_subset_bounded(&self, chunks: &ArraySubset) -> Result<ArraySubset, ArrayError> {
         let chunks_subset = self.chunks_subset(chunks)?;
-        Ok(unsafe { chunks_subset.bound_unchecked(self.shape()) })
+        Ok(unsafe { chunks_subset.bound_unchecked(&self.subset_all().end_exc()) })
     }
 
     /// Get the chunk array representation at `chunk_index`.
     ///
+    /// This is required to encode or decode a chunk, so it is also where the [must understand level](crate::config::Config#must-understand-level) is enforced for array data access.
+    ///
     /// # Errors
-    /// Returns [`ArrayError::InvalidChunkGridIndicesError`] if the `chunk_indices` are incompatible with the chunk grid.
+    /// Returns
+    ///  - [`ArrayError::InvalidChunkGridIndicesError`] if the `chunk_indices` are incompatible with the chunk grid, or
+    ///  - [`ArrayError::UnsupportedAdditionalFieldError`] if the array has an additional field that must be understood and the must understand level is not [`MustUnderstandLevel::Permissive`].
     pub fn chunk_array_representation(
         &self,
         chunk_indices: &[u64],
     ) -> Result<ChunkRepresentation, ArrayError> {
+        if let Err(err) = validate_additional_fields(self.additional_fields()) {
+            if global_config().must_understand_level() != MustUnderstandLevel::Permissive {
+                return Err(err.into());
+            }
+        }
         (self.chunk_grid().chunk_shape(chunk_indices, self.shape())?).map_or_else(
             || {
                 Err(ArrayError::InvalidChunkGridIndicesError(
@@ -767,17 +1163,185 @@ impl<TStorage: ?Sized> Array<TStorage> {
             .chunks_in_array_subset(array_subset, self.shape())
     }
 
-    /// Calculate the recommended codec concurrency.
+    /// Return the number of elements of `array_subset` that are outside the bounds of the array.
+    ///
+    /// This is the number of elements padded with the fill value by an array subset retrieve with [`CodecOptions::allow_oob_fill`](crate::array::codec::CodecOptions::allow_oob_fill) enabled.
+    ///
+    /// # Errors
+    /// Returns [`IncompatibleDimensionalityError`] if the array subset has an incorrect dimensionality.
+    pub fn num_oob_elements(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<u64, IncompatibleDimensionalityError> {
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(IncompatibleDimensionalityError::new(
+                array_subset.dimensionality(),
+                self.dimensionality(),
+            ));
+        }
+        let num_elements_inbounds = self
+            .array_subset_inbounds(array_subset)
+            .map_or(0, |subset| subset.num_elements());
+        Ok(array_subset.num_elements() - num_elements_inbounds)
+    }
+
+    /// Return the region of `array_subset` within the bounds of the array, or [`None`] if there is no such region.
+    fn array_subset_inbounds(&self, array_subset: &ArraySubset) -> Option<ArraySubset> {
+        let subset_all = self.subset_all();
+        if itertools::izip!(
+            array_subset.start(),
+            array_subset.end_exc(),
+            subset_all.start(),
+            subset_all.end_exc()
+        )
+        .any(|(start, end, start_all, end_all)| *start >= end_all || end <= *start_all)
+        {
+            None
+        } else {
+            Some(unsafe { array_subset.overlap_unchecked(&subset_all) })
+        }
+    }
+
+    /// Return true if `array_subset` is entirely within the bounds of the array.
+    fn array_subset_is_inbounds(&self, array_subset: &ArraySubset) -> bool {
+        let subset_all = self.subset_all();
+        itertools::izip!(
+            array_subset.start(),
+            array_subset.end_exc(),
+            subset_all.start(),
+            subset_all.end_exc()
+        )
+        .all(|(start, end, start_all, end_all)| start >= start_all && end <= end_all)
+    }
+
+    /// Return a mask of the elements of `bytes` that are equal to the fill value.
+    ///
+    /// See [`FillValue::equals_element`].
+    fn fill_value_mask(&self, bytes: &ArrayBytes<'_>) -> Vec<bool> {
+        match bytes {
+            ArrayBytes::Fixed(bytes) => {
+                let data_type_size = self.data_type().fixed_size().unwrap_or(1).max(1);
+                bytes
+                    .chunks_exact(data_type_size)
+                    .map(|element| self.fill_value().equals_element(self.data_type(), element))
+                    .collect()
+            }
+            ArrayBytes::Variable(bytes, offsets) => offsets
+                .windows(2)
+                .map(|range| {
+                    self.fill_value()
+                        .equals_element(self.data_type(), &bytes[range[0]..range[1]])
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns true if every element of `bytes` is equal to the fill value.
+    ///
+    /// See [`FillValue::equals_element`].
+    fn is_fill_value_nan_aware(&self, bytes: &ArrayBytes<'_>) -> bool {
+        match bytes {
+            ArrayBytes::Fixed(bytes) => {
+                let data_type_size = self.data_type().fixed_size().unwrap_or(1).max(1);
+                bytes
+                    .chunks_exact(data_type_size)
+                    .all(|element| self.fill_value().equals_element(self.data_type(), element))
+            }
+            ArrayBytes::Variable(bytes, offsets) => offsets.windows(2).all(|range| {
+                self.fill_value()
+                    .equals_element(self.data_type(), &bytes[range[0]..range[1]])
+            }),
+        }
+    }
+
+    /// Pad the bytes of the in-bounds region of `array_subset` to `array_subset` with the fill value.
+    fn pad_oob_fill(
+        &self,
+        array_subset: &ArraySubset,
+        inbounds: Option<(ArraySubset, ArrayBytes<'_>)>,
+    ) -> Result<ArrayBytes<'static>, ArrayError> {
+        let array_size = ArraySize::new(self.data_type().size(), array_subset.num_elements());
+        let bytes = ArrayBytes::new_fill_value(array_size, self.fill_value());
+        if let Some((inbounds_subset, inbounds_bytes)) = inbounds {
+            inbounds_bytes.validate(inbounds_subset.num_elements(), self.data_type().size())?;
+            let inbounds_subset = inbounds_subset.relative_to(array_subset.start())?;
+            Ok(unsafe {
+                update_array_bytes(
+                    bytes,
+                    array_subset.shape(),
+                    &inbounds_subset,
+                    &inbounds_bytes,
+                    self.data_type().size(),
+                )
+            })
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    /// Calculate the recommended codec concurrency of the chunk at `chunk_indices`.
     fn recommended_codec_concurrency(
         &self,
+        chunk_indices: &[u64],
         chunk_representation: &ChunkRepresentation,
     ) -> Result<RecommendedConcurrency, ArrayError> {
         Ok(self
-            .codecs()
+            .chunk_codecs(chunk_indices)
             .recommended_concurrency(chunk_representation)?)
     }
 }
 
+/// Validate additional fields other than those understood by `zarrs`.
+fn validate_additional_fields(
+    additional_fields: &AdditionalFields,
+) -> Result<(), UnsupportedAdditionalFieldError> {
+    additional_fields
+        .iter()
+        .filter(|(name, _)| *name != CHUNK_CODECS_FIELD)
+        .try_for_each(|(name, field)| field.validate(name))
+}
+
+/// Deserialize Zarr V3 array metadata for the array at `path` from the value of `key`.
+///
+/// A missing `fill_value` is substituted with a default matching the data type if enabled by the [default missing fill value](crate::config::Config#default-missing-fill-value) configuration.
+pub(crate) fn array_metadata_v3_from_slice(
+    path: &NodePath,
+    key: &StoreKey,
+    metadata: &[u8],
+) -> Result<ArrayMetadataV3, StorageError> {
+    let invalid_metadata =
+        |err: serde_json::Error| StorageError::InvalidMetadata(key.clone(), err.to_string());
+    if !global_config().default_missing_fill_value() {
+        return serde_json::from_slice(metadata).map_err(invalid_metadata);
+    }
+
+    let mut metadata: serde_json::Value =
+        serde_json::from_slice(metadata).map_err(invalid_metadata)?;
+    if let Some(fields) = metadata.as_object_mut() {
+        if !fields.contains_key("fill_value") {
+            let data_type = fields
+                .get("data_type")
+                .and_then(|data_type| serde_json::from_value(data_type.clone()).ok())
+                .and_then(|data_type| DataType::from_metadata(&data_type).ok());
+            if let Some(data_type) = data_type {
+                let fill_value = match data_type.size() {
+                    DataTypeSize::Fixed(size) => FillValue::new(vec![0; size]),
+                    DataTypeSize::Variable => FillValue::new(vec![]),
+                };
+                let fill_value = data_type.metadata_fill_value(&fill_value);
+                log::warn!(
+                    "array at {path} has no fill value, a default fill value of {fill_value} is applied"
+                );
+                fields.insert(
+                    "fill_value".to_string(),
+                    serde_json::to_value(fill_value).map_err(invalid_metadata)?,
+                );
+            }
+        }
+    }
+    serde_json::from_value(metadata).map_err(invalid_metadata)
+}
+
 #[cfg(feature = "ndarray")]
 /// Convert an ndarray into a vec with standard layout
 fn ndarray_into_vec<T: Clone, D: ndarray::Dimension>(array: ndarray::Array<T, D>) -> Vec<T> {
@@ -792,6 +1356,9 @@ fn ndarray_into_vec<T: Clone, D: ndarray::Dimension>(array: ndarray::Array<T, D>
 
 mod array_sync_readable;
 
+mod array_sync_chunks_iterator;
+pub use array_sync_chunks_iterator::ArrayChunksIterator;
+
 mod array_sync_writable;
 
 mod array_sync_readable_writable;
@@ -908,7 +1475,7 @@ pub fn bytes_to_ndarray<T: bytemuck::Pod>(
 
 #[cfg(test)]
 mod tests {
-    use crate::storage::store::MemoryStore;
+    use crate::storage::{store::MemoryStore, ListableStorageTraits, WritableStorageTraits};
     use zarrs_filesystem::FilesystemStore;
 
     use super::*;
@@ -1136,6 +1703,1562 @@ mod tests {
         )
     }
 
+    fn array_v2_dimension_separator(separator: &str) {
+        use crate::storage::{StoreKey, WritableStorageTraits};
+
+        let store = Arc::new(MemoryStore::new());
+        let zarray = format!(
+            r#"{{
+                "zarr_format": 2,
+                "shape": [2, 2, 4],
+                "chunks": [2, 2, 2],
+                "dtype": "|u1",
+                "compressor": null,
+                "fill_value": 0,
+                "order": "C",
+                "filters": null,
+                "dimension_separator": "{separator}"
+            }}"#
+        );
+        store
+            .set(
+                &StoreKey::new("array/.zarray").unwrap(),
+                zarray.into_bytes().into(),
+            )
+            .unwrap();
+        for (chunk, value) in [("0", 1u8), ("1", 2u8)] {
+            let key = format!("array/0{separator}0{separator}{chunk}");
+            store
+                .set(&StoreKey::new(key).unwrap(), vec![value; 8].into())
+                .unwrap();
+        }
+
+        let array = Array::open(store, "/array").unwrap();
+        assert_eq!(
+            array.chunk_key(&[0, 0, 1]).as_str(),
+            format!("array/0{separator}0{separator}1")
+        );
+        let elements = array
+            .retrieve_array_subset_elements::<u8>(&array.subset_all())
+            .unwrap();
+        assert_eq!(
+            elements,
+            vec![1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2]
+        );
+    }
+
+    #[test]
+    fn array_v2_dimension_separator_dot() {
+        array_v2_dimension_separator(".");
+    }
+
+    #[test]
+    fn array_v2_dimension_separator_slash() {
+        array_v2_dimension_separator("/");
+    }
+
+    #[test]
+    fn array_v2_structured_data_type() {
+        use crate::storage::StoreKey;
+
+        let store = Arc::new(MemoryStore::new());
+        // A 6 byte item of an int16 field and a 2 element uint16 subarray field
+        let zarray = r#"{
+                "zarr_format": 2,
+                "shape": [3],
+                "chunks": [2],
+                "dtype": [["a", "<i2"], ["b", ">u2", [2]]],
+                "compressor": null,
+                "fill_value": "AQIDBAUG",
+                "order": "C",
+                "filters": null
+            }"#;
+        store
+            .set(
+                &StoreKey::new("array/.zarray").unwrap(),
+                zarray.as_bytes().to_vec().into(),
+            )
+            .unwrap();
+
+        let array = Array::open(store, "/array").unwrap();
+        assert_eq!(array.data_type(), &DataType::RawBits(6));
+        array
+            .store_array_subset(
+                &ArraySubset::new_with_ranges(&[1..3]),
+                (10..22).collect::<Vec<u8>>(),
+            )
+            .unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset(&array.subset_all())
+                .unwrap()
+                .into_fixed()
+                .unwrap()
+                .as_ref(),
+            &[1, 2, 3, 4, 5, 6, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
+        );
+    }
+
+    #[test]
+    fn array_chunk_grid_origin() {
+        let store = Arc::new(MemoryStore::default());
+        let chunk_grid =
+            chunk_grid::RegularOriginChunkGrid::new(vec![4, 4].try_into().unwrap(), vec![10, 20])
+                .unwrap();
+        let array = ArrayBuilder::new(
+            vec![6, 6],
+            DataType::UInt8,
+            ChunkGrid::new(chunk_grid),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        assert_eq!(
+            array.subset_all(),
+            ArraySubset::new_with_ranges(&[10..16, 20..26])
+        );
+        assert_eq!(
+            array.chunk_subset_bounded(&[1, 1]).unwrap(),
+            ArraySubset::new_with_ranges(&[14..16, 24..26])
+        );
+
+        array
+            .store_array_subset_elements::<u8>(
+                &ArraySubset::new_with_ranges(&[13..15, 23..25]),
+                &[1; 4],
+            )
+            .unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[1, 1]).unwrap(),
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_ranges(&[
+                    12..15,
+                    23..24
+                ]))
+                .unwrap(),
+            vec![0, 1, 1]
+        );
+
+        // Indices before the origin are not part of the array
+        let subset_before_origin = ArraySubset::new_with_ranges(&[8..12, 20..22]);
+        assert!(array
+            .retrieve_array_subset_elements::<u8>(&subset_before_origin)
+            .is_err());
+        assert!(array
+            .store_array_subset_elements::<u8>(&subset_before_origin, &[1; 8])
+            .is_err());
+    }
+
+    #[test]
+    fn array_retrieve_array_subset_elements_with_mask() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(f32::NAN),
+        )
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_chunk_elements::<f32>(&[0, 1], &[1.0, f32::from_bits(0x7fc0_0001), 2.0, 3.0])
+            .unwrap();
+
+        let (elements, mask) = array
+            .retrieve_array_subset_elements_with_mask::<f32>(&ArraySubset::new_with_ranges(&[
+                0..2,
+                1..4,
+            ]))
+            .unwrap();
+        assert_eq!(elements.len(), mask.len());
+        assert!(elements[0].is_nan());
+        assert_eq!(elements[1], 1.0);
+        assert_eq!(mask, vec![true, false, true, true, false, false]);
+    }
+
+    fn array_zero_size_dimension(shape: Vec<u64>, chunk_shape: Vec<u64>) {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            shape,
+            DataType::UInt8,
+            chunk_shape.try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/")
+        .unwrap();
+        array.store_metadata().unwrap();
+
+        assert!(array.subset_all().is_empty());
+        assert_eq!(array.chunk_grid_shape().unwrap().iter().product::<u64>(), 0);
+        assert!(array
+            .chunks_in_array_subset(&array.subset_all())
+            .unwrap()
+            .unwrap()
+            .is_empty());
+
+        // Writes of empty subsets are no-ops
+        array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &[])
+            .unwrap();
+        array.store_all::<u8>(&[]).unwrap();
+        assert!(array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &[1])
+            .is_err());
+        assert_eq!(store.list().unwrap().len(), 1); // only the metadata
+
+        // Reads return empty buffers
+        assert!(array
+            .retrieve_array_subset_elements::<u8>(&array.subset_all())
+            .unwrap()
+            .is_empty());
+        assert!(array.retrieve_all::<u8>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn array_zero_size_dimension_1d() {
+        array_zero_size_dimension(vec![0], vec![4]);
+    }
+
+    #[test]
+    fn array_zero_size_dimension_2d() {
+        array_zero_size_dimension(vec![3, 0], vec![2, 2]);
+    }
+
+    #[test]
+    fn array_store_array_subset_assume_new_chunks() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![6, 6],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &[1; 36])
+            .unwrap();
+
+        let options = codec::CodecOptions::builder()
+            .assume_new_chunks(true)
+            .build();
+        array
+            .store_array_subset_elements_opt::<u8>(
+                &ArraySubset::new_with_ranges(&[0..5, 0..5]),
+                &[2; 25],
+                &options,
+            )
+            .unwrap();
+        let elements = array
+            .retrieve_array_subset_elements::<u8>(&array.subset_all())
+            .unwrap();
+        // The edge chunks are padded with the fill value rather than updated, so the existing 1s are overwritten
+        let expected: Vec<u8> = (0..6)
+            .flat_map(|i| (0..6).map(move |j| if i < 5 && j < 5 { 2 } else { 0 }))
+            .collect();
+        assert_eq!(elements, expected);
+
+        assert!(matches!(
+            array.store_array_subset_elements_opt::<u8>(
+                &ArraySubset::new_with_ranges(&[1..3, 0..4]),
+                &[3; 8],
+                &options,
+            ),
+            Err(ArrayError::UnalignedSubset(_))
+        ));
+    }
+
+    #[test]
+    fn array_chunk_key() {
+        use chunk_key_encoding::{DefaultChunkKeyEncoding, V2ChunkKeyEncoding};
+
+        let store = Arc::new(MemoryStore::default());
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        );
+        let array = builder.build(store.clone(), "/group/array").unwrap();
+        assert_eq!(array.chunk_key(&[0, 1]).as_str(), "group/array/c/0/1");
+
+        builder.chunk_key_encoding(DefaultChunkKeyEncoding::new_dot().into());
+        let array = builder.build(store.clone(), "/group/array").unwrap();
+        assert_eq!(array.chunk_key(&[0, 1]).as_str(), "group/array/c.0.1");
+
+        builder.chunk_key_encoding(V2ChunkKeyEncoding::new_dot().into());
+        let array = builder.build(store.clone(), "/").unwrap();
+        assert_eq!(array.chunk_key(&[0, 1]).as_str(), "0.1");
+        // Keys do not depend on the chunk grid
+        assert_eq!(array.chunk_key(&[5, 6, 7]).as_str(), "5.6.7");
+
+        builder.chunk_key_encoding(V2ChunkKeyEncoding::new_slash().into());
+        let array = builder.build(store, "/array").unwrap();
+        assert_eq!(array.chunk_key(&[0, 1]).as_str(), "array/0/1");
+    }
+
+    #[test]
+    fn array_chunk_indices_from_key() {
+        use chunk_key_encoding::{DefaultChunkKeyEncoding, V2ChunkKeyEncoding};
+
+        let store = Arc::new(MemoryStore::default());
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        );
+        let key = |key: &str| StoreKey::new(key).unwrap();
+
+        let array = builder.build(store.clone(), "/group/array").unwrap();
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/c/0/12")),
+            Some(vec![0, 12])
+        );
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/zarr.json")),
+            None
+        );
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/c.0.1")),
+            None
+        );
+        assert_eq!(array.chunk_indices_from_key(&key("group/array/c/0")), None);
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/c/0/01")),
+            None
+        );
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/other/c/0/1")),
+            None
+        );
+
+        builder.chunk_key_encoding(DefaultChunkKeyEncoding::new_dot().into());
+        let array = builder.build(store.clone(), "/group/array").unwrap();
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/c.3.4")),
+            Some(vec![3, 4])
+        );
+        assert_eq!(
+            array.chunk_indices_from_key(&key("group/array/c/3/4")),
+            None
+        );
+
+        builder.chunk_key_encoding(V2ChunkKeyEncoding::new_dot().into());
+        let array = builder.build(store.clone(), "/").unwrap();
+        assert_eq!(array.chunk_indices_from_key(&key("5.6")), Some(vec![5, 6]));
+        assert_eq!(array.chunk_indices_from_key(&key(".zarray")), None);
+        assert_eq!(array.chunk_indices_from_key(&key("5/6")), None);
+
+        builder.chunk_key_encoding(V2ChunkKeyEncoding::new_slash().into());
+        let array = builder.build(store.clone(), "/array").unwrap();
+        assert_eq!(
+            array.chunk_indices_from_key(&key("array/5/6")),
+            Some(vec![5, 6])
+        );
+        assert_eq!(array.chunk_indices_from_key(&key("array/.zattrs")), None);
+
+        // A zero-dimensional array
+        let mut builder = ArrayBuilder::new(
+            vec![],
+            DataType::UInt8,
+            Vec::<u64>::new().try_into().unwrap(),
+            FillValue::from(0u8),
+        );
+        let array = builder.build(store.clone(), "/array").unwrap();
+        assert_eq!(array.chunk_indices_from_key(&key("array/c")), Some(vec![]));
+        builder.chunk_key_encoding(V2ChunkKeyEncoding::new_dot().into());
+        let array = builder.build(store, "/array").unwrap();
+        assert_eq!(array.chunk_indices_from_key(&key("array/0")), Some(vec![]));
+        assert_eq!(array.chunk_indices_from_key(&key("array/1")), None);
+    }
+
+    #[test]
+    fn array_exists() {
+        use crate::storage::WritableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        assert!(!Array::exists(&store, "/array").unwrap());
+        ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap()
+        .store_metadata()
+        .unwrap();
+        assert!(Array::exists(&store, "/array").unwrap());
+
+        store
+            .set(
+                &StoreKey::new("array_v2/.zarray").unwrap(),
+                b"{}".to_vec().into(),
+            )
+            .unwrap();
+        assert!(Array::exists(&store, "/array_v2").unwrap());
+
+        crate::group::GroupBuilder::new()
+            .build(store.clone(), "/group")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        assert!(!Array::exists(&store, "/group").unwrap());
+
+        store
+            .set(&StoreKey::new("invalid/zarr.json").unwrap(), vec![].into())
+            .unwrap();
+        assert!(Array::exists(&store, "/invalid").is_err());
+    }
+
+    #[test]
+    fn array_reload() {
+        let store = Arc::new(MemoryStore::default());
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+
+        let mut array_other = Array::open(store.clone(), "/array").unwrap();
+        array_other.set_shape(vec![16, 16]);
+        array_other.store_metadata().unwrap();
+
+        assert_eq!(array.shape(), &[8, 8]);
+        array.set_deduplicate_chunk_reads(true);
+        array.reload().unwrap();
+        assert_eq!(array.shape(), &[16, 16]);
+        assert_eq!(array.path().as_str(), "/array");
+        // The handle settings and storage are preserved
+        assert!(array.deduplicate_chunk_reads());
+        assert!(Arc::ptr_eq(&array.storage, &store));
+
+        array.erase_metadata().unwrap();
+        assert!(array.reload().is_err());
+        assert_eq!(array.shape(), &[16, 16]);
+    }
+
+    #[test]
+    fn array_retrieve_array_subset_oob_fill() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![5, 5],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(255u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        let elements: Vec<u8> = (0..25).collect();
+        array
+            .store_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![5, 5]), &elements)
+            .unwrap();
+
+        let options = codec::CodecOptions::builder().allow_oob_fill(true).build();
+
+        // Partially out-of-bounds
+        let subset = ArraySubset::new_with_ranges(&[3..6, 4..7]);
+        assert_eq!(array.num_oob_elements(&subset).unwrap(), 7);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements_opt::<u8>(&subset, &options)
+                .unwrap(),
+            vec![19, 255, 255, 24, 255, 255, 255, 255, 255]
+        );
+
+        // Entirely out-of-bounds
+        let subset = ArraySubset::new_with_ranges(&[6..8, 0..2]);
+        assert_eq!(array.num_oob_elements(&subset).unwrap(), 4);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements_opt::<u8>(&subset, &options)
+                .unwrap(),
+            vec![255; 4]
+        );
+
+        // In-bounds
+        let subset = ArraySubset::new_with_ranges(&[1..3, 1..3]);
+        assert_eq!(array.num_oob_elements(&subset).unwrap(), 0);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements_opt::<u8>(&subset, &options)
+                .unwrap(),
+            vec![6, 7, 11, 12]
+        );
+    }
+
+    #[test]
+    fn array_retrieve_array_subset_single_chunk() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![5, 5],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        let elements: Vec<u8> = (0..25).collect();
+        array
+            .store_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![5, 5]), &elements)
+            .unwrap();
+
+        // Edge chunks are cropped, so the bounded chunk subset does not match the chunk subset
+        assert_ne!(
+            array.chunk_subset(&[2, 2]).unwrap(),
+            array.chunk_subset_bounded(&[2, 2]).unwrap()
+        );
+
+        // Retrieve all chunks (general path)
+        let general = array
+            .retrieve_array_subset_elements::<u8>(&ArraySubset::new_with_shape(vec![5, 5]))
+            .unwrap();
+        assert_eq!(general, elements);
+
+        for chunk_indices in &ArraySubset::new_with_shape(vec![3, 3]).indices() {
+            let chunk_subset = array.chunk_subset_bounded(&chunk_indices).unwrap();
+            let expected: Vec<u8> = chunk_subset
+                .indices()
+                .into_iter()
+                .map(|indices| general[usize::try_from(indices[0] * 5 + indices[1]).unwrap()])
+                .collect();
+
+            // Retrieve a single chunk (fast path for interior chunks)
+            let single = array
+                .retrieve_array_subset_elements::<u8>(&chunk_subset)
+                .unwrap();
+            assert_eq!(single, expected);
+        }
+    }
+
+    #[test]
+    fn array_store_array_subset_new_chunk_fill_value() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(f32::NAN),
+        )
+        .build(store, "/")
+        .unwrap();
+
+        let options = codec::CodecOptions::builder()
+            .new_chunk_fill_value(Some(FillValue::from(0.0f32)))
+            .build();
+        array
+            .store_array_subset_elements_opt::<f32>(
+                &ArraySubset::new_with_ranges(&[0..1, 0..1]),
+                &[1.0],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<f32>(&[0, 0]).unwrap(),
+            vec![1.0, 0.0, 0.0, 0.0]
+        );
+
+        // Existing chunks are updated, not refilled
+        let options = codec::CodecOptions::builder()
+            .new_chunk_fill_value(Some(FillValue::from(5.0f32)))
+            .build();
+        array
+            .store_array_subset_elements_opt::<f32>(
+                &ArraySubset::new_with_ranges(&[1..2, 1..2]),
+                &[2.0],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<f32>(&[0, 0]).unwrap(),
+            vec![1.0, 0.0, 0.0, 2.0]
+        );
+        // Unwritten chunks still have the array fill value
+        assert!(array
+            .retrieve_chunk_elements::<f32>(&[1, 1])
+            .unwrap()
+            .iter()
+            .all(|v| v.is_nan()));
+
+        let options = codec::CodecOptions::builder()
+            .new_chunk_fill_value(Some(FillValue::from(0u8)))
+            .build();
+        assert!(matches!(
+            array.store_array_subset_elements_opt::<f32>(
+                &ArraySubset::new_with_ranges(&[2..3, 2..3]),
+                &[1.0],
+                &options,
+            ),
+            Err(ArrayError::InvalidFillValue(_))
+        ));
+    }
+
+    #[test]
+    fn array_store_all() {
+        use crate::storage::ListableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![3, 5],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store.clone(), "/")
+        .unwrap();
+
+        let elements: Vec<u16> = (0..15).collect();
+        array.store_all(&elements).unwrap();
+        assert_eq!(array.retrieve_all::<u16>().unwrap(), elements);
+
+        let elements: Vec<u16> = (100..115).collect();
+        array.store_all(&elements).unwrap();
+        assert_eq!(array.retrieve_all::<u16>().unwrap(), elements);
+
+        // Invalid inputs error before anything is written
+        array
+            .erase_chunks(&ArraySubset::new_with_shape(vec![2, 3]))
+            .unwrap();
+        assert!(matches!(
+            array.store_all::<u16>(&[1; 14]),
+            Err(ArrayError::InvalidDataShape(_, _))
+        ));
+        assert!(matches!(
+            array.store_all::<u8>(&[1; 15]),
+            Err(ArrayError::IncompatibleElementType)
+        ));
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn array_retrieve_all() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![3, 5],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(1.5f32),
+        )
+        .build(store.clone(), "/")
+        .unwrap();
+        assert_eq!(array.retrieve_all::<f32>().unwrap(), vec![1.5; 15]);
+        assert!(matches!(
+            array.retrieve_all::<u32>(),
+            Err(ArrayError::IncompatibleElementType)
+        ));
+
+        let array = ArrayBuilder::new(
+            vec![1 << 20; 3],
+            DataType::UInt8,
+            vec![1, 1, 1].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        assert!(matches!(
+            array.retrieve_all::<u8>(),
+            Err(ArrayError::RetrieveSizeLimitExceeded(size, _)) if size == 1 << 60
+        ));
+
+        // The element limit applies to subsets and can be overridden per call
+        let subset = ArraySubset::new_with_shape(vec![1 << 10, 1 << 10, 1 << 20]);
+        assert!(matches!(
+            array.retrieve_array_subset(&subset),
+            Err(ArrayError::RetrieveElementLimitExceeded(num_elements, limit))
+                if num_elements == 1 << 40 && limit == 1 << 32
+        ));
+        let options = codec::CodecOptions::builder()
+            .retrieve_element_limit(64)
+            .build();
+        let subset = ArraySubset::new_with_shape(vec![4, 4, 5]);
+        assert!(matches!(
+            array.retrieve_array_subset_opt(&subset, &options),
+            Err(ArrayError::RetrieveElementLimitExceeded(80, 64))
+        ));
+        let subset = ArraySubset::new_with_shape(vec![4, 4, 4]);
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements_opt::<u8>(&subset, &options)
+                .unwrap(),
+            vec![0; 64]
+        );
+        let options = codec::CodecOptions::builder()
+            .retrieve_size_limit(u64::MAX)
+            .build();
+        assert!(matches!(
+            array.retrieve_all_opt::<u8>(&options),
+            Err(ArrayError::RetrieveElementLimitExceeded(num_elements, _)) if num_elements == 1 << 60
+        ));
+    }
+
+    #[test]
+    fn array_retrieve_array_subset_into() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_array_subset_elements(&array.subset_all(), &(0..16u16).collect::<Vec<_>>())
+            .unwrap();
+
+        // The output buffer is reused across subsets of the same shape
+        let mut output = vec![0u8; 6 * 2];
+        for subset in [
+            ArraySubset::new_with_ranges(&[1..3, 1..4]),
+            ArraySubset::new_with_ranges(&[0..2, 0..3]),
+            ArraySubset::new_with_ranges(&[2..4, 1..4]),
+        ] {
+            array
+                .retrieve_array_subset_into(&subset, &mut output)
+                .unwrap();
+            assert_eq!(
+                output,
+                array
+                    .retrieve_array_subset(&subset)
+                    .unwrap()
+                    .into_fixed()
+                    .unwrap()
+                    .to_vec()
+            );
+        }
+
+        // Out-of-bounds elements are filled if allowed
+        let subset = ArraySubset::new_with_ranges(&[3..5, 3..6]);
+        let options = codec::CodecOptions::builder().allow_oob_fill(true).build();
+        array
+            .retrieve_array_subset_into_opt(&subset, &mut output, &options)
+            .unwrap();
+        assert_eq!(
+            output,
+            array
+                .retrieve_array_subset_opt(&subset, &options)
+                .unwrap()
+                .into_fixed()
+                .unwrap()
+                .to_vec()
+        );
+
+        assert!(matches!(
+            array.retrieve_array_subset_into(
+                &ArraySubset::new_with_ranges(&[0..2, 0..2]),
+                &mut output
+            ),
+            Err(ArrayError::InvalidBytesInputSize(12, 8))
+        ));
+
+        // The element limit is checked before the output size, which overflows without a limit
+        let subset = ArraySubset::new_with_shape(vec![u64::MAX, 2]);
+        assert!(matches!(
+            array.retrieve_array_subset_into(&subset, &mut output),
+            Err(ArrayError::RetrieveElementLimitExceeded(u64::MAX, _))
+        ));
+        let options = codec::CodecOptions::builder()
+            .retrieve_element_limit(u64::MAX)
+            .build();
+        assert!(matches!(
+            array.retrieve_array_subset_into_opt(&subset, &mut output, &options),
+            Err(ArrayError::InvalidBytesInputSize(12, u64::MAX))
+        ));
+    }
+
+    #[test]
+    fn array_store_array_subset_concurrent_target() {
+        let subset = ArraySubset::new_with_ranges(&[1..9, 2..10]);
+        let elements: Vec<u16> = (0..subset.num_elements() as u16).collect();
+        let mut expected = vec![1u16; 100];
+        for (i, indices) in subset.indices().iter().enumerate() {
+            expected[usize::try_from(indices[0] * 10 + indices[1]).unwrap()] = elements[i];
+        }
+
+        for concurrent_target in [1, 2, 8] {
+            let store = Arc::new(MemoryStore::default());
+            let array = ArrayBuilder::new(
+                vec![10, 10],
+                DataType::UInt16,
+                vec![3, 3].try_into().unwrap(),
+                FillValue::from(0u16),
+            )
+            .build(store, "/")
+            .unwrap();
+            array
+                .store_array_subset_elements(&array.subset_all(), &[1u16; 100])
+                .unwrap();
+
+            // Chunks partially covered by the subset (including edge chunks) are updated rather than replaced
+            let options = codec::CodecOptions::builder()
+                .concurrent_target(concurrent_target)
+                .build();
+            array
+                .store_array_subset_elements_opt(&subset, &elements, &options)
+                .unwrap();
+            assert_eq!(
+                array
+                    .retrieve_array_subset_elements::<u16>(&array.subset_all())
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn array_chunk_codecs() {
+        use crate::storage::ReadableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        let mut array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .array_to_bytes_codec(Arc::new(codec::BytesCodec::little()))
+        .build(store.clone(), "/")
+        .unwrap();
+        let codecs_big = Arc::new(CodecChain::new(
+            vec![],
+            Arc::new(codec::BytesCodec::big()),
+            vec![],
+        ));
+        array
+            .set_chunk_codecs(&[0, 1], Some(codecs_big.clone()))
+            .unwrap();
+        assert!(array.set_chunk_codecs(&[0, 2], None).is_err());
+        array.store_metadata().unwrap();
+        array.store_chunk_elements::<u16>(&[0, 0], &[1; 4]).unwrap();
+        array.store_chunk_elements::<u16>(&[0, 1], &[1; 4]).unwrap();
+        assert_eq!(
+            store.get(&array.chunk_key(&[0, 0])).unwrap().unwrap(),
+            vec![1, 0, 1, 0, 1, 0, 1, 0]
+        );
+        assert_eq!(
+            store.get(&array.chunk_key(&[0, 1])).unwrap().unwrap(),
+            vec![0, 1, 0, 1, 0, 1, 0, 1]
+        );
+
+        // The override is read from the metadata and must be understood by other implementations
+        let metadata = serde_json::to_value(array.metadata()).unwrap();
+        assert_eq!(metadata["zarrs_chunk_codecs"]["must_understand"], true);
+        let mut array = Array::open(store.clone(), "/").unwrap();
+        assert_eq!(
+            array.chunk_codecs(&[0, 1]).create_metadatas(),
+            codecs_big.create_metadatas()
+        );
+        assert_eq!(
+            array
+                .retrieve_array_subset_elements::<u16>(&array.subset_all())
+                .unwrap(),
+            vec![1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        array.set_chunk_codecs(&[0, 1], None).unwrap();
+        assert!(array.additional_fields().is_empty());
+
+        // The number of overridden chunks is bounded
+        let mut array = ArrayBuilder::new(
+            vec![MAX_CHUNK_CODECS as u64 + 1],
+            DataType::UInt16,
+            vec![1].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store.clone(), "/limit")
+        .unwrap();
+        for i in 0..MAX_CHUNK_CODECS as u64 {
+            array
+                .set_chunk_codecs(&[i], Some(codecs_big.clone()))
+                .unwrap();
+        }
+        assert!(matches!(
+            array.set_chunk_codecs(&[MAX_CHUNK_CODECS as u64], Some(codecs_big.clone())),
+            Err(ArrayError::ChunkCodecsLimitExceeded(MAX_CHUNK_CODECS))
+        ));
+        array
+            .set_chunk_codecs(&[0], Some(codecs_big.clone()))
+            .unwrap();
+
+        // Zarr V2 metadata cannot record an override
+        let mut array = ArrayBuilderV2::new(
+            vec![4, 4],
+            vec![2, 2].try_into().unwrap(),
+            "<u2",
+            crate::metadata::v2::array::FillValueMetadataV2::Number(0.into()),
+        )
+        .build(store, "/v2")
+        .unwrap();
+        assert!(matches!(
+            array.set_chunk_codecs(&[0, 1], Some(codecs_big)),
+            Err(ArrayError::UnsupportedMethod(_))
+        ));
+    }
+
+    #[test]
+    fn array_chunk_size_report() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/")
+        .unwrap();
+        assert!(array.chunk_size_report().unwrap().is_empty());
+
+        array.store_chunk_elements::<u8>(&[1, 0], &[2; 4]).unwrap();
+        array.store_chunk_elements::<u8>(&[0, 1], &[1; 4]).unwrap();
+
+        // Metadata, stray keys, and chunks outside of the chunk grid are not reported
+        array.store_metadata().unwrap();
+        store
+            .set(&StoreKey::new("c/2/0").unwrap(), vec![0].into())
+            .unwrap();
+        store
+            .set(&StoreKey::new("stray").unwrap(), vec![0].into())
+            .unwrap();
+        let report = array.chunk_size_report().unwrap();
+        assert_eq!(report, vec![(vec![0, 1], 4), (vec![1, 0], 4)]);
+        for (chunk_indices, size) in report {
+            let decoded_size = array
+                .chunk_array_representation(&chunk_indices)
+                .unwrap()
+                .fixed_size()
+                .unwrap();
+            assert_eq!(decoded_size as u64, size);
+        }
+    }
+
+    #[test]
+    fn array_deduplicate_chunk_reads() {
+        use crate::{
+            array::chunk_key_encoding::ChunkKeyEncodingTraits,
+            metadata::v3::MetadataV3,
+            storage::storage_adapter::metered::{
+                InMemoryStorageMetrics, MeteredStorageAdapter, StorageOperation,
+            },
+        };
+
+        // Every chunk is stored at the same key
+        #[derive(Debug)]
+        struct BroadcastChunkKeyEncoding;
+
+        impl ChunkKeyEncodingTraits for BroadcastChunkKeyEncoding {
+            fn create_metadata(&self) -> MetadataV3 {
+                MetadataV3::new("broadcast")
+            }
+
+            fn encode(&self, _chunk_grid_indices: &[u64]) -> StoreKey {
+                StoreKey::new("c").unwrap()
+            }
+        }
+
+        let metrics = Arc::new(InMemoryStorageMetrics::new());
+        let store = Arc::new(MeteredStorageAdapter::new(
+            Arc::new(MemoryStore::default()),
+            metrics.clone(),
+        ));
+        let mut array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .chunk_key_encoding(BroadcastChunkKeyEncoding.into())
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_chunk_elements::<u8>(&[0, 0], &[1, 2, 3, 4])
+            .unwrap();
+        let expected = vec![
+            1, 2, 1, 2, //
+            3, 4, 3, 4, //
+            1, 2, 1, 2, //
+            3, 4, 3, 4, //
+        ];
+
+        let subset = ArraySubset::new_with_shape(vec![4, 4]);
+        let gets = metrics.requests(StorageOperation::Get);
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            expected
+        );
+        assert_eq!(metrics.requests(StorageOperation::Get) - gets, 4);
+
+        array.set_deduplicate_chunk_reads(true);
+        let gets = metrics.requests(StorageOperation::Get);
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            expected
+        );
+        assert_eq!(metrics.requests(StorageOperation::Get) - gets, 1);
+
+        // A subset overlapping part of each chunk
+        let subset = ArraySubset::new_with_ranges(&[1..3, 0..3]);
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            vec![3, 4, 3, 1, 2, 1]
+        );
+    }
+
+    #[test]
+    fn array_open_validated() {
+        use crate::storage::WritableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array.store_chunk_elements::<u8>(&[1, 1], &[1; 4]).unwrap();
+        assert!(Array::open_validated(store.clone(), "/array").is_ok());
+
+        // A chunk written with the wrong separator
+        let key = StoreKey::new("array/c.0.1").unwrap();
+        store.set(&key, vec![0; 4].into()).unwrap();
+        assert!(matches!(
+            Array::open_validated(store.clone(), "/array"),
+            Err(ArrayCreateError::ChunkKeyMismatch(mismatch)) if mismatch == key
+        ));
+        store.erase(&key).unwrap();
+
+        // A chunk outside of the chunk grid
+        let key = StoreKey::new("array/c/2/0").unwrap();
+        store.set(&key, vec![0; 4].into()).unwrap();
+        assert!(Array::open_validated(store.clone(), "/array").is_err());
+        assert!(Array::open(store, "/array").is_ok());
+    }
+
+    #[test]
+    fn array_bool() {
+        use crate::storage::{ReadableStorageTraits, WritableStorageTraits};
+
+        let store = Arc::new(MemoryStore::default());
+        #[allow(unused_mut)]
+        let mut builder = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Bool,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(true),
+        );
+        #[cfg(feature = "gzip")]
+        builder.bytes_to_bytes_codecs(vec![Arc::new(codec::GzipCodec::new(5).unwrap())]);
+        let array = builder.build(store.clone(), "/bool").unwrap();
+        array.store_metadata().unwrap();
+        let mask = [true, false, false, true];
+        array.store_chunk_elements::<bool>(&[0, 0], &mask).unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<bool>(&[0, 0]).unwrap(),
+            mask
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<bool>(&[1, 1]).unwrap(),
+            [true; 4]
+        );
+        let array = Array::open(store.clone(), "/bool").unwrap();
+        assert_eq!(array.fill_value(), &FillValue::from(true));
+
+        // A mask written by zarr-python (V2, one byte per element), with nonzero bytes read as true
+        store
+            .set(
+                &StoreKey::new("mask/.zarray").unwrap(),
+                br#"{"zarr_format": 2, "shape": [4], "chunks": [4], "dtype": "|b1", "fill_value": false, "order": "C", "compressor": null, "filters": null}"#
+                    .to_vec()
+                    .into(),
+            )
+            .unwrap();
+        store
+            .set(&StoreKey::new("mask/0").unwrap(), vec![0, 1, 2, 255].into())
+            .unwrap();
+        let array = Array::open(store.clone(), "/mask").unwrap();
+        assert_eq!(array.fill_value(), &FillValue::from(false));
+        assert_eq!(
+            array.retrieve_all::<bool>().unwrap(),
+            [false, true, true, true]
+        );
+        array.store_metadata().unwrap();
+        let zarray: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("mask/.zarray").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zarray["fill_value"], false);
+    }
+
+    #[test]
+    fn array_prune_orphan_chunks() {
+        use crate::storage::ReadableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &[1; 64])
+            .unwrap();
+        assert_eq!(array.prune_orphan_chunks().unwrap(), 0);
+
+        // Chunks with any index >= 3 are entirely outside of the shape, the edge chunks are kept
+        array.set_shape(vec![5, 5]);
+        assert_eq!(array.prune_orphan_chunks().unwrap(), 16 - 9);
+        for chunk_indices in &ArraySubset::new_with_shape(vec![4, 4]).indices() {
+            let exists = store
+                .get(&array.chunk_key(&chunk_indices))
+                .unwrap()
+                .is_some();
+            assert_eq!(exists, chunk_indices.iter().all(|&i| i < 3));
+        }
+        assert!(store
+            .get(&StoreKey::new("array/zarr.json").unwrap())
+            .unwrap()
+            .is_some());
+        assert_eq!(array.prune_orphan_chunks().unwrap(), 0);
+    }
+
+    #[test]
+    fn array_vacuum() {
+        use crate::storage::ReadableStorageTraits;
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::Float32,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(f32::NAN),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let options = codec::CodecOptions::builder()
+            .store_empty_chunks(true)
+            .build();
+        array
+            .store_chunk_elements_opt::<f32>(&[0, 0], &[f32::NAN; 4], &options)
+            .unwrap();
+        // A NaN with a different payload is still equal to the fill value
+        let nan_payload = f32::from_bits(0x7fc0_0001);
+        array
+            .store_chunk_elements_opt::<f32>(&[0, 1], &[nan_payload; 4], &options)
+            .unwrap();
+        array
+            .store_chunk_elements_opt::<f32>(
+                &[1, 0],
+                &[f32::NAN, 1.0, f32::NAN, f32::NAN],
+                &options,
+            )
+            .unwrap();
+
+        assert_eq!(array.vacuum().unwrap(), 2);
+        for chunk_indices in &ArraySubset::new_with_shape(vec![2, 2]).indices() {
+            let exists = store
+                .get(&array.chunk_key(&chunk_indices))
+                .unwrap()
+                .is_some();
+            assert_eq!(exists, chunk_indices == [1, 0]);
+        }
+        let elements = array.retrieve_all::<f32>().unwrap();
+        assert_eq!(elements.iter().filter(|e| e.is_nan()).count(), 15);
+        assert_eq!(array.vacuum().unwrap(), 0);
+    }
+
+    #[test]
+    fn array_iter_chunks_read_ahead() {
+        use crate::storage::{ReadableStorageTraits, WritableStorageTraits};
+
+        /// A store which panics on every request.
+        struct PanickingStore;
+        impl ReadableStorageTraits for PanickingStore {
+            fn get_partial_values_key(
+                &self,
+                _key: &StoreKey,
+                _byte_ranges: &[crate::byte_range::ByteRange],
+            ) -> Result<Option<Vec<crate::storage::Bytes>>, StorageError> {
+                panic!("storage failure")
+            }
+
+            fn size_key(&self, _key: &StoreKey) -> Result<Option<u64>, StorageError> {
+                panic!("storage failure")
+            }
+        }
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        let chunks = ArraySubset::new_with_shape(vec![4, 4]);
+        for chunk_indices in &chunks.indices() {
+            let value = u8::try_from(chunk_indices[0] * 4 + chunk_indices[1]).unwrap();
+            array
+                .store_chunk_elements::<u8>(&chunk_indices, &[value; 4])
+                .unwrap();
+        }
+        // A chunk that fails to decode
+        store
+            .set(&StoreKey::new("array/c/1/2").unwrap(), vec![0; 3].into())
+            .unwrap();
+
+        for read_ahead in [0, 1, 3, 32] {
+            let results: Vec<_> = array
+                .iter_chunks(&chunks)
+                .unwrap()
+                .read_ahead(read_ahead)
+                .collect();
+            assert_eq!(results.len(), 16);
+            for (result, chunk_indices) in results.into_iter().zip(chunks.indices().iter()) {
+                if chunk_indices == [1, 2] {
+                    assert!(matches!(result, Err(ArrayError::CodecError(_))));
+                } else {
+                    let (indices, bytes) = result.unwrap();
+                    assert_eq!(indices, chunk_indices);
+                    assert_eq!(bytes, array.retrieve_chunk(&chunk_indices).unwrap(),);
+                }
+            }
+        }
+
+        // Dropping the iterator stops retrieval
+        let mut iter = array.iter_chunks(&chunks).unwrap().read_ahead(2);
+        assert!(iter.next().unwrap().is_ok());
+        drop(iter);
+
+        // A read ahead thread that stops unexpectedly is an error
+        let results: Vec<_> = ArrayChunksIterator::new(
+            &array,
+            Arc::new(PanickingStore),
+            chunks.clone(),
+            codec::CodecOptions::default(),
+        )
+        .read_ahead(2)
+        .collect();
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0],
+            Err(ArrayError::StorageError(StorageError::Other(_)))
+        ));
+
+        assert!(array
+            .iter_chunks(&ArraySubset::new_with_shape(vec![4]))
+            .is_err());
+    }
+
+    #[test]
+    fn array_iter_chunks_partial() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![5, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &(0..20).collect::<Vec<_>>())
+            .unwrap();
+
+        let chunk_grid = ArraySubset::new_with_shape(array.chunk_grid_shape().unwrap());
+        let chunks: Vec<_> = array
+            .iter_chunks(&chunk_grid)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|(indices, _)| indices.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![0, 0],
+                vec![0, 1],
+                vec![1, 0],
+                vec![1, 1],
+                vec![2, 0],
+                vec![2, 1]
+            ]
+        );
+        assert_eq!(chunks[1].1, array.retrieve_chunk(&[0, 1]).unwrap());
+        // The last row of chunks is a partial chunk, decoded in full
+        assert_eq!(chunks[4].1, ArrayBytes::new_flen(vec![16, 17, 0, 0]));
+        let chunks_iter: Vec<_> = array.chunks_iter().unwrap().map(Result::unwrap).collect();
+        assert_eq!(chunks_iter, chunks);
+
+        let subset_chunks = array
+            .chunks_in_array_subset(&ArraySubset::new_with_ranges(&[1..3, 3..4]))
+            .unwrap()
+            .unwrap();
+        let chunks: Vec<_> = array
+            .iter_chunks(&subset_chunks)
+            .unwrap()
+            .map(|chunk| chunk.unwrap().0)
+            .collect();
+        assert_eq!(chunks, vec![vec![0, 1], vec![1, 1]]);
+        let chunks_iter_subset: Vec<_> = array
+            .chunks_iter_subset(&ArraySubset::new_with_ranges(&[1..3, 3..4]))
+            .unwrap()
+            .map(|chunk| chunk.unwrap().0)
+            .collect();
+        assert_eq!(chunks_iter_subset, chunks);
+        assert!(array
+            .chunks_iter_subset(&ArraySubset::new_with_shape(vec![4]))
+            .is_err());
+    }
+
+    #[test]
+    fn array_retrieve_chunks_bounded() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![5, 3],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/array")
+        .unwrap();
+        array
+            .store_array_subset_elements::<u8>(&array.subset_all(), &(1..=15).collect::<Vec<_>>())
+            .unwrap();
+
+        // The last row and column of chunks extend past the array shape
+        let chunks = ArraySubset::new_with_ranges(&[1..3, 1..2]);
+        assert_eq!(
+            array.retrieve_chunks(&chunks).unwrap(),
+            ArrayBytes::new_flen(vec![9, 0, 12, 0, 15, 0, 0, 0])
+        );
+        assert_eq!(
+            array.retrieve_chunks_bounded(&chunks).unwrap(),
+            ArrayBytes::new_flen(vec![9, 12, 15])
+        );
+        assert_eq!(
+            array
+                .retrieve_chunks_bounded(&ArraySubset::new_with_shape(
+                    array.chunk_grid_shape().unwrap()
+                ))
+                .unwrap(),
+            array.retrieve_array_subset(&array.subset_all()).unwrap()
+        );
+        assert!(array
+            .retrieve_chunks_bounded(&ArraySubset::new_with_shape(vec![1]))
+            .is_err());
+    }
+
+    #[test]
+    fn array_vlen_utf8() {
+        use crate::storage::{ReadableStorageTraits, WritableStorageTraits};
+
+        // A Zarr V3 string array as written by zarr-python
+        let store = Arc::new(MemoryStore::default());
+        let metadata = serde_json::json!({
+            "zarr_format": 3,
+            "node_type": "array",
+            "shape": [4],
+            "data_type": "string",
+            "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [2]}},
+            "chunk_key_encoding": {"name": "default"},
+            "fill_value": "",
+            "codecs": [{"name": "vlen-utf8", "configuration": {}}]
+        });
+        store
+            .set(
+                &StoreKey::new("array/zarr.json").unwrap(),
+                serde_json::to_vec(&metadata).unwrap().into(),
+            )
+            .unwrap();
+        let array = Array::open(store.clone(), "/array").unwrap();
+        assert_eq!(array.data_type(), &DataType::String);
+        assert_eq!(array.fill_value(), &FillValue::from(""));
+
+        array
+            .store_chunk_elements(&[0], &["a".to_string(), "bc".to_string()])
+            .unwrap();
+        let encoded = store
+            .get(&StoreKey::new("array/c/0").unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            encoded.to_vec(),
+            [
+                &2u32.to_le_bytes()[..],
+                &1u32.to_le_bytes(),
+                b"a",
+                &2u32.to_le_bytes(),
+                b"bc"
+            ]
+            .concat()
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<String>(&[0]).unwrap(),
+            vec!["a".to_string(), "bc".to_string()]
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<String>(&[1]).unwrap(),
+            vec![String::new(), String::new()]
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn array_async_store_metadata_opt_convert_version() {
+        use crate::storage::AsyncReadableStorageTraits;
+
+        let store = Arc::new(zarrs_object_store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        let array = ArrayBuilderV2::new(
+            vec![4, 4],
+            vec![2, 2].try_into().unwrap(),
+            "|u1",
+            crate::metadata::v2::array::FillValueMetadataV2::Number(0.into()),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+
+        let mut options = ArrayMetadataOptions::default();
+        array.async_store_metadata_opt(&options).await.unwrap();
+        let key_v2 = StoreKey::new("array/.zarray").unwrap();
+        let key_v3 = StoreKey::new("array/zarr.json").unwrap();
+        assert!(store.get(&key_v2).await.unwrap().is_some());
+        assert!(store.get(&key_v3).await.unwrap().is_none());
+
+        options.set_metadata_convert_version(MetadataConvertVersion::V3);
+        array.async_store_metadata_opt(&options).await.unwrap();
+        let array_v3 =
+            Array::async_open_opt(store, "/array", &crate::config::MetadataRetrieveVersion::V3)
+                .await
+                .unwrap();
+        assert!(matches!(array_v3.metadata(), ArrayMetadata::V3(_)));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn array_async_exists() {
+        let store = Arc::new(zarrs_object_store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        assert!(!Array::async_exists(&store, "/array").await.unwrap());
+        ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap()
+        .async_store_metadata()
+        .await
+        .unwrap();
+        assert!(Array::async_exists(&store, "/array").await.unwrap());
+        assert!(!crate::group::Group::async_exists(&store, "/array")
+            .await
+            .unwrap());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn array_async_open_consolidated() {
+        use crate::{
+            config::MetadataRetrieveVersion,
+            node::{meta_key_v3, ConsolidatedMetadataMap, ConsolidatedMetadataSource},
+        };
+
+        let store = Arc::new(zarrs_object_store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        let array_path = "/array";
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store.clone(), array_path)
+        .unwrap();
+        array.async_store_metadata().await.unwrap();
+
+        // Open from consolidated metadata, even if absent from the store
+        let mut consolidated = ConsolidatedMetadataMap::new();
+        consolidated.insert(
+            meta_key_v3(array.path()),
+            serde_json::to_value(array.metadata()).unwrap(),
+        );
+        let consolidated: &dyn ConsolidatedMetadataSource = &consolidated;
+        let array_consolidated = Array::async_open_consolidated_opt(
+            Arc::new(zarrs_object_store::AsyncObjectStore::new(
+                object_store::memory::InMemory::new(),
+            )),
+            array_path,
+            Some(consolidated),
+            &MetadataRetrieveVersion::Default,
+        )
+        .await
+        .unwrap();
+        assert_eq!(array_consolidated.metadata(), array.metadata());
+        assert!(!array_consolidated
+            .async_metadata_matches_store()
+            .await
+            .unwrap());
+
+        // Fall back to the store if absent from consolidated metadata
+        let array_store = Array::async_open_consolidated_opt(
+            store.clone(),
+            array_path,
+            Some(&ConsolidatedMetadataMap::new()),
+            &MetadataRetrieveVersion::Default,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            array_store.metadata(),
+            &array.metadata_opt(&ArrayMetadataOptions::default())
+        );
+        assert!(array_store.async_metadata_matches_store().await.unwrap());
+
+        // Detect stale consolidated metadata
+        let consolidated_metadata = serde_json::to_value(array.metadata()).unwrap();
+        array
+            .attributes_mut()
+            .insert("key".to_string(), "value".into());
+        array.async_store_metadata().await.unwrap();
+        let consolidated =
+            ConsolidatedMetadataMap::from([(meta_key_v3(array.path()), consolidated_metadata)]);
+        let array_stale = Array::async_open_consolidated_opt(
+            store,
+            array_path,
+            Some(&consolidated),
+            &MetadataRetrieveVersion::Default,
+        )
+        .await
+        .unwrap();
+        assert!(!array_stale.async_metadata_matches_store().await.unwrap());
+    }
+
     // fn array_subset_locking(locks: StoreLocks, expect_equal: bool) {
     //     let store = Arc::new(MemoryStore::new_with_locks(locks));
 
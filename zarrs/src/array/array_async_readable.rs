@@ -6,26 +6,51 @@ use unsafe_cell_slice::UnsafeCellSlice;
 use crate::{
     array_subset::ArraySubset,
     config::MetadataRetrieveVersion,
-    node::{meta_key_v2_array, meta_key_v2_attributes, meta_key_v3, NodePath},
-    storage::{AsyncBytes, AsyncReadableStorageTraits, StorageError, StorageHandle},
+    node::{
+        meta_key_v2_array, meta_key_v2_attributes, meta_key_v3, node_type_v3, strip_bom,
+        ConsolidatedMetadataSource, NodePath,
+    },
+    storage::{
+        storage_adapter::async_timeout::AsyncTimeoutStorageAdapter, AsyncBytes,
+        AsyncListableStorageTraits, AsyncReadableStorage, AsyncReadableStorageTraits, StorageError,
+        StorageHandle, StoreKey, StorePrefix,
+    },
 };
 
 use super::{
     array_bytes::{copy_fill_value_into, merge_chunks_vlen},
+    array_metadata_v3_from_slice,
     codec::{
         options::CodecOptions, ArrayToBytesCodecTraits, AsyncArrayPartialDecoderTraits,
         AsyncStoragePartialDecoder,
     },
     concurrency::concurrency_chunks_and_codec,
     element::ElementOwned,
-    Array, ArrayBytes, ArrayCreateError, ArrayError, ArrayMetadata, ArrayMetadataV2,
-    ArrayMetadataV3, ArraySize, DataTypeSize,
+    Array, ArrayBytes, ArrayCreateError, ArrayError, ArrayIndices, ArrayMetadata, ArrayMetadataV2,
+    ArraySize, DataTypeSize,
 };
 
 #[cfg(feature = "ndarray")]
 use super::elements_to_ndarray;
 
 impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
+    /// Async variant of [`exists`](Array::exists).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_exists(
+        storage: &Arc<TStorage>,
+        path: &str,
+    ) -> Result<bool, ArrayCreateError> {
+        let node_path: NodePath = path.try_into()?;
+        let key_v3 = meta_key_v3(&node_path);
+        if let Some(metadata) = storage.get(&key_v3).await? {
+            return Ok(node_type_v3(key_v3, &metadata)? == "array");
+        }
+        Ok(storage
+            .size_key(&meta_key_v2_array(&node_path))
+            .await?
+            .is_some())
+    }
+
     /// Async variant of [`open`](Array::open).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_open(
@@ -48,8 +73,8 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             // Try V3
             let key_v3 = meta_key_v3(&node_path);
             if let Some(metadata) = storage.get(&key_v3).await? {
-                let metadata: ArrayMetadataV3 = serde_json::from_slice(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
+                let metadata =
+                    array_metadata_v3_from_slice(&node_path, &key_v3, strip_bom(&metadata))?;
                 return Self::new_with_metadata(storage, path, ArrayMetadata::V3(metadata));
             }
         }
@@ -58,15 +83,16 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             // Try V2
             let key_v2 = meta_key_v2_array(&node_path);
             if let Some(metadata) = storage.get(&key_v2).await? {
-                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
 
                 let attributes_key = meta_key_v2_attributes(&node_path);
                 let attributes = storage.get(&attributes_key).await?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
 
                 return Self::new_with_metadata(storage, path, ArrayMetadata::V2(metadata));
@@ -76,6 +102,58 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         Err(ArrayCreateError::MissingMetadata)
     }
 
+    /// Async variant of [`reload`](Array::reload).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_reload(&mut self) -> Result<(), ArrayCreateError> {
+        let reloaded = Self::async_open(self.storage.clone(), self.path.as_str()).await?;
+        self.replace_metadata(reloaded);
+        Ok(())
+    }
+
+    /// Async variant of [`open_opt`](Array::open_opt) that first consults `consolidated` metadata.
+    ///
+    /// If `consolidated` is provided and holds the metadata of the array, the array is opened without a storage request.
+    /// Otherwise, the metadata is read from the store as in [`async_open_opt`](Array::async_open_opt).
+    ///
+    /// Consolidated metadata can be stale.
+    /// Use [`async_metadata_matches_store`](Array::async_metadata_matches_store) to detect if it does not match the metadata in the store.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is a storage error, the consolidated metadata cannot be loaded, or any metadata is invalid.
+    pub async fn async_open_consolidated_opt(
+        storage: Arc<TStorage>,
+        path: &str,
+        consolidated: Option<&dyn ConsolidatedMetadataSource>,
+        version: &MetadataRetrieveVersion,
+    ) -> Result<Array<TStorage>, ArrayCreateError> {
+        if let Some(consolidated) = consolidated {
+            let node_path = NodePath::new(path)?;
+            if let Some(metadata) = consolidated.consolidated_array_metadata(&node_path, version)? {
+                return Self::new_with_metadata(storage, path, metadata);
+            }
+        }
+        Self::async_open_opt(storage, path, version).await
+    }
+
+    /// Returns true if the metadata of the array matches the metadata of the array in the store.
+    ///
+    /// This can detect if an array opened with [`async_open_consolidated_opt`](Array::async_open_consolidated_opt) has stale consolidated metadata.
+    /// Returns false if the array metadata is missing from the store.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is a storage error or the metadata in the store is invalid.
+    pub async fn async_metadata_matches_store(&self) -> Result<bool, ArrayCreateError> {
+        let version = match self.metadata() {
+            ArrayMetadata::V3(_) => MetadataRetrieveVersion::V3,
+            ArrayMetadata::V2(_) => MetadataRetrieveVersion::V2,
+        };
+        match Self::async_open_opt(self.storage.clone(), self.path().as_str(), &version).await {
+            Ok(array) => Ok(array.metadata() == self.metadata()),
+            Err(ArrayCreateError::MissingMetadata) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Async variant of [`retrieve_chunk_if_exists`](Array::retrieve_chunk_if_exists).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunk_if_exists(
@@ -116,10 +194,8 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         &self,
         chunk_indices: &[u64],
     ) -> Result<Option<AsyncBytes>, StorageError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
         let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle)
+            .async_readable_storage(&CodecOptions::default())
             .await?;
 
         storage_transformer
@@ -168,6 +244,16 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_chunks_bounded`](Array::retrieve_chunks_bounded).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_bounded(
+        &self,
+        chunks: &ArraySubset,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        self.async_retrieve_chunks_bounded_opt(chunks, &CodecOptions::default())
+            .await
+    }
+
     /// Async variant of [`retrieve_chunks_elements`](Array::retrieve_chunks_elements).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_retrieve_chunks_elements<T: ElementOwned + Send + Sync>(
@@ -251,6 +337,19 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_array_subset_elements_with_mask`](Array::retrieve_array_subset_elements_with_mask).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_array_subset_elements_with_mask<T: ElementOwned + Send + Sync>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<(Vec<T>, Vec<bool>), ArrayError> {
+        self.async_retrieve_array_subset_elements_with_mask_opt(
+            array_subset,
+            &CodecOptions::default(),
+        )
+        .await
+    }
+
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_array_subset_ndarray`](Array::retrieve_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
@@ -262,6 +361,14 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_all`](Array::retrieve_all).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_all<T: ElementOwned + Send + Sync>(
+        &self,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.async_retrieve_all_opt(&CodecOptions::default()).await
+    }
+
     /// Async variant of [`partial_decoder`](Array::partial_decoder).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
     pub async fn async_partial_decoder(
@@ -288,11 +395,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                 chunk_indices.to_vec(),
             ));
         }
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle)
-            .await?;
+        let storage_transformer = self.async_readable_storage(options).await?;
         let chunk_encoded = storage_transformer
             .get(&self.chunk_key(chunk_indices))
             .await
@@ -301,7 +404,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             let chunk_encoded: Vec<u8> = chunk_encoded.into();
             let chunk_representation = self.chunk_array_representation(chunk_indices)?;
             let bytes = self
-                .codecs()
+                .chunk_codecs(chunk_indices)
                 .decode(Cow::Owned(chunk_encoded), &chunk_representation, options)
                 .map_err(ArrayError::CodecError)?;
             bytes.validate(
@@ -327,9 +430,11 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         if let Some(chunk) = chunk {
             Ok(chunk)
         } else {
-            let chunk_shape = self.chunk_shape(chunk_indices)?;
-            let array_size =
-                ArraySize::new(self.data_type().size(), chunk_shape.num_elements_u64());
+            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+            let array_size = ArraySize::new(
+                chunk_representation.data_type().size(),
+                chunk_representation.num_elements(),
+            );
             Ok(ArrayBytes::new_fill_value(array_size, self.fill_value()))
         }
     }
@@ -348,11 +453,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                 chunk_indices.to_vec(),
             ));
         }
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle)
-            .await?;
+        let storage_transformer = self.async_readable_storage(options).await?;
         let chunk_encoded = storage_transformer
             .get(&self.chunk_key(chunk_indices))
             .await
@@ -360,7 +461,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         if let Some(chunk_encoded) = chunk_encoded {
             let chunk_encoded: Vec<u8> = chunk_encoded.into();
             let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            self.codecs()
+            self.chunk_codecs(chunk_indices)
                 .decode_into(
                     Cow::Owned(chunk_encoded),
                     &chunk_representation,
@@ -468,11 +569,7 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         chunks: &ArraySubset,
         options: &CodecOptions,
     ) -> Result<Vec<Option<AsyncBytes>>, StorageError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle)
-            .await?;
+        let storage_transformer = self.async_readable_storage(options).await?;
 
         let retrieve_encoded_chunk = |chunk_indices: Vec<u64>| {
             let storage_transformer = storage_transformer.clone();
@@ -510,6 +607,25 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             .await
     }
 
+    /// Async variant of [`retrieve_chunks_bounded_opt`](Array::retrieve_chunks_bounded_opt).
+    #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+    pub async fn async_retrieve_chunks_bounded_opt(
+        &self,
+        chunks: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        if chunks.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                chunks.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+
+        let array_subset = self.chunks_subset_bounded(chunks)?;
+        self.async_retrieve_array_subset_opt(&array_subset, options)
+            .await
+    }
+
     /// Async variant of [`retrieve_chunks_elements_opt`](Array::retrieve_chunks_elements_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_retrieve_chunks_elements_opt<T: ElementOwned + Send + Sync>(
@@ -551,6 +667,20 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                 self.shape().to_vec(),
             ));
         }
+        Self::retrieve_element_limit_validate(array_subset.shape(), options)?;
+
+        if options.allow_oob_fill() && !self.array_subset_is_inbounds(array_subset) {
+            // Retrieve the in-bounds region and pad with the fill value
+            let inbounds = if let Some(inbounds_subset) = self.array_subset_inbounds(array_subset) {
+                let inbounds_bytes =
+                    Box::pin(self.async_retrieve_array_subset_opt(&inbounds_subset, options))
+                        .await?;
+                Some((inbounds_subset, inbounds_bytes))
+            } else {
+                None
+            };
+            return self.pad_oob_fill(array_subset, inbounds);
+        }
 
         // Find the chunks intersecting this array subset
         let chunks = self.chunks_in_array_subset(array_subset)?;
@@ -588,10 +718,10 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             }
             _ => {
                 // Calculate chunk/codec concurrency
-                let chunk_representation =
-                    self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+                let chunk_indices0 = vec![0; self.dimensionality()];
+                let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_chunks,
@@ -599,6 +729,20 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
                     &codec_concurrency,
                 );
 
+                if self.deduplicate_chunk_reads() {
+                    let chunk_groups = self.chunk_groups_by_key(&chunks)?;
+                    if chunk_groups.len() < num_chunks {
+                        return self
+                            .async_retrieve_array_subset_chunk_groups(
+                                array_subset,
+                                chunk_groups,
+                                chunk_concurrent_limit,
+                                &options,
+                            )
+                            .await;
+                    }
+                }
+
                 match chunk_representation.data_type().size() {
                     DataTypeSize::Variable => {
                         let retrieve_chunk = |chunk_indices: Vec<u64>| {
@@ -693,6 +837,31 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Async variant of [`retrieve_array_subset_chunk_groups`](Array::retrieve_array_subset_chunk_groups).
+    async fn async_retrieve_array_subset_chunk_groups(
+        &self,
+        array_subset: &ArraySubset,
+        chunk_groups: Vec<Vec<ArrayIndices>>,
+        chunk_concurrent_limit: usize,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        let retrieve_chunk_group = |chunk_group: Vec<ArrayIndices>| async move {
+            let chunk_bytes = self
+                .async_retrieve_chunk_opt(&chunk_group[0], options)
+                .await?;
+            self.chunk_group_bytes_and_subsets(array_subset, &chunk_group, &chunk_bytes)
+        };
+        let chunk_bytes_and_subsets: Vec<Vec<(ArrayBytes<'_>, ArraySubset)>> =
+            futures::stream::iter(chunk_groups.into_iter().map(retrieve_chunk_group))
+                .buffered(chunk_concurrent_limit)
+                .try_collect()
+                .await?;
+        self.merge_chunk_group_bytes(
+            array_subset,
+            chunk_bytes_and_subsets.into_iter().flatten().collect(),
+        )
+    }
+
     /// Async variant of [`retrieve_array_subset_elements_opt`](Array::retrieve_array_subset_elements_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_retrieve_array_subset_elements_opt<T: ElementOwned + Send + Sync>(
@@ -707,6 +876,34 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         Ok(elements)
     }
 
+    /// Async variant of [`retrieve_array_subset_elements_with_mask_opt`](Array::retrieve_array_subset_elements_with_mask_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_array_subset_elements_with_mask_opt<
+        T: ElementOwned + Send + Sync,
+    >(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<(Vec<T>, Vec<bool>), ArrayError> {
+        let bytes = self
+            .async_retrieve_array_subset_opt(array_subset, options)
+            .await?;
+        let mask = self.fill_value_mask(&bytes);
+        let elements = T::from_array_bytes(self.data_type(), bytes)?;
+        Ok((elements, mask))
+    }
+
+    /// Async variant of [`retrieve_all_opt`](Array::retrieve_all_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_retrieve_all_opt<T: ElementOwned + Send + Sync>(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_all_validate::<T>(options)?;
+        self.async_retrieve_array_subset_elements_opt(&self.subset_all(), options)
+            .await
+    }
+
     #[cfg(feature = "ndarray")]
     /// Async variant of [`retrieve_array_subset_ndarray_opt`](Array::retrieve_array_subset_ndarray_opt).
     #[allow(clippy::missing_errors_doc)]
@@ -744,16 +941,12 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             self.async_retrieve_chunk_opt(chunk_indices, options)
                 .await?
         } else {
-            let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-            let storage_transformer = self
-                .storage_transformers()
-                .create_async_readable_transformer(storage_handle)
-                .await?;
+            let storage_transformer = self.async_readable_storage(options).await?;
             let input_handle = Arc::new(AsyncStoragePartialDecoder::new(
                 storage_transformer,
                 self.chunk_key(chunk_indices),
             ));
-            self.codecs
+            self.chunk_codecs(chunk_indices)
                 .clone()
                 .async_partial_decoder(input_handle, &chunk_representation, options)
                 .await?
@@ -796,18 +989,14 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
             )
             .await
         } else {
-            let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-            let storage_transformer = self
-                .storage_transformers()
-                .create_async_readable_transformer(storage_handle)
-                .await?;
+            let storage_transformer = self.async_readable_storage(options).await?;
             let input_handle = Arc::new(AsyncStoragePartialDecoder::new(
                 storage_transformer,
                 self.chunk_key(chunk_indices),
             ));
 
             Ok(self
-                .codecs
+                .chunk_codecs(chunk_indices)
                 .clone()
                 .async_partial_decoder(input_handle, &chunk_representation, options)
                 .await?
@@ -853,20 +1042,87 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits + 'static> Array<TStorage> {
         chunk_indices: &[u64],
         options: &CodecOptions,
     ) -> Result<Arc<dyn AsyncArrayPartialDecoderTraits>, ArrayError> {
-        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
-        let storage_transformer = self
-            .storage_transformers()
-            .create_async_readable_transformer(storage_handle)
-            .await?;
+        let storage_transformer = self.async_readable_storage(options).await?;
         let input_handle = Arc::new(AsyncStoragePartialDecoder::new(
             storage_transformer,
             self.chunk_key(chunk_indices),
         ));
         let chunk_representation = self.chunk_array_representation(chunk_indices)?;
         Ok(self
-            .codecs
+            .chunk_codecs(chunk_indices)
             .clone()
             .async_partial_decoder(input_handle, &chunk_representation, options)
             .await?)
     }
+
+    /// Return the async readable storage of the array with its storage transformers applied.
+    ///
+    /// Each read is subject to the [async read timeout](CodecOptions::async_read_timeout) if set.
+    async fn async_readable_storage(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<AsyncReadableStorage, StorageError> {
+        let storage_handle: AsyncReadableStorage =
+            if let Some(timeout) = options.async_read_timeout() {
+                Arc::new(AsyncTimeoutStorageAdapter::new(
+                    self.storage.clone(),
+                    timeout,
+                ))
+            } else {
+                Arc::new(StorageHandle::new(self.storage.clone()))
+            };
+        self.storage_transformers()
+            .create_async_readable_transformer(storage_handle)
+            .await
+    }
+}
+
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits + 'static>
+    Array<TStorage>
+{
+    /// Async variant of [`chunk_size_report`](Array::chunk_size_report).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_chunk_size_report(&self) -> Result<Vec<(ArrayIndices, u64)>, StorageError> {
+        self.async_chunk_size_report_opt(&CodecOptions::default())
+            .await
+    }
+
+    /// Async variant of [`chunk_size_report_opt`](Array::chunk_size_report_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_chunk_size_report_opt(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<Vec<(ArrayIndices, u64)>, StorageError> {
+        let storage_transformer = self.async_readable_storage(options).await?;
+        let listable_transformer = self
+            .storage_transformers()
+            .create_async_listable_transformer(Arc::new(StorageHandle::new(self.storage.clone())))
+            .await?;
+
+        let prefix: StorePrefix = self.path().try_into()?;
+        let chunk_keys: Vec<(ArrayIndices, StoreKey)> = listable_transformer
+            .list_prefix(&prefix)
+            .await?
+            .into_iter()
+            .filter_map(|key| Some((self.chunk_grid_indices_from_key(&key)?, key)))
+            .collect();
+
+        let chunk_size = |(chunk_indices, key): (ArrayIndices, StoreKey)| {
+            let storage_transformer = storage_transformer.clone();
+            async move {
+                storage_transformer
+                    .size_key(&key)
+                    .await
+                    .map(|size| size.map(|size| (chunk_indices, size)))
+            }
+        };
+        let sizes: Vec<Option<(ArrayIndices, u64)>> =
+            futures::stream::iter(chunk_keys.into_iter().map(chunk_size))
+                .buffered(options.concurrent_target())
+                .try_collect()
+                .await?;
+        let mut sizes: Vec<(ArrayIndices, u64)> = sizes.into_iter().flatten().collect();
+        sizes.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(sizes)
+    }
 }
@@ -6,7 +6,7 @@ use crate::{
 
 use super::{
     array_bytes::update_array_bytes, codec::options::CodecOptions,
-    concurrency::concurrency_chunks_and_codec, Array, ArrayError, Element,
+    concurrency::concurrency_chunks_and_codec, Array, ArrayError, ArraySize, Element,
 };
 
 impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TStorage> {
@@ -91,6 +91,16 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
         .await
     }
 
+    /// Async variant of [`store_all`](Array::store_all).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_all<T: Element + Send + Sync>(
+        &self,
+        elements: &[T],
+    ) -> Result<(), ArrayError> {
+        self.async_store_all_opt(elements, &CodecOptions::default())
+            .await
+    }
+
     #[cfg(feature = "ndarray")]
     /// Async variant of [`store_array_subset_ndarray`](Array::store_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
@@ -136,6 +146,9 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                 chunk_shape,
             ));
         }
+        if options.assume_new_chunks() && chunk_subset.start().iter().any(|&x| x != 0) {
+            return Err(ArrayError::UnalignedSubset(chunk_subset.clone()));
+        }
 
         if chunk_subset.shape() == chunk_shape && chunk_subset.start().iter().all(|&x| x == 0) {
             // The subset spans the whole chunk, so store the bytes directly and skip decoding
@@ -152,10 +165,20 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
 
             // TODO: Add async partial encoding
 
-            // Decode the entire chunk
-            let chunk_bytes_old = self
-                .async_retrieve_chunk_opt(chunk_indices, options)
-                .await?;
+            // Decode the entire chunk, or fill it if it is new
+            let new_chunk_fill_value = self.new_chunk_fill_value(options)?;
+            let chunk_bytes_old = if options.assume_new_chunks() {
+                None
+            } else {
+                self.async_retrieve_chunk_if_exists_opt(chunk_indices, options)
+                    .await?
+            };
+            let chunk_bytes_old = chunk_bytes_old.unwrap_or_else(|| {
+                ArrayBytes::new_fill_value(
+                    ArraySize::new(self.data_type().size(), chunk_shape.iter().product()),
+                    new_chunk_fill_value,
+                )
+            });
 
             // Update the chunk
             let chunk_bytes_new = unsafe {
@@ -236,6 +259,13 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
             ));
         }
 
+        // Storing an empty array subset (e.g. in an array with a zero-size dimension) is a no-op
+        if array_subset.is_empty() {
+            let subset_bytes = subset_bytes.into();
+            subset_bytes.validate(0, self.data_type().size())?;
+            return Ok(());
+        }
+
         // Find the chunks intersecting this array subset
         let chunks = self.chunks_in_array_subset(array_subset)?;
         let Some(chunks) = chunks else {
@@ -244,6 +274,11 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
                 self.shape().to_vec(),
             ));
         };
+        if options.assume_new_chunks()
+            && self.chunk_subset(chunks.start())?.start() != array_subset.start()
+        {
+            return Err(ArrayError::UnalignedSubset(array_subset.clone()));
+        }
         let num_chunks = chunks.num_elements_usize();
         if num_chunks == 1 {
             let chunk_indices = chunks.start();
@@ -268,9 +303,10 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
             subset_bytes.validate(array_subset.num_elements(), self.data_type().size())?;
 
             // Calculate chunk/codec concurrency
-            let chunk_representation =
-                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
-            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let chunk_indices0 = vec![0; self.dimensionality()];
+            let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
+            let codec_concurrency =
+                self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
             let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                 options.concurrent_target(),
                 num_chunks,
@@ -312,6 +348,19 @@ impl<TStorage: ?Sized + AsyncReadableWritableStorageTraits + 'static> Array<TSto
         Ok(())
     }
 
+    /// Async variant of [`store_all_opt`](Array::store_all_opt).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_store_all_opt<T: Element + Send + Sync>(
+        &self,
+        elements: &[T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let options = self.store_all_options(elements.len(), options)?;
+        let bytes = T::into_array_bytes(self.data_type(), elements)?;
+        self.async_store_array_subset_opt(&self.subset_all(), bytes, &options)
+            .await
+    }
+
     /// Async variant of [`store_array_subset_elements_opt`](Array::store_array_subset_elements_opt).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_store_array_subset_elements_opt<T: Element + Send + Sync>(
@@ -6,7 +6,10 @@ use crate::{
     array::ArrayBytes,
     array_subset::ArraySubset,
     config::{global_config, MetadataEraseVersion},
-    node::{meta_key_v2_array, meta_key_v2_attributes, meta_key_v3},
+    node::{
+        async_set_metadata, meta_key_v2_array, meta_key_v2_attributes, meta_key_v3,
+        metadata_v3_to_json,
+    },
     storage::{AsyncBytes, AsyncWritableStorageTraits, StorageError, StorageHandle},
 };
 
@@ -44,22 +47,32 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
         match metadata {
             ArrayMetadata::V3(metadata) => {
                 let key = meta_key_v3(path);
-                let json = serde_json::to_vec_pretty(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_transformer.set(&key, json.into()).await
+                let json =
+                    metadata_v3_to_json(&key, &metadata, options.serialize_empty_attributes())?;
+                async_set_metadata(
+                    &*storage_transformer,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
+                .await
             }
             ArrayMetadata::V2(metadata) => {
                 let mut metadata = metadata.clone();
 
-                if !metadata.attributes.is_empty() {
+                if !metadata.attributes.is_empty() || options.serialize_empty_attributes() {
                     // Store .zattrs
                     let key = meta_key_v2_attributes(path);
                     let json = serde_json::to_vec_pretty(&metadata.attributes).map_err(|err| {
                         StorageError::InvalidMetadata(key.clone(), err.to_string())
                     })?;
-                    storage_transformer
-                        .set(&meta_key_v2_attributes(path), json.into())
-                        .await?;
+                    async_set_metadata(
+                        &*storage_transformer,
+                        &key,
+                        json.into(),
+                        options.stage_metadata_writes(),
+                    )
+                    .await?;
 
                     metadata.attributes = serde_json::Map::default();
                 }
@@ -68,7 +81,13 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
                 let key = meta_key_v2_array(path);
                 let json = serde_json::to_vec_pretty(&metadata)
                     .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_transformer.set(&key, json.into()).await
+                async_set_metadata(
+                    &*storage_transformer,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
+                .await
             }
         }
     }
@@ -251,7 +270,7 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
             self.async_erase_chunk(chunk_indices).await?;
         } else {
             let chunk_encoded = self
-                .codecs()
+                .chunk_codecs(chunk_indices)
                 .encode(chunk_bytes, &chunk_array_representation, options)
                 .map_err(ArrayError::CodecError)?;
             let chunk_encoded = AsyncBytes::from(chunk_encoded.to_vec());
@@ -340,10 +359,10 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits + 'static> Array<TStorage> {
                 chunks_bytes.validate(array_subset.num_elements(), self.data_type().size())?;
 
                 // Calculate chunk/codec concurrency
-                let chunk_representation =
-                    self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+                let chunk_indices0 = vec![0; self.dimensionality()];
+                let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_chunks,
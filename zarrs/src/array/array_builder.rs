@@ -1,25 +1,29 @@
-use std::sync::Arc;
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
 
 use crate::{
+    config::{global_config, DuplicateDimensionNames},
     metadata::{v3::AdditionalFields, ChunkKeySeparator},
     node::NodePath,
 };
 
 use super::{
+    chunk_codecs::ChunkCodecs,
     chunk_key_encoding::{ChunkKeyEncoding, DefaultChunkKeyEncoding},
     codec::{
         array_to_bytes::vlen::VlenCodec, ArrayToArrayCodecTraits, ArrayToBytesCodecTraits,
-        BytesCodec, BytesToBytesCodecTraits,
+        BytesCodec, BytesToBytesCodecTraits, CodecOptions,
     },
     data_type::IncompatibleFillValueError,
-    Array, ArrayCreateError, ArrayMetadata, ArrayMetadataV3, ArrayShape, ChunkGrid, CodecChain,
-    DataType, DimensionName, FillValue, StorageTransformerChain,
+    Array, ArrayBytes, ArrayCreateError, ArrayError, ArrayMetadata, ArrayMetadataV3, ArrayShape,
+    ChunkGrid, ChunkRepresentation, CodecChain, DataType, DimensionName, FillValue,
+    StorageTransformerChain,
 };
 
 /// An [`Array`] builder.
 ///
 /// The array builder is initialised from an array shape, data type, chunk grid, and fill value.
 ///  - The only codec enabled by default is `bytes` (with native endian encoding), so the output is uncompressed.
+///    The default codecs can be changed in the global [`Config`](crate::config::Config#default-array-to-bytes-codec).
 ///  - The default chunk key encoding is `default` with the `/` chunk key separator.
 ///  - Attributes, storage transformers, and dimension names are empty.
 ///  - Codecs are configured to use multiple threads where possible.
@@ -98,20 +102,34 @@ impl ArrayBuilder {
         fill_value: FillValue,
     ) -> Self {
         let is_fixed_size = data_type.fixed_size().is_some();
+        let (array_to_bytes_codec, bytes_to_bytes_codecs, chunk_key_separator) = {
+            let config = global_config();
+            let array_to_bytes_codec: Arc<dyn ArrayToBytesCodecTraits> = if is_fixed_size {
+                config
+                    .default_array_to_bytes_codec()
+                    .cloned()
+                    .unwrap_or_else(|| Arc::new(BytesCodec::default()))
+            } else {
+                Arc::<VlenCodec>::default()
+                // Arc::<VlenV2Codec>::default()
+            };
+            (
+                array_to_bytes_codec,
+                config.default_bytes_to_bytes_codecs().to_vec(),
+                config.default_chunk_key_separator(),
+            )
+        };
         Self {
             shape,
             data_type,
             chunk_grid,
-            chunk_key_encoding: ChunkKeyEncoding::new(DefaultChunkKeyEncoding::default()),
+            chunk_key_encoding: ChunkKeyEncoding::new(DefaultChunkKeyEncoding::new(
+                chunk_key_separator,
+            )),
             fill_value,
             array_to_array_codecs: Vec::default(),
-            array_to_bytes_codec: if is_fixed_size {
-                Arc::<BytesCodec>::default()
-            } else {
-                Arc::<VlenCodec>::default()
-                // Arc::<VlenV2Codec>::default()
-            },
-            bytes_to_bytes_codecs: Vec::default(),
+            array_to_bytes_codec,
+            bytes_to_bytes_codecs,
             attributes: serde_json::Map::default(),
             storage_transformers: StorageTransformerChain::default(),
             dimension_names: None,
@@ -166,7 +184,7 @@ impl ArrayBuilder {
 
     /// Set the chunk key encoding.
     ///
-    /// If left unmodified, the array will use `default` chunk key encoding with the `/` chunk key separator.
+    /// If left unmodified, the array will use `default` chunk key encoding with the [default chunk key separator](crate::config::Config#default-chunk-key-separator) (`/` unless configured otherwise).
     pub fn chunk_key_encoding(&mut self, chunk_key_encoding: ChunkKeyEncoding) -> &mut Self {
         self.chunk_key_encoding = chunk_key_encoding;
         self
@@ -174,7 +192,7 @@ impl ArrayBuilder {
 
     /// Set the chunk key encoding to default with `separator`.
     ///
-    /// If left unmodified, the array will use `default` chunk key encoding with the `/` chunk key separator.
+    /// If left unmodified, the array will use `default` chunk key encoding with the [default chunk key separator](crate::config::Config#default-chunk-key-separator) (`/` unless configured otherwise).
     pub fn chunk_key_encoding_default_separator(
         &mut self,
         separator: ChunkKeySeparator,
@@ -197,6 +215,7 @@ impl ArrayBuilder {
     /// Set the array to bytes codec.
     ///
     /// If left unmodified, the array will default to using the `bytes` codec with native endian encoding.
+    /// This overrides the [default array to bytes codec](crate::config::Config#default-array-to-bytes-codec) of the global config.
     pub fn array_to_bytes_codec(
         &mut self,
         array_to_bytes_codec: Arc<dyn ArrayToBytesCodecTraits>,
@@ -208,6 +227,7 @@ impl ArrayBuilder {
     /// Set the bytes to bytes codecs.
     ///
     /// If left unmodified, the array will have no bytes to bytes codecs.
+    /// This overrides the [default bytes to bytes codecs](crate::config::Config#default-bytes-to-bytes-codecs) of the global config.
     pub fn bytes_to_bytes_codecs(
         &mut self,
         bytes_to_bytes_codecs: Vec<Arc<dyn BytesToBytesCodecTraits>>,
@@ -216,6 +236,78 @@ impl ArrayBuilder {
         self
     }
 
+    /// Select the bytes to bytes codecs by trialling compression codecs on a `sample` chunk, and return the resulting codec chain.
+    ///
+    /// The `sample` is the decoded bytes of a representative chunk with the shape of the chunk at the origin of the chunk grid.
+    /// It is encoded with the array to array and array to bytes codecs of the builder, and then with each candidate compression codec in order of increasing expected encoding cost:
+    ///  - no compression,
+    ///  - `blosc` (`lz4`) with compression levels 1, 5, and 9,
+    ///  - `zstd` with compression levels 1, 3, 9, and 19, and
+    ///  - `gzip` with compression levels 1, 6, and 9.
+    ///
+    /// Candidates are only trialled if their codec feature is enabled.
+    /// The first candidate with a compression ratio (decoded size / encoded size) of at least `target_compression_ratio` is selected, which is the cheapest candidate to encode that meets the target.
+    /// If no candidate reaches the target, the candidate with the highest compression ratio is selected.
+    /// Encoding times are not measured, so the selection is deterministic for a given sample and target.
+    ///
+    /// The selected codecs are set as the bytes to bytes codecs of the builder.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the chunk at the origin of the chunk grid is invalid,
+    ///  - the fill value is incompatible with the data type, or
+    ///  - the sample cannot be encoded (e.g. it does not match the chunk shape).
+    pub fn autotune_bytes_to_bytes_codecs(
+        &mut self,
+        sample: ArrayBytes<'_>,
+        target_compression_ratio: f64,
+    ) -> Result<CodecChain, ArrayError> {
+        let chunk_indices = vec![0; self.chunk_grid.dimensionality()];
+        let chunk_shape = self
+            .chunk_grid
+            .chunk_shape(&chunk_indices, &self.shape)?
+            .ok_or_else(|| ArrayError::InvalidChunkGridIndicesError(chunk_indices))?;
+        let chunk_representation = ChunkRepresentation::new(
+            chunk_shape.to_vec(),
+            self.data_type.clone(),
+            self.fill_value.clone(),
+        )?;
+
+        let options = CodecOptions::default();
+        let encoded = CodecChain::new(
+            self.array_to_array_codecs.clone(),
+            self.array_to_bytes_codec.clone(),
+            vec![],
+        )
+        .encode(sample, &chunk_representation, &options)?;
+
+        let mut best_compression_ratio = f64::NEG_INFINITY;
+        let mut bytes_to_bytes_codecs = vec![];
+        for candidate in autotune_candidates(self.data_type.fixed_size()) {
+            let mut compressed = Cow::Borrowed(encoded.as_ref());
+            for codec in &candidate {
+                compressed = Cow::Owned(codec.encode(compressed, &options)?.into_owned());
+            }
+            #[allow(clippy::cast_precision_loss)]
+            let compression_ratio = encoded.len() as f64 / compressed.len().max(1) as f64;
+            let target_reached = compression_ratio >= target_compression_ratio;
+            if target_reached || compression_ratio > best_compression_ratio {
+                best_compression_ratio = compression_ratio;
+                bytes_to_bytes_codecs = candidate;
+            }
+            if target_reached {
+                break;
+            }
+        }
+
+        self.bytes_to_bytes_codecs = bytes_to_bytes_codecs;
+        Ok(CodecChain::new(
+            self.array_to_array_codecs.clone(),
+            self.array_to_bytes_codec.clone(),
+            self.bytes_to_bytes_codecs.clone(),
+        ))
+    }
+
     /// Set the user defined attributes.
     ///
     /// If left unmodified, the user defined attributes of the array will be empty.
@@ -271,42 +363,103 @@ impl ArrayBuilder {
         self
     }
 
-    /// Build into an [`Array`].
+    /// Validate the array configuration without building the array.
     ///
-    /// # Errors
+    /// This runs every check performed by [`build`](ArrayBuilder::build), except for path validation, and collects all of the errors rather than returning the first.
+    /// The storage is not accessed.
     ///
-    /// Returns [`ArrayCreateError`] if there is an error creating the array.
-    /// This can be due to a storage error, an invalid path, or a problem with array configuration.
-    pub fn build<TStorage: ?Sized>(
-        &self,
-        storage: Arc<TStorage>,
-        path: &str,
-    ) -> Result<Array<TStorage>, ArrayCreateError> {
-        let path: NodePath = path.try_into()?;
+    /// # Errors
+    /// Returns every [`ArrayCreateError`] with the array configuration, in the order they are checked by [`build`](ArrayBuilder::build).
+    pub fn validate(&self) -> Result<(), Vec<ArrayCreateError>> {
+        let errors = self.validation_errors(None);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Return every error with the array configuration.
+    fn validation_errors(&self, path: Option<&NodePath>) -> Vec<ArrayCreateError> {
+        let mut errors = Vec::new();
         if self.chunk_grid.dimensionality() != self.shape.len() {
-            return Err(ArrayCreateError::InvalidChunkGridDimensionality(
+            errors.push(ArrayCreateError::InvalidChunkGridDimensionality(
                 self.chunk_grid.dimensionality(),
                 self.shape.len(),
             ));
         }
         if let Some(dimension_names) = &self.dimension_names {
             if dimension_names.len() != self.shape.len() {
-                return Err(ArrayCreateError::InvalidDimensionNames(
+                errors.push(ArrayCreateError::InvalidDimensionNames(
                     dimension_names.len(),
                     self.shape.len(),
                 ));
             }
+            let duplicate_dimension_names = global_config().duplicate_dimension_names();
+            if duplicate_dimension_names != DuplicateDimensionNames::Allow {
+                let mut names = HashSet::new();
+                if let Some(duplicate) = dimension_names
+                    .iter()
+                    .filter_map(DimensionName::as_str)
+                    .find(|name| !names.insert(*name))
+                {
+                    if duplicate_dimension_names == DuplicateDimensionNames::Error {
+                        errors.push(ArrayCreateError::DuplicateDimensionName(
+                            duplicate.to_string(),
+                        ));
+                    } else if let Some(path) = path {
+                        log::warn!("array at {path} has duplicate dimension name {duplicate:?}");
+                    }
+                }
+            }
         }
 
         if let Some(data_type_size) = self.data_type.fixed_size() {
             if data_type_size != self.fill_value.size() {
-                return Err(IncompatibleFillValueError::new(
-                    self.data_type.name(),
-                    self.fill_value.clone(),
-                )
-                .into());
+                errors.push(
+                    IncompatibleFillValueError::new(self.data_type.name(), self.fill_value.clone())
+                        .into(),
+                );
+            }
+        }
+
+        if let Some(data_type_size) = self.data_type.fixed_size() {
+            let chunk_indices = vec![0; self.shape.len()];
+            if let Ok(Some(chunk_shape)) = self.chunk_grid.chunk_shape(&chunk_indices, &self.shape)
+            {
+                let chunk_size = chunk_shape.iter().fold(data_type_size as u64, |size, dim| {
+                    size.saturating_mul(dim.get())
+                });
+                let chunk_size_limit = global_config().chunk_size_limit();
+                if chunk_size > chunk_size_limit {
+                    errors.push(ArrayCreateError::ChunkTooLarge(
+                        chunk_size,
+                        chunk_size_limit,
+                    ));
+                }
             }
         }
+        errors
+    }
+
+    /// Build into an [`Array`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrayCreateError`] if there is an error creating the array.
+    /// This can be due to a storage error, an invalid path, or a problem with array configuration.
+    /// The decoded size of the chunk at the origin of the chunk grid must not exceed the [chunk size limit](crate::config::Config#chunk-size-limit).
+    /// This covers every chunk of a regular chunk grid, but not the other chunks of an irregular chunk grid (e.g. `rectangular`).
+    /// Use [`validate`](ArrayBuilder::validate) to get every problem with the array configuration.
+    pub fn build<TStorage: ?Sized>(
+        &self,
+        storage: Arc<TStorage>,
+        path: &str,
+    ) -> Result<Array<TStorage>, ArrayCreateError> {
+        let path: NodePath = path.try_into()?;
+        if let Some(err) = self.validation_errors(Some(&path)).into_iter().next() {
+            return Err(err);
+        }
 
         let codec_chain = CodecChain::new(
             self.array_to_array_codecs.clone(),
@@ -340,11 +493,13 @@ impl ArrayBuilder {
                 self.array_to_bytes_codec.clone(),
                 self.bytes_to_bytes_codecs.clone(),
             )),
+            chunk_codecs: ChunkCodecs::default(),
             storage_transformers: self.storage_transformers.clone(),
             // attributes: self.attributes.clone(),
             dimension_names: self.dimension_names.clone(),
             // additional_fields: self.additional_fields.clone(),
             metadata: array_metadata,
+            deduplicate_chunk_reads: false,
         })
     }
 
@@ -363,6 +518,79 @@ impl ArrayBuilder {
     }
 }
 
+/// Return the candidate bytes to bytes codecs of [`ArrayBuilder::autotune_bytes_to_bytes_codecs`] in order of increasing expected encoding cost.
+fn autotune_candidates(
+    data_type_size: Option<usize>,
+) -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    std::iter::once(vec![])
+        .chain(autotune_candidates_blosc(data_type_size))
+        .chain(autotune_candidates_zstd())
+        .chain(autotune_candidates_gzip())
+        .collect()
+}
+
+#[cfg(feature = "blosc")]
+fn autotune_candidates_blosc(
+    data_type_size: Option<usize>,
+) -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    use super::codec::{
+        bytes_to_bytes::blosc::{BloscCompressionLevel, BloscCompressor, BloscShuffleMode},
+        BloscCodec,
+    };
+    let (shuffle_mode, typesize) = match data_type_size {
+        Some(typesize) if typesize > 1 => (BloscShuffleMode::Shuffle, Some(typesize)),
+        _ => (BloscShuffleMode::NoShuffle, None),
+    };
+    [1u8, 5, 9]
+        .into_iter()
+        .filter_map(|clevel| {
+            let clevel = BloscCompressionLevel::try_from(clevel).ok()?;
+            let codec =
+                BloscCodec::new(BloscCompressor::LZ4, clevel, None, shuffle_mode, typesize).ok()?;
+            Some(vec![Arc::new(codec) as Arc<dyn BytesToBytesCodecTraits>])
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "blosc"))]
+fn autotune_candidates_blosc(
+    _data_type_size: Option<usize>,
+) -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    vec![]
+}
+
+#[cfg(feature = "zstd")]
+fn autotune_candidates_zstd() -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    [1, 3, 9, 19]
+        .into_iter()
+        .map(|level| {
+            let codec = super::codec::ZstdCodec::new(level, false);
+            vec![Arc::new(codec) as Arc<dyn BytesToBytesCodecTraits>]
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "zstd"))]
+fn autotune_candidates_zstd() -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    vec![]
+}
+
+#[cfg(feature = "gzip")]
+fn autotune_candidates_gzip() -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    [1, 6, 9]
+        .into_iter()
+        .filter_map(|level| {
+            let codec = super::codec::GzipCodec::new(level).ok()?;
+            Some(vec![Arc::new(codec) as Arc<dyn BytesToBytesCodecTraits>])
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "gzip"))]
+fn autotune_candidates_gzip() -> Vec<Vec<Arc<dyn BytesToBytesCodecTraits>>> {
+    vec![]
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -428,6 +656,49 @@ mod tests {
         assert_eq!(builder.additional_fields, builder2.additional_fields);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn array_builder_autotune_bytes_to_bytes_codecs() {
+        let mut builder = ArrayBuilder::new(
+            vec![64, 64],
+            DataType::UInt16,
+            vec![32, 32].try_into().unwrap(),
+            FillValue::from(0u16),
+        );
+        let elements: Vec<u16> = (0..32 * 32).map(|i| i % 16).collect();
+        let sample = ArrayBytes::from(crate::array::transmute_to_bytes_vec(elements));
+
+        // No compression reaches the target
+        let codec_chain = builder
+            .autotune_bytes_to_bytes_codecs(sample.clone(), 1.0)
+            .unwrap();
+        assert!(codec_chain.bytes_to_bytes_codecs().is_empty());
+        assert!(builder.bytes_to_bytes_codecs.is_empty());
+
+        // Deterministic
+        let codec_chain = builder
+            .autotune_bytes_to_bytes_codecs(sample.clone(), 4.0)
+            .unwrap();
+        #[cfg(any(feature = "blosc", feature = "zstd", feature = "gzip"))]
+        assert_eq!(codec_chain.bytes_to_bytes_codecs().len(), 1);
+        let codec_chain_again = builder
+            .autotune_bytes_to_bytes_codecs(sample.clone(), 4.0)
+            .unwrap();
+        assert_eq!(
+            codec_chain.create_metadatas(),
+            codec_chain_again.create_metadatas()
+        );
+
+        // Unreachable target selects the candidate with the highest compression ratio
+        assert!(builder
+            .autotune_bytes_to_bytes_codecs(sample, f64::INFINITY)
+            .is_ok());
+
+        // Invalid sample
+        let sample = ArrayBytes::from(vec![0u8; 3]);
+        assert!(builder.autotune_bytes_to_bytes_codecs(sample, 4.0).is_err());
+    }
+
     #[test]
     fn array_builder_invalid() {
         let storage = Arc::new(MemoryStore::new());
@@ -457,4 +728,82 @@ mod tests {
         builder.dimension_names(["z", "y", "x"].into());
         assert!(builder.build(storage.clone(), "/").is_err());
     }
+
+    #[test]
+    fn array_builder_chunk_too_large() {
+        let storage = Arc::new(MemoryStore::new());
+        let builder = ArrayBuilder::new(
+            vec![1 << 20, 1 << 20],
+            DataType::UInt64,
+            vec![1 << 20, 1 << 20].try_into().unwrap(),
+            FillValue::from(0u64),
+        );
+        assert!(matches!(
+            builder.build(storage.clone(), "/"),
+            Err(ArrayCreateError::ChunkTooLarge(size, limit)) if size == 8 << 40 && limit == 4 << 30
+        ));
+
+        // A chunk at the limit is permitted
+        let builder = ArrayBuilder::new(
+            vec![1 << 20, 1 << 20],
+            DataType::UInt64,
+            vec![1 << 14, 1 << 15].try_into().unwrap(),
+            FillValue::from(0u64),
+        );
+        assert!(builder.build(storage, "/").is_ok());
+    }
+
+    #[test]
+    fn array_builder_validate() {
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::Int8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0i8),
+        );
+        assert!(builder.validate().is_ok());
+
+        builder
+            .chunk_grid(vec![2, 2, 2].try_into().unwrap())
+            .fill_value(FillValue::from(0i16))
+            .dimension_names(["z", "y", "x"].into());
+        let errors = builder.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(
+            errors[0],
+            ArrayCreateError::InvalidChunkGridDimensionality(3, 2)
+        ));
+        assert!(matches!(
+            errors[1],
+            ArrayCreateError::InvalidDimensionNames(3, 2)
+        ));
+        assert!(matches!(errors[2], ArrayCreateError::InvalidFillValue(_)));
+        // build returns the first error
+        assert!(matches!(
+            builder.build(Arc::new(MemoryStore::new()), "/"),
+            Err(ArrayCreateError::InvalidChunkGridDimensionality(3, 2))
+        ));
+    }
+
+    #[test]
+    fn array_builder_default_chunk_key_separator() {
+        let storage = Arc::new(MemoryStore::new());
+        let mut builder = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::Int8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0i8),
+        );
+        let separator = global_config().default_chunk_key_separator();
+        let array = builder.build(storage.clone(), "/").unwrap();
+        assert_eq!(
+            array.chunk_key(&[1, 2]).as_str(),
+            format!("c{separator}1{separator}2")
+        );
+
+        // An explicit separator overrides the default
+        builder.chunk_key_encoding_default_separator(ChunkKeySeparator::Dot);
+        let array = builder.build(storage, "/").unwrap();
+        assert_eq!(array.chunk_key(&[1, 2]).as_str(), "c.1.2");
+    }
 }
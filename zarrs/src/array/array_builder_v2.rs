@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use crate::metadata::{
+    v2::{
+        array::{ArrayMetadataV2Order, DataTypeMetadataV2, FillValueMetadataV2},
+        MetadataV2,
+    },
+    v2_to_v3::data_type_metadata_v2_to_v3_data_type,
+    ChunkKeySeparator,
+};
+
+use super::{Array, ArrayCreateError, ArrayMetadata, ArrayMetadataV2, ArrayShape, ChunkShape};
+
+/// A Zarr V2 [`Array`] builder.
+///
+/// The builder is initialised from an array shape, chunk shape, `NumPy` data type string (e.g. `"<f4"`), and fill value.
+///  - The order is `C`, the dimension separator is `.`, and there is no compressor or filters.
+///  - Attributes are empty.
+///
+/// [`Array::store_metadata`] writes the metadata as `.zarray` (and `.zattrs` if there are attributes), so the array can be read by Zarr V2 implementations (e.g. `zarr-python` 2.x).
+///
+/// For example:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # use std::sync::Arc;
+/// use zarrs::array::ArrayBuilderV2;
+/// use zarrs::metadata::v2::array::FillValueMetadataV2;
+/// # let store = Arc::new(zarrs::storage::store::MemoryStore::new());
+/// let array = ArrayBuilderV2::new(
+///     vec![8, 8], // array shape
+///     vec![4, 4].try_into()?, // chunk shape
+///     "<f4",
+///     FillValueMetadataV2::NaN,
+/// )
+/// .build(store.clone(), "/group/array")?;
+/// array.store_metadata()?; // write .zarray
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArrayBuilderV2 {
+    /// Array shape.
+    pub shape: ArrayShape,
+    /// Chunk shape.
+    pub chunks: ChunkShape,
+    /// Data type.
+    pub dtype: DataTypeMetadataV2,
+    /// Memory layout of each chunk.
+    pub order: ArrayMetadataV2Order,
+    /// Filters.
+    pub filters: Option<Vec<MetadataV2>>,
+    /// Compressor.
+    pub compressor: Option<MetadataV2>,
+    /// Fill value.
+    pub fill_value: FillValueMetadataV2,
+    /// Dimension separator.
+    pub dimension_separator: ChunkKeySeparator,
+    /// Attributes.
+    pub attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ArrayBuilderV2 {
+    /// Create a new Zarr V2 array builder.
+    #[must_use]
+    pub fn new(
+        shape: ArrayShape,
+        chunks: ChunkShape,
+        dtype: impl Into<String>,
+        fill_value: FillValueMetadataV2,
+    ) -> Self {
+        Self {
+            shape,
+            chunks,
+            dtype: DataTypeMetadataV2::Simple(dtype.into()),
+            order: ArrayMetadataV2Order::C,
+            filters: None,
+            compressor: None,
+            fill_value,
+            dimension_separator: ChunkKeySeparator::Dot,
+            attributes: serde_json::Map::default(),
+        }
+    }
+
+    /// Set the shape.
+    pub fn shape(&mut self, shape: ArrayShape) -> &mut Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Set the chunk shape.
+    pub fn chunks(&mut self, chunks: ChunkShape) -> &mut Self {
+        self.chunks = chunks;
+        self
+    }
+
+    /// Set the data type.
+    pub fn dtype(&mut self, dtype: DataTypeMetadataV2) -> &mut Self {
+        self.dtype = dtype;
+        self
+    }
+
+    /// Set the order.
+    pub fn order(&mut self, order: ArrayMetadataV2Order) -> &mut Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the filters.
+    pub fn filters(&mut self, filters: Option<Vec<MetadataV2>>) -> &mut Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Set the compressor.
+    pub fn compressor(&mut self, compressor: Option<MetadataV2>) -> &mut Self {
+        self.compressor = compressor;
+        self
+    }
+
+    /// Set the fill value.
+    pub fn fill_value(&mut self, fill_value: FillValueMetadataV2) -> &mut Self {
+        self.fill_value = fill_value;
+        self
+    }
+
+    /// Set the dimension separator.
+    pub fn dimension_separator(&mut self, dimension_separator: ChunkKeySeparator) -> &mut Self {
+        self.dimension_separator = dimension_separator;
+        self
+    }
+
+    /// Set the attributes.
+    pub fn attributes(
+        &mut self,
+        attributes: serde_json::Map<String, serde_json::Value>,
+    ) -> &mut Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Build into [`ArrayMetadataV2`].
+    ///
+    /// # Errors
+    /// Returns an [`ArrayCreateError`] if the chunk shape does not match the dimensionality of the array shape, or the data type is not supported.
+    pub fn build_metadata(&self) -> Result<ArrayMetadataV2, ArrayCreateError> {
+        if self.chunks.len() != self.shape.len() {
+            return Err(ArrayCreateError::InvalidChunkGridDimensionality(
+                self.chunks.len(),
+                self.shape.len(),
+            ));
+        }
+        data_type_metadata_v2_to_v3_data_type(&self.dtype)
+            .map_err(|err| ArrayCreateError::UnsupportedZarrV2Array(err.to_string()))?;
+
+        let mut metadata = ArrayMetadataV2::new(
+            self.shape.clone(),
+            self.chunks.clone(),
+            self.dtype.clone(),
+            self.fill_value.clone(),
+            self.compressor.clone(),
+            self.filters.clone(),
+        );
+        metadata.order = self.order.clone();
+        metadata.dimension_separator = self.dimension_separator;
+        metadata.attributes.clone_from(&self.attributes);
+        Ok(metadata)
+    }
+
+    /// Build into an [`Array`].
+    ///
+    /// # Errors
+    /// Returns an [`ArrayCreateError`] if the metadata is invalid or the array is not supported by `zarrs`.
+    pub fn build<TStorage: ?Sized>(
+        &self,
+        storage: Arc<TStorage>,
+        path: &str,
+    ) -> Result<Array<TStorage>, ArrayCreateError> {
+        Array::new_with_metadata(storage, path, ArrayMetadata::V2(self.build_metadata()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::{store::MemoryStore, ReadableStorageTraits, StoreKey};
+
+    use super::*;
+
+    #[test]
+    fn array_builder_v2() {
+        let store = Arc::new(MemoryStore::new());
+        let mut attributes = serde_json::Map::new();
+        attributes.insert("key".to_string(), "value".into());
+        let array = ArrayBuilderV2::new(
+            vec![4, 4],
+            vec![2, 2].try_into().unwrap(),
+            "<u2",
+            FillValueMetadataV2::Number(0.into()),
+        )
+        .order(ArrayMetadataV2Order::C)
+        .dimension_separator(ChunkKeySeparator::Slash)
+        .attributes(attributes)
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array
+            .store_chunk_elements::<u16>(&[0, 1], &[1, 2, 3, 4])
+            .unwrap();
+
+        // Split V2 metadata is written
+        let zarray: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("array/.zarray").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zarray["zarr_format"], 2);
+        assert_eq!(zarray["dtype"], "<u2");
+        assert_eq!(zarray["dimension_separator"], "/");
+        assert!(zarray.get("attributes").is_none());
+        let zattrs: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("array/.zattrs").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zattrs["key"], "value");
+        assert!(store
+            .get(&StoreKey::new("array/0/1").unwrap())
+            .unwrap()
+            .is_some());
+
+        let array = Array::open(store, "/array").unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<u16>(&[0, 1]).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn array_builder_v2_base64_fill_value() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilderV2::new(
+            vec![4],
+            vec![2].try_into().unwrap(),
+            "|V2",
+            FillValueMetadataV2::Bytes(vec![1, 2]),
+        )
+        .build(store.clone(), "/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+
+        // The fill value is written as base64
+        let zarray: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("array/.zarray").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(zarray["fill_value"], "AQI=");
+
+        let array = Array::open(store, "/array").unwrap();
+        array.store_chunk(&[1], &[3, 4, 5, 6]).unwrap();
+        assert_eq!(
+            array
+                .retrieve_array_subset(&array.subset_all())
+                .unwrap()
+                .into_fixed()
+                .unwrap()
+                .as_ref(),
+            &[1, 2, 1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn array_builder_v2_invalid() {
+        let store = Arc::new(MemoryStore::new());
+        let builder = ArrayBuilderV2::new(
+            vec![4, 4],
+            vec![2, 2].try_into().unwrap(),
+            "<x9",
+            FillValueMetadataV2::Null,
+        );
+        assert!(builder.build_metadata().is_err());
+        assert!(builder.build(store.clone(), "/array").is_err());
+
+        let builder = ArrayBuilderV2::new(
+            vec![4, 4],
+            vec![2].try_into().unwrap(),
+            "<u2",
+            FillValueMetadataV2::Null,
+        );
+        assert!(builder.build(store, "/array").is_err());
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     metadata::v3::UnsupportedAdditionalFieldError,
     node::NodePathError,
     plugin::PluginCreateError,
-    storage::StorageError,
+    storage::{StorageError, StoreKey},
 };
 
 use super::{
@@ -52,6 +52,14 @@ pub enum ArrayCreateError {
     /// The number of dimension names does not match the array dimensionality.
     #[error("the number of dimension names {0} does not match array dimensionality {1}")]
     InvalidDimensionNames(usize, usize),
+    /// A non-null dimension name is used more than once.
+    ///
+    /// See the [duplicate dimension names](crate::config::Config#duplicate-dimension-names) configuration.
+    #[error("duplicate dimension name {_0:?}")]
+    DuplicateDimensionName(String),
+    /// The decoded size of a chunk exceeds the [chunk size limit](crate::config::Config#chunk-size-limit).
+    #[error("chunk size {_0} bytes exceeds the chunk size limit of {_1} bytes")]
+    ChunkTooLarge(u64, u64),
     /// Storage error.
     #[error(transparent)]
     StorageError(#[from] StorageError),
@@ -61,6 +69,9 @@ pub enum ArrayCreateError {
     /// The Zarr V2 array is unsupported.
     #[error("unsupported Zarr V2 array: {_0}")]
     UnsupportedZarrV2Array(String),
+    /// A key in the store does not match the chunk key encoding and chunk grid of the array.
+    #[error("key {_0} does not match the chunk key encoding and chunk grid of the array")]
+    ChunkKeyMismatch(StoreKey),
 }
 
 /// Array errors.
@@ -78,6 +89,11 @@ pub enum ArrayError {
     /// Invalid chunk grid indices.
     #[error("invalid chunk grid indices: {_0:?}")]
     InvalidChunkGridIndicesError(Vec<u64>),
+    /// An additional field that must be understood.
+    ///
+    /// See the [must understand level](crate::config::Config#must-understand-level) configuration.
+    #[error(transparent)]
+    UnsupportedAdditionalFieldError(#[from] UnsupportedAdditionalFieldError),
     /// Incompatible dimensionality.
     #[error(transparent)]
     IncompatibleDimensionalityError(#[from] IncompatibleDimensionalityError),
@@ -87,6 +103,9 @@ pub enum ArrayError {
     /// Incompatible chunk subset.
     #[error("chunk subset {_0} is not compatible with chunk {_1:?} with shape {_2:?}")]
     InvalidChunkSubset(ArraySubset, ArrayIndices, ArrayShape),
+    /// A subset that does not start on a chunk boundary.
+    #[error("subset {_0} does not start on a chunk boundary")]
+    UnalignedSubset(ArraySubset),
     /// An unexpected chunk decoded size.
     #[error("got chunk decoded size {_0:?}, expected {_1:?}")]
     UnexpectedChunkDecodedSize(usize, usize),
@@ -102,11 +121,24 @@ pub enum ArrayError {
     /// Invalid data shape.
     #[error("data has shape {_0:?}, expected {_1:?}")]
     InvalidDataShape(Vec<usize>, Vec<usize>),
+    /// An incompatible fill value.
+    #[error(transparent)]
+    InvalidFillValue(#[from] IncompatibleFillValueError),
     /// Invalid element value.
     ///
-    /// For example
-    ///  - a bool array with a value not equal to 0 (false) or 1 (true).
-    ///  - a string with invalid utf-8 encoding.
+    /// For example, a string with invalid utf-8 encoding.
     #[error("Invalid element value")]
     InvalidElementValue,
+    /// The decoded size of a read exceeds the [retrieve size limit](crate::config::Config#retrieve-size-limit).
+    #[error("retrieving {_0} bytes exceeds the retrieve size limit of {_1} bytes")]
+    RetrieveSizeLimitExceeded(u64, u64),
+    /// The number of elements of a read exceeds the [retrieve element limit](crate::config::Config#retrieve-element-limit).
+    #[error("retrieving {_0} elements exceeds the retrieve element limit of {_1} elements")]
+    RetrieveElementLimitExceeded(u64, u64),
+    /// An unsupported method.
+    #[error("unsupported method: {_0}")]
+    UnsupportedMethod(String),
+    /// The number of chunks with overridden codecs exceeds the limit.
+    #[error("the codecs of at most {_0} chunks can be overridden")]
+    ChunkCodecsLimitExceeded(usize),
 }
@@ -1,4 +1,4 @@
-use crate::config::{global_config, MetadataConvertVersion};
+use crate::config::{global_config, Config, MetadataConvertVersion};
 
 /// Options for writing array metadata.
 #[derive(Debug, Clone)]
@@ -6,19 +6,28 @@ pub struct ArrayMetadataOptions {
     experimental_codec_store_metadata_if_encode_only: bool,
     convert_version: MetadataConvertVersion,
     include_zarrs_metadata: bool,
+    serialize_empty_attributes: bool,
+    stage_metadata_writes: bool,
 }
 
 impl Default for ArrayMetadataOptions {
     fn default() -> Self {
+        Self::from_config(&global_config())
+    }
+}
+
+impl ArrayMetadataOptions {
+    /// Create array metadata options from `config` rather than the global config.
+    pub(crate) fn from_config(config: &Config) -> Self {
         Self {
             experimental_codec_store_metadata_if_encode_only: false,
-            convert_version: global_config().metadata_convert_version(),
-            include_zarrs_metadata: global_config().include_zarrs_metadata(),
+            convert_version: config.metadata_convert_version(),
+            include_zarrs_metadata: config.include_zarrs_metadata(),
+            serialize_empty_attributes: config.serialize_empty_attributes(),
+            stage_metadata_writes: config.stage_metadata_writes(),
         }
     }
-}
 
-impl ArrayMetadataOptions {
     /// Return the [experimental codec store metadata if encode only](crate::config::Config#experimental-codec-store-metadata-if-encode-only) setting.
     #[must_use]
     pub fn experimental_codec_store_metadata_if_encode_only(&self) -> bool {
@@ -60,4 +69,31 @@ impl ArrayMetadataOptions {
         self.include_zarrs_metadata = include_zarrs_metadata;
         self
     }
+
+    /// Get the [serialize empty attributes](crate::config::Config#serialize-empty-attributes) configuration.
+    #[must_use]
+    pub fn serialize_empty_attributes(&self) -> bool {
+        self.serialize_empty_attributes
+    }
+
+    /// Set the [serialize empty attributes](crate::config::Config#serialize-empty-attributes) configuration.
+    pub fn set_serialize_empty_attributes(
+        &mut self,
+        serialize_empty_attributes: bool,
+    ) -> &mut Self {
+        self.serialize_empty_attributes = serialize_empty_attributes;
+        self
+    }
+
+    /// Get the [stage metadata writes](crate::config::Config#stage-metadata-writes) configuration.
+    #[must_use]
+    pub fn stage_metadata_writes(&self) -> bool {
+        self.stage_metadata_writes
+    }
+
+    /// Set the [stage metadata writes](crate::config::Config#stage-metadata-writes) configuration.
+    pub fn set_stage_metadata_writes(&mut self, stage_metadata_writes: bool) -> &mut Self {
+        self.stage_metadata_writes = stage_metadata_writes;
+        self
+    }
 }
@@ -1,8 +1,12 @@
-use super::{codec::ShardingCodecConfiguration, Array, ArrayShape, ChunkGrid, ChunkShape};
+use super::{
+    codec::ShardingCodecConfiguration, Array, ArrayShape, ChunkGrid, ChunkShape, CodecChain,
+};
 
 /// An [`Array`] extension trait to simplify working with arrays using the `sharding_indexed` codec.
 pub trait ArrayShardedExt {
     /// Returns true if the array to bytes codec of the array is `sharding_indexed`.
+    ///
+    /// If the codecs of some chunks are [overridden](Array::set_chunk_codecs), the array is only considered sharded if the array to bytes codec of every overridden chunk is also `sharding_indexed` with the same inner chunk shape.
     fn is_sharded(&self) -> bool;
 
     /// Return the inner chunk shape as defined in the `sharding_indexed` codec metadata.
@@ -30,43 +34,62 @@ pub trait ArrayShardedExt {
     fn inner_chunk_grid_shape(&self) -> Option<ArrayShape>;
 }
 
+/// Return the inner chunk shape of `codecs` if its array to bytes codec is `sharding_indexed`.
+fn codecs_inner_chunk_shape(codecs: &CodecChain) -> Option<ChunkShape> {
+    let codec_metadata = codecs
+        .array_to_bytes_codec()
+        .create_metadata()
+        .expect("the array to bytes codec should have metadata");
+    if codec_metadata.name() != super::codec::array_to_bytes::sharding::IDENTIFIER {
+        // TODO: Add codec::identifier()?
+        return None;
+    }
+    if let Ok(ShardingCodecConfiguration::V1(sharding_configuration)) =
+        codec_metadata.to_configuration()
+    {
+        Some(sharding_configuration.chunk_shape)
+    } else {
+        None
+    }
+}
+
+/// Return the effective inner chunk shape of `codecs` if its array to bytes codec is `sharding_indexed`.
+fn codecs_effective_inner_chunk_shape(codecs: &CodecChain) -> Option<ChunkShape> {
+    let mut inner_chunk_shape = codecs_inner_chunk_shape(codecs)?;
+    for codec in codecs.array_to_array_codecs().iter().rev() {
+        inner_chunk_shape = codec
+            .compute_decoded_shape(inner_chunk_shape)
+            .expect("the inner chunk shape is compatible");
+    }
+    Some(inner_chunk_shape)
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Apply `f` to the default codecs and the codecs of every overridden chunk, returning the result if it is the same for all of them.
+    fn consistent_codecs_shape(
+        &self,
+        f: impl Fn(&CodecChain) -> Option<ChunkShape>,
+    ) -> Option<ChunkShape> {
+        let shape = f(&self.codecs)?;
+        self.chunk_codecs
+            .values()
+            .all(|codecs| f(codecs).as_ref() == Some(&shape))
+            .then_some(shape)
+    }
+}
+
 impl<TStorage: ?Sized> ArrayShardedExt for Array<TStorage> {
     fn is_sharded(&self) -> bool {
-        self.codecs
-            .array_to_bytes_codec()
-            .create_metadata()
-            .expect("the array to bytes codec should have metadata")
-            .name() // TODO: Add codec::identifier()?
-            == super::codec::array_to_bytes::sharding::IDENTIFIER
+        self.inner_chunk_shape().is_some()
     }
 
     fn inner_chunk_shape(&self) -> Option<ChunkShape> {
-        let codec_metadata = self
-            .codecs
-            .array_to_bytes_codec()
-            .create_metadata()
-            .expect("the array to bytes codec should have metadata");
-        if let Ok(ShardingCodecConfiguration::V1(sharding_configuration)) =
-            codec_metadata.to_configuration()
-        {
-            Some(sharding_configuration.chunk_shape)
-        } else {
-            None
-        }
+        self.consistent_codecs_shape(codecs_inner_chunk_shape)
     }
 
     fn effective_inner_chunk_shape(&self) -> Option<ChunkShape> {
-        let inner_chunk_shape = self.inner_chunk_shape();
-        if let Some(mut inner_chunk_shape) = inner_chunk_shape {
-            for codec in self.codecs().array_to_array_codecs().iter().rev() {
-                inner_chunk_shape = codec
-                    .compute_decoded_shape(inner_chunk_shape)
-                    .expect("the inner chunk shape is compatible");
-            }
-            Some(inner_chunk_shape)
-        } else {
-            None
-        }
+        self.inner_chunk_shape()?;
+        self.consistent_codecs_shape(codecs_effective_inner_chunk_shape)
     }
 
     fn inner_chunk_grid(&self) -> ChunkGrid {
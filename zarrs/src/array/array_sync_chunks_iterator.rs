@@ -0,0 +1,202 @@
+use std::{
+    borrow::Cow,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+use crate::{
+    array_subset::ArraySubset,
+    storage::{MaybeBytes, ReadableStorage, ReadableStorageTraits, StorageError, StoreKey},
+};
+
+use super::{
+    codec::{ArrayToBytesCodecTraits, CodecOptions},
+    unravel_index, Array, ArrayBytes, ArrayError, ArrayIndices, ArraySize,
+};
+
+/// The encoded bytes of a chunk retrieved ahead of time, paired with its chunk indices.
+type PrefetchedChunk = (ArrayIndices, Result<MaybeBytes, StorageError>);
+
+/// The channels to and from the background retrieval thread, and the number of chunks requested but not yet received.
+struct ReadAhead {
+    requests: Sender<(ArrayIndices, StoreKey)>,
+    prefetched: Receiver<PrefetchedChunk>,
+    in_flight: usize,
+}
+
+impl ReadAhead {
+    /// Spawn a thread that retrieves the encoded bytes of requested chunks from `storage`.
+    fn new(storage: ReadableStorage) -> Self {
+        let (requests, requests_receiver) = channel::<(ArrayIndices, StoreKey)>();
+        let (sender, prefetched) = channel();
+        std::thread::spawn(move || {
+            for (chunk_indices, key) in requests_receiver {
+                let encoded = storage.get(&key);
+                if sender.send((chunk_indices, encoded)).is_err() {
+                    // The iterator was dropped
+                    break;
+                }
+            }
+        });
+        Self {
+            requests,
+            prefetched,
+            in_flight: 0,
+        }
+    }
+}
+
+/// An iterator over the decoded chunks of an [`Array`] in order, created by [`Array::iter_chunks`].
+///
+/// Each item is the chunk indices and the decoded chunk bytes, or the fill value if the chunk does not exist.
+///
+/// By default, each chunk is retrieved from the store when it is reached.
+/// With [`read_ahead`](ArrayChunksIterator::read_ahead), the encoded bytes of the following chunks are retrieved on a background thread while the current chunk is being processed.
+/// This overlaps storage IO with decoding and any processing by the caller, which is beneficial for sequential scans of high latency stores.
+#[must_use]
+pub struct ArrayChunksIterator<'a, TStorage: ?Sized> {
+    array: &'a Array<TStorage>,
+    storage: ReadableStorage,
+    chunks: ArraySubset,
+    position: std::ops::Range<u64>,
+    options: CodecOptions,
+    read_ahead: usize,
+    prefetch: Option<ReadAhead>,
+}
+
+impl<'a, TStorage: ?Sized + ReadableStorageTraits + 'static> ArrayChunksIterator<'a, TStorage> {
+    pub(super) fn new(
+        array: &'a Array<TStorage>,
+        storage: ReadableStorage,
+        chunks: ArraySubset,
+        options: CodecOptions,
+    ) -> Self {
+        let position = 0..chunks.num_elements();
+        Self {
+            array,
+            storage,
+            chunks,
+            position,
+            options,
+            read_ahead: 0,
+            prefetch: None,
+        }
+    }
+
+    /// Set the number of chunks to retrieve ahead of the current chunk. Defaults to `0`.
+    ///
+    /// At most `read_ahead` chunks are retrieved and held in memory ahead of the current chunk, the background retrieval pauses until the iterator advances.
+    /// Chunk indices are computed lazily as chunks are requested, so iterating over a large chunk grid does not allocate for every chunk up front.
+    /// A storage error for a retrieved chunk is returned when that chunk is reached.
+    pub fn read_ahead(mut self, read_ahead: usize) -> Self {
+        self.read_ahead = read_ahead;
+        self
+    }
+
+    /// Return the indices of the next chunk in `chunks`, computed lazily from the `position`.
+    fn next_chunk_indices(
+        chunks: &ArraySubset,
+        position: &mut std::ops::Range<u64>,
+    ) -> Option<ArrayIndices> {
+        let index = position.next()?;
+        let mut chunk_indices = unravel_index(index, chunks.shape());
+        std::iter::zip(chunk_indices.iter_mut(), chunks.start())
+            .for_each(|(chunk_index, start)| *chunk_index += start);
+        Some(chunk_indices)
+    }
+
+    /// Receive the next chunk retrieved on a background thread.
+    ///
+    /// Chunks are requested from the background thread so that `read_ahead` chunks remain in flight after the next chunk is received.
+    /// If the background thread has stopped (e.g. the storage panicked), an error is returned and the iteration ends.
+    fn next_prefetched(&mut self) -> Option<Result<PrefetchedChunk, ArrayError>> {
+        let storage = &self.storage;
+        let prefetch = self
+            .prefetch
+            .get_or_insert_with(|| ReadAhead::new(storage.clone()));
+        while prefetch.in_flight <= self.read_ahead {
+            let Some(chunk_indices) = Self::next_chunk_indices(&self.chunks, &mut self.position)
+            else {
+                break;
+            };
+            let key = self.array.chunk_key(&chunk_indices);
+            if prefetch.requests.send((chunk_indices, key)).is_err() {
+                return Some(Err(self.read_ahead_stopped()));
+            }
+            prefetch.in_flight += 1;
+        }
+        if prefetch.in_flight == 0 {
+            return None;
+        }
+        prefetch.in_flight -= 1;
+        match prefetch.prefetched.recv() {
+            Ok(prefetched) => Some(Ok(prefetched)),
+            Err(_) => Some(Err(self.read_ahead_stopped())),
+        }
+    }
+
+    /// End the iteration after the background retrieval thread stopped unexpectedly.
+    fn read_ahead_stopped(&mut self) -> ArrayError {
+        self.position = self.position.end..self.position.end;
+        if let Some(prefetch) = &mut self.prefetch {
+            prefetch.in_flight = 0;
+        }
+        ArrayError::StorageError(StorageError::Other(
+            "the read ahead thread stopped before retrieving all chunks".to_string(),
+        ))
+    }
+
+    fn decode(
+        &self,
+        chunk_indices: &[u64],
+        encoded: MaybeBytes,
+    ) -> Result<ArrayBytes<'a>, ArrayError> {
+        let chunk_representation = self.array.chunk_array_representation(chunk_indices)?;
+        if let Some(encoded) = encoded {
+            let encoded: Vec<u8> = encoded.into();
+            let bytes = self
+                .array
+                .chunk_codecs(chunk_indices)
+                .decode(Cow::Owned(encoded), &chunk_representation, &self.options)
+                .map_err(ArrayError::CodecError)?;
+            bytes.validate(
+                chunk_representation.num_elements(),
+                chunk_representation.data_type().size(),
+            )?;
+            Ok(bytes)
+        } else {
+            let array_size = ArraySize::new(
+                chunk_representation.data_type().size(),
+                chunk_representation.num_elements(),
+            );
+            Ok(ArrayBytes::new_fill_value(
+                array_size,
+                self.array.fill_value(),
+            ))
+        }
+    }
+}
+
+impl<'a, TStorage: ?Sized + ReadableStorageTraits + 'static> Iterator
+    for ArrayChunksIterator<'a, TStorage>
+{
+    type Item = Result<(ArrayIndices, ArrayBytes<'a>), ArrayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk_indices, encoded) = if self.read_ahead > 0 || self.prefetch.is_some() {
+            match self.next_prefetched()? {
+                Ok(prefetched) => prefetched,
+                Err(err) => return Some(Err(err)),
+            }
+        } else {
+            let chunk_indices = Self::next_chunk_indices(&self.chunks, &mut self.position)?;
+            let encoded = self.storage.get(&self.array.chunk_key(&chunk_indices));
+            (chunk_indices, encoded)
+        };
+        Some(
+            encoded
+                .map_err(ArrayError::StorageError)
+                .and_then(|encoded| self.decode(&chunk_indices, encoded))
+                .map(|bytes| (chunk_indices, bytes)),
+        )
+    }
+}
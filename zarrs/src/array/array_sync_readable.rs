@@ -8,25 +8,50 @@ use crate::{
     array::{ArrayBytes, ArrayMetadataV2},
     array_subset::ArraySubset,
     config::MetadataRetrieveVersion,
-    node::{meta_key_v2_array, meta_key_v2_attributes, meta_key_v3, NodePath},
-    storage::{ReadableStorageTraits, StorageError, StorageHandle},
+    node::{
+        meta_key_v2_array, meta_key_v2_attributes, meta_key_v3, node_type_v3, strip_bom,
+        ConsolidatedMetadataSource, NodePath,
+    },
+    storage::{
+        ListableStorageTraits, ReadableStorageTraits, StorageError, StorageHandle, StoreKey,
+        StorePrefix,
+    },
 };
 
 use super::{
     array_bytes::{copy_fill_value_into, merge_chunks_vlen},
+    array_metadata_v3_from_slice,
+    array_sync_chunks_iterator::ArrayChunksIterator,
     codec::{
         options::CodecOptions, ArrayPartialDecoderTraits, ArrayToBytesCodecTraits,
         StoragePartialDecoder,
     },
     concurrency::concurrency_chunks_and_codec,
     element::ElementOwned,
-    Array, ArrayCreateError, ArrayError, ArrayMetadata, ArrayMetadataV3, ArraySize, DataTypeSize,
+    Array, ArrayCreateError, ArrayError, ArrayIndices, ArrayMetadata, ArraySize, DataTypeSize,
 };
 
 #[cfg(feature = "ndarray")]
 use super::elements_to_ndarray;
 
 impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
+    /// Return true if an array exists in `storage` at `path`.
+    ///
+    /// This checks for Zarr V3 array metadata (`zarr.json` with an `array` node type) and then Zarr V2 array metadata (`.zarray`).
+    /// At most two storage requests are made, the store is not listed, and the array is not constructed.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if `path` is invalid, there is a storage error, or `zarr.json` is invalid.
+    /// An absent array is not an error.
+    pub fn exists(storage: &Arc<TStorage>, path: &str) -> Result<bool, ArrayCreateError> {
+        let node_path: NodePath = path.try_into()?;
+        let key_v3 = meta_key_v3(&node_path);
+        if let Some(metadata) = storage.get(&key_v3)? {
+            return Ok(node_type_v3(key_v3, &metadata)? == "array");
+        }
+        Ok(storage.size_key(&meta_key_v2_array(&node_path))?.is_some())
+    }
+
     /// Open an existing array in `storage` at `path` with default [`MetadataRetrieveVersion`].
     /// The metadata is read from the store.
     ///
@@ -52,8 +77,8 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             // Try V3
             let key_v3 = meta_key_v3(&node_path);
             if let Some(metadata) = storage.get(&key_v3)? {
-                let metadata: ArrayMetadataV3 = serde_json::from_slice(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
+                let metadata =
+                    array_metadata_v3_from_slice(&node_path, &key_v3, strip_bom(&metadata))?;
                 return Self::new_with_metadata(storage, path, ArrayMetadata::V3(metadata));
             }
         }
@@ -62,15 +87,16 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             // Try V2
             let key_v2 = meta_key_v2_array(&node_path);
             if let Some(metadata) = storage.get(&key_v2)? {
-                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
 
                 let attributes_key = meta_key_v2_attributes(&node_path);
                 let attributes = storage.get(&attributes_key)?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
 
                 return Self::new_with_metadata(storage, path, ArrayMetadata::V2(metadata));
@@ -80,6 +106,44 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         Err(ArrayCreateError::MissingMetadata)
     }
 
+    /// Open an existing array in `storage` at `path`, first consulting `consolidated` metadata.
+    ///
+    /// If `consolidated` holds the metadata of the array, the array is opened without a storage request.
+    /// Otherwise, the metadata is read from the store as in [`open`](Array::open).
+    ///
+    /// Zarr V2 (`.zmetadata`) and Zarr V3 (inline `consolidated_metadata`) consolidated metadata can be retrieved with [`ConsolidatedMetadata::retrieve`](crate::node::ConsolidatedMetadata::retrieve).
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is a storage error, the consolidated metadata cannot be loaded, or any metadata is invalid.
+    pub fn open_with_consolidated(
+        storage: Arc<TStorage>,
+        path: &str,
+        consolidated: &dyn ConsolidatedMetadataSource,
+    ) -> Result<Self, ArrayCreateError> {
+        let node_path = NodePath::new(path)?;
+        if let Some(metadata) = consolidated
+            .consolidated_array_metadata(&node_path, &MetadataRetrieveVersion::Default)?
+        {
+            return Self::new_with_metadata(storage, path, metadata);
+        }
+        Self::open(storage, path)
+    }
+
+    /// Reload the array metadata from the store, replacing the in-memory metadata.
+    ///
+    /// This refreshes an array handle after its metadata is updated externally (e.g. by another process).
+    /// The storage, path, and settings of the array handle (e.g. [`deduplicate_chunk_reads`](Array::deduplicate_chunk_reads)) are preserved.
+    /// The metadata is retrieved as in [`open`](Array::open).
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is a storage error or any metadata is invalid.
+    /// The array is unchanged if reloading fails.
+    pub fn reload(&mut self) -> Result<(), ArrayCreateError> {
+        let reloaded = Self::open(self.storage.clone(), self.path.as_str())?;
+        self.replace_metadata(reloaded);
+        Ok(())
+    }
+
     /// Read and decode the chunk at `chunk_indices` into its bytes if it exists with default codec options.
     ///
     /// # Errors
@@ -234,8 +298,90 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         .collect()
     }
 
+    /// Return an iterator over the decoded chunks in `chunks` with default codec options.
+    ///
+    /// The chunks are in order of the chunk indices returned by `chunks.indices().into_iter()`.
+    /// To iterate over every chunk of the array, pass a subset with the [`chunk_grid_shape`](Array::chunk_grid_shape).
+    /// To iterate over the chunks intersecting an array subset, pass the output of [`chunks_in_array_subset`](Array::chunks_in_array_subset).
+    /// Use [`ArrayChunksIterator::read_ahead`] to retrieve chunks ahead of the current chunk in the background.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `chunks` is incompatible with the dimensionality of the array or there is an underlying store error.
+    pub fn iter_chunks(
+        &self,
+        chunks: &ArraySubset,
+    ) -> Result<ArrayChunksIterator<'_, TStorage>, ArrayError> {
+        self.iter_chunks_opt(chunks, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`iter_chunks`](Array::iter_chunks).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn iter_chunks_opt(
+        &self,
+        chunks: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayChunksIterator<'_, TStorage>, ArrayError> {
+        if chunks.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                chunks.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle)?;
+        Ok(ArrayChunksIterator::new(
+            self,
+            storage_transformer,
+            chunks.clone(),
+            options.clone(),
+        ))
+    }
+
+    /// Return an iterator over all decoded chunks of the array with default codec options.
+    ///
+    /// The chunks are in row-major (C) order of their chunk grid indices.
+    /// Each chunk is retrieved and decoded lazily as the iterator advances, so at most one decoded chunk is held in memory by the iterator.
+    /// The iterator is empty if the chunk grid shape is unknown.
+    /// This is equivalent to [`iter_chunks`](Array::iter_chunks) over the [`chunk_grid_shape`](Array::chunk_grid_shape).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if there is an underlying store error.
+    pub fn chunks_iter(&self) -> Result<ArrayChunksIterator<'_, TStorage>, ArrayError> {
+        let chunk_grid_shape = self
+            .chunk_grid_shape()
+            .unwrap_or_else(|| vec![0; self.dimensionality()]);
+        self.iter_chunks(&ArraySubset::new_with_shape(chunk_grid_shape))
+    }
+
+    /// Return an iterator over the decoded chunks of the array that intersect `array_subset` with default codec options.
+    ///
+    /// The entire chunks are decoded, not just their intersection with `array_subset`.
+    /// This is equivalent to [`iter_chunks`](Array::iter_chunks) over the output of [`chunks_in_array_subset`](Array::chunks_in_array_subset).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if `array_subset` is incompatible with the dimensionality of the array or there is an underlying store error.
+    pub fn chunks_iter_subset(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArrayChunksIterator<'_, TStorage>, ArrayError> {
+        let chunks = self
+            .chunks_in_array_subset(array_subset)
+            .map_err(|_| {
+                ArrayError::InvalidArraySubset(array_subset.clone(), self.shape().to_vec())
+            })?
+            .unwrap_or_else(|| ArraySubset::new_with_shape(vec![0; self.dimensionality()]));
+        self.iter_chunks(&chunks)
+    }
+
     /// Read and decode the chunks at `chunks` into their bytes.
     ///
+    /// `chunks` is a subset of the chunk grid, and the output covers the [`chunks_subset`](Array::chunks_subset) of the chunks.
+    /// The chunks are retrieved and decoded concurrently, as in [`retrieve_array_subset`](Array::retrieve_array_subset).
+    /// Elements of edge chunks outside of the array shape hold the fill value.
+    /// To exclude them, use [`retrieve_chunks_bounded`](Array::retrieve_chunks_bounded) instead.
+    ///
     /// # Errors
     /// Returns an [`ArrayError`] if
     ///  - any chunk indices in `chunks` are invalid,
@@ -248,6 +394,23 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_chunks_opt(chunks, &CodecOptions::default())
     }
 
+    /// Read and decode the chunks at `chunks` into their bytes, trimmed to the array shape.
+    ///
+    /// The output covers the [`chunks_subset_bounded`](Array::chunks_subset_bounded) of the chunks.
+    /// Unlike [`retrieve_chunks`](Array::retrieve_chunks), elements of edge chunks outside of the array shape are excluded.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if any chunk indices in `chunks` are invalid or an error condition in [`Array::retrieve_chunks_opt`].
+    ///
+    /// # Panics
+    /// Panics if the number of array elements in the chunks exceeds `usize::MAX`.
+    pub fn retrieve_chunks_bounded(
+        &self,
+        chunks: &ArraySubset,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        self.retrieve_chunks_bounded_opt(chunks, &CodecOptions::default())
+    }
+
     /// Read and decode the chunks at `chunks` into a vector of their elements.
     ///
     /// # Errors
@@ -347,6 +510,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
     /// # Errors
     /// Returns an [`ArrayError`] if:
     ///  - the `array_subset` dimensionality does not match the chunk grid dimensionality,
+    ///  - the number of elements in the `array_subset` exceeds the [retrieve element limit](crate::config::Config#retrieve-element-limit),
     ///  - there is a codec decoding error, or
     ///  - an underlying store error.
     ///
@@ -359,6 +523,27 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_array_subset_opt(array_subset, &CodecOptions::default())
     }
 
+    /// Read and decode the `array_subset` of array into the `output` bytes.
+    ///
+    /// This is equivalent to [`retrieve_array_subset`](Array::retrieve_array_subset), but chunks are decoded directly into a caller-provided buffer rather than a newly allocated one.
+    /// `output` must have a length of the number of elements in `array_subset` multiplied by the data type size.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if:
+    ///  - the data type has a variable size,
+    ///  - the length of `output` does not match the size of `array_subset`,
+    ///  - the `array_subset` dimensionality does not match the chunk grid dimensionality,
+    ///  - the number of elements in the `array_subset` exceeds the [retrieve element limit](crate::config::Config#retrieve-element-limit),
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn retrieve_array_subset_into(
+        &self,
+        array_subset: &ArraySubset,
+        output: &mut [u8],
+    ) -> Result<(), ArrayError> {
+        self.retrieve_array_subset_into_opt(array_subset, output, &CodecOptions::default())
+    }
+
     /// Read and decode the `array_subset` of array into a vector of its elements.
     ///
     /// # Errors
@@ -375,6 +560,26 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_array_subset_elements_opt(array_subset, &CodecOptions::default())
     }
 
+    /// Read and decode the `array_subset` of array into a vector of its elements and a mask of missing elements.
+    ///
+    /// The mask is `true` where an element is equal to the fill value, including elements of chunks that do not exist.
+    /// Fill value comparison uses [`FillValue::equals_element`](crate::array::FillValue::equals_element), so any NaN matches a NaN fill value.
+    /// The mask has the same length and order as the elements.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if:
+    ///  - the size of `T` does not match the data type size,
+    ///  - the decoded bytes cannot be transmuted,
+    ///  - an array subset is invalid or out of bounds of the array,
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn retrieve_array_subset_elements_with_mask<T: ElementOwned>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<(Vec<T>, Vec<bool>), ArrayError> {
+        self.retrieve_array_subset_elements_with_mask_opt(array_subset, &CodecOptions::default())
+    }
+
     #[cfg(feature = "ndarray")]
     /// Read and decode the `array_subset` of array into an [`ndarray::ArrayD`].
     ///
@@ -393,6 +598,22 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_array_subset_ndarray_opt(array_subset, &CodecOptions::default())
     }
 
+    /// Read and decode the entire array into a vector of its elements.
+    ///
+    /// Missing chunks are materialised with the fill value.
+    /// Chunks are decoded one at a time directly into the output, so encoded and decoded copies of the whole array are not held simultaneously.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if:
+    ///  - `T` is incompatible with the data type,
+    ///  - the decoded size of the array exceeds the [retrieve size limit](crate::config::Config#retrieve-size-limit),
+    ///  - the number of elements of the array exceeds the [retrieve element limit](crate::config::Config#retrieve-element-limit),
+    ///  - there is a codec decoding error, or
+    ///  - an underlying store error.
+    pub fn retrieve_all<T: ElementOwned>(&self) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_all_opt(&CodecOptions::default())
+    }
+
     /// Initialises a partial decoder for the chunk at `chunk_indices`.
     ///
     /// # Errors
@@ -431,7 +652,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             let chunk_encoded: Vec<u8> = chunk_encoded.into();
             let chunk_representation = self.chunk_array_representation(chunk_indices)?;
             let bytes = self
-                .codecs()
+                .chunk_codecs(chunk_indices)
                 .decode(Cow::Owned(chunk_encoded), &chunk_representation, options)
                 .map_err(ArrayError::CodecError)?;
             Ok(Some(bytes))
@@ -451,9 +672,11 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         if let Some(chunk) = chunk {
             Ok(chunk)
         } else {
-            let chunk_shape = self.chunk_shape(chunk_indices)?;
-            let array_size =
-                ArraySize::new(self.data_type().size(), chunk_shape.num_elements_u64());
+            let chunk_representation = self.chunk_array_representation(chunk_indices)?;
+            let array_size = ArraySize::new(
+                chunk_representation.data_type().size(),
+                chunk_representation.num_elements(),
+            );
             Ok(ArrayBytes::new_fill_value(array_size, self.fill_value()))
         }
     }
@@ -481,7 +704,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         if let Some(chunk_encoded) = chunk_encoded {
             let chunk_encoded: Vec<u8> = chunk_encoded.into();
             let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-            self.codecs()
+            self.chunk_codecs(chunk_indices)
                 .decode_into(
                     Cow::Owned(chunk_encoded),
                     &chunk_representation,
@@ -586,6 +809,24 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         self.retrieve_array_subset_opt(&array_subset, options)
     }
 
+    /// Explicit options version of [`retrieve_chunks_bounded`](Array::retrieve_chunks_bounded).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_chunks_bounded_opt(
+        &self,
+        chunks: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        if chunks.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                chunks.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+
+        let array_subset = self.chunks_subset_bounded(chunks)?;
+        self.retrieve_array_subset_opt(&array_subset, options)
+    }
+
     /// Explicit options version of [`retrieve_chunks_elements`](Array::retrieve_chunks_elements).
     #[allow(clippy::missing_errors_doc)]
     pub fn retrieve_chunks_elements_opt<T: ElementOwned>(
@@ -623,6 +864,18 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                 self.shape().to_vec(),
             ));
         }
+        Self::retrieve_element_limit_validate(array_subset.shape(), options)?;
+
+        if options.allow_oob_fill() && !self.array_subset_is_inbounds(array_subset) {
+            // Retrieve the in-bounds region and pad with the fill value
+            let inbounds = if let Some(inbounds_subset) = self.array_subset_inbounds(array_subset) {
+                let inbounds_bytes = self.retrieve_array_subset_opt(&inbounds_subset, options)?;
+                Some((inbounds_subset, inbounds_bytes))
+            } else {
+                None
+            };
+            return self.pad_oob_fill(array_subset, inbounds);
+        }
 
         // Find the chunks intersecting this array subset
         let chunks = self.chunks_in_array_subset(array_subset)?;
@@ -658,12 +911,12 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                 }
             }
             _ => {
-                let chunk_representation =
-                    self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+                let chunk_indices0 = vec![0; self.dimensionality()];
+                let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
 
                 // Calculate chunk/codec concurrency
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_chunks,
@@ -671,6 +924,18 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                     &codec_concurrency,
                 );
 
+                if self.deduplicate_chunk_reads() {
+                    let chunk_groups = self.chunk_groups_by_key(&chunks)?;
+                    if chunk_groups.len() < num_chunks {
+                        return self.retrieve_array_subset_chunk_groups(
+                            array_subset,
+                            chunk_groups,
+                            chunk_concurrent_limit,
+                            &options,
+                        );
+                    }
+                }
+
                 match chunk_representation.data_type().size() {
                     DataTypeSize::Variable => {
                         // Retrieve all the chunks
@@ -711,40 +976,15 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                         {
                             let output =
                                 UnsafeCellSlice::new_from_vec_with_spare_capacity(&mut output);
-                            let retrieve_chunk = |chunk_indices: Vec<u64>| {
-                                let chunk_subset = self.chunk_subset(&chunk_indices)?;
-                                let chunk_subset_overlap = chunk_subset.overlap(array_subset)?;
-                                unsafe {
-                                    self.retrieve_chunk_subset_into(
-                                        &chunk_indices,
-                                        &chunk_subset_overlap.relative_to(chunk_subset.start())?,
-                                        &output,
-                                        array_subset.shape(),
-                                        &chunk_subset_overlap.relative_to(array_subset.start())?,
-                                        &options,
-                                    )?;
-                                }
-                                // let chunk_subset_bytes = self.retrieve_chunk_subset_opt(
-                                //     &chunk_indices,
-                                //     &chunk_subset_overlap.relative_to(chunk_subset.start())?,
-                                //     &options,
-                                // )?;
-                                // update_bytes_flen(
-                                //     &output,
-                                //     array_subset.shape(),
-                                //     &chunk_subset_bytes.into_fixed()?,
-                                //     &chunk_subset_overlap.relative_to(array_subset.start())?,
-                                //     data_type_size,
-                                // );
-                                Ok::<_, ArrayError>(())
-                            };
-                            let indices = chunks.indices();
-                            iter_concurrent_limit!(
-                                chunk_concurrent_limit,
-                                indices,
-                                try_for_each,
-                                retrieve_chunk
-                            )?;
+                            unsafe {
+                                self.retrieve_array_subset_chunks_into(
+                                    array_subset,
+                                    &chunks,
+                                    &output,
+                                    chunk_concurrent_limit,
+                                    &options,
+                                )?;
+                            }
                         }
                         unsafe { output.set_len(size_output) };
                         Ok(ArrayBytes::from(output))
@@ -754,6 +994,126 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         }
     }
 
+    /// Decode the intersections of `array_subset` with `chunks` into `output`, which holds the elements of `array_subset` with a fixed size data type.
+    ///
+    /// # Safety
+    /// `output` must have the size of `array_subset` and not be accessed elsewhere during decoding.
+    unsafe fn retrieve_array_subset_chunks_into(
+        &self,
+        array_subset: &ArraySubset,
+        chunks: &ArraySubset,
+        output: &UnsafeCellSlice<u8>,
+        chunk_concurrent_limit: usize,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let retrieve_chunk = |chunk_indices: Vec<u64>| {
+            let chunk_subset = self.chunk_subset(&chunk_indices)?;
+            let chunk_subset_overlap = chunk_subset.overlap(array_subset)?;
+            unsafe {
+                self.retrieve_chunk_subset_into(
+                    &chunk_indices,
+                    &chunk_subset_overlap.relative_to(chunk_subset.start())?,
+                    output,
+                    array_subset.shape(),
+                    &chunk_subset_overlap.relative_to(array_subset.start())?,
+                    options,
+                )?;
+            }
+            Ok::<_, ArrayError>(())
+        };
+        let indices = chunks.indices();
+        iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            indices,
+            try_for_each,
+            retrieve_chunk
+        )
+    }
+
+    /// Explicit options version of [`retrieve_array_subset_into`](Array::retrieve_array_subset_into).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_into_opt(
+        &self,
+        array_subset: &ArraySubset,
+        output: &mut [u8],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        if array_subset.dimensionality() != self.dimensionality() {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        }
+        let DataTypeSize::Fixed(data_type_size) = self.data_type().size() else {
+            return Err(ArrayError::UnsupportedMethod(
+                "retrieving into a buffer is not supported for variable size data types"
+                    .to_string(),
+            ));
+        };
+        Self::retrieve_element_limit_validate(array_subset.shape(), options)?;
+        let size_output = array_subset
+            .shape()
+            .iter()
+            .try_fold(data_type_size as u64, |size, &dim| size.checked_mul(dim));
+        if size_output != Some(output.len() as u64) {
+            return Err(ArrayError::InvalidBytesInputSize(
+                output.len(),
+                size_output.unwrap_or(u64::MAX),
+            ));
+        }
+
+        let retrieve_and_copy =
+            |options: &CodecOptions, output: &mut [u8]| -> Result<(), ArrayError> {
+                let bytes = self.retrieve_array_subset_opt(array_subset, options)?;
+                output.copy_from_slice(&bytes.into_fixed()?);
+                Ok(())
+            };
+
+        if options.allow_oob_fill() && !self.array_subset_is_inbounds(array_subset) {
+            return retrieve_and_copy(options, output);
+        }
+
+        // Find the chunks intersecting this array subset
+        let chunks = self.chunks_in_array_subset(array_subset)?;
+        let Some(chunks) = chunks else {
+            return Err(ArrayError::InvalidArraySubset(
+                array_subset.clone(),
+                self.shape().to_vec(),
+            ));
+        };
+        let num_chunks = chunks.num_elements_usize();
+        if num_chunks == 0 {
+            return Ok(());
+        }
+
+        // Calculate chunk/codec concurrency
+        let chunk_indices0 = vec![0; self.dimensionality()];
+        let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
+        let codec_concurrency =
+            self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            num_chunks,
+            options,
+            &codec_concurrency,
+        );
+
+        if self.deduplicate_chunk_reads() && self.chunk_groups_by_key(&chunks)?.len() < num_chunks {
+            return retrieve_and_copy(&options, output);
+        }
+
+        let output = UnsafeCellSlice::new(output);
+        unsafe {
+            self.retrieve_array_subset_chunks_into(
+                array_subset,
+                &chunks,
+                &output,
+                chunk_concurrent_limit,
+                &options,
+            )
+        }
+    }
+
     /// Explicit options version of [`retrieve_array_subset_elements`](Array::retrieve_array_subset_elements).
     #[allow(clippy::missing_errors_doc)]
     pub fn retrieve_array_subset_elements_opt<T: ElementOwned>(
@@ -767,6 +1127,29 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
         )
     }
 
+    /// Explicit options version of [`retrieve_array_subset_elements_with_mask`](Array::retrieve_array_subset_elements_with_mask).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_elements_with_mask_opt<T: ElementOwned>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<(Vec<T>, Vec<bool>), ArrayError> {
+        let bytes = self.retrieve_array_subset_opt(array_subset, options)?;
+        let mask = self.fill_value_mask(&bytes);
+        let elements = T::from_array_bytes(self.data_type(), bytes)?;
+        Ok((elements, mask))
+    }
+
+    /// Explicit options version of [`retrieve_all`](Array::retrieve_all).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_all_opt<T: ElementOwned>(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_all_validate::<T>(options)?;
+        self.retrieve_array_subset_elements_opt(&self.subset_all(), options)
+    }
+
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`retrieve_array_subset_ndarray`](Array::retrieve_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc)]
@@ -810,7 +1193,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
                 self.chunk_key(chunk_indices),
             ));
 
-            self.codecs
+            self.chunk_codecs(chunk_indices)
                 .clone()
                 .partial_decoder(input_handle, &chunk_representation, options)?
                 .partial_decode(&[chunk_subset.clone()], options)?
@@ -854,7 +1237,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             ));
 
             Ok(self
-                .codecs
+                .chunk_codecs(chunk_indices)
                 .clone()
                 .partial_decoder(input_handle, &chunk_representation, options)?
                 .partial_decode_into(chunk_subset, output, output_shape, output_subset, options)?)
@@ -905,9 +1288,124 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Array<TStorage> {
             self.chunk_key(chunk_indices),
         ));
         let chunk_representation = self.chunk_array_representation(chunk_indices)?;
-        Ok(self
-            .codecs
-            .clone()
-            .partial_decoder(input_handle, &chunk_representation, options)?)
+        Ok(self.chunk_codecs(chunk_indices).clone().partial_decoder(
+            input_handle,
+            &chunk_representation,
+            options,
+        )?)
+    }
+
+    /// Retrieve `array_subset` from groups of chunks that share a store key, retrieving and decoding each group once.
+    fn retrieve_array_subset_chunk_groups(
+        &self,
+        array_subset: &ArraySubset,
+        chunk_groups: Vec<Vec<ArrayIndices>>,
+        chunk_concurrent_limit: usize,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        let retrieve_chunk_group = |chunk_group: Vec<ArrayIndices>| {
+            let chunk_bytes = self.retrieve_chunk_opt(&chunk_group[0], options)?;
+            self.chunk_group_bytes_and_subsets(array_subset, &chunk_group, &chunk_bytes)
+        };
+        let chunk_bytes_and_subsets: Vec<(ArrayBytes<'_>, ArraySubset)> = iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            chunk_groups,
+            map,
+            retrieve_chunk_group
+        )
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+        self.merge_chunk_group_bytes(array_subset, chunk_bytes_and_subsets)
+    }
+}
+
+/// The maximum number of keys checked by [`Array::open_validated`].
+const OPEN_VALIDATED_SAMPLE_SIZE: usize = 8;
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static> Array<TStorage> {
+    /// Open an existing array in `storage` at `path` and check that the keys under the array match its chunk layout.
+    ///
+    /// This is an opt-in consistency check for metadata that does not match the stored chunks, which would otherwise silently read as the fill value.
+    /// The keys under the array are listed and a sample of up to 8 of them are checked against the chunk key encoding and chunk grid of the array.
+    /// For example, this detects chunks written with a `.` separator under an array with a `/` separator.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError::ChunkKeyMismatch`] if a sampled key is not a valid chunk key of the array.
+    /// Otherwise, returns [`ArrayCreateError`] under the same conditions as [`Array::open`].
+    pub fn open_validated(storage: Arc<TStorage>, path: &str) -> Result<Self, ArrayCreateError> {
+        let array = Self::open(storage, path)?;
+        let prefix: StorePrefix = array.path().try_into().map_err(StorageError::from)?;
+        let chunk_keys: Vec<StoreKey> = array
+            .storage
+            .list_prefix(&prefix)?
+            .into_iter()
+            .filter(|key| {
+                !matches!(
+                    key.as_str().rsplit('/').next(),
+                    Some("zarr.json" | ".zarray" | ".zattrs" | ".zgroup" | ".zmetadata")
+                )
+            })
+            .collect();
+        let stride = chunk_keys.len().div_ceil(OPEN_VALIDATED_SAMPLE_SIZE).max(1);
+        for key in chunk_keys.iter().step_by(stride) {
+            if !array.is_chunk_key(key) {
+                return Err(ArrayCreateError::ChunkKeyMismatch(key.clone()));
+            }
+        }
+        Ok(array)
+    }
+
+    /// Return true if `key` is the key of a chunk within the chunk grid.
+    fn is_chunk_key(&self, key: &StoreKey) -> bool {
+        self.chunk_grid_indices_from_key(key).is_some()
+    }
+
+    /// Return the chunk indices and stored (encoded) size in bytes of each chunk that exists in the store.
+    ///
+    /// Chunks are not retrieved or decoded.
+    /// The keys under the array are listed once, and only the sizes of the listed chunks are requested from the store.
+    /// Absent chunks are skipped.
+    /// The compression ratio of a chunk can be computed by pairing its stored size with the decoded size of its [`chunk_array_representation`](Array::chunk_array_representation) (e.g. [`fixed_size`](super::ChunkRepresentation::fixed_size)).
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn chunk_size_report(&self) -> Result<Vec<(ArrayIndices, u64)>, StorageError> {
+        self.chunk_size_report_opt(&CodecOptions::default())
+    }
+
+    /// Explicit options version of [`chunk_size_report`](Array::chunk_size_report).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn chunk_size_report_opt(
+        &self,
+        options: &CodecOptions,
+    ) -> Result<Vec<(ArrayIndices, u64)>, StorageError> {
+        let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
+        let storage_transformer = self
+            .storage_transformers()
+            .create_readable_transformer(storage_handle.clone())?;
+        let listable_transformer = self
+            .storage_transformers()
+            .create_listable_transformer(storage_handle)?;
+
+        let prefix: StorePrefix = self.path().try_into()?;
+        let chunk_keys: Vec<(ArrayIndices, StoreKey)> = listable_transformer
+            .list_prefix(&prefix)?
+            .into_iter()
+            .filter_map(|key| Some((self.chunk_grid_indices_from_key(&key)?, key)))
+            .collect();
+
+        let chunk_size = |(chunk_indices, key): (ArrayIndices, StoreKey)| {
+            storage_transformer
+                .size_key(&key)
+                .map(|size| size.map(|size| (chunk_indices, size)))
+        };
+        let sizes: Vec<Option<(ArrayIndices, u64)>> =
+            iter_concurrent_limit!(options.concurrent_target(), chunk_keys, map, chunk_size)
+                .collect::<Result<_, _>>()?;
+        let mut sizes: Vec<(ArrayIndices, u64)> = sizes.into_iter().flatten().collect();
+        sizes.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        Ok(sizes)
     }
 }
@@ -5,7 +5,10 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use crate::{
     array::ArrayBytes,
     array_subset::ArraySubset,
-    storage::{ReadableWritableStorageTraits, StorageHandle},
+    storage::{
+        ReadableWritableListableStorageTraits, ReadableWritableStorageTraits, StorageError,
+        StorageHandle, StorePrefix,
+    },
 };
 
 use super::{
@@ -14,7 +17,7 @@ use super::{
         StoragePartialDecoder, StoragePartialEncoder,
     },
     concurrency::concurrency_chunks_and_codec,
-    update_array_bytes, Array, ArrayError, Element,
+    update_array_bytes, Array, ArrayError, ArrayIndices, ArraySize, Element,
 };
 
 impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage> {
@@ -151,6 +154,25 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
         self.store_array_subset_ndarray_opt(subset_start, subset_array, &CodecOptions::default())
     }
 
+    /// Encode `elements` and store them as the entire array.
+    ///
+    /// `elements` are in C (row-major) order and span the whole array, so an [`ArraySubset`] does not need to be constructed.
+    /// Every chunk is written in full without being retrieved first, so this is well suited to small arrays such as lookup tables and coordinates.
+    ///
+    /// Use [`store_all_opt`](Array::store_all_opt) to control codec options.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if
+    ///  - the length of `elements` does not match the number of elements in the array,
+    ///  - the size of `T` does not match the data type size,
+    ///  - there is a codec encoding error, or
+    ///  - an underlying store error.
+    ///
+    /// No chunks are written if the length of `elements` or `T` is invalid.
+    pub fn store_all<T: Element>(&self, elements: &[T]) -> Result<(), ArrayError> {
+        self.store_all_opt(elements, &CodecOptions::default())
+    }
+
     /////////////////////////////////////////////////////////////////////////////
     // Advanced methods
     /////////////////////////////////////////////////////////////////////////////
@@ -177,6 +199,9 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                 chunk_shape,
             ));
         }
+        if options.assume_new_chunks() && chunk_subset.start().iter().any(|&x| x != 0) {
+            return Err(ArrayError::UnalignedSubset(chunk_subset.clone()));
+        }
 
         if chunk_subset.shape() == chunk_shape && chunk_subset.start().iter().all(|&x| x == 0) {
             // The subset spans the whole chunk, so store the bytes directly and skip decoding
@@ -190,13 +215,31 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
             // let mutex = self.storage.mutex(&key)?;
             // let _lock = mutex.lock();
 
-            if options.experimental_partial_encoding() {
+            // Use the partial encoder if requested, or if every codec can encode a chunk subset in place
+            if (options.experimental_partial_encoding()
+                || !self
+                    .chunk_codecs(chunk_indices)
+                    .any_partial_encoder_encodes_all())
+                && !options.assume_new_chunks()
+                && options.new_chunk_fill_value().is_none()
+            {
                 let partial_encoder = self.partial_encoder(chunk_indices, options)?;
                 Ok(partial_encoder
                     .partial_encode(&[(chunk_subset, chunk_subset_bytes)], options)?)
             } else {
-                // Decode the entire chunk
-                let chunk_bytes_old = self.retrieve_chunk_opt(chunk_indices, options)?;
+                // Decode the entire chunk, or fill it if it is new
+                let new_chunk_fill_value = self.new_chunk_fill_value(options)?;
+                let chunk_bytes_old = if options.assume_new_chunks() {
+                    None
+                } else {
+                    self.retrieve_chunk_if_exists_opt(chunk_indices, options)?
+                };
+                let chunk_bytes_old = chunk_bytes_old.unwrap_or_else(|| {
+                    ArrayBytes::new_fill_value(
+                        ArraySize::new(self.data_type().size(), chunk_shape.iter().product()),
+                        new_chunk_fill_value,
+                    )
+                });
                 chunk_bytes_old.validate(chunk_shape.iter().product(), self.data_type().size())?;
 
                 // Update the chunk
@@ -269,6 +312,13 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
             ));
         }
 
+        // Storing an empty array subset (e.g. in an array with a zero-size dimension) is a no-op
+        if array_subset.is_empty() {
+            let subset_bytes = subset_bytes.into();
+            subset_bytes.validate(0, self.data_type().size())?;
+            return Ok(());
+        }
+
         // Find the chunks intersecting this array subset
         let chunks = self.chunks_in_array_subset(array_subset)?;
         let Some(chunks) = chunks else {
@@ -277,6 +327,11 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
                 self.shape().to_vec(),
             ));
         };
+        if options.assume_new_chunks()
+            && self.chunk_subset(chunks.start())?.start() != array_subset.start()
+        {
+            return Err(ArrayError::UnalignedSubset(array_subset.clone()));
+        }
         let num_chunks = chunks.num_elements_usize();
         if num_chunks == 1 {
             let chunk_indices = chunks.start();
@@ -298,9 +353,10 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
             let subset_bytes = subset_bytes.into();
             subset_bytes.validate(array_subset.num_elements(), self.data_type().size())?;
             // Calculate chunk/codec concurrency
-            let chunk_representation =
-                self.chunk_array_representation(&vec![0; self.dimensionality()])?;
-            let codec_concurrency = self.recommended_codec_concurrency(&chunk_representation)?;
+            let chunk_indices0 = vec![0; self.dimensionality()];
+            let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
+            let codec_concurrency =
+                self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
             let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                 options.concurrent_target(),
                 num_chunks,
@@ -351,6 +407,18 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
         self.store_array_subset_opt(array_subset, subset_bytes, options)
     }
 
+    /// Explicit options version of [`store_all`](Array::store_all).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_all_opt<T: Element>(
+        &self,
+        elements: &[T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let options = self.store_all_options(elements.len(), options)?;
+        let bytes = T::into_array_bytes(self.data_type(), elements)?;
+        self.store_array_subset_opt(&self.subset_all(), bytes, &options)
+    }
+
     #[cfg(feature = "ndarray")]
     /// Explicit options version of [`store_array_subset_ndarray`](Array::store_array_subset_ndarray).
     #[allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
@@ -405,7 +473,7 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
             self.chunk_key(chunk_indices),
         ));
 
-        Ok(self.codecs.clone().partial_encoder(
+        Ok(self.chunk_codecs(chunk_indices).clone().partial_encoder(
             input_handle,
             output_handle,
             &chunk_representation,
@@ -413,3 +481,75 @@ impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> Array<TStorage>
         )?)
     }
 }
+
+impl<TStorage: ?Sized + ReadableWritableListableStorageTraits + 'static> Array<TStorage> {
+    /// Erase the stored chunks that are entirely the fill value with default codec options, returning the number of chunks erased.
+    ///
+    /// See [`vacuum_opt`](Array::vacuum_opt).
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if there is a codec decoding error or an underlying store error.
+    pub fn vacuum(&self) -> Result<usize, ArrayError> {
+        self.vacuum_opt(&CodecOptions::default())
+    }
+
+    /// Erase the stored chunks that are entirely the fill value, returning the number of chunks erased.
+    ///
+    /// Chunks that are entirely the fill value accumulate when they are written with [`store_empty_chunks`](CodecOptions::store_empty_chunks) enabled, or by other Zarr implementations.
+    /// Since a chunk that does not exist is read as the fill value, erasing them reclaims storage without changing the content of the array and speeds up subsequent reads.
+    ///
+    /// The keys under the array are listed, and the chunks within the chunk grid are decoded concurrently and erased if every element is equal to the fill value.
+    /// Fill value comparison uses [`FillValue::equals_element`](crate::array::FillValue::equals_element), so any NaN matches a NaN fill value.
+    /// Chunks outside of the chunk grid are not considered, see [`prune_orphan_chunks`](Array::prune_orphan_chunks).
+    ///
+    /// This method assumes a single writer.
+    /// A chunk written by another writer between being decoded and erased may be lost, so do not run it concurrently with writes to the array.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if there is a codec decoding error or an underlying store error.
+    pub fn vacuum_opt(&self, options: &CodecOptions) -> Result<usize, ArrayError> {
+        let Some(chunk_grid_shape) = self.chunk_grid_shape() else {
+            return Ok(0);
+        };
+        let prefix: StorePrefix = self.path().try_into().map_err(StorageError::from)?;
+        let chunks: Vec<ArrayIndices> = self
+            .storage
+            .list_prefix(&prefix)?
+            .iter()
+            .filter_map(|key| self.chunk_indices_from_key(key))
+            .filter(|chunk_indices| {
+                std::iter::zip(chunk_indices, &chunk_grid_shape).all(|(i, n)| i < n)
+            })
+            .collect();
+
+        // Calculate chunk/codec concurrency
+        let chunk_indices0 = vec![0; self.dimensionality()];
+        let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
+        let codec_concurrency =
+            self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
+        let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
+            options.concurrent_target(),
+            chunks.len(),
+            options,
+            &codec_concurrency,
+        );
+
+        let vacuum_chunk = |chunk_indices: ArrayIndices| -> Result<bool, ArrayError> {
+            let chunk_bytes = self.retrieve_chunk_opt(&chunk_indices, &options)?;
+            if self.is_fill_value_nan_aware(&chunk_bytes) {
+                self.erase_chunk(&chunk_indices)?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        };
+        let erased = rayon_iter_concurrent_limit::iter_concurrent_limit!(
+            chunk_concurrent_limit,
+            chunks,
+            map,
+            vacuum_chunk
+        )
+        .collect::<Result<Vec<bool>, ArrayError>>()?;
+        Ok(erased.into_iter().filter(|erased| *erased).count())
+    }
+}
@@ -347,10 +347,10 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> ArrayShardedReadableExt
                 Ok(ArrayBytes::new_fill_value(array_size, self.fill_value()))
             } else {
                 // Calculate chunk/codec concurrency
-                let chunk_representation =
-                    self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+                let chunk_indices0 = vec![0; self.dimensionality()];
+                let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_shards,
@@ -478,7 +478,7 @@ mod tests {
     use crate::{
         array::{
             codec::{array_to_bytes::sharding::ShardingCodecBuilder, TransposeCodec},
-            ArrayBuilder, DataType, FillValue,
+            ArrayBuilder, CodecChain, DataType, FillValue,
         },
         array_subset::ArraySubset,
         storage::{
@@ -644,6 +644,47 @@ mod tests {
         array_sharded_ext_impl(false)
     }
 
+    #[test]
+    fn array_sharded_ext_chunk_codecs() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Arc::new(MemoryStore::default());
+        let sharded_codecs =
+            |inner_chunk_shape: Vec<u64>| -> Result<_, Box<dyn std::error::Error>> {
+                Ok(Arc::new(CodecChain::new(
+                    vec![],
+                    Arc::new(ShardingCodecBuilder::new(inner_chunk_shape.try_into()?).build()),
+                    vec![],
+                )))
+            };
+        let mut array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![4, 4].try_into()?,
+            FillValue::from(0u16),
+        )
+        .array_to_bytes_codec(Arc::new(
+            ShardingCodecBuilder::new(vec![2, 2].try_into()?).build(),
+        ))
+        .build(store, "/array")?;
+
+        // An override with the same inner chunk shape keeps the array sharded
+        array.set_chunk_codecs(&[0, 0], Some(sharded_codecs(vec![2, 2])?))?;
+        assert!(array.is_sharded());
+        assert_eq!(array.inner_chunk_shape(), Some(vec![2, 2].try_into()?));
+
+        // Otherwise, the inner chunk grid is not consistent across the array
+        array.set_chunk_codecs(&[0, 1], Some(sharded_codecs(vec![4, 4])?))?;
+        assert!(!array.is_sharded());
+        assert_eq!(array.inner_chunk_shape(), None);
+        assert_eq!(
+            array.inner_chunk_grid().grid_shape(array.shape())?,
+            Some(vec![2, 2])
+        );
+
+        array.set_chunk_codecs(&[0, 1], None)?;
+        assert!(array.is_sharded());
+        Ok(())
+    }
+
     fn array_sharded_ext_impl_transpose(
         valid_inner_chunk_shape: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -7,14 +7,19 @@ use crate::{
     array::ArrayBytes,
     array_subset::ArraySubset,
     config::{global_config, MetadataEraseVersion},
-    node::{meta_key_v2_array, meta_key_v2_attributes, meta_key_v3},
-    storage::{Bytes, StorageError, StorageHandle, WritableStorageTraits},
+    node::{
+        meta_key_v2_array, meta_key_v2_attributes, meta_key_v3, metadata_v3_to_json, set_metadata,
+    },
+    storage::{
+        Bytes, ListableStorageTraits, StorageError, StorageHandle, StorePrefix,
+        WritableStorageTraits,
+    },
 };
 
 use super::{
     codec::{options::CodecOptions, ArrayToBytesCodecTraits},
     concurrency::concurrency_chunks_and_codec,
-    Array, ArrayError, ArrayMetadata, ArrayMetadataOptions, Element,
+    Array, ArrayError, ArrayIndices, ArrayMetadata, ArrayMetadataOptions, Element,
 };
 
 impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
@@ -48,20 +53,30 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
         match metadata {
             ArrayMetadata::V3(metadata) => {
                 let key = meta_key_v3(path);
-                let json = serde_json::to_vec_pretty(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_transformer.set(&key, json.into())
+                let json =
+                    metadata_v3_to_json(&key, &metadata, options.serialize_empty_attributes())?;
+                set_metadata(
+                    &*storage_transformer,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
             }
             ArrayMetadata::V2(metadata) => {
                 let mut metadata = metadata.clone();
 
-                if !metadata.attributes.is_empty() {
+                if !metadata.attributes.is_empty() || options.serialize_empty_attributes() {
                     // Store .zattrs
                     let key = meta_key_v2_attributes(path);
                     let json = serde_json::to_vec_pretty(&metadata.attributes).map_err(|err| {
                         StorageError::InvalidMetadata(key.clone(), err.to_string())
                     })?;
-                    storage_transformer.set(&meta_key_v2_attributes(path), json.into())?;
+                    set_metadata(
+                        &*storage_transformer,
+                        &key,
+                        json.into(),
+                        options.stage_metadata_writes(),
+                    )?;
 
                     metadata.attributes = serde_json::Map::default();
                 }
@@ -70,7 +85,12 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
                 let key = meta_key_v2_array(path);
                 let json = serde_json::to_vec_pretty(&metadata)
                     .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_transformer.set(&key, json.into())
+                set_metadata(
+                    &*storage_transformer,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
             }
         }
     }
@@ -277,7 +297,7 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
             self.erase_chunk(chunk_indices)?;
         } else {
             let chunk_encoded = self
-                .codecs()
+                .chunk_codecs(chunk_indices)
                 .encode(chunk_bytes, &chunk_array_representation, options)
                 .map_err(ArrayError::CodecError)?;
             let chunk_encoded = Bytes::from(chunk_encoded.into_owned());
@@ -366,10 +386,10 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
                 chunks_bytes.validate(array_subset.num_elements(), self.data_type().size())?;
 
                 // Calculate chunk/codec concurrency
-                let chunk_representation =
-                    self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+                let chunk_indices0 = vec![0; self.dimensionality()];
+                let chunk_representation = self.chunk_array_representation(&chunk_indices0)?;
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_chunks,
@@ -430,3 +450,33 @@ impl<TStorage: ?Sized + WritableStorageTraits + 'static> Array<TStorage> {
         }
     }
 }
+
+impl<TStorage: ?Sized + ListableStorageTraits + WritableStorageTraits + 'static> Array<TStorage> {
+    /// Erase the chunks that are entirely outside of the shape of the array, returning the number of chunks erased.
+    ///
+    /// Chunks become orphaned when the array is shrunk (e.g. with [`set_shape`](Array::set_shape)), since they are not erased automatically.
+    /// The keys under the array are listed, and those that are chunk keys of the array with chunk indices outside of the chunk grid are erased.
+    /// Chunks that intersect the shape of the array (including partial chunks at the edge of the array) are kept.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn prune_orphan_chunks(&self) -> Result<usize, StorageError> {
+        let Some(chunk_grid_shape) = self.chunk_grid_shape() else {
+            return Ok(0);
+        };
+        let prefix: StorePrefix = self.path().try_into()?;
+        let orphan_chunks: Vec<ArrayIndices> = self
+            .storage
+            .list_prefix(&prefix)?
+            .iter()
+            .filter_map(|key| self.chunk_indices_from_key(key))
+            .filter(|chunk_indices| {
+                std::iter::zip(chunk_indices, &chunk_grid_shape).any(|(i, n)| i >= n)
+            })
+            .collect();
+        for chunk_indices in &orphan_chunks {
+            self.erase_chunk(chunk_indices)?;
+        }
+        Ok(orphan_chunks.len())
+    }
+}
@@ -1,13 +1,21 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
-use crate::storage::ReadableStorageTraits;
+use crate::storage::{ReadableStorageTraits, StorageError};
 
-use super::{codec::CodecOptions, Array, ArrayBytes, ArrayError, RawBytes};
+use super::{
+    codec::{ArrayToBytesCodecTraits, CodecOptions},
+    Array, ArrayBytes, ArrayError, ArraySize, RawBytes,
+};
 
 // pub mod array_chunk_cache_ext_decoded_sync;
 // pub mod array_chunk_cache_ext_encoded_sync;
 pub(crate) mod array_chunk_cache_ext_sync;
+pub(crate) mod cached_array;
+pub(crate) mod chunk_cache_diagnostics;
+#[macro_use]
 pub(crate) mod chunk_cache_lru;
+pub(crate) mod chunk_cache_policy;
+pub(crate) mod chunk_cache_shared;
 // pub mod chunk_cache_lru_chunk_limit_thread_local;
 // pub mod chunk_cache_lru_size_limit_thread_local;
 
@@ -21,18 +29,120 @@ pub type ChunkCacheTypeDecoded = ArrayBytes<'static>;
 pub trait ChunkCacheType: Send + Sync + 'static {
     /// The size of the chunk in bytes.
     fn size(&self) -> usize;
+
+    /// Retrieve the chunk at `chunk_indices` of `array` from its store.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the underlying array retrieval method fails.
+    fn retrieve<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Self, ArrayError>
+    where
+        Self: Sized;
+
+    /// Decode the cached `chunk` at `chunk_indices` of `array`.
+    ///
+    /// # Errors
+    /// Returns an [`ArrayError`] if the chunk cannot be decoded.
+    fn decode<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        chunk: Arc<Self>,
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError>;
 }
 
 impl ChunkCacheType for ChunkCacheTypeEncoded {
     fn size(&self) -> usize {
         self.as_ref().map_or(0, |v| v.len())
     }
+
+    fn retrieve<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        _options: &CodecOptions,
+    ) -> Result<Self, ArrayError> {
+        Ok(array.retrieve_encoded_chunk(chunk_indices)?.map(Cow::Owned))
+    }
+
+    fn decode<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        chunk: Arc<Self>,
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        if let Some(chunk_encoded) = chunk.as_ref() {
+            let chunk_representation = array.chunk_array_representation(chunk_indices)?;
+            let bytes = array
+                .chunk_codecs(chunk_indices)
+                .decode(Cow::Borrowed(chunk_encoded), &chunk_representation, options)
+                .map_err(ArrayError::CodecError)?;
+            bytes.validate(
+                chunk_representation.num_elements(),
+                chunk_representation.data_type().size(),
+            )?;
+            Ok(Arc::new(bytes.into_owned()))
+        } else {
+            let chunk_shape = array.chunk_shape(chunk_indices)?;
+            let array_size =
+                ArraySize::new(array.data_type().size(), chunk_shape.num_elements_u64());
+            Ok(Arc::new(ArrayBytes::new_fill_value(
+                array_size,
+                array.fill_value(),
+            )))
+        }
+    }
 }
 
 impl ChunkCacheType for ChunkCacheTypeDecoded {
     fn size(&self) -> usize {
         ArrayBytes::size(self)
     }
+
+    fn retrieve<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Self, ArrayError> {
+        Ok(array
+            .retrieve_chunk_opt(chunk_indices, options)?
+            .into_owned())
+    }
+
+    fn decode<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        chunk: Arc<Self>,
+        _array: &Array<TStorage>,
+        _chunk_indices: &[u64],
+        _options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        Ok(chunk)
+    }
+}
+
+/// Retrieve and decode a chunk through `cache`, retrieving it from the store and inserting it if it is not cached.
+pub(crate) fn retrieve_chunk_through_cache<CT, C, TStorage>(
+    cache: &C,
+    array: &Array<TStorage>,
+    chunk_indices: &[u64],
+    options: &CodecOptions,
+) -> Result<Arc<ArrayBytes<'static>>, ArrayError>
+where
+    CT: ChunkCacheType,
+    C: ChunkCache<CT> + ?Sized,
+    TStorage: ?Sized + ReadableStorageTraits + 'static,
+{
+    let chunk = cache
+        .try_get_or_insert_with::<_, ArrayError>(chunk_indices.to_vec(), || {
+            Ok(Arc::new(CT::retrieve(array, chunk_indices, options)?))
+        })
+        .map_err(|err| {
+            // moka returns an Arc'd error, unwrap it noting that ArrayError is not cloneable
+            Arc::try_unwrap(err)
+                .unwrap_or_else(|err| ArrayError::StorageError(StorageError::from(err.to_string())))
+        })?;
+    CT::decode(chunk, array, chunk_indices, options)
 }
 
 /// Traits for a chunk cache.
@@ -306,8 +306,8 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> ArrayChunkCacheExt<TSto
             ));
         };
 
-        let chunk_representation0 =
-            self.chunk_array_representation(&vec![0; self.dimensionality()])?;
+        let chunk_indices0 = vec![0; self.dimensionality()];
+        let chunk_representation0 = self.chunk_array_representation(&chunk_indices0)?;
 
         let num_chunks = chunks.num_elements_usize();
         match num_chunks {
@@ -341,7 +341,7 @@ impl<TStorage: ?Sized + ReadableStorageTraits + 'static> ArrayChunkCacheExt<TSto
                 // Calculate chunk/codec concurrency
                 let num_chunks = chunks.num_elements_usize();
                 let codec_concurrency =
-                    self.recommended_codec_concurrency(&chunk_representation0)?;
+                    self.recommended_codec_concurrency(&chunk_indices0, &chunk_representation0)?;
                 let (chunk_concurrent_limit, options) = concurrency_chunks_and_codec(
                     options.concurrent_target(),
                     num_chunks,
@@ -0,0 +1,647 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use crate::{
+    array::{codec::CodecOptions, Array, ArrayBytes, ArrayError, Element, ElementOwned},
+    array_subset::ArraySubset,
+    storage::{
+        ReadableStorageTraits, ReadableWritableStorageTraits, StorageError, WritableStorageTraits,
+    },
+};
+
+use super::{
+    array_chunk_cache_ext_sync::ArrayChunkCacheExt,
+    chunk_cache_lru::{ChunkCacheDecodedLruChunkLimit, ChunkCacheDecodedLruSizeLimit},
+    chunk_cache_shared::{ChunkCacheDecodedSharedArray, ChunkCacheDecodedSharedSizeLimit},
+    ChunkCache, ChunkCacheTypeDecoded,
+};
+
+/// The LRU decoded chunk cache underlying a [`CachedArray`].
+enum CachedArrayLru {
+    /// A cache bounded by a number of chunks.
+    ChunkLimit(ChunkCacheDecodedLruChunkLimit),
+    /// A cache bounded by the size of the decoded chunks in bytes.
+    SizeLimit(ChunkCacheDecodedLruSizeLimit),
+    /// The chunks of the array in a cache shared by multiple arrays.
+    Shared(ChunkCacheDecodedSharedArray),
+}
+
+/// The decoded chunk cache of a [`CachedArray`], which counts cache hits and misses.
+struct CachedArrayChunks {
+    cache: CachedArrayLru,
+    /// Incremented whenever chunks are invalidated.
+    ///
+    /// A chunk retrieved while chunks are invalidated may be stale, so it is only inserted if the generation is unchanged.
+    /// A shared cache has one generation for all of its arrays, so invalidation through any of them is seen.
+    generation: Arc<Mutex<u64>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl CachedArrayChunks {
+    fn new(cache: CachedArrayLru) -> Self {
+        let generation = match &cache {
+            CachedArrayLru::Shared(cache) => cache.generation().clone(),
+            CachedArrayLru::ChunkLimit(_) | CachedArrayLru::SizeLimit(_) => Arc::new(Mutex::new(0)),
+        };
+        Self {
+            cache,
+            generation,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    fn remove(&self, chunk_indices: &[u64]) {
+        let mut generation = self.generation.lock().unwrap();
+        match &self.cache {
+            CachedArrayLru::ChunkLimit(cache) => cache.invalidate(chunk_indices),
+            CachedArrayLru::SizeLimit(cache) => cache.invalidate(chunk_indices),
+            CachedArrayLru::Shared(cache) => cache.invalidate(chunk_indices),
+        }
+        *generation += 1;
+    }
+
+    fn clear(&self) {
+        let mut generation = self.generation.lock().unwrap();
+        match &self.cache {
+            CachedArrayLru::ChunkLimit(cache) => cache.invalidate_all(),
+            CachedArrayLru::SizeLimit(cache) => cache.invalidate_all(),
+            CachedArrayLru::Shared(cache) => cache.invalidate_all(),
+        }
+        *generation += 1;
+    }
+
+    /// Return the cached chunk at `chunk_indices`, or retrieve it with `f` and cache it unless chunks were invalidated in the meantime.
+    fn get_or_insert_with(
+        &self,
+        chunk_indices: &[u64],
+        f: impl FnOnce() -> Result<Arc<ChunkCacheTypeDecoded>, ArrayError>,
+    ) -> Result<Arc<ChunkCacheTypeDecoded>, ArrayError> {
+        let generation = *self.generation.lock().unwrap();
+        if let Some(chunk) = self.get(chunk_indices) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(chunk);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let chunk = f()?;
+        let generation_now = self.generation.lock().unwrap();
+        if *generation_now == generation {
+            self.insert(chunk_indices.to_vec(), chunk.clone());
+        }
+        Ok(chunk)
+    }
+}
+
+impl ChunkCache<ChunkCacheTypeDecoded> for CachedArrayChunks {
+    fn retrieve_chunk<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        &self,
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        self.get_or_insert_with(chunk_indices, || {
+            Ok(Arc::new(
+                array
+                    .retrieve_chunk_opt(chunk_indices, options)?
+                    .into_owned(),
+            ))
+        })
+    }
+
+    fn get(&self, chunk_indices: &[u64]) -> Option<Arc<ChunkCacheTypeDecoded>> {
+        match &self.cache {
+            CachedArrayLru::ChunkLimit(cache) => cache.get(chunk_indices),
+            CachedArrayLru::SizeLimit(cache) => cache.get(chunk_indices),
+            CachedArrayLru::Shared(cache) => cache.get(chunk_indices),
+        }
+    }
+
+    fn insert(&self, chunk_indices: Vec<u64>, chunk: Arc<ChunkCacheTypeDecoded>) {
+        match &self.cache {
+            CachedArrayLru::ChunkLimit(cache) => cache.insert(chunk_indices, chunk),
+            CachedArrayLru::SizeLimit(cache) => cache.insert(chunk_indices, chunk),
+            CachedArrayLru::Shared(cache) => cache.insert(chunk_indices, chunk),
+        }
+    }
+
+    fn try_get_or_insert_with<F, E>(
+        &self,
+        chunk_indices: Vec<u64>,
+        f: F,
+    ) -> Result<Arc<ChunkCacheTypeDecoded>, Arc<ArrayError>>
+    where
+        F: FnOnce() -> Result<Arc<ChunkCacheTypeDecoded>, ArrayError>,
+    {
+        self.get_or_insert_with(&chunk_indices, f).map_err(Arc::new)
+    }
+
+    fn len(&self) -> usize {
+        match &self.cache {
+            CachedArrayLru::ChunkLimit(cache) => cache.len(),
+            CachedArrayLru::SizeLimit(cache) => cache.len(),
+            CachedArrayLru::Shared(cache) => cache.len(),
+        }
+    }
+}
+
+/// An [`Array`] with an LRU (least recently used) cache of decoded chunks.
+///
+/// Created with [`Array::with_chunk_cache`], [`CachedArray::new_with_size_limit`], or [`CachedArray::new_with_shared_cache`].
+/// Arrays opened from a [`Hierarchy`](crate::node::Hierarchy) use the chunk cache of the hierarchy.
+/// Retrieve methods serve decoded chunks from the cache and only retrieve and decode chunks that are not cached, so repeatedly reading overlapping subsets decodes each chunk once.
+/// The cache is bounded by a number of chunks or a size in bytes (optionally shared with other arrays), and is safe to share across threads.
+///
+/// Store methods write through to the array and invalidate the cached chunks they affect.
+/// Writes made through the inner [`array`](CachedArray::array), or by other array handles, are not seen by the cache; use [`clear_cache`](CachedArray::clear_cache) after such writes.
+///
+/// This is a convenience wrapper over the [`ArrayChunkCacheExt`] retrieve methods with a cache owned by the array.
+pub struct CachedArray<TStorage: ?Sized> {
+    array: Array<TStorage>,
+    cache: CachedArrayChunks,
+}
+
+impl<TStorage: ?Sized> Array<TStorage> {
+    /// Wrap the array with an LRU (least recently used) cache of up to `capacity` decoded chunks.
+    ///
+    /// See [`CachedArray`].
+    #[must_use]
+    pub fn with_chunk_cache(self, capacity: usize) -> CachedArray<TStorage> {
+        CachedArray::new(self, capacity)
+    }
+}
+
+impl<TStorage: ?Sized> CachedArray<TStorage> {
+    /// Create a new cached array with a cache of up to `capacity` decoded chunks.
+    #[must_use]
+    pub fn new(array: Array<TStorage>, capacity: usize) -> Self {
+        Self {
+            array,
+            cache: CachedArrayChunks::new(CachedArrayLru::ChunkLimit(
+                ChunkCacheDecodedLruChunkLimit::new(capacity as u64),
+            )),
+        }
+    }
+
+    /// Create a new cached array with a cache of decoded chunks up to `capacity` bytes.
+    ///
+    /// A chunk larger than `capacity` is not cached.
+    #[must_use]
+    pub fn new_with_size_limit(array: Array<TStorage>, capacity: usize) -> Self {
+        Self {
+            array,
+            cache: CachedArrayChunks::new(CachedArrayLru::SizeLimit(
+                ChunkCacheDecodedLruSizeLimit::new(capacity as u64),
+            )),
+        }
+    }
+
+    /// Create a new cached array with the chunks of the array cached in `chunk_cache`, which can be shared by multiple arrays.
+    ///
+    /// Writes through this cached array only invalidate the chunks of this array in `chunk_cache`.
+    #[must_use]
+    pub fn new_with_shared_cache(
+        array: Array<TStorage>,
+        chunk_cache: &ChunkCacheDecodedSharedSizeLimit,
+    ) -> Self {
+        let cache = chunk_cache.array_cache(array.path());
+        Self {
+            array,
+            cache: CachedArrayChunks::new(CachedArrayLru::Shared(cache)),
+        }
+    }
+
+    /// Return the underlying array.
+    ///
+    /// Writes through the underlying array do not invalidate the cache.
+    #[must_use]
+    pub fn array(&self) -> &Array<TStorage> {
+        &self.array
+    }
+
+    /// Unwrap the underlying array, dropping the cache.
+    #[must_use]
+    pub fn into_inner(self) -> Array<TStorage> {
+        self.array
+    }
+
+    /// Remove all chunks from the cache.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+
+    /// Return the number of chunks in the cache.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[must_use]
+    pub fn cache_len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Return the number of chunk retrievals served from the cache.
+    #[must_use]
+    pub fn cache_hits(&self) -> usize {
+        self.cache.hits.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of chunk retrievals that were not cached and were retrieved from the store.
+    #[must_use]
+    pub fn cache_misses(&self) -> usize {
+        self.cache.misses.load(Ordering::Relaxed)
+    }
+
+    /// Reset the cache hit and miss counters.
+    pub fn reset_cache_counters(&self) {
+        self.cache.hits.store(0, Ordering::Relaxed);
+        self.cache.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Remove the chunks intersecting `array_subset` from the cache.
+    fn invalidate_array_subset(&self, array_subset: &ArraySubset) {
+        match self.array.chunks_in_array_subset(array_subset) {
+            Ok(Some(chunks)) => {
+                for chunk_indices in &chunks.indices() {
+                    self.cache.remove(&chunk_indices);
+                }
+            }
+            Ok(None) | Err(_) => self.clear_cache(),
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> CachedArray<TStorage> {
+    /// Read and decode the chunk at `chunk_indices`, using the cache.
+    ///
+    /// See [`Array::retrieve_chunk`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_chunk(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        self.retrieve_chunk_opt(chunk_indices, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`retrieve_chunk`](CachedArray::retrieve_chunk).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_chunk_opt(
+        &self,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        self.array
+            .retrieve_chunk_opt_cached(&self.cache, chunk_indices, options)
+    }
+
+    /// Read and decode the chunk at `chunk_indices` into a vector of its elements, using the cache.
+    ///
+    /// See [`Array::retrieve_chunk_elements`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_chunk_elements<T: ElementOwned>(
+        &self,
+        chunk_indices: &[u64],
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_chunk_elements_opt(chunk_indices, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`retrieve_chunk_elements`](CachedArray::retrieve_chunk_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_chunk_elements_opt<T: ElementOwned>(
+        &self,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.array
+            .retrieve_chunk_elements_opt_cached(&self.cache, chunk_indices, options)
+    }
+
+    /// Read and decode the `array_subset` of the array, using the cache.
+    ///
+    /// See [`Array::retrieve_array_subset`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        self.retrieve_array_subset_opt(array_subset, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`retrieve_array_subset`](CachedArray::retrieve_array_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_opt(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'_>, ArrayError> {
+        self.array
+            .retrieve_array_subset_opt_cached(&self.cache, array_subset, options)
+    }
+
+    /// Read and decode the `array_subset` of the array into a vector of its elements, using the cache.
+    ///
+    /// See [`Array::retrieve_array_subset_elements`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_elements<T: ElementOwned>(
+        &self,
+        array_subset: &ArraySubset,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.retrieve_array_subset_elements_opt(array_subset, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`retrieve_array_subset_elements`](CachedArray::retrieve_array_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn retrieve_array_subset_elements_opt<T: ElementOwned>(
+        &self,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<Vec<T>, ArrayError> {
+        self.array
+            .retrieve_array_subset_elements_opt_cached(&self.cache, array_subset, options)
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits + 'static> CachedArray<TStorage> {
+    /// Encode `chunk_bytes` and store at `chunk_indices`, invalidating the cached chunk.
+    ///
+    /// See [`Array::store_chunk`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_chunk<'a>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: impl Into<ArrayBytes<'a>>,
+    ) -> Result<(), ArrayError> {
+        self.store_chunk_opt(chunk_indices, chunk_bytes, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`store_chunk`](CachedArray::store_chunk).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_chunk_opt<'a>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_bytes: impl Into<ArrayBytes<'a>>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let result = self
+            .array
+            .store_chunk_opt(chunk_indices, chunk_bytes, options);
+        self.cache.remove(chunk_indices);
+        result
+    }
+
+    /// Encode `chunk_elements` and store at `chunk_indices`, invalidating the cached chunk.
+    ///
+    /// See [`Array::store_chunk_elements`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_chunk_elements<T: Element>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_elements: &[T],
+    ) -> Result<(), ArrayError> {
+        self.store_chunk_elements_opt(chunk_indices, chunk_elements, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`store_chunk_elements`](CachedArray::store_chunk_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_chunk_elements_opt<T: Element>(
+        &self,
+        chunk_indices: &[u64],
+        chunk_elements: &[T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let result = self
+            .array
+            .store_chunk_elements_opt(chunk_indices, chunk_elements, options);
+        self.cache.remove(chunk_indices);
+        result
+    }
+
+    /// Erase the chunk at `chunk_indices`, invalidating the cached chunk.
+    ///
+    /// See [`Array::erase_chunk`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn erase_chunk(&self, chunk_indices: &[u64]) -> Result<(), StorageError> {
+        let result = self.array.erase_chunk(chunk_indices);
+        self.cache.remove(chunk_indices);
+        result
+    }
+}
+
+impl<TStorage: ?Sized + ReadableWritableStorageTraits + 'static> CachedArray<TStorage> {
+    /// Encode `subset_bytes` and store in `array_subset`, invalidating the cached chunks intersecting `array_subset`.
+    ///
+    /// See [`Array::store_array_subset`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset<'a>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: impl Into<ArrayBytes<'a>>,
+    ) -> Result<(), ArrayError> {
+        self.store_array_subset_opt(array_subset, subset_bytes, &CodecOptions::default())
+    }
+
+    /// Explicit options version of [`store_array_subset`](CachedArray::store_array_subset).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_opt<'a>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_bytes: impl Into<ArrayBytes<'a>>,
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let result = self
+            .array
+            .store_array_subset_opt(array_subset, subset_bytes, options);
+        self.invalidate_array_subset(array_subset);
+        result
+    }
+
+    /// Encode `subset_elements` and store in `array_subset`, invalidating the cached chunks intersecting `array_subset`.
+    ///
+    /// See [`Array::store_array_subset_elements`].
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_elements<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_elements: &[T],
+    ) -> Result<(), ArrayError> {
+        self.store_array_subset_elements_opt(
+            array_subset,
+            subset_elements,
+            &CodecOptions::default(),
+        )
+    }
+
+    /// Explicit options version of [`store_array_subset_elements`](CachedArray::store_array_subset_elements).
+    #[allow(clippy::missing_errors_doc)]
+    pub fn store_array_subset_elements_opt<T: Element>(
+        &self,
+        array_subset: &ArraySubset,
+        subset_elements: &[T],
+        options: &CodecOptions,
+    ) -> Result<(), ArrayError> {
+        let result =
+            self.array
+                .store_array_subset_elements_opt(array_subset, subset_elements, options);
+        self.invalidate_array_subset(array_subset);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        node::NodePath,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn cached_array() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+        array
+            .store_array_subset_elements(&array.subset_all(), &[1u8; 64])
+            .unwrap();
+        let array = array.with_chunk_cache(2);
+
+        // Overlapping subsets only retrieve each chunk once
+        let subset = ArraySubset::new_with_ranges(&[3..5, 0..4]);
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            vec![1u8; 8]
+        );
+        assert_eq!(
+            array.retrieve_array_subset_elements::<u8>(&subset).unwrap(),
+            vec![1u8; 8]
+        );
+        assert_eq!(array.cache_hits(), 2);
+        assert_eq!(array.cache_misses(), 2);
+        assert_eq!(array.cache_len(), 2);
+
+        // Writes invalidate the affected chunks
+        array
+            .store_array_subset_elements(&ArraySubset::new_with_ranges(&[0..1, 0..1]), &[2u8])
+            .unwrap();
+        assert_eq!(array.cache_len(), 1);
+        assert_eq!(array.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap()[0], 2);
+        array.store_chunk_elements(&[1, 0], &[3u8; 16]).unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[1, 0]).unwrap(),
+            vec![3u8; 16]
+        );
+        array.erase_chunk(&[1, 0]).unwrap();
+        assert_eq!(
+            array.retrieve_chunk_elements::<u8>(&[1, 0]).unwrap(),
+            vec![0u8; 16]
+        );
+
+        // The cache is bounded
+        array
+            .retrieve_array_subset_elements::<u8>(&array.array().subset_all())
+            .unwrap();
+        assert_eq!(array.cache_len(), 2);
+
+        array.clear_cache();
+        array.reset_cache_counters();
+        assert_eq!(array.cache_len(), 0);
+        assert_eq!(array.cache_hits(), 0);
+        assert_eq!(array.cache_misses(), 0);
+    }
+
+    #[test]
+    fn cached_array_size_limit() {
+        let store = Arc::new(MemoryStore::new());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt16,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .build(store, "/")
+        .unwrap();
+
+        // Each chunk is 32 bytes
+        let cached = CachedArray::new_with_size_limit(array, 64);
+        cached
+            .retrieve_array_subset_elements::<u16>(&cached.array().subset_all())
+            .unwrap();
+        assert_eq!(cached.cache_len(), 2);
+        let array = cached.into_inner();
+        let cached = CachedArray::new_with_size_limit(array, 16);
+        cached.retrieve_chunk(&[0, 0]).unwrap();
+        assert_eq!(cached.cache_len(), 0);
+    }
+
+    #[test]
+    fn cached_array_invalidate_during_retrieve() {
+        let cache = CachedArrayChunks::new(CachedArrayLru::ChunkLimit(
+            ChunkCacheDecodedLruChunkLimit::new(2),
+        ));
+        let chunk = || Arc::new(ArrayBytes::new_flen(vec![0u8; 4]));
+
+        // A chunk retrieved while it is invalidated may be stale and is not cached
+        let retrieved = cache
+            .get_or_insert_with(&[0], || {
+                cache.remove(&[0]);
+                Ok(chunk())
+            })
+            .unwrap();
+        assert_eq!(retrieved.size(), 4);
+        assert!(cache.get(&[0]).is_none());
+        cache
+            .get_or_insert_with(&[0], || {
+                cache.clear();
+                Ok(chunk())
+            })
+            .unwrap();
+        assert!(cache.get(&[0]).is_none());
+
+        // Otherwise it is cached
+        cache.get_or_insert_with(&[0], || Ok(chunk())).unwrap();
+        assert!(cache.get(&[0]).is_some());
+        assert_eq!(cache.hits.load(Ordering::Relaxed), 0);
+        assert_eq!(cache.misses.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn cached_array_shared_invalidate_during_retrieve() {
+        let chunk_cache = ChunkCacheDecodedSharedSizeLimit::new(1024);
+        let path = NodePath::root();
+        let cache = CachedArrayChunks::new(CachedArrayLru::Shared(chunk_cache.array_cache(&path)));
+        let cache_other =
+            CachedArrayChunks::new(CachedArrayLru::Shared(chunk_cache.array_cache(&path)));
+        let chunk = || Arc::new(ArrayBytes::new_flen(vec![0u8; 4]));
+
+        // A chunk invalidated through another handle of the shared cache during retrieval is not cached
+        cache
+            .get_or_insert_with(&[0], || {
+                cache_other.remove(&[0]);
+                Ok(chunk())
+            })
+            .unwrap();
+        assert!(cache.get(&[0]).is_none());
+        cache
+            .get_or_insert_with(&[0], || {
+                chunk_cache.clear();
+                Ok(chunk())
+            })
+            .unwrap();
+        assert!(cache.get(&[0]).is_none());
+
+        cache.get_or_insert_with(&[0], || Ok(chunk())).unwrap();
+        assert!(cache_other.get(&[0]).is_some());
+    }
+}
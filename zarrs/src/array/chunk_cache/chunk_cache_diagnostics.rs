@@ -0,0 +1,254 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{
+    array::{codec::CodecOptions, Array, ArrayBytes, ArrayError, ArrayIndices},
+    storage::ReadableStorageTraits,
+};
+
+use super::{retrieve_chunk_through_cache, ChunkCache, ChunkCacheType};
+
+/// The source of a chunk retrieved through a [`ChunkCacheDiagnostics`] cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkCacheSource {
+    /// The chunk was served from the cache.
+    Cache,
+    /// The chunk was not in the cache and was fetched from the store.
+    Store,
+}
+
+/// A record of a chunk request made through a [`ChunkCacheDiagnostics`] cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkCacheRecord {
+    /// The chunk indices.
+    pub chunk_indices: ArrayIndices,
+    /// The source of the chunk.
+    pub source: ChunkCacheSource,
+}
+
+/// A chunk cache wrapper that counts whether each requested chunk was served from the cache or fetched from the store.
+///
+/// This is intended for measuring the effectiveness of a chunk cache configuration.
+/// The data path of the inner cache is unchanged, so using the inner cache directly has no diagnostic overhead.
+///
+/// Hits and misses are counted atomically.
+/// The most recent requests are also recorded, up to the [record capacity](ChunkCacheDiagnostics::with_record_capacity).
+///
+/// ```rust
+/// # use zarrs::array::{ChunkCacheDecodedLruChunkLimit, ChunkCacheDiagnostics};
+/// let cache = ChunkCacheDiagnostics::new(ChunkCacheDecodedLruChunkLimit::new(16));
+/// // array.retrieve_array_subset_opt_cached(&cache, ...)
+/// println!("hits: {}, misses: {}", cache.hits(), cache.misses());
+/// ```
+pub struct ChunkCacheDiagnostics<C> {
+    cache: C,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    record_capacity: usize,
+    records: Mutex<VecDeque<ChunkCacheRecord>>,
+}
+
+impl<C> ChunkCacheDiagnostics<C> {
+    /// The default maximum number of records retained by a [`ChunkCacheDiagnostics`] cache.
+    pub const DEFAULT_RECORD_CAPACITY: usize = 1024;
+
+    /// Create a new [`ChunkCacheDiagnostics`] wrapping `cache`.
+    ///
+    /// Up to [`DEFAULT_RECORD_CAPACITY`](Self::DEFAULT_RECORD_CAPACITY) records are retained.
+    #[must_use]
+    pub fn new(cache: C) -> Self {
+        Self::with_record_capacity(cache, Self::DEFAULT_RECORD_CAPACITY)
+    }
+
+    /// Create a new [`ChunkCacheDiagnostics`] wrapping `cache` that retains up to `record_capacity` of the most recent records.
+    ///
+    /// A `record_capacity` of zero disables recording, and only hits and misses are counted.
+    #[must_use]
+    pub fn with_record_capacity(cache: C, record_capacity: usize) -> Self {
+        Self {
+            cache,
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            record_capacity,
+            records: Mutex::default(),
+        }
+    }
+
+    /// Return the inner cache.
+    #[must_use]
+    pub fn inner(&self) -> &C {
+        &self.cache
+    }
+
+    /// Return the retained records of chunk requests in the order they were made.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    #[must_use]
+    pub fn records(&self) -> Vec<ChunkCacheRecord> {
+        self.records.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Return the retained records of chunk requests and clear them.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn take_records(&self) -> Vec<ChunkCacheRecord> {
+        std::mem::take(&mut *self.records.lock().unwrap()).into()
+    }
+
+    /// Return the number of chunk requests served from the cache.
+    #[must_use]
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of chunk requests fetched from the store.
+    #[must_use]
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Reset the hit and miss counters and clear the retained records.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn reset(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.records.lock().unwrap().clear();
+    }
+
+    fn record(&self, chunk_indices: Vec<u64>, source: ChunkCacheSource) {
+        match source {
+            ChunkCacheSource::Cache => &self.hits,
+            ChunkCacheSource::Store => &self.misses,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+        if self.record_capacity > 0 {
+            let mut records = self.records.lock().unwrap();
+            if records.len() == self.record_capacity {
+                records.pop_front();
+            }
+            records.push_back(ChunkCacheRecord {
+                chunk_indices,
+                source,
+            });
+        }
+    }
+}
+
+impl<CT: ChunkCacheType, C: ChunkCache<CT>> ChunkCache<CT> for ChunkCacheDiagnostics<C> {
+    fn retrieve_chunk<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        &self,
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        // Classify the request by whether the lookup in try_get_or_insert_with fetches the chunk
+        retrieve_chunk_through_cache(self, array, chunk_indices, options)
+    }
+
+    fn get(&self, chunk_indices: &[u64]) -> Option<Arc<CT>> {
+        self.cache.get(chunk_indices)
+    }
+
+    fn insert(&self, chunk_indices: Vec<u64>, chunk: Arc<CT>) {
+        self.cache.insert(chunk_indices, chunk);
+    }
+
+    fn try_get_or_insert_with<F, E>(
+        &self,
+        chunk_indices: Vec<u64>,
+        f: F,
+    ) -> Result<Arc<CT>, Arc<ArrayError>>
+    where
+        F: FnOnce() -> Result<Arc<CT>, ArrayError>,
+    {
+        let mut source = ChunkCacheSource::Cache;
+        let chunk = self
+            .cache
+            .try_get_or_insert_with::<_, E>(chunk_indices.clone(), || {
+                source = ChunkCacheSource::Store;
+                f()
+            });
+        self.record(chunk_indices, source);
+        chunk
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        array::{
+            ArrayBuilder, ArrayChunkCacheExt, ChunkCacheDecodedLruChunkLimit,
+            ChunkCacheEncodedLruChunkLimit, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+        storage::store::MemoryStore,
+    };
+
+    #[test]
+    fn array_chunk_cache_diagnostics() {
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![8, 8],
+            DataType::UInt8,
+            vec![4, 4].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(store, "/")
+        .unwrap();
+
+        let cache = ChunkCacheDiagnostics::new(ChunkCacheDecodedLruChunkLimit::new(4));
+        let subset = ArraySubset::new_with_ranges(&[3..5, 0..4]);
+        array
+            .retrieve_array_subset_opt_cached(&cache, &subset, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+
+        array
+            .retrieve_array_subset_opt_cached(&cache, &subset, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 2);
+
+        let records = cache.take_records();
+        assert_eq!(records.len(), 4);
+        assert!(records.iter().all(|record| record.chunk_indices[1] == 0));
+        assert!(cache.records().is_empty());
+
+        // Only the most recent records are retained
+        let cache =
+            ChunkCacheDiagnostics::with_record_capacity(ChunkCacheEncodedLruChunkLimit::new(4), 3);
+        let subset = array.subset_all();
+        array
+            .retrieve_array_subset_opt_cached(&cache, &subset, &CodecOptions::default())
+            .unwrap();
+        array
+            .retrieve_array_subset_opt_cached(&cache, &subset, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(cache.hits(), 4);
+        assert_eq!(cache.misses(), 4);
+        let records = cache.records();
+        assert_eq!(records.len(), 3);
+        assert!(records
+            .iter()
+            .all(|record| record.source == ChunkCacheSource::Cache));
+        cache.reset();
+        assert_eq!(cache.hits(), 0);
+        assert!(cache.records().is_empty());
+    }
+}
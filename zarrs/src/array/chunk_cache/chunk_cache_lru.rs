@@ -10,15 +10,10 @@ use moka::{
 };
 use thread_local::ThreadLocal;
 
-use crate::{
-    array::{codec::ArrayToBytesCodecTraits, ArrayBytes, ArrayError, ArrayIndices, ArraySize},
-    storage::StorageError,
-};
+use crate::array::{ArrayError, ArrayIndices};
 
 use super::{ChunkCache, ChunkCacheType, ChunkCacheTypeDecoded, ChunkCacheTypeEncoded};
 
-use std::borrow::Cow;
-
 type ChunkIndices = ArrayIndices;
 
 /// A chunk cache with a fixed chunk capacity.
@@ -81,6 +76,16 @@ impl<CT: ChunkCacheType> ChunkCacheLruChunkLimit<CT> {
             .build();
         Self { cache }
     }
+
+    /// Remove the chunk at `chunk_indices` from the cache.
+    pub(crate) fn invalidate(&self, chunk_indices: &[u64]) {
+        self.cache.invalidate(&chunk_indices.to_vec());
+    }
+
+    /// Remove all chunks from the cache.
+    pub(crate) fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+    }
 }
 
 impl<CT: ChunkCacheType> ChunkCacheLruSizeLimit<CT> {
@@ -93,6 +98,16 @@ impl<CT: ChunkCacheType> ChunkCacheLruSizeLimit<CT> {
             .build();
         Self { cache }
     }
+
+    /// Remove the chunk at `chunk_indices` from the cache.
+    pub(crate) fn invalidate(&self, chunk_indices: &[u64]) {
+        self.cache.invalidate(&chunk_indices.to_vec());
+    }
+
+    /// Remove all chunks from the cache.
+    pub(crate) fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+    }
 }
 
 impl<CT: ChunkCacheType> ChunkCacheLruChunkLimitThreadLocal<CT> {
@@ -166,47 +181,12 @@ macro_rules! impl_ChunkCacheLruEncoded {
             chunk_indices: &[u64],
             options: &crate::array::codec::CodecOptions,
         ) -> Result<Arc<crate::array::ArrayBytes<'static>>, ArrayError> {
-            let chunk_encoded = self
-                .try_get_or_insert_with::<_, ArrayError>(chunk_indices.to_vec(), || {
-                    Ok(Arc::new(
-                        array.retrieve_encoded_chunk(chunk_indices)?.map(Cow::Owned),
-                    ))
-                })
-                .map_err(|err| {
-                    // moka returns an Arc'd error, unwrap it noting that ArrayError is not cloneable
-                    Arc::try_unwrap(err).unwrap_or_else(|err| {
-                        ArrayError::StorageError(StorageError::from(err.to_string()))
-                    })
-                })?;
-            // let chunk_encoded = if let Some(chunk) = cache.get(chunk_indices) {
-            //     chunk
-            // } else {
-            //     let chunk = self.retrieve_encoded_chunk(chunk_indices)?.map(Cow::Owned);
-            //     let chunk = Arc::new(chunk);
-            //     cache.insert(chunk_indices.to_vec(), chunk.clone());
-            //     chunk
-            // };
-
-            if let Some(chunk_encoded) = chunk_encoded.as_ref() {
-                let chunk_representation = array.chunk_array_representation(chunk_indices)?;
-                let bytes = array
-                    .codecs()
-                    .decode(Cow::Borrowed(chunk_encoded), &chunk_representation, options)
-                    .map_err(ArrayError::CodecError)?;
-                bytes.validate(
-                    chunk_representation.num_elements(),
-                    chunk_representation.data_type().size(),
-                )?;
-                Ok(Arc::new(bytes.into_owned()))
-            } else {
-                let chunk_shape = array.chunk_shape(chunk_indices)?;
-                let array_size =
-                    ArraySize::new(array.data_type().size(), chunk_shape.num_elements_u64());
-                Ok(Arc::new(ArrayBytes::new_fill_value(
-                    array_size,
-                    array.fill_value(),
-                )))
-            }
+            crate::array::chunk_cache::retrieve_chunk_through_cache::<ChunkCacheTypeEncoded, _, _>(
+                self,
+                array,
+                chunk_indices,
+                options,
+            )
         }
     };
 }
@@ -219,29 +199,12 @@ macro_rules! impl_ChunkCacheLruDecoded {
             chunk_indices: &[u64],
             options: &crate::array::codec::CodecOptions,
         ) -> Result<Arc<crate::array::ArrayBytes<'static>>, ArrayError> {
-            self.try_get_or_insert_with::<_, ArrayError>(chunk_indices.to_vec(), || {
-                Ok(Arc::new(
-                    array
-                        .retrieve_chunk_opt(chunk_indices, options)?
-                        .into_owned(),
-                ))
-            })
-            .map_err(|err| {
-                // moka returns an Arc'd error, unwrap it noting that ArrayError is not cloneable
-                Arc::try_unwrap(err).unwrap_or_else(|err| {
-                    ArrayError::StorageError(StorageError::from(err.to_string()))
-                })
-            })
-            // if let Some(chunk) = cache.get(chunk_indices) {
-            //     Ok(chunk)
-            // } else {
-            //     let chunk = Arc::new(
-            //         array.retrieve_chunk_opt(chunk_indices, options)?
-            //             .into_owned(),
-            //     );
-            //     cache.insert(chunk_indices.to_vec(), chunk.clone());
-            //     Ok(chunk)
-            // }
+            crate::array::chunk_cache::retrieve_chunk_through_cache::<ChunkCacheTypeDecoded, _, _>(
+                self,
+                array,
+                chunk_indices,
+                options,
+            )
         }
     };
 }
@@ -0,0 +1,318 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use crate::array::{ArrayError, ArrayIndices};
+
+use super::{ChunkCache, ChunkCacheType, ChunkCacheTypeDecoded, ChunkCacheTypeEncoded};
+
+type ChunkIndices = ArrayIndices;
+
+/// The eviction policy of a [`ChunkCachePolicyChunkLimit`] or [`ChunkCachePolicySizeLimit`] chunk cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkCacheEvictionPolicy {
+    /// Evict the least recently used chunk.
+    ///
+    /// This suits random access workloads with locality, where recently used chunks are likely to be used again.
+    #[default]
+    Lru,
+    /// Evict the least frequently used chunk, with ties broken by evicting the least recently used chunk.
+    ///
+    /// This suits workloads where a set of "hot" chunks is accessed repeatedly amongst many other chunks.
+    Lfu,
+    /// Evict the most recently used chunk.
+    ///
+    /// This suits repeated single-pass sequential scans over more chunks than fit in the cache.
+    /// An LRU cache evicts each chunk before it is revisited by the next scan and never hits, whereas an MRU cache retains the chunks at the start of the scan.
+    Mru,
+}
+
+struct ChunkCacheEntry<T> {
+    chunk: Arc<T>,
+    frequency: u64,
+    tick: u64,
+}
+
+impl<T> ChunkCacheEntry<T> {
+    /// The key of the entry in the eviction order.
+    fn order(&self, policy: ChunkCacheEvictionPolicy) -> (u64, u64) {
+        match policy {
+            ChunkCacheEvictionPolicy::Lru | ChunkCacheEvictionPolicy::Mru => (0, self.tick),
+            ChunkCacheEvictionPolicy::Lfu => (self.frequency, self.tick),
+        }
+    }
+}
+
+/// The state of a policy chunk cache, guarded by a mutex.
+struct ChunkCachePolicyInner<T> {
+    entries: HashMap<ChunkIndices, ChunkCacheEntry<T>>,
+    order: BTreeSet<((u64, u64), ChunkIndices)>,
+    tick: u64,
+    size: usize,
+}
+
+impl<T: ChunkCacheType> ChunkCachePolicyInner<T> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeSet::new(),
+            tick: 0,
+            size: 0,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, policy: ChunkCacheEvictionPolicy, chunk_indices: &[u64]) -> Option<Arc<T>> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(chunk_indices)?;
+        self.order
+            .remove(&(entry.order(policy), chunk_indices.to_vec()));
+        entry.frequency += 1;
+        entry.tick = tick;
+        self.order
+            .insert((entry.order(policy), chunk_indices.to_vec()));
+        Some(entry.chunk.clone())
+    }
+
+    fn remove(&mut self, policy: ChunkCacheEvictionPolicy, chunk_indices: &[u64]) {
+        if let Some(entry) = self.entries.remove(chunk_indices) {
+            self.order
+                .remove(&(entry.order(policy), chunk_indices.to_vec()));
+            self.size -= entry.chunk.size();
+        }
+    }
+
+    fn evict(&mut self, policy: ChunkCacheEvictionPolicy) {
+        let evicted = match policy {
+            ChunkCacheEvictionPolicy::Lru | ChunkCacheEvictionPolicy::Lfu => self.order.pop_first(),
+            ChunkCacheEvictionPolicy::Mru => self.order.pop_last(),
+        };
+        if let Some((_, chunk_indices)) = evicted {
+            if let Some(entry) = self.entries.remove(&chunk_indices) {
+                self.size -= entry.chunk.size();
+            }
+        }
+    }
+
+    /// Insert a chunk, evicting chunks while `is_full` returns true for the number of chunks and size of the cache.
+    fn insert(
+        &mut self,
+        policy: ChunkCacheEvictionPolicy,
+        chunk_indices: ChunkIndices,
+        chunk: Arc<T>,
+        is_full: impl Fn(usize, usize) -> bool,
+    ) {
+        self.remove(policy, &chunk_indices);
+        if is_full(0, 0) {
+            // The chunk does not fit in an empty cache
+            return;
+        }
+        while is_full(self.entries.len(), self.size) {
+            self.evict(policy);
+        }
+        let entry = ChunkCacheEntry {
+            chunk,
+            frequency: 1,
+            tick: self.next_tick(),
+        };
+        self.size += entry.chunk.size();
+        self.order
+            .insert((entry.order(policy), chunk_indices.clone()));
+        self.entries.insert(chunk_indices, entry);
+    }
+}
+
+/// A chunk cache with a fixed chunk capacity and a configurable [`ChunkCacheEvictionPolicy`].
+pub struct ChunkCachePolicyChunkLimit<T: ChunkCacheType> {
+    cache: Mutex<ChunkCachePolicyInner<T>>,
+    capacity: usize,
+    policy: ChunkCacheEvictionPolicy,
+}
+
+/// An encoded chunk cache with a fixed chunk capacity and a configurable eviction policy.
+pub type ChunkCacheEncodedPolicyChunkLimit = ChunkCachePolicyChunkLimit<ChunkCacheTypeEncoded>;
+
+/// A decoded chunk cache with a fixed chunk capacity and a configurable eviction policy.
+pub type ChunkCacheDecodedPolicyChunkLimit = ChunkCachePolicyChunkLimit<ChunkCacheTypeDecoded>;
+
+/// A chunk cache with a fixed size capacity and a configurable [`ChunkCacheEvictionPolicy`].
+pub struct ChunkCachePolicySizeLimit<T: ChunkCacheType> {
+    cache: Mutex<ChunkCachePolicyInner<T>>,
+    capacity: usize,
+    policy: ChunkCacheEvictionPolicy,
+}
+
+/// An encoded chunk cache with a fixed size capacity in bytes and a configurable eviction policy.
+pub type ChunkCacheEncodedPolicySizeLimit = ChunkCachePolicySizeLimit<ChunkCacheTypeEncoded>;
+
+/// A decoded chunk cache with a fixed size capacity in bytes and a configurable eviction policy.
+pub type ChunkCacheDecodedPolicySizeLimit = ChunkCachePolicySizeLimit<ChunkCacheTypeDecoded>;
+
+impl<CT: ChunkCacheType> ChunkCachePolicyChunkLimit<CT> {
+    /// Create a new [`ChunkCachePolicyChunkLimit`] with a capacity in chunks of `chunk_capacity` and an eviction `policy`.
+    #[must_use]
+    pub fn new(chunk_capacity: u64, policy: ChunkCacheEvictionPolicy) -> Self {
+        Self {
+            cache: Mutex::new(ChunkCachePolicyInner::new()),
+            capacity: usize::try_from(chunk_capacity).unwrap_or(usize::MAX),
+            policy,
+        }
+    }
+
+    /// Return the eviction policy.
+    #[must_use]
+    pub fn eviction_policy(&self) -> ChunkCacheEvictionPolicy {
+        self.policy
+    }
+}
+
+impl<CT: ChunkCacheType> ChunkCachePolicySizeLimit<CT> {
+    /// Create a new [`ChunkCachePolicySizeLimit`] with a capacity in bytes of `capacity` and an eviction `policy`.
+    #[must_use]
+    pub fn new(capacity: u64, policy: ChunkCacheEvictionPolicy) -> Self {
+        Self {
+            cache: Mutex::new(ChunkCachePolicyInner::new()),
+            capacity: usize::try_from(capacity).unwrap_or(usize::MAX),
+            policy,
+        }
+    }
+
+    /// Return the eviction policy.
+    #[must_use]
+    pub fn eviction_policy(&self) -> ChunkCacheEvictionPolicy {
+        self.policy
+    }
+}
+
+macro_rules! impl_ChunkCachePolicyChunkLimit {
+    ($ct:ty) => {
+        fn get(&self, chunk_indices: &[u64]) -> Option<Arc<$ct>> {
+            self.cache.lock().unwrap().get(self.policy, chunk_indices)
+        }
+
+        fn insert(&self, chunk_indices: ChunkIndices, chunk: Arc<$ct>) {
+            let capacity = self.capacity;
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(self.policy, chunk_indices, chunk, |len, _size| {
+                    len >= capacity
+                });
+        }
+
+        fn len(&self) -> usize {
+            self.cache.lock().unwrap().entries.len()
+        }
+    };
+}
+
+macro_rules! impl_ChunkCachePolicySizeLimit {
+    ($ct:ty) => {
+        fn get(&self, chunk_indices: &[u64]) -> Option<Arc<$ct>> {
+            self.cache.lock().unwrap().get(self.policy, chunk_indices)
+        }
+
+        fn insert(&self, chunk_indices: ChunkIndices, chunk: Arc<$ct>) {
+            let capacity = self.capacity;
+            let chunk_size = chunk.size();
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(self.policy, chunk_indices, chunk, |_len, size| {
+                    size + chunk_size > capacity
+                });
+        }
+
+        fn len(&self) -> usize {
+            self.cache.lock().unwrap().entries.len()
+        }
+    };
+}
+
+impl ChunkCache<ChunkCacheTypeEncoded> for ChunkCacheEncodedPolicyChunkLimit {
+    impl_ChunkCacheLruEncoded!();
+    impl_ChunkCachePolicyChunkLimit!(ChunkCacheTypeEncoded);
+}
+
+impl ChunkCache<ChunkCacheTypeDecoded> for ChunkCacheDecodedPolicyChunkLimit {
+    impl_ChunkCacheLruDecoded!();
+    impl_ChunkCachePolicyChunkLimit!(ChunkCacheTypeDecoded);
+}
+
+impl ChunkCache<ChunkCacheTypeEncoded> for ChunkCacheEncodedPolicySizeLimit {
+    impl_ChunkCacheLruEncoded!();
+    impl_ChunkCachePolicySizeLimit!(ChunkCacheTypeEncoded);
+}
+
+impl ChunkCache<ChunkCacheTypeDecoded> for ChunkCacheDecodedPolicySizeLimit {
+    impl_ChunkCacheLruDecoded!();
+    impl_ChunkCachePolicySizeLimit!(ChunkCacheTypeDecoded);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(value: u8) -> Arc<ChunkCacheTypeDecoded> {
+        Arc::new(vec![value; 4].into())
+    }
+
+    fn cached(cache: &impl ChunkCache<ChunkCacheTypeDecoded>, chunks: &[u64]) -> Vec<bool> {
+        chunks.iter().map(|i| cache.get(&[*i]).is_some()).collect()
+    }
+
+    #[test]
+    fn chunk_cache_eviction_policy_lru() {
+        let cache = ChunkCacheDecodedPolicyChunkLimit::new(2, ChunkCacheEvictionPolicy::default());
+        assert_eq!(cache.eviction_policy(), ChunkCacheEvictionPolicy::Lru);
+        cache.insert(vec![0], chunk(0));
+        cache.insert(vec![1], chunk(1));
+        assert!(cache.get(&[0]).is_some());
+        cache.insert(vec![2], chunk(2));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cached(&cache, &[0, 1, 2]), vec![true, false, true]);
+    }
+
+    #[test]
+    fn chunk_cache_eviction_policy_lfu() {
+        let cache = ChunkCacheDecodedPolicyChunkLimit::new(2, ChunkCacheEvictionPolicy::Lfu);
+        cache.insert(vec![0], chunk(0));
+        cache.insert(vec![1], chunk(1));
+        assert!(cache.get(&[0]).is_some());
+        assert!(cache.get(&[0]).is_some());
+        assert!(cache.get(&[1]).is_some());
+        cache.insert(vec![2], chunk(2));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cached(&cache, &[0, 1, 2]), vec![true, false, true]);
+    }
+
+    #[test]
+    fn chunk_cache_eviction_policy_mru() {
+        let cache = ChunkCacheDecodedPolicyChunkLimit::new(2, ChunkCacheEvictionPolicy::Mru);
+        cache.insert(vec![0], chunk(0));
+        cache.insert(vec![1], chunk(1));
+        cache.insert(vec![2], chunk(2));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cached(&cache, &[0, 1, 2]), vec![true, false, true]);
+    }
+
+    #[test]
+    fn chunk_cache_eviction_policy_size_limit() {
+        let cache = ChunkCacheDecodedPolicySizeLimit::new(8, ChunkCacheEvictionPolicy::Mru);
+        cache.insert(vec![0], chunk(0));
+        cache.insert(vec![1], chunk(1));
+        cache.insert(vec![2], chunk(2));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cached(&cache, &[0, 1, 2]), vec![true, false, true]);
+
+        // A chunk larger than the capacity is not cached
+        cache.insert(vec![3], Arc::new(vec![3; 9].into()));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&[3]).is_none());
+    }
+}
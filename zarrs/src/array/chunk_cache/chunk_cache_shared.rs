@@ -0,0 +1,169 @@
+use std::sync::{Arc, Mutex};
+
+use moka::{
+    policy::EvictionPolicy,
+    sync::{Cache, CacheBuilder},
+};
+
+use crate::{
+    array::{codec::CodecOptions, Array, ArrayBytes, ArrayError, ArrayIndices},
+    node::NodePath,
+    storage::{ReadableStorageTraits, StorageError},
+};
+
+use super::{ChunkCache, ChunkCacheTypeDecoded};
+
+type SharedChunkKey = (NodePath, ArrayIndices);
+
+/// An LRU (least recently used) decoded chunk cache with a fixed size capacity in bytes that is shared by multiple arrays.
+///
+/// Chunks are keyed by the path of their array and their chunk indices, so arrays in a hierarchy share one cache budget.
+/// The cache is cheap to clone, and clones share the same cache.
+/// Use [`array_cache`](ChunkCacheDecodedSharedSizeLimit::array_cache) to get the [`ChunkCache`] of an array.
+#[derive(Clone)]
+pub struct ChunkCacheDecodedSharedSizeLimit {
+    cache: Cache<SharedChunkKey, Arc<ChunkCacheTypeDecoded>>,
+    /// Incremented whenever chunks are invalidated, shared with the chunk cache of each array.
+    generation: Arc<Mutex<u64>>,
+}
+
+impl ChunkCacheDecodedSharedSizeLimit {
+    /// Create a new [`ChunkCacheDecodedSharedSizeLimit`] with a capacity in bytes of `capacity`.
+    #[must_use]
+    pub fn new(capacity: u64) -> Self {
+        let cache = CacheBuilder::new(capacity)
+            .eviction_policy(EvictionPolicy::lru())
+            .weigher(|_k, v: &Arc<ChunkCacheTypeDecoded>| {
+                u32::try_from(v.size()).unwrap_or(u32::MAX)
+            })
+            .build();
+        Self {
+            cache,
+            generation: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Return the chunk cache of the array at `path`.
+    #[must_use]
+    pub fn array_cache(&self, path: &NodePath) -> ChunkCacheDecodedSharedArray {
+        ChunkCacheDecodedSharedArray {
+            cache: self.cache.clone(),
+            path: path.clone(),
+            generation: self.generation.clone(),
+        }
+    }
+
+    /// Return the number of chunks in the cache across all arrays.
+    ///
+    /// # Panics
+    /// Panics if the number of chunks exceeds [`usize::MAX`].
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.run_pending_tasks();
+        usize::try_from(self.cache.entry_count()).unwrap()
+    }
+
+    /// Returns true if the cache is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all chunks from the cache.
+    ///
+    /// # Panics
+    /// Panics if the underlying lock has been poisoned.
+    pub fn clear(&self) {
+        let mut generation = self.generation.lock().unwrap();
+        self.cache.invalidate_all();
+        self.cache.run_pending_tasks();
+        *generation += 1;
+    }
+}
+
+/// The chunk cache of an array in a [`ChunkCacheDecodedSharedSizeLimit`].
+#[derive(Clone)]
+pub struct ChunkCacheDecodedSharedArray {
+    cache: Cache<SharedChunkKey, Arc<ChunkCacheTypeDecoded>>,
+    path: NodePath,
+    generation: Arc<Mutex<u64>>,
+}
+
+impl ChunkCacheDecodedSharedArray {
+    /// Return the path of the array.
+    #[must_use]
+    pub fn path(&self) -> &NodePath {
+        &self.path
+    }
+
+    /// Return the invalidation generation of the shared cache.
+    pub(crate) fn generation(&self) -> &Arc<Mutex<u64>> {
+        &self.generation
+    }
+
+    fn key(&self, chunk_indices: Vec<u64>) -> SharedChunkKey {
+        (self.path.clone(), chunk_indices)
+    }
+
+    /// Remove the chunk at `chunk_indices` of this array from the cache.
+    pub(crate) fn invalidate(&self, chunk_indices: &[u64]) {
+        self.cache.invalidate(&self.key(chunk_indices.to_vec()));
+    }
+
+    /// Remove all chunks of this array from the cache.
+    pub(crate) fn invalidate_all(&self) {
+        for (key, _) in self.cache.iter().filter(|(key, _)| key.0 == self.path) {
+            self.cache.invalidate(key.as_ref());
+        }
+    }
+}
+
+impl ChunkCache<ChunkCacheTypeDecoded> for ChunkCacheDecodedSharedArray {
+    fn retrieve_chunk<TStorage: ?Sized + ReadableStorageTraits + 'static>(
+        &self,
+        array: &Array<TStorage>,
+        chunk_indices: &[u64],
+        options: &CodecOptions,
+    ) -> Result<Arc<ArrayBytes<'static>>, ArrayError> {
+        self.try_get_or_insert_with::<_, ArrayError>(chunk_indices.to_vec(), || {
+            Ok(Arc::new(
+                array
+                    .retrieve_chunk_opt(chunk_indices, options)?
+                    .into_owned(),
+            ))
+        })
+        .map_err(|err| {
+            // moka returns an Arc'd error, unwrap it noting that ArrayError is not cloneable
+            Arc::try_unwrap(err)
+                .unwrap_or_else(|err| ArrayError::StorageError(StorageError::from(err.to_string())))
+        })
+    }
+
+    fn get(&self, chunk_indices: &[u64]) -> Option<Arc<ChunkCacheTypeDecoded>> {
+        self.cache.get(&self.key(chunk_indices.to_vec()))
+    }
+
+    fn insert(&self, chunk_indices: Vec<u64>, chunk: Arc<ChunkCacheTypeDecoded>) {
+        self.cache.insert(self.key(chunk_indices), chunk);
+    }
+
+    fn try_get_or_insert_with<F, E>(
+        &self,
+        chunk_indices: Vec<u64>,
+        f: F,
+    ) -> Result<Arc<ChunkCacheTypeDecoded>, Arc<ArrayError>>
+    where
+        F: FnOnce() -> Result<Arc<ChunkCacheTypeDecoded>, ArrayError>,
+    {
+        self.cache.try_get_with(self.key(chunk_indices), f)
+    }
+
+    /// Return the number of chunks of this array in the cache.
+    fn len(&self) -> usize {
+        self.cache.run_pending_tasks();
+        self.cache
+            .iter()
+            .filter(|(key, _)| key.0 == self.path)
+            .count()
+    }
+}
@@ -0,0 +1,86 @@
+//! Per-chunk codec overrides.
+//!
+//! Overrides are recorded in the `zarrs_chunk_codecs` additional field of Zarr V3 array metadata.
+//! The field is annotated with `"must_understand": true`, so other Zarr implementations refuse to read an array with overrides rather than decoding the overridden chunks incorrectly.
+//! The number of overridden chunks is limited to `MAX_CHUNK_CODECS` so that the array metadata stays small.
+//!
+//! ### Example additional field JSON
+//! ```json
+//! "zarrs_chunk_codecs": {
+//!   "must_understand": true,
+//!   "chunks": [
+//!     {
+//!       "chunk": [0, 1],
+//!       "codecs": [{"name": "bytes", "configuration": {"endian": "little"}}]
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    metadata::v3::{AdditionalField, MetadataV3},
+    plugin::PluginCreateError,
+};
+
+use super::{ArrayIndices, CodecChain};
+
+/// The name of the additional field recording per-chunk codec overrides.
+pub(crate) const CHUNK_CODECS_FIELD: &str = "zarrs_chunk_codecs";
+
+/// The maximum number of chunks with overridden codecs.
+pub(crate) const MAX_CHUNK_CODECS: usize = 1024;
+
+/// The codecs of an overridden chunk.
+#[derive(Serialize, Deserialize)]
+struct ChunkCodecsMetadata {
+    chunk: ArrayIndices,
+    codecs: Vec<MetadataV3>,
+}
+
+/// The codec chain of each overridden chunk.
+pub(crate) type ChunkCodecs = HashMap<ArrayIndices, Arc<CodecChain>>;
+
+/// Create the per-chunk codec overrides from the `zarrs_chunk_codecs` additional field.
+pub(crate) fn chunk_codecs_from_field(
+    field: &AdditionalField,
+) -> Result<ChunkCodecs, PluginCreateError> {
+    let chunks = field
+        .as_map()
+        .get("chunks")
+        .cloned()
+        .unwrap_or(serde_json::Value::Array(vec![]));
+    let chunks: Vec<ChunkCodecsMetadata> = serde_json::from_value(chunks).map_err(|err| {
+        PluginCreateError::from(format!("invalid {CHUNK_CODECS_FIELD} field: {err}"))
+    })?;
+    chunks
+        .into_iter()
+        .map(|chunk| {
+            let codecs = CodecChain::from_metadata(&chunk.codecs)?;
+            Ok((chunk.chunk, Arc::new(codecs)))
+        })
+        .collect()
+}
+
+/// Create the `zarrs_chunk_codecs` additional field from per-chunk codec overrides.
+pub(crate) fn chunk_codecs_to_field(chunk_codecs: &ChunkCodecs) -> AdditionalField {
+    let mut chunks: Vec<ChunkCodecsMetadata> = chunk_codecs
+        .iter()
+        .map(|(chunk, codecs)| ChunkCodecsMetadata {
+            chunk: chunk.clone(),
+            codecs: codecs.create_metadatas(),
+        })
+        .collect();
+    chunks.sort_unstable_by(|a, b| a.chunk.cmp(&b.chunk));
+    let mut map = serde_json::Map::new();
+    map.insert("must_understand".to_string(), serde_json::Value::Bool(true));
+    map.insert(
+        "chunks".to_string(),
+        serde_json::to_value(chunks).expect("chunk codecs metadata is serialisable"),
+    );
+    serde_json::from_value(serde_json::Value::Object(map))
+        .expect("chunk codecs metadata is a valid additional field")
+}
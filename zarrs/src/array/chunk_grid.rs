@@ -5,12 +5,13 @@
 //! A [`ChunkGrid`] is a [`Box`] wrapped chunk grid which implements [`ChunkGridTraits`].
 //! Chunk grids are Zarr extension points and they can be registered through [`inventory`] as a [`ChunkGridPlugin`].
 //!
-//! Includes a [`RegularChunkGrid`] and [`RectangularChunkGrid`] implementation.
+//! Includes a [`RegularChunkGrid`] and [`RectangularChunkGrid`] implementation, and a [`RegularOriginChunkGrid`] `zarrs` extension.
 //!
 //! A regular chunk grid can be created from a [`ChunkShape`] and similar. See its [`from`/`try_from` implementations](./struct.ChunkGrid.html#trait-implementations).
 
 pub mod rectangular;
 pub mod regular;
+pub mod regular_origin;
 
 use std::num::NonZeroU64;
 use std::sync::Arc;
@@ -19,9 +20,11 @@ pub use crate::metadata::v3::array::chunk_grid::rectangular::{
     RectangularChunkGridConfiguration, RectangularChunkGridDimensionConfiguration,
 };
 pub use crate::metadata::v3::array::chunk_grid::regular::RegularChunkGridConfiguration;
+pub use crate::metadata::v3::array::chunk_grid::regular_origin::RegularOriginChunkGridConfiguration;
 
 pub use rectangular::RectangularChunkGrid;
 pub use regular::RegularChunkGrid;
+pub use regular_origin::RegularOriginChunkGrid;
 
 use derive_more::{Deref, From};
 
@@ -69,6 +72,9 @@ impl ChunkGrid {
                 rectangular::IDENTIFIER => {
                     return rectangular::create_chunk_grid_rectangular(metadata);
                 }
+                regular_origin::IDENTIFIER => {
+                    return regular_origin::create_chunk_grid_regular_origin(metadata);
+                }
                 _ => {}
             }
         }
@@ -134,6 +140,13 @@ pub trait ChunkGridTraits: core::fmt::Debug + Send + Sync {
     /// The dimensionality of the grid.
     fn dimensionality(&self) -> usize;
 
+    /// The origin of the grid (i.e. the array indices of the first element of the first chunk).
+    ///
+    /// This is all zeros unless the chunk grid supports a non-zero origin.
+    fn origin(&self) -> ArrayIndices {
+        vec![0; self.dimensionality()]
+    }
+
     /// The grid shape (i.e. number of chunks).
     ///
     /// Zero sized array dimensions are considered "unlimited".
@@ -353,14 +366,17 @@ pub trait ChunkGridTraits: core::fmt::Debug + Send + Sync {
 
     /// Check if array indices are in-bounds.
     ///
-    /// Ensures array indices are within the array shape.
-    /// Zero sized array dimensions are considered "unlimited" and always in-bounds.
+    /// Ensures array indices are within the array shape relative to the [origin](ChunkGridTraits::origin).
+    /// Zero sized array dimensions are considered "unlimited" and always in-bounds after the origin.
     #[must_use]
     fn array_indices_inbounds(&self, array_indices: &[u64], array_shape: &[u64]) -> bool {
         array_indices.len() == self.dimensionality()
             && array_shape.len() == self.dimensionality()
-            && std::iter::zip(array_indices, array_shape)
-                .all(|(&index, &shape)| shape == 0 || index < shape)
+            && itertools::izip!(array_indices, array_shape, self.origin()).all(
+                |(&index, &shape, origin)| {
+                    index >= origin && (shape == 0 || index - origin < shape)
+                },
+            )
     }
 
     /// Check if chunk indices are in-bounds.
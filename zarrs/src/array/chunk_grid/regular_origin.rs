@@ -0,0 +1,241 @@
+//! The `zarrs.regular_origin` chunk grid.
+//!
+//! A `regular` chunk grid with a non-zero origin.
+//! The array spans the indices from the origin to the origin plus the array shape.
+//! This is useful where an array is a window into a larger index space (e.g. a tile of a global geospatial grid).
+//! Array indices before the origin are not part of the grid.
+//!
+//! <div class="warning">
+//! This chunk grid is a `zarrs` extension and is incompatible with other Zarr V3 implementations.
+//! </div>
+
+use std::num::NonZeroU64;
+
+use crate::{
+    array::{chunk_grid::ChunkGridPlugin, ArrayIndices, ArrayShape, ChunkShape},
+    array_subset::IncompatibleDimensionalityError,
+    metadata::v3::{array::chunk_grid::regular_origin, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use super::RegularOriginChunkGridConfiguration;
+use super::{ChunkGrid, ChunkGridTraits};
+
+pub use regular_origin::IDENTIFIER;
+
+// Register the chunk grid.
+inventory::submit! {
+    ChunkGridPlugin::new(IDENTIFIER, is_name_regular_origin, create_chunk_grid_regular_origin)
+}
+
+fn is_name_regular_origin(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+}
+
+/// Create a `zarrs.regular_origin` chunk grid from metadata.
+///
+/// # Errors
+/// Returns a [`PluginCreateError`] if the metadata is invalid for a `zarrs.regular_origin` chunk grid.
+pub(crate) fn create_chunk_grid_regular_origin(
+    metadata: &MetadataV3,
+) -> Result<ChunkGrid, PluginCreateError> {
+    let configuration: RegularOriginChunkGridConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "chunk grid", metadata.clone()))?;
+    let chunk_grid = RegularOriginChunkGrid::new(configuration.chunk_shape, configuration.origin)
+        .map_err(|_| {
+        PluginMetadataInvalidError::new(IDENTIFIER, "chunk grid", metadata.clone())
+    })?;
+    Ok(ChunkGrid::new(chunk_grid))
+}
+
+/// A `zarrs.regular_origin` chunk grid.
+#[derive(Debug, Clone)]
+pub struct RegularOriginChunkGrid {
+    chunk_shape: ChunkShape,
+    origin: ArrayIndices,
+}
+
+impl RegularOriginChunkGrid {
+    /// Create a new `zarrs.regular_origin` chunk grid with chunk shape `chunk_shape` and grid origin `origin`.
+    ///
+    /// # Errors
+    /// Returns [`IncompatibleDimensionalityError`] if the length of `origin` does not match the length of `chunk_shape`.
+    pub fn new(
+        chunk_shape: ChunkShape,
+        origin: ArrayIndices,
+    ) -> Result<Self, IncompatibleDimensionalityError> {
+        if origin.len() == chunk_shape.len() {
+            Ok(Self {
+                chunk_shape,
+                origin,
+            })
+        } else {
+            Err(IncompatibleDimensionalityError::new(
+                origin.len(),
+                chunk_shape.len(),
+            ))
+        }
+    }
+
+    /// Return the chunk shape.
+    #[must_use]
+    pub fn chunk_shape(&self) -> &[NonZeroU64] {
+        self.chunk_shape.as_slice()
+    }
+}
+
+impl ChunkGridTraits for RegularOriginChunkGrid {
+    fn create_metadata(&self) -> MetadataV3 {
+        let configuration = RegularOriginChunkGridConfiguration {
+            chunk_shape: self.chunk_shape.clone(),
+            origin: self.origin.clone(),
+        };
+        MetadataV3::new_with_serializable_configuration(IDENTIFIER, &configuration).unwrap()
+    }
+
+    fn dimensionality(&self) -> usize {
+        self.chunk_shape.len()
+    }
+
+    fn origin(&self) -> ArrayIndices {
+        self.origin.clone()
+    }
+
+    unsafe fn grid_shape_unchecked(&self, array_shape: &[u64]) -> Option<ArrayShape> {
+        assert_eq!(array_shape.len(), self.dimensionality());
+        Some(
+            std::iter::zip(array_shape, self.chunk_shape.as_slice())
+                .map(|(a, s)| a.div_ceil(s.get()))
+                .collect(),
+        )
+    }
+
+    /// The chunk shape. Fixed for a `zarrs.regular_origin` grid.
+    unsafe fn chunk_shape_unchecked(
+        &self,
+        chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ChunkShape> {
+        debug_assert_eq!(self.dimensionality(), chunk_indices.len());
+        Some(self.chunk_shape.clone())
+    }
+
+    /// The chunk shape as an [`ArrayShape`] ([`Vec<u64>`]). Fixed for a `zarrs.regular_origin` grid.
+    unsafe fn chunk_shape_u64_unchecked(
+        &self,
+        chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayShape> {
+        debug_assert_eq!(self.dimensionality(), chunk_indices.len());
+        Some(
+            self.chunk_shape
+                .iter()
+                .copied()
+                .map(NonZeroU64::get)
+                .collect::<ArrayShape>(),
+        )
+    }
+
+    unsafe fn chunk_origin_unchecked(
+        &self,
+        chunk_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        debug_assert_eq!(self.dimensionality(), chunk_indices.len());
+        Some(
+            itertools::izip!(chunk_indices, self.chunk_shape.as_slice(), &self.origin)
+                .map(|(i, s, o)| o + i * s.get())
+                .collect(),
+        )
+    }
+
+    unsafe fn chunk_indices_unchecked(
+        &self,
+        array_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        debug_assert_eq!(self.dimensionality(), array_indices.len());
+        itertools::izip!(array_indices, self.chunk_shape.as_slice(), &self.origin)
+            .map(|(i, s, o)| Some(i.checked_sub(*o)? / s.get()))
+            .collect()
+    }
+
+    unsafe fn chunk_element_indices_unchecked(
+        &self,
+        array_indices: &[u64],
+        _array_shape: &[u64],
+    ) -> Option<ArrayIndices> {
+        debug_assert_eq!(self.dimensionality(), array_indices.len());
+        itertools::izip!(array_indices, self.chunk_shape.as_slice(), &self.origin)
+            .map(|(i, s, o)| Some(i.checked_sub(*o)? % s.get()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_grid_regular_origin() {
+        let array_shape: ArrayShape = vec![5, 7];
+        let chunk_shape: ChunkShape = vec![2, 3].try_into().unwrap();
+        assert!(RegularOriginChunkGrid::new(chunk_shape.clone(), vec![10]).is_err());
+        let chunk_grid = RegularOriginChunkGrid::new(chunk_shape, vec![10, 20]).unwrap();
+
+        assert_eq!(chunk_grid.origin(), vec![10, 20]);
+        assert_eq!(
+            chunk_grid.grid_shape(&array_shape).unwrap(),
+            Some(vec![3, 3])
+        );
+        assert_eq!(
+            chunk_grid.chunk_origin(&[1, 1], &array_shape).unwrap(),
+            Some(vec![12, 23])
+        );
+        assert_eq!(
+            chunk_grid.chunk_indices(&[13, 26], &array_shape).unwrap(),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            chunk_grid
+                .chunk_element_indices(&[13, 26], &array_shape)
+                .unwrap(),
+            Some(vec![1, 0])
+        );
+        assert_eq!(
+            chunk_grid.chunk_indices(&[9, 26], &array_shape).unwrap(),
+            None
+        );
+        assert!(chunk_grid.array_indices_inbounds(&[10, 20], &array_shape));
+        assert!(chunk_grid.array_indices_inbounds(&[14, 26], &array_shape));
+        assert!(!chunk_grid.array_indices_inbounds(&[9, 20], &array_shape));
+        assert!(!chunk_grid.array_indices_inbounds(&[15, 20], &array_shape));
+    }
+
+    #[test]
+    fn chunk_grid_regular_origin_metadata() {
+        let chunk_grid =
+            RegularOriginChunkGrid::new(vec![2, 3].try_into().unwrap(), vec![10, 20]).unwrap();
+        let metadata = chunk_grid.create_metadata();
+        assert_eq!(
+            metadata.to_string(),
+            r#"zarrs.regular_origin {"chunk_shape":[2,3],"origin":[10,20]}"#
+        );
+        let chunk_grid = create_chunk_grid_regular_origin(&metadata).unwrap();
+        assert_eq!(chunk_grid.origin(), vec![10, 20]);
+
+        let metadata: MetadataV3 = serde_json::from_str(
+            r#"{"name":"zarrs.regular_origin","configuration":{"chunk_shape":[2,3],"origin":[1]}}"#,
+        )
+        .unwrap();
+        assert!(create_chunk_grid_regular_origin(&metadata).is_err());
+
+        // The regular chunk grid does not accept an origin
+        let metadata: MetadataV3 = serde_json::from_str(
+            r#"{"name":"regular","configuration":{"chunk_shape":[2,3],"origin":[10,20]}}"#,
+        )
+        .unwrap();
+        assert!(ChunkGrid::from_metadata(&metadata).is_err());
+    }
+}
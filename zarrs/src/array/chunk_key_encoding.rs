@@ -1,6 +1,69 @@
 //! Zarr chunk key encodings. Includes a [default](default::DefaultChunkKeyEncoding) and [v2](v2::V2ChunkKeyEncoding) implementation.
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#chunk-key-encoding>.
+//!
+//! ## Custom Chunk Key Encodings
+//! A custom chunk key encoding implements [`ChunkKeyEncodingTraits`] and is registered as a [`ChunkKeyEncodingPlugin`] with [`inventory::submit!`].
+//! Arrays reference a chunk key encoding by name in their metadata, and chunk reads and writes dispatch through [`ChunkKeyEncodingTraits::encode`].
+//! Opening an array with a chunk key encoding that is not registered fails with [`PluginCreateError::Unsupported`].
+//!
+//! For example, a legacy layout with chunk keys like `tile_1_2.bin`:
+//! ```
+//! # use std::sync::Arc;
+//! use zarrs::array::{
+//!     chunk_key_encoding::{ChunkKeyEncoding, ChunkKeyEncodingPlugin, ChunkKeyEncodingTraits},
+//!     Array, ArrayBuilder, DataType, FillValue,
+//! };
+//! use zarrs::metadata::v3::MetadataV3;
+//! use zarrs::plugin::{PluginCreateError, PluginMetadataInvalidError};
+//! use zarrs::storage::{ReadableStorageTraits, StoreKey};
+//!
+//! #[derive(Debug)]
+//! struct TileChunkKeyEncoding;
+//!
+//! impl ChunkKeyEncodingTraits for TileChunkKeyEncoding {
+//!     fn create_metadata(&self) -> MetadataV3 {
+//!         MetadataV3::new("tile")
+//!     }
+//!
+//!     fn encode(&self, chunk_grid_indices: &[u64]) -> StoreKey {
+//!         let indices: Vec<String> = chunk_grid_indices.iter().map(u64::to_string).collect();
+//!         StoreKey::new(format!("tile_{}.bin", indices.join("_"))).unwrap()
+//!     }
+//! }
+//!
+//! fn create_chunk_key_encoding_tile(
+//!     metadata: &MetadataV3,
+//! ) -> Result<ChunkKeyEncoding, PluginCreateError> {
+//!     if metadata.configuration_is_none_or_empty() {
+//!         Ok(ChunkKeyEncoding::new(TileChunkKeyEncoding))
+//!     } else {
+//!         Err(PluginMetadataInvalidError::new("tile", "chunk key encoding", metadata.clone()).into())
+//!     }
+//! }
+//!
+//! inventory::submit! {
+//!     ChunkKeyEncodingPlugin::new("tile", |name| name == "tile", create_chunk_key_encoding_tile)
+//! }
+//!
+//! # let store = Arc::new(zarrs::storage::store::MemoryStore::new());
+//! let array = ArrayBuilder::new(
+//!     vec![4, 4],
+//!     DataType::UInt8,
+//!     vec![2, 2].try_into()?,
+//!     FillValue::from(0u8),
+//! )
+//! .chunk_key_encoding(TileChunkKeyEncoding.into())
+//! .build(store.clone(), "/array")?;
+//! array.store_metadata()?;
+//! array.store_chunk_elements::<u8>(&[1, 0], &[1, 2, 3, 4])?;
+//! assert!(store.get(&StoreKey::new("array/tile_1_0.bin")?)?.is_some());
+//!
+//! // The registered chunk key encoding is created from the array metadata
+//! let array = Array::open(store, "/array")?;
+//! assert_eq!(array.retrieve_chunk_elements::<u8>(&[1, 0])?, vec![1, 2, 3, 4]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
 pub mod default;
 pub mod v2;
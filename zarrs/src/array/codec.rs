@@ -22,6 +22,12 @@ pub use options::{CodecOptions, CodecOptionsBuilder};
 pub use array_to_array::bitround::{
     BitroundCodec, BitroundCodecConfiguration, BitroundCodecConfigurationV1,
 };
+#[cfg(feature = "delta")]
+pub use array_to_array::delta::{DeltaCodec, DeltaCodecConfiguration, DeltaCodecConfigurationV1};
+#[cfg(feature = "fixedscaleoffset")]
+pub use array_to_array::fixedscaleoffset::{
+    FixedScaleOffsetCodec, FixedScaleOffsetCodecConfiguration, FixedScaleOffsetCodecConfigurationV1,
+};
 #[cfg(feature = "transpose")]
 pub use array_to_array::transpose::{
     TransposeCodec, TransposeCodecConfiguration, TransposeCodecConfigurationV1,
@@ -42,8 +48,16 @@ pub use array_to_bytes::sharding::{
 pub use array_to_bytes::zfp::{ZfpCodec, ZfpCodecConfiguration, ZfpCodecConfigurationV1};
 
 // Bytes to bytes
+#[cfg(feature = "adler32")]
+pub use bytes_to_bytes::adler32::{
+    Adler32Codec, Adler32CodecConfiguration, Adler32CodecConfigurationV1,
+};
 #[cfg(feature = "blosc")]
 pub use bytes_to_bytes::blosc::{BloscCodec, BloscCodecConfiguration, BloscCodecConfigurationV1};
+#[cfg(feature = "brotli")]
+pub use bytes_to_bytes::brotli::{
+    BrotliCodec, BrotliCodecConfiguration, BrotliCodecConfigurationV1,
+};
 #[cfg(feature = "bz2")]
 pub use bytes_to_bytes::bz2::{Bz2Codec, Bz2CodecConfiguration, Bz2CodecConfigurationV1};
 #[cfg(feature = "crc32c")]
@@ -52,6 +66,8 @@ pub use bytes_to_bytes::crc32c::{
 };
 #[cfg(feature = "gzip")]
 pub use bytes_to_bytes::gzip::{GzipCodec, GzipCodecConfiguration, GzipCodecConfigurationV1};
+#[cfg(feature = "lz4")]
+pub use bytes_to_bytes::lz4::{Lz4Codec, Lz4CodecConfiguration, Lz4CodecConfigurationV1};
 #[cfg(feature = "zstd")]
 pub use bytes_to_bytes::zstd::{ZstdCodec, ZstdCodecConfiguration, ZstdCodecConfigurationV1};
 
@@ -78,6 +94,13 @@ pub use array_to_array_partial_encoder_default::ArrayToArrayPartialEncoderDefaul
 mod bytes_partial_encoder_default;
 pub use bytes_partial_encoder_default::BytesPartialEncoderDefault;
 
+mod codec_registry;
+mod codec_transform_info;
+pub use codec_registry::{
+    register_codec, registered_codec_identifiers, CodecAlreadyRegisteredError,
+};
+pub use codec_transform_info::CodecTransformInfo;
+
 use crate::storage::{StoreKeyOffsetValue, WritableStorage};
 use crate::{
     array_subset::{ArraySubset, IncompatibleArraySubsetAndShapeError},
@@ -120,11 +143,10 @@ impl Codec {
     ///
     /// # Errors
     /// Returns [`PluginCreateError`] if the metadata is invalid or not associated with a registered codec plugin.
+    /// Codec plugins are registered at compile time with [`inventory::submit!`] or at runtime with [`register_codec`].
     pub fn from_metadata(metadata: &MetadataV3) -> Result<Self, PluginCreateError> {
-        for plugin in inventory::iter::<CodecPlugin> {
-            if plugin.match_name(metadata.name()) {
-                return plugin.create(metadata);
-            }
+        if let Some(plugin) = codec_registry::find_codec_plugin(metadata.name()) {
+            return plugin.create(metadata);
         }
         #[cfg(miri)]
         {
@@ -138,6 +160,16 @@ impl Codec {
                 array_to_array::bitround::IDENTIFIER => {
                     return array_to_array::bitround::create_codec_bitround(metadata);
                 }
+                #[cfg(feature = "delta")]
+                array_to_array::delta::IDENTIFIER => {
+                    return array_to_array::delta::create_codec_delta(metadata);
+                }
+                #[cfg(feature = "fixedscaleoffset")]
+                array_to_array::fixedscaleoffset::IDENTIFIER => {
+                    return array_to_array::fixedscaleoffset::create_codec_fixedscaleoffset(
+                        metadata,
+                    );
+                }
                 array_to_bytes::bytes::IDENTIFIER => {
                     return array_to_bytes::bytes::create_codec_bytes(metadata);
                 }
@@ -156,13 +188,22 @@ impl Codec {
                 array_to_bytes::vlen::IDENTIFIER => {
                     return array_to_bytes::vlen::create_codec_vlen(metadata);
                 }
-                array_to_bytes::vlen_v2::IDENTIFIER => {
+                array_to_bytes::vlen_v2::IDENTIFIER
+                | array_to_bytes::vlen_v2::IDENTIFIER_VLEN_UTF8 => {
                     return array_to_bytes::vlen_v2::create_codec_vlen_v2(metadata);
                 }
+                #[cfg(feature = "adler32")]
+                bytes_to_bytes::adler32::IDENTIFIER => {
+                    return bytes_to_bytes::adler32::create_codec_adler32(metadata);
+                }
                 #[cfg(feature = "blosc")]
                 bytes_to_bytes::blosc::IDENTIFIER => {
                     return bytes_to_bytes::blosc::create_codec_blosc(metadata);
                 }
+                #[cfg(feature = "brotli")]
+                bytes_to_bytes::brotli::IDENTIFIER => {
+                    return bytes_to_bytes::brotli::create_codec_brotli(metadata);
+                }
                 #[cfg(feature = "bz2")]
                 bytes_to_bytes::bz2::IDENTIFIER => {
                     return bytes_to_bytes::bz2::create_codec_bz2(metadata);
@@ -179,6 +220,10 @@ impl Codec {
                 bytes_to_bytes::gzip::IDENTIFIER => {
                     return bytes_to_bytes::gzip::create_codec_gzip(metadata);
                 }
+                #[cfg(feature = "lz4")]
+                bytes_to_bytes::lz4::IDENTIFIER => {
+                    return bytes_to_bytes::lz4::create_codec_lz4(metadata);
+                }
                 #[cfg(feature = "zstd")]
                 bytes_to_bytes::zstd::IDENTIFIER => {
                     return bytes_to_bytes::zstd::create_codec_zstd(metadata);
@@ -214,6 +259,22 @@ pub trait CodecTraits: Send + Sync {
     /// Indicates if a partial decoder decodes all bytes from its input handle and its output should be cached for optimal performance.
     /// If true, a cache will be inserted at some point *after* it in a [`CodecChain`] partial decoder.
     fn partial_decoder_decodes_all(&self) -> bool;
+
+    /// Indicates if a partial encoder decodes and re-encodes an entire chunk when encoding a subset of it.
+    ///
+    /// Defaults to true.
+    /// A codec that can write a subset of a chunk in place (e.g. the `bytes` codec) should return false.
+    fn partial_encoder_encodes_all(&self) -> bool {
+        true
+    }
+
+    /// Describe how the codec transforms an input of `input_len` bytes, without encoding any data.
+    ///
+    /// This returns whether the codec changes size and is lossy, and the estimated encoded length.
+    /// The default implementation describes a lossless codec that does not change the size of its input.
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_same_size(input_len)
+    }
 }
 
 /// Traits for both array to array and array to bytes codecs.
@@ -283,6 +344,17 @@ pub trait BytesPartialDecoderTraits: Send + Sync {
             .partial_decode(&[ByteRange::FromStart(0, None)], options)?
             .map(|mut v| v.remove(0)))
     }
+
+    /// Return the size of the bytes in the input handle.
+    ///
+    /// Returns [`None`] if the input handle does not exist.
+    /// The default implementation decodes all bytes.
+    ///
+    /// # Errors
+    /// Returns [`CodecError`] if a codec fails.
+    fn size(&self, options: &CodecOptions) -> Result<Option<u64>, CodecError> {
+        Ok(self.decode(options)?.map(|bytes| bytes.len() as u64))
+    }
 }
 
 #[cfg(feature = "async")]
@@ -505,6 +577,10 @@ impl BytesPartialDecoderTraits for StoragePartialDecoder {
                     .collect()
             }))
     }
+
+    fn size(&self, _options: &CodecOptions) -> Result<Option<u64>, CodecError> {
+        Ok(self.storage.size_key(&self.key)?)
+    }
 }
 
 #[cfg(feature = "async")]
@@ -971,6 +1047,11 @@ pub enum CodecError {
     /// An embedded checksum does not match the decoded value.
     #[error("the checksum is invalid")]
     InvalidChecksum,
+    /// Trailing bytes were found after an encoded stream.
+    ///
+    /// Trailing bytes can be ignored with [`CodecOptions::allow_trailing_bytes()`].
+    #[error("found {_0} trailing bytes after the encoded stream")]
+    TrailingBytes(usize),
     /// A store error.
     #[error(transparent)]
     StorageError(#[from] StorageError),
@@ -2,5 +2,50 @@
 
 #[cfg(feature = "bitround")]
 pub mod bitround;
+#[cfg(feature = "delta")]
+pub mod delta;
+#[cfg(feature = "fixedscaleoffset")]
+pub mod fixedscaleoffset;
 #[cfg(feature = "transpose")]
 pub mod transpose;
+
+#[cfg(any(feature = "delta", feature = "fixedscaleoffset"))]
+use crate::array::DataType;
+
+/// Parse a numeric `NumPy` data type string (e.g. `"<f8"` or `"int16"`), as used in the configuration of `numcodecs` filters.
+///
+/// The byte order is ignored, since array to array codecs operate on native endian elements.
+#[cfg(any(feature = "delta", feature = "fixedscaleoffset"))]
+fn numpy_data_type_from_str(dtype: &str) -> Option<DataType> {
+    match dtype.trim_start_matches(['<', '>', '|', '=']) {
+        "i1" | "int8" => Some(DataType::Int8),
+        "i2" | "int16" => Some(DataType::Int16),
+        "i4" | "int32" => Some(DataType::Int32),
+        "i8" | "int64" => Some(DataType::Int64),
+        "u1" | "uint8" => Some(DataType::UInt8),
+        "u2" | "uint16" => Some(DataType::UInt16),
+        "u4" | "uint32" => Some(DataType::UInt32),
+        "u8" | "uint64" => Some(DataType::UInt64),
+        "f4" | "float32" => Some(DataType::Float32),
+        "f8" | "float64" => Some(DataType::Float64),
+        _ => None,
+    }
+}
+
+/// Return the little endian `NumPy` data type string of a numeric data type supported by [`numpy_data_type_from_str`].
+#[cfg(any(feature = "delta", feature = "fixedscaleoffset"))]
+fn numpy_data_type_to_str(data_type: &DataType) -> Option<&'static str> {
+    match data_type {
+        DataType::Int8 => Some("|i1"),
+        DataType::Int16 => Some("<i2"),
+        DataType::Int32 => Some("<i4"),
+        DataType::Int64 => Some("<i8"),
+        DataType::UInt8 => Some("|u1"),
+        DataType::UInt16 => Some("<u2"),
+        DataType::UInt32 => Some("<u4"),
+        DataType::UInt64 => Some("<u8"),
+        DataType::Float32 => Some("<f4"),
+        DataType::Float64 => Some("<f8"),
+        _ => None,
+    }
+}
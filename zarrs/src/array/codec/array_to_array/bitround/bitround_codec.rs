@@ -5,7 +5,7 @@ use crate::{
         codec::{
             options::CodecOptions, ArrayBytes, ArrayCodecTraits, ArrayPartialDecoderTraits,
             ArrayPartialEncoderTraits, ArrayToArrayCodecTraits, ArrayToArrayPartialEncoderDefault,
-            CodecError, CodecTraits, RecommendedConcurrency,
+            CodecError, CodecTraits, CodecTransformInfo, RecommendedConcurrency,
         },
         ArrayMetadataOptions, ChunkRepresentation, ChunkShape, DataType,
     },
@@ -74,6 +74,10 @@ impl CodecTraits for BitroundCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         false
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new(false, true, input_len)
+    }
 }
 
 impl ArrayCodecTraits for BitroundCodec {
@@ -0,0 +1,504 @@
+//! The `delta` array to array codec.
+//!
+//! Encodes the differences between successive elements along an axis, and decodes them with a cumulative sum.
+//! Delta encoding typically improves the compression of smoothly varying data, such as integer time series.
+//!
+//! Differences are computed along the last axis unless another `axis` is configured.
+//! This codec is compatible with the [numcodecs Delta](https://numcodecs.readthedocs.io/en/stable/filter/delta.html) filter for one-dimensional chunks:
+//!  - differences are computed in the `dtype` data type and converted to the `astype` data type, and
+//!  - integer differences and sums wrap around on overflow.
+//!
+//! The `numcodecs` `Delta` filter computes differences along the flattened (C order) chunk, which differs from the last axis for multidimensional chunks.
+//!
+//! <div class="warning">
+//! This codec is experimental and is incompatible with other Zarr V3 implementations.
+//! </div>
+//!
+//! This codec requires the `delta` feature, which is disabled by default.
+//!
+//! See [`DeltaCodecConfigurationV1`] for example `JSON` metadata.
+
+mod delta_codec;
+mod delta_partial_decoder;
+
+use std::sync::Arc;
+
+pub use crate::metadata::v3::array::codec::delta::{
+    DeltaCodecConfiguration, DeltaCodecConfigurationV1,
+};
+pub use delta_codec::DeltaCodec;
+
+use crate::{
+    array::{
+        codec::{Codec, CodecError, CodecPlugin},
+        DataType,
+    },
+    config::global_config,
+    metadata::v3::{array::codec::delta, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use delta::IDENTIFIER;
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_delta, create_codec_delta)
+}
+
+fn is_name_delta(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+        || name
+            == global_config()
+                .experimental_codec_names()
+                .get(IDENTIFIER)
+                .expect("experimental codec identifier in global map")
+}
+
+pub(crate) fn create_codec_delta(metadata: &MetadataV3) -> Result<Codec, PluginCreateError> {
+    let configuration: DeltaCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Arc::new(
+        DeltaCodec::new_with_configuration(&configuration)
+            .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?,
+    );
+    Ok(Codec::ArrayToArray(codec))
+}
+
+/// An element that can be delta encoded.
+trait DeltaElement: bytemuck::Pod {
+    fn delta_sub(self, rhs: Self) -> Self;
+
+    fn delta_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_delta_element_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl DeltaElement for $t {
+                fn delta_sub(self, rhs: Self) -> Self {
+                    self.wrapping_sub(rhs)
+                }
+
+                fn delta_add(self, rhs: Self) -> Self {
+                    self.wrapping_add(rhs)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_delta_element_float {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl DeltaElement for $t {
+                fn delta_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+
+                fn delta_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_element_int!(i8, i16, i32, i64, u8, u16, u32, u64);
+impl_delta_element_float!(f32, f64);
+
+/// Return the stride and length of `axis` in a chunk with `shape`.
+fn axis_stride_and_len(shape: &[u64], axis: i64) -> Result<(usize, usize), CodecError> {
+    let to_usize = |value: u64| usize::try_from(value).unwrap();
+    let dimensionality = i64::try_from(shape.len()).unwrap();
+    let axis_normalised = if axis < 0 {
+        axis + dimensionality
+    } else {
+        axis
+    };
+    if !(0..dimensionality).contains(&axis_normalised) {
+        return Err(CodecError::Other(format!(
+            "axis {axis} is out of bounds for a chunk with {dimensionality} dimensions"
+        )));
+    }
+    let axis = usize::try_from(axis_normalised).unwrap();
+    Ok((
+        to_usize(shape[axis + 1..].iter().product()),
+        to_usize(shape[axis]),
+    ))
+}
+
+/// Replace each element with its difference from the previous element along an axis with `stride` and `len`.
+fn delta_encode_elements<T: DeltaElement>(elements: &mut [T], stride: usize, len: usize) {
+    for i in (0..elements.len()).rev() {
+        if (i / stride) % len != 0 {
+            elements[i] = elements[i].delta_sub(elements[i - stride]);
+        }
+    }
+}
+
+/// Replace each element with the cumulative sum of the elements before it along an axis with `stride` and `len`.
+fn delta_decode_elements<T: DeltaElement>(elements: &mut [T], stride: usize, len: usize) {
+    for i in 0..elements.len() {
+        if (i / stride) % len != 0 {
+            elements[i] = elements[i].delta_add(elements[i - stride]);
+        }
+    }
+}
+
+/// Delta encode or decode the native endian elements in `bytes` with data type `data_type`.
+fn delta_bytes(
+    bytes: Vec<u8>,
+    data_type: &DataType,
+    stride: usize,
+    len: usize,
+    encode: bool,
+) -> Result<Vec<u8>, CodecError> {
+    macro_rules! delta {
+        ( $t:ty ) => {{
+            let mut elements = crate::array::transmute_from_bytes_vec::<$t>(bytes);
+            if encode {
+                delta_encode_elements(&mut elements, stride, len);
+            } else {
+                delta_decode_elements(&mut elements, stride, len);
+            }
+            crate::array::transmute_to_bytes_vec(elements)
+        }};
+    }
+    Ok(match data_type {
+        DataType::Int8 => delta!(i8),
+        DataType::Int16 => delta!(i16),
+        DataType::Int32 => delta!(i32),
+        DataType::Int64 => delta!(i64),
+        DataType::UInt8 => delta!(u8),
+        DataType::UInt16 => delta!(u16),
+        DataType::UInt32 => delta!(u32),
+        DataType::UInt64 => delta!(u64),
+        DataType::Float32 => delta!(f32),
+        DataType::Float64 => delta!(f64),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+/// Delta encode the native endian elements in `bytes` with data type `dtype` and shape `shape`, and convert them to `astype`.
+fn encode_bytes(
+    bytes: Vec<u8>,
+    shape: &[u64],
+    axis: i64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let (stride, len) = axis_stride_and_len(shape, axis)?;
+    let bytes = delta_bytes(bytes, dtype, stride, len, true)?;
+    cast_bytes(bytes, dtype, astype)
+}
+
+/// Convert the native endian elements in `bytes` from `astype` to `dtype`, and delta decode them.
+fn decode_bytes(
+    bytes: Vec<u8>,
+    shape: &[u64],
+    axis: i64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let (stride, len) = axis_stride_and_len(shape, axis)?;
+    let bytes = cast_bytes(bytes, astype, dtype)?;
+    delta_bytes(bytes, dtype, stride, len, false)
+}
+
+/// A numeric element converted from any data type supported by the codec.
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+/// Convert the native endian elements in `bytes` from data type `from` to `to`.
+///
+/// Like `NumPy`, conversion between integer data types wraps around.
+/// Conversion from a floating point to an integer data type truncates towards zero and saturates at the bounds of the data type.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_lossless,
+    clippy::unnecessary_cast
+)]
+fn cast_bytes(bytes: Vec<u8>, from: &DataType, to: &DataType) -> Result<Vec<u8>, CodecError> {
+    if from == to {
+        return Ok(bytes);
+    }
+
+    macro_rules! read {
+        ( $t:ty, $variant:ident, $as:ty ) => {
+            bytes
+                .chunks_exact(std::mem::size_of::<$t>())
+                .map(|element| {
+                    Number::$variant(<$t>::from_ne_bytes(element.try_into().unwrap()) as $as)
+                })
+                .collect()
+        };
+    }
+    let numbers: Vec<Number> = match from {
+        DataType::Int8 => read!(i8, Int, i128),
+        DataType::Int16 => read!(i16, Int, i128),
+        DataType::Int32 => read!(i32, Int, i128),
+        DataType::Int64 => read!(i64, Int, i128),
+        DataType::UInt8 => read!(u8, Int, i128),
+        DataType::UInt16 => read!(u16, Int, i128),
+        DataType::UInt32 => read!(u32, Int, i128),
+        DataType::UInt64 => read!(u64, Int, i128),
+        DataType::Float32 => read!(f32, Float, f64),
+        DataType::Float64 => read!(f64, Float, f64),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                from.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    };
+
+    macro_rules! write {
+        ( $t:ty ) => {
+            numbers
+                .iter()
+                .flat_map(|number| {
+                    match *number {
+                        Number::Int(value) => value as $t,
+                        Number::Float(value) => value as $t,
+                    }
+                    .to_ne_bytes()
+                })
+                .collect()
+        };
+    }
+    Ok(match to {
+        DataType::Int8 => write!(i8),
+        DataType::Int16 => write!(i16),
+        DataType::Int32 => write!(i32),
+        DataType::Int64 => write!(i64),
+        DataType::UInt8 => write!(u8),
+        DataType::UInt16 => write!(u16),
+        DataType::UInt32 => write!(u32),
+        DataType::UInt64 => write!(u64),
+        DataType::Float32 => write!(f32),
+        DataType::Float64 => write!(f64),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                to.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::Arc};
+
+    use itertools::Itertools;
+
+    use crate::{
+        array::{
+            codec::{ArrayToArrayCodecTraits, ArrayToBytesCodecTraits, BytesCodec, CodecOptions},
+            ArrayBytes, ChunkRepresentation,
+        },
+        array_subset::ArraySubset,
+    };
+
+    use super::*;
+
+    fn codec_delta_encode_decode<T: bytemuck::Pod, U: bytemuck::Pod>(
+        codec: &DeltaCodec,
+        shape: &[u64],
+        data_type: DataType,
+        fill_value: T,
+        elements: Vec<T>,
+    ) -> (Vec<U>, Vec<T>) {
+        let chunk_representation = ChunkRepresentation::new(
+            shape
+                .iter()
+                .map(|&size| NonZeroU64::new(size).unwrap())
+                .collect(),
+            data_type,
+            crate::array::FillValue::new(bytemuck::bytes_of(&fill_value).to_vec()),
+        )
+        .unwrap();
+        let bytes = ArrayBytes::from(crate::array::transmute_to_bytes_vec(elements));
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let encoded_elements = crate::array::transmute_from_bytes_vec::<U>(
+            encoded.clone().into_fixed().unwrap().into_owned(),
+        );
+        let decoded = codec
+            .decode(encoded, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let decoded_elements =
+            crate::array::transmute_from_bytes_vec::<T>(decoded.into_fixed().unwrap().into_owned());
+        (encoded_elements, decoded_elements)
+    }
+
+    #[test]
+    fn codec_delta_axis() {
+        let elements: Vec<i32> = vec![1, 2, 4, 10, 7, 7];
+        for (axis, answer) in [
+            (-1, vec![1, 1, 2, 10, -3, 0]),
+            (1, vec![1, 1, 2, 10, -3, 0]),
+            (0, vec![1, 2, 4, 9, 5, 3]),
+            (-2, vec![1, 2, 4, 9, 5, 3]),
+        ] {
+            let codec = DeltaCodec::new_with_axis(DataType::Int32, DataType::Int32, axis).unwrap();
+            let (encoded, decoded) = codec_delta_encode_decode::<i32, i32>(
+                &codec,
+                &[2, 3],
+                DataType::Int32,
+                0,
+                elements.clone(),
+            );
+            assert_eq!(encoded, answer);
+            assert_eq!(decoded, elements);
+        }
+    }
+
+    #[test]
+    fn codec_delta_default_last_axis() {
+        const JSON: &str = r#"{ "dtype": "<i4" }"#;
+        let codec_configuration: DeltaCodecConfiguration = serde_json::from_str(JSON).unwrap();
+        for codec in [
+            DeltaCodec::new(DataType::Int32, DataType::Int32).unwrap(),
+            DeltaCodec::new_with_configuration(&codec_configuration).unwrap(),
+        ] {
+            let (encoded, _decoded) = codec_delta_encode_decode::<i32, i32>(
+                &codec,
+                &[2, 2],
+                DataType::Int32,
+                0,
+                vec![5, 6, 8, 9],
+            );
+            assert_eq!(encoded, vec![5, 1, 8, 1]);
+        }
+    }
+
+    #[test]
+    fn codec_delta_wraparound_unsigned() {
+        let codec = DeltaCodec::new(DataType::UInt8, DataType::UInt8).unwrap();
+        let elements: Vec<u8> = vec![0, 255, 1, 0];
+        let (encoded, decoded) =
+            codec_delta_encode_decode::<u8, u8>(&codec, &[4], DataType::UInt8, 0, elements.clone());
+        // 255 - 0 = 255, 1 - 255 = 2 (mod 256), 0 - 1 = 255 (mod 256)
+        assert_eq!(encoded, vec![0, 255, 2, 255]);
+        assert_eq!(decoded, elements);
+    }
+
+    #[test]
+    fn codec_delta_wraparound_signed() {
+        let codec = DeltaCodec::new(DataType::Int8, DataType::Int8).unwrap();
+        let elements: Vec<i8> = vec![-128, 127, -128, 0];
+        let (encoded, decoded) =
+            codec_delta_encode_decode::<i8, i8>(&codec, &[4], DataType::Int8, 0, elements.clone());
+        // 127 - -128 = -1 (mod 256), -128 - 127 = 1 (mod 256), 0 - -128 = -128 (mod 256)
+        assert_eq!(encoded, vec![-128, -1, 1, -128]);
+        assert_eq!(decoded, elements);
+    }
+
+    #[test]
+    fn codec_delta_astype() {
+        // numcodecs.Delta(dtype='<i8', astype='<i2')
+        const JSON: &str = r#"{ "dtype": "<i8", "astype": "<i2" }"#;
+        let codec_configuration: DeltaCodecConfiguration = serde_json::from_str(JSON).unwrap();
+        let codec = DeltaCodec::new_with_configuration(&codec_configuration).unwrap();
+        let elements: Vec<i64> = vec![100_000, 100_001, 100_003, 99_990];
+        let (encoded, decoded) =
+            codec_delta_encode_decode::<i64, i16>(&codec, &[4], DataType::Int64, 0, elements);
+        // The first element does not fit in an int16 and wraps around, like numcodecs
+        assert_eq!(encoded, vec![-31_072, 1, 2, -13]);
+        assert_eq!(decoded, vec![-31_072, -31_071, -31_069, -31_082]);
+    }
+
+    #[test]
+    fn codec_delta_float() {
+        let codec = DeltaCodec::new(DataType::Float64, DataType::Float64).unwrap();
+        let elements: Vec<f64> = vec![0.5, 1.0, 3.0, -1.0];
+        let (encoded, decoded) = codec_delta_encode_decode::<f64, f64>(
+            &codec,
+            &[4],
+            DataType::Float64,
+            0.0,
+            elements.clone(),
+        );
+        assert_eq!(encoded, vec![0.5, 0.5, 2.0, -4.0]);
+        assert_eq!(decoded, elements);
+    }
+
+    #[test]
+    fn codec_delta_invalid() {
+        let codec = DeltaCodec::new_with_axis(DataType::Int32, DataType::Int32, 2).unwrap();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(2).unwrap(), NonZeroU64::new(2).unwrap()],
+            DataType::Int32,
+            0i32.into(),
+        )
+        .unwrap();
+        assert!(codec.compute_encoded_size(&chunk_representation).is_err());
+        assert!(DeltaCodec::new(DataType::Bool, DataType::Bool).is_err());
+    }
+
+    #[test]
+    fn codec_delta_partial_decode() {
+        let codec = Arc::new(DeltaCodec::new(DataType::UInt16, DataType::UInt16).unwrap());
+
+        let elements: Vec<u16> = (0..32).map(|i| i * i).collect();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![(elements.len() as u64).try_into().unwrap()],
+            DataType::UInt16,
+            0u16.into(),
+        )
+        .unwrap();
+        let bytes: ArrayBytes = crate::array::transmute_to_bytes_vec(elements).into();
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap()
+            .into_owned();
+        let decoded_regions = [
+            ArraySubset::new_with_ranges(&[3..5]),
+            ArraySubset::new_with_ranges(&[17..21]),
+        ];
+        let input_handle = Arc::new(std::io::Cursor::new(encoded.into_fixed().unwrap()));
+        let bytes_codec = Arc::new(BytesCodec::default());
+        let input_handle = bytes_codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap();
+        let decoded_partial_chunk = decoded_partial_chunk
+            .into_iter()
+            .map(|bytes| {
+                crate::array::transmute_from_bytes_vec::<u16>(
+                    bytes.into_fixed().unwrap().into_owned(),
+                )
+            })
+            .collect_vec();
+        let answer: &[Vec<u16>] = &[vec![9, 16], vec![289, 324, 361, 400]];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+}
@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use crate::{
+    array::{
+        codec::{
+            array_to_array::{numpy_data_type_from_str, numpy_data_type_to_str},
+            options::CodecOptions,
+            ArrayBytes, ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderTraits,
+            ArrayToArrayCodecTraits, ArrayToArrayPartialEncoderDefault, CodecError, CodecTraits,
+            CodecTransformInfo, RecommendedConcurrency,
+        },
+        ArrayMetadataOptions, ChunkRepresentation, ChunkShape, DataType, FillValue,
+    },
+    config::global_config,
+    metadata::v3::MetadataV3,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::{
+    axis_stride_and_len, decode_bytes, delta_partial_decoder, encode_bytes,
+    DeltaCodecConfiguration, DeltaCodecConfigurationV1, IDENTIFIER,
+};
+
+/// A `delta` codec implementation.
+#[derive(Clone, Debug)]
+pub struct DeltaCodec {
+    dtype: DataType,
+    astype: DataType,
+    axis: i64,
+    configuration: DeltaCodecConfigurationV1,
+}
+
+impl DeltaCodec {
+    /// Create a new `delta` codec that computes differences along the last axis.
+    ///
+    /// Differences between values of data type `dtype` are encoded with data type `astype`.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::UnsupportedDataType`] if `dtype` or `astype` is not a supported numeric data type.
+    pub fn new(dtype: DataType, astype: DataType) -> Result<Self, CodecError> {
+        Self::new_with_axis(dtype, astype, -1)
+    }
+
+    /// Create a new `delta` codec that computes differences along `axis`.
+    ///
+    /// Negative axes count from the last axis.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::UnsupportedDataType`] if `dtype` or `astype` is not a supported numeric data type.
+    pub fn new_with_axis(dtype: DataType, astype: DataType, axis: i64) -> Result<Self, CodecError> {
+        let numpy_dtype = |data_type: &DataType| {
+            numpy_data_type_to_str(data_type)
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    CodecError::UnsupportedDataType(data_type.clone(), IDENTIFIER.to_string())
+                })
+        };
+        let configuration = DeltaCodecConfigurationV1 {
+            dtype: numpy_dtype(&dtype)?,
+            astype: Some(numpy_dtype(&astype)?),
+            axis: Some(axis),
+        };
+        Ok(Self {
+            dtype,
+            astype,
+            axis,
+            configuration,
+        })
+    }
+
+    /// Create a new `delta` codec from a configuration.
+    ///
+    /// Differences are computed along the last axis if the configuration has no `axis`.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::Other`] if the `dtype` or `astype` of the configuration is not a supported numeric data type.
+    pub fn new_with_configuration(
+        configuration: &DeltaCodecConfiguration,
+    ) -> Result<Self, CodecError> {
+        let DeltaCodecConfiguration::V1(configuration) = configuration;
+        let parse = |dtype: &str| {
+            numpy_data_type_from_str(dtype).ok_or_else(|| {
+                CodecError::Other(format!(
+                    "unsupported data type {dtype} for the {IDENTIFIER} codec"
+                ))
+            })
+        };
+        let dtype = parse(&configuration.dtype)?;
+        let astype = configuration
+            .astype
+            .as_deref()
+            .map_or_else(|| Ok(dtype.clone()), parse)?;
+        Ok(Self {
+            dtype,
+            astype,
+            axis: configuration.axis.unwrap_or(-1),
+            configuration: configuration.clone(),
+        })
+    }
+
+    /// Return an error if `decoded_representation` does not have the `dtype` of the codec or lacks the codec axis.
+    fn validate_representation(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<(), CodecError> {
+        if decoded_representation.data_type() != &self.dtype {
+            return Err(CodecError::UnsupportedDataType(
+                decoded_representation.data_type().clone(),
+                IDENTIFIER.to_string(),
+            ));
+        }
+        axis_stride_and_len(&decoded_representation.shape_u64(), self.axis)?;
+        Ok(())
+    }
+}
+
+impl CodecTraits for DeltaCodec {
+    fn create_metadata_opt(&self, _options: &ArrayMetadataOptions) -> Option<MetadataV3> {
+        Some(
+            MetadataV3::new_with_serializable_configuration(
+                global_config()
+                    .experimental_codec_names()
+                    .get(super::IDENTIFIER)
+                    .expect("experimental codec identifier in global map"),
+                &self.configuration,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        // The data types of the codec are always fixed size
+        let dtype_size = self.dtype.fixed_size().unwrap_or(1);
+        let astype_size = self.astype.fixed_size().unwrap_or(1);
+        CodecTransformInfo::new(
+            dtype_size != astype_size,
+            self.dtype != self.astype,
+            input_len / dtype_size * astype_size,
+        )
+    }
+}
+
+impl ArrayCodecTraits for DeltaCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToArrayCodecTraits for DeltaCodec {
+    fn dynamic(self: Arc<Self>) -> Arc<dyn ArrayToArrayCodecTraits> {
+        self as Arc<dyn ArrayToArrayCodecTraits>
+    }
+
+    fn encode<'a>(
+        &self,
+        bytes: ArrayBytes<'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<ArrayBytes<'a>, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        let bytes = bytes.into_fixed()?.into_owned();
+        let encoded = encode_bytes(
+            bytes,
+            &decoded_representation.shape_u64(),
+            self.axis,
+            &self.dtype,
+            &self.astype,
+        )?;
+        Ok(ArrayBytes::from(encoded))
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: ArrayBytes<'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<ArrayBytes<'a>, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        let bytes = bytes.into_fixed()?.into_owned();
+        let decoded = decode_bytes(
+            bytes,
+            &decoded_representation.shape_u64(),
+            self.axis,
+            &self.dtype,
+            &self.astype,
+        )?;
+        Ok(ArrayBytes::from(decoded))
+    }
+
+    fn partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn ArrayPartialDecoderTraits>, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        Ok(Arc::new(delta_partial_decoder::DeltaPartialDecoder::new(
+            input_handle,
+            decoded_representation.clone(),
+            self.axis,
+            self.astype.clone(),
+        )))
+    }
+
+    fn partial_encoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        output_handle: Arc<dyn ArrayPartialEncoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn ArrayPartialEncoderTraits>, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        Ok(Arc::new(ArrayToArrayPartialEncoderDefault::new(
+            input_handle,
+            output_handle,
+            decoded_representation.clone(),
+            self,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn AsyncArrayPartialDecoderTraits>, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        Ok(Arc::new(
+            delta_partial_decoder::AsyncDeltaPartialDecoder::new(
+                input_handle,
+                decoded_representation.clone(),
+                self.axis,
+                self.astype.clone(),
+            ),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<ChunkRepresentation, CodecError> {
+        self.validate_representation(decoded_representation)?;
+        // An encoded chunk of zeros decodes to a chunk of zeros
+        let fill_value = vec![0; self.astype.fixed_size().unwrap_or(1)];
+        ChunkRepresentation::new(
+            decoded_representation.shape().to_vec(),
+            self.astype.clone(),
+            FillValue::new(fill_value),
+        )
+        .map_err(|err| CodecError::Other(err.to_string()))
+    }
+
+    fn compute_decoded_shape(&self, encoded_shape: ChunkShape) -> Result<ChunkShape, CodecError> {
+        Ok(encoded_shape)
+    }
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use crate::{
+    array::{
+        codec::{ArrayBytes, ArrayPartialDecoderTraits, CodecError, CodecOptions},
+        ChunkRepresentation, DataType,
+    },
+    array_subset::ArraySubset,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::decode_bytes;
+
+/// Decode the entire encoded chunk and extract `array_subsets`.
+fn extract_decoded_subsets(
+    encoded: ArrayBytes<'_>,
+    array_subsets: &[ArraySubset],
+    decoded_representation: &ChunkRepresentation,
+    axis: i64,
+    astype: &DataType,
+) -> Result<Vec<ArrayBytes<'static>>, CodecError> {
+    let shape = decoded_representation.shape_u64();
+    let dtype = decoded_representation.data_type();
+    let decoded = ArrayBytes::from(decode_bytes(
+        encoded.into_fixed()?.into_owned(),
+        &shape,
+        axis,
+        dtype,
+        astype,
+    )?);
+    array_subsets
+        .iter()
+        .map(|array_subset| {
+            Ok(decoded
+                .extract_array_subset(array_subset, &shape, dtype)?
+                .into_owned())
+        })
+        .collect()
+}
+
+/// Partial decoder for the `delta` codec.
+pub(crate) struct DeltaPartialDecoder {
+    input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+    decoded_representation: ChunkRepresentation,
+    axis: i64,
+    astype: DataType,
+}
+
+impl DeltaPartialDecoder {
+    /// Create a new partial decoder for the `delta` codec.
+    pub(crate) fn new(
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        decoded_representation: ChunkRepresentation,
+        axis: i64,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            axis,
+            astype,
+        }
+    }
+}
+
+impl ArrayPartialDecoderTraits for DeltaPartialDecoder {
+    fn data_type(&self) -> &DataType {
+        self.decoded_representation.data_type()
+    }
+
+    fn partial_decode(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<ArrayBytes<'_>>, CodecError> {
+        // Every element depends on the elements before it, so the entire chunk is decoded
+        let chunk_subset = ArraySubset::new_with_shape(self.decoded_representation.shape_u64());
+        let encoded = self
+            .input_handle
+            .partial_decode(&[chunk_subset], options)?
+            .remove(0);
+        extract_decoded_subsets(
+            encoded,
+            array_subsets,
+            &self.decoded_representation,
+            self.axis,
+            &self.astype,
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `delta` codec.
+pub(crate) struct AsyncDeltaPartialDecoder {
+    input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+    decoded_representation: ChunkRepresentation,
+    axis: i64,
+    astype: DataType,
+}
+
+#[cfg(feature = "async")]
+impl AsyncDeltaPartialDecoder {
+    /// Create a new partial decoder for the `delta` codec.
+    pub(crate) fn new(
+        input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+        decoded_representation: ChunkRepresentation,
+        axis: i64,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+            axis,
+            astype,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncDeltaPartialDecoder {
+    fn data_type(&self) -> &DataType {
+        self.decoded_representation.data_type()
+    }
+
+    async fn partial_decode(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<ArrayBytes<'_>>, CodecError> {
+        // Every element depends on the elements before it, so the entire chunk is decoded
+        let chunk_subset = ArraySubset::new_with_shape(self.decoded_representation.shape_u64());
+        let encoded = self
+            .input_handle
+            .partial_decode(&[chunk_subset], options)
+            .await?
+            .remove(0);
+        extract_decoded_subsets(
+            encoded,
+            array_subsets,
+            &self.decoded_representation,
+            self.axis,
+            &self.astype,
+        )
+    }
+}
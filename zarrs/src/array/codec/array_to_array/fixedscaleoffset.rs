@@ -0,0 +1,330 @@
+//! The `fixedscaleoffset` array to array codec.
+//!
+//! Quantises values with a fixed scale and offset, typically to store floating point data as integers.
+//! Values are encoded as `round((value - offset) * scale)` in the `astype` data type, and decoded as `value / scale + offset` in the `dtype` data type.
+//!
+//! This codec is compatible with the [numcodecs FixedScaleOffset](https://numcodecs.readthedocs.io/en/stable/filter/fixedscaleoffset.html) filter:
+//!  - values are rounded half to even,
+//!  - arithmetic is performed in double precision, and
+//!  - values outside of the range of an integer `astype` saturate at its minimum or maximum.
+//!
+//! <div class="warning">
+//! This codec is experimental and is incompatible with other Zarr V3 implementations.
+//! </div>
+//!
+//! This codec requires the `fixedscaleoffset` feature, which is disabled by default.
+//!
+//! See [`FixedScaleOffsetCodecConfigurationV1`] for example `JSON` metadata.
+
+mod fixedscaleoffset_codec;
+mod fixedscaleoffset_partial_decoder;
+
+use std::sync::Arc;
+
+pub use crate::metadata::v3::array::codec::fixedscaleoffset::{
+    FixedScaleOffsetCodecConfiguration, FixedScaleOffsetCodecConfigurationV1,
+};
+pub use fixedscaleoffset_codec::FixedScaleOffsetCodec;
+
+use crate::{
+    array::{
+        codec::{Codec, CodecError, CodecPlugin},
+        DataType,
+    },
+    config::global_config,
+    metadata::v3::{array::codec::fixedscaleoffset, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use fixedscaleoffset::IDENTIFIER;
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_fixedscaleoffset, create_codec_fixedscaleoffset)
+}
+
+fn is_name_fixedscaleoffset(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+        || name
+            == global_config()
+                .experimental_codec_names()
+                .get(IDENTIFIER)
+                .expect("experimental codec identifier in global map")
+}
+
+pub(crate) fn create_codec_fixedscaleoffset(
+    metadata: &MetadataV3,
+) -> Result<Codec, PluginCreateError> {
+    let configuration: FixedScaleOffsetCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Arc::new(
+        FixedScaleOffsetCodec::new_with_configuration(&configuration)
+            .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?,
+    );
+    Ok(Codec::ArrayToArray(codec))
+}
+
+/// Convert the native endian elements in `bytes` with data type `data_type` to [`f64`].
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_lossless,
+    clippy::unnecessary_cast
+)]
+fn bytes_to_f64(bytes: &[u8], data_type: &DataType) -> Result<Vec<f64>, CodecError> {
+    macro_rules! convert {
+        ( $t:ty ) => {
+            bytes
+                .chunks_exact(std::mem::size_of::<$t>())
+                .map(|element| <$t>::from_ne_bytes(element.try_into().unwrap()) as f64)
+                .collect()
+        };
+    }
+    Ok(match data_type {
+        DataType::Int8 => convert!(i8),
+        DataType::Int16 => convert!(i16),
+        DataType::Int32 => convert!(i32),
+        DataType::Int64 => convert!(i64),
+        DataType::UInt8 => convert!(u8),
+        DataType::UInt16 => convert!(u16),
+        DataType::UInt32 => convert!(u32),
+        DataType::UInt64 => convert!(u64),
+        DataType::Float32 => convert!(f32),
+        DataType::Float64 => convert!(f64),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+/// Convert `values` to native endian elements with data type `data_type`.
+///
+/// Conversion to an integer data type truncates towards zero and saturates at the bounds of the data type.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::unnecessary_cast
+)]
+fn f64_to_bytes(
+    values: impl Iterator<Item = f64>,
+    data_type: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    macro_rules! convert {
+        ( $t:ty ) => {
+            values
+                .flat_map(|value| (value as $t).to_ne_bytes())
+                .collect()
+        };
+    }
+    Ok(match data_type {
+        DataType::Int8 => convert!(i8),
+        DataType::Int16 => convert!(i16),
+        DataType::Int32 => convert!(i32),
+        DataType::Int64 => convert!(i64),
+        DataType::UInt8 => convert!(u8),
+        DataType::UInt16 => convert!(u16),
+        DataType::UInt32 => convert!(u32),
+        DataType::UInt64 => convert!(u64),
+        DataType::Float32 => convert!(f32),
+        DataType::Float64 => convert!(f64),
+        _ => {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    })
+}
+
+/// Encode `bytes` with data type `dtype` to `astype`.
+fn encode_bytes(
+    bytes: &[u8],
+    offset: f64,
+    scale: f64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let values = bytes_to_f64(bytes, dtype)?;
+    f64_to_bytes(
+        values
+            .into_iter()
+            .map(|value| ((value - offset) * scale).round_ties_even()),
+        astype,
+    )
+}
+
+/// Decode `bytes` with data type `astype` to `dtype`.
+fn decode_bytes(
+    bytes: &[u8],
+    offset: f64,
+    scale: f64,
+    dtype: &DataType,
+    astype: &DataType,
+) -> Result<Vec<u8>, CodecError> {
+    let values = bytes_to_f64(bytes, astype)?;
+    f64_to_bytes(
+        values.into_iter().map(|value| value / scale + offset),
+        dtype,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::Arc};
+
+    use itertools::Itertools;
+
+    use crate::{
+        array::{
+            codec::{ArrayToArrayCodecTraits, ArrayToBytesCodecTraits, BytesCodec, CodecOptions},
+            ArrayBytes, ChunkRepresentation,
+        },
+        array_subset::ArraySubset,
+    };
+
+    use super::*;
+
+    #[test]
+    fn codec_fixedscaleoffset_numcodecs() {
+        // numcodecs.FixedScaleOffset(offset=1000, scale=10, dtype='<f8', astype='u1')
+        const JSON: &'static str =
+            r#"{ "offset": 1000, "scale": 10, "dtype": "<f8", "astype": "|u1" }"#;
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(10).unwrap()],
+            DataType::Float64,
+            1000.0f64.into(),
+        )
+        .unwrap();
+        let elements: Vec<f64> = vec![
+            1000.0, 1000.111, 1000.222, 1000.333, 1000.444, 1000.556, 1000.667, 1000.778, 1000.889,
+            1001.0,
+        ];
+        let bytes = ArrayBytes::from(crate::array::transmute_to_bytes_vec(elements));
+
+        let codec_configuration: FixedScaleOffsetCodecConfiguration =
+            serde_json::from_str(JSON).unwrap();
+        let codec = FixedScaleOffsetCodec::new_with_configuration(&codec_configuration).unwrap();
+        let encoded_representation = codec.compute_encoded_size(&chunk_representation).unwrap();
+        assert_eq!(encoded_representation.data_type(), &DataType::UInt8);
+        assert_eq!(encoded_representation.fill_value().as_ne_bytes(), &[0]);
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        // Output of numcodecs
+        assert_eq!(
+            encoded.clone().into_fixed().unwrap().to_vec(),
+            vec![0, 1, 2, 3, 4, 6, 7, 8, 9, 10]
+        );
+
+        let decoded = codec
+            .decode(encoded, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let decoded_elements = crate::array::transmute_from_bytes_vec::<f64>(
+            decoded.into_fixed().unwrap().into_owned(),
+        );
+        let answer = [
+            1000.0, 1000.1, 1000.2, 1000.3, 1000.4, 1000.6, 1000.7, 1000.8, 1000.9, 1001.0,
+        ];
+        for (decoded, answer) in decoded_elements.iter().zip(answer) {
+            assert!((decoded - answer).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_round_saturate() {
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(6).unwrap()],
+            DataType::Float32,
+            0.0f32.into(),
+        )
+        .unwrap();
+        let elements: Vec<f32> = vec![0.5, 1.5, 2.5, -0.5, 1000.0, -1000.0];
+        let bytes = ArrayBytes::from(crate::array::transmute_to_bytes_vec(elements));
+
+        let codec =
+            FixedScaleOffsetCodec::new(0.0, 1.0, DataType::Float32, DataType::Int8).unwrap();
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap();
+        let encoded_elements = crate::array::transmute_from_bytes_vec::<i8>(
+            encoded.into_fixed().unwrap().into_owned(),
+        );
+        assert_eq!(encoded_elements, &[0, 2, 2, 0, 127, -128]);
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_invalid() {
+        assert!(FixedScaleOffsetCodec::new_with_configuration(
+            &serde_json::from_str(r#"{ "offset": 0, "scale": 1, "dtype": "<c8" }"#).unwrap()
+        )
+        .is_err());
+        let codec =
+            FixedScaleOffsetCodec::new(0.0, 1.0, DataType::Float32, DataType::UInt16).unwrap();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![NonZeroU64::new(4).unwrap()],
+            DataType::Float64,
+            0.0f64.into(),
+        )
+        .unwrap();
+        assert!(codec.compute_encoded_size(&chunk_representation).is_err());
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_partial_decode() {
+        let codec = Arc::new(
+            FixedScaleOffsetCodec::new(10.0, 4.0, DataType::Float32, DataType::UInt16).unwrap(),
+        );
+
+        let elements: Vec<f32> = (0..32).map(|i| i as f32 + 10.0).collect();
+        let chunk_representation = ChunkRepresentation::new(
+            vec![(elements.len() as u64).try_into().unwrap()],
+            DataType::Float32,
+            0.0f32.into(),
+        )
+        .unwrap();
+        let encoded_representation = codec.compute_encoded_size(&chunk_representation).unwrap();
+        let bytes: ArrayBytes = crate::array::transmute_to_bytes_vec(elements).into();
+
+        let encoded = codec
+            .encode(bytes, &chunk_representation, &CodecOptions::default())
+            .unwrap()
+            .into_owned();
+        let decoded_regions = [
+            ArraySubset::new_with_ranges(&[3..5]),
+            ArraySubset::new_with_ranges(&[17..21]),
+        ];
+        let input_handle = Arc::new(std::io::Cursor::new(encoded.into_fixed().unwrap()));
+        let bytes_codec = Arc::new(BytesCodec::default());
+        let input_handle = bytes_codec
+            .partial_decoder(
+                input_handle,
+                &encoded_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &chunk_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap();
+        let decoded_partial_chunk = decoded_partial_chunk
+            .into_iter()
+            .map(|bytes| {
+                crate::array::transmute_from_bytes_vec::<f32>(
+                    bytes.into_fixed().unwrap().into_owned(),
+                )
+            })
+            .collect_vec();
+        let answer: &[Vec<f32>] = &[vec![13.0, 14.0], vec![27.0, 28.0, 29.0, 30.0]];
+        assert_eq!(answer, decoded_partial_chunk);
+    }
+}
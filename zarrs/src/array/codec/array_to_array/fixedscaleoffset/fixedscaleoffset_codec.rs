@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use crate::{
+    array::{
+        codec::{
+            array_to_array::{numpy_data_type_from_str, numpy_data_type_to_str},
+            options::CodecOptions,
+            ArrayBytes, ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderTraits,
+            ArrayToArrayCodecTraits, ArrayToArrayPartialEncoderDefault, CodecError, CodecTraits,
+            CodecTransformInfo, RecommendedConcurrency,
+        },
+        ArrayMetadataOptions, ChunkRepresentation, ChunkShape, DataType, FillValue,
+    },
+    config::global_config,
+    metadata::v3::MetadataV3,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::{
+    decode_bytes, encode_bytes, fixedscaleoffset_partial_decoder,
+    FixedScaleOffsetCodecConfiguration, FixedScaleOffsetCodecConfigurationV1, IDENTIFIER,
+};
+
+/// A `fixedscaleoffset` codec implementation.
+#[derive(Clone, Debug)]
+pub struct FixedScaleOffsetCodec {
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+    configuration: FixedScaleOffsetCodecConfigurationV1,
+}
+
+impl FixedScaleOffsetCodec {
+    /// Create a new `fixedscaleoffset` codec.
+    ///
+    /// Values of data type `dtype` are encoded as `round((value - offset) * scale)` with data type `astype`.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::UnsupportedDataType`] if `dtype` or `astype` is not a supported numeric data type.
+    pub fn new(
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Result<Self, CodecError> {
+        let numpy_dtype = |data_type: &DataType| {
+            numpy_data_type_to_str(data_type)
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    CodecError::UnsupportedDataType(data_type.clone(), IDENTIFIER.to_string())
+                })
+        };
+        let configuration = FixedScaleOffsetCodecConfigurationV1 {
+            offset,
+            scale,
+            dtype: numpy_dtype(&dtype)?,
+            astype: Some(numpy_dtype(&astype)?),
+        };
+        Ok(Self {
+            offset,
+            scale,
+            dtype,
+            astype,
+            configuration,
+        })
+    }
+
+    /// Create a new `fixedscaleoffset` codec from a configuration.
+    ///
+    /// # Errors
+    /// Returns [`CodecError::Other`] if the `dtype` or `astype` of the configuration is not a supported numeric data type.
+    pub fn new_with_configuration(
+        configuration: &FixedScaleOffsetCodecConfiguration,
+    ) -> Result<Self, CodecError> {
+        let FixedScaleOffsetCodecConfiguration::V1(configuration) = configuration;
+        let parse = |dtype: &str| {
+            numpy_data_type_from_str(dtype).ok_or_else(|| {
+                CodecError::Other(format!(
+                    "unsupported data type {dtype} for the {IDENTIFIER} codec"
+                ))
+            })
+        };
+        let dtype = parse(&configuration.dtype)?;
+        let astype = configuration
+            .astype
+            .as_deref()
+            .map_or_else(|| Ok(dtype.clone()), parse)?;
+        Ok(Self {
+            offset: configuration.offset,
+            scale: configuration.scale,
+            dtype,
+            astype,
+            configuration: configuration.clone(),
+        })
+    }
+
+    /// Return an error if `decoded_representation` does not have the `dtype` of the codec.
+    fn validate_data_type(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<(), CodecError> {
+        if decoded_representation.data_type() == &self.dtype {
+            Ok(())
+        } else {
+            Err(CodecError::UnsupportedDataType(
+                decoded_representation.data_type().clone(),
+                IDENTIFIER.to_string(),
+            ))
+        }
+    }
+}
+
+impl CodecTraits for FixedScaleOffsetCodec {
+    fn create_metadata_opt(&self, _options: &ArrayMetadataOptions) -> Option<MetadataV3> {
+        Some(
+            MetadataV3::new_with_serializable_configuration(
+                global_config()
+                    .experimental_codec_names()
+                    .get(super::IDENTIFIER)
+                    .expect("experimental codec identifier in global map"),
+                &self.configuration,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        false
+    }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        // The data types of the codec are always fixed size
+        let dtype_size = self.dtype.fixed_size().unwrap_or(1);
+        let astype_size = self.astype.fixed_size().unwrap_or(1);
+        CodecTransformInfo::new(
+            dtype_size != astype_size,
+            true,
+            input_len / dtype_size * astype_size,
+        )
+    }
+}
+
+impl ArrayCodecTraits for FixedScaleOffsetCodec {
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &ChunkRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl ArrayToArrayCodecTraits for FixedScaleOffsetCodec {
+    fn dynamic(self: Arc<Self>) -> Arc<dyn ArrayToArrayCodecTraits> {
+        self as Arc<dyn ArrayToArrayCodecTraits>
+    }
+
+    fn encode<'a>(
+        &self,
+        bytes: ArrayBytes<'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<ArrayBytes<'a>, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        let bytes = bytes.into_fixed()?;
+        let encoded = encode_bytes(&bytes, self.offset, self.scale, &self.dtype, &self.astype)?;
+        Ok(ArrayBytes::from(encoded))
+    }
+
+    fn decode<'a>(
+        &self,
+        bytes: ArrayBytes<'a>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<ArrayBytes<'a>, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        let bytes = bytes.into_fixed()?;
+        let decoded = decode_bytes(&bytes, self.offset, self.scale, &self.dtype, &self.astype)?;
+        Ok(ArrayBytes::from(decoded))
+    }
+
+    fn partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn ArrayPartialDecoderTraits>, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        Ok(Arc::new(
+            fixedscaleoffset_partial_decoder::FixedScaleOffsetPartialDecoder::new(
+                input_handle,
+                self.offset,
+                self.scale,
+                self.dtype.clone(),
+                self.astype.clone(),
+            ),
+        ))
+    }
+
+    fn partial_encoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        output_handle: Arc<dyn ArrayPartialEncoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn ArrayPartialEncoderTraits>, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        Ok(Arc::new(ArrayToArrayPartialEncoderDefault::new(
+            input_handle,
+            output_handle,
+            decoded_representation.clone(),
+            self,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn AsyncArrayPartialDecoderTraits>, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        Ok(Arc::new(
+            fixedscaleoffset_partial_decoder::AsyncFixedScaleOffsetPartialDecoder::new(
+                input_handle,
+                self.offset,
+                self.scale,
+                self.dtype.clone(),
+                self.astype.clone(),
+            ),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &ChunkRepresentation,
+    ) -> Result<ChunkRepresentation, CodecError> {
+        self.validate_data_type(decoded_representation)?;
+        let fill_value = encode_bytes(
+            decoded_representation.fill_value().as_ne_bytes(),
+            self.offset,
+            self.scale,
+            &self.dtype,
+            &self.astype,
+        )?;
+        ChunkRepresentation::new(
+            decoded_representation.shape().to_vec(),
+            self.astype.clone(),
+            FillValue::new(fill_value),
+        )
+        .map_err(|err| CodecError::Other(err.to_string()))
+    }
+
+    fn compute_decoded_shape(&self, encoded_shape: ChunkShape) -> Result<ChunkShape, CodecError> {
+        Ok(encoded_shape)
+    }
+}
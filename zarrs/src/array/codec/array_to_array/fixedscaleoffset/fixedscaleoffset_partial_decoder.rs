@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use crate::{
+    array::{
+        codec::{ArrayBytes, ArrayPartialDecoderTraits, CodecError, CodecOptions},
+        DataType,
+    },
+    array_subset::ArraySubset,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncArrayPartialDecoderTraits;
+
+use super::decode_bytes;
+
+/// Partial decoder for the `fixedscaleoffset` codec.
+pub(crate) struct FixedScaleOffsetPartialDecoder {
+    input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+}
+
+impl FixedScaleOffsetPartialDecoder {
+    /// Create a new partial decoder for the `fixedscaleoffset` codec.
+    pub(crate) fn new(
+        input_handle: Arc<dyn ArrayPartialDecoderTraits>,
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            offset,
+            scale,
+            dtype,
+            astype,
+        }
+    }
+}
+
+impl ArrayPartialDecoderTraits for FixedScaleOffsetPartialDecoder {
+    fn data_type(&self) -> &DataType {
+        &self.dtype
+    }
+
+    fn partial_decode(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<ArrayBytes<'_>>, CodecError> {
+        let bytes = self.input_handle.partial_decode(array_subsets, options)?;
+
+        let mut bytes_out = Vec::with_capacity(bytes.len());
+        for bytes in bytes {
+            let bytes = bytes.into_fixed()?;
+            let decoded = decode_bytes(&bytes, self.offset, self.scale, &self.dtype, &self.astype)?;
+            bytes_out.push(decoded.into());
+        }
+
+        Ok(bytes_out)
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `fixedscaleoffset` codec.
+pub(crate) struct AsyncFixedScaleOffsetPartialDecoder {
+    input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+    offset: f64,
+    scale: f64,
+    dtype: DataType,
+    astype: DataType,
+}
+
+#[cfg(feature = "async")]
+impl AsyncFixedScaleOffsetPartialDecoder {
+    /// Create a new partial decoder for the `fixedscaleoffset` codec.
+    pub(crate) fn new(
+        input_handle: Arc<dyn AsyncArrayPartialDecoderTraits>,
+        offset: f64,
+        scale: f64,
+        dtype: DataType,
+        astype: DataType,
+    ) -> Self {
+        Self {
+            input_handle,
+            offset,
+            scale,
+            dtype,
+            astype,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncArrayPartialDecoderTraits for AsyncFixedScaleOffsetPartialDecoder {
+    fn data_type(&self) -> &DataType {
+        &self.dtype
+    }
+
+    async fn partial_decode(
+        &self,
+        array_subsets: &[ArraySubset],
+        options: &CodecOptions,
+    ) -> Result<Vec<ArrayBytes<'_>>, CodecError> {
+        let bytes = self
+            .input_handle
+            .partial_decode(array_subsets, options)
+            .await?;
+
+        let mut bytes_out = Vec::with_capacity(bytes.len());
+        for bytes in bytes {
+            let bytes = bytes.into_fixed()?;
+            let decoded = decode_bytes(&bytes, self.offset, self.scale, &self.dtype, &self.astype)?;
+            bytes_out.push(decoded.into());
+        }
+
+        Ok(bytes_out)
+    }
+}
@@ -6,6 +6,7 @@
 
 mod bytes_codec;
 mod bytes_partial_decoder;
+mod bytes_partial_encoder;
 
 use std::sync::Arc;
 
@@ -261,6 +262,89 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn codec_bytes_complex_non_finite() {
+        let chunk_shape = vec![NonZeroU64::new(4).unwrap()];
+        let components32 = [
+            (f32::NAN, 1.0),
+            (f32::INFINITY, f32::NEG_INFINITY),
+            (-0.0, f32::NAN),
+            (f32::MIN_POSITIVE, f32::MAX),
+        ];
+        let components64 = components32.map(|(re, im)| (f64::from(re), f64::from(im)));
+        let complex64: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = components32
+            .iter()
+            .map(|(re, im)| {
+                let ne = [re.to_ne_bytes(), im.to_ne_bytes()].concat();
+                let le = [re.to_le_bytes(), im.to_le_bytes()].concat();
+                let be = [re.to_be_bytes(), im.to_be_bytes()].concat();
+                (ne, le, be)
+            })
+            .collect();
+        let complex128: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = components64
+            .iter()
+            .map(|(re, im)| {
+                let ne = [re.to_ne_bytes(), im.to_ne_bytes()].concat();
+                let le = [re.to_le_bytes(), im.to_le_bytes()].concat();
+                let be = [re.to_be_bytes(), im.to_be_bytes()].concat();
+                (ne, le, be)
+            })
+            .collect();
+
+        for (data_type, fill_value, elements) in [
+            (
+                DataType::Complex64,
+                FillValue::from(num::complex::Complex32::new(f32::NAN, f32::NAN)),
+                complex64,
+            ),
+            (
+                DataType::Complex128,
+                FillValue::from(num::complex::Complex64::new(f64::NAN, f64::NAN)),
+                complex128,
+            ),
+        ] {
+            let chunk_representation =
+                ChunkRepresentation::new(chunk_shape.clone(), data_type, fill_value).unwrap();
+            let bytes: ArrayBytes = elements
+                .iter()
+                .flat_map(|e| e.0.clone())
+                .collect::<Vec<_>>()
+                .into();
+            for (endianness, expected) in [
+                (
+                    Endianness::Little,
+                    elements
+                        .iter()
+                        .flat_map(|e| e.1.clone())
+                        .collect::<Vec<_>>(),
+                ),
+                (
+                    Endianness::Big,
+                    elements
+                        .iter()
+                        .flat_map(|e| e.2.clone())
+                        .collect::<Vec<_>>(),
+                ),
+            ] {
+                let codec = BytesCodec::new(Some(endianness));
+                let encoded = codec
+                    .encode(
+                        bytes.clone(),
+                        &chunk_representation,
+                        &CodecOptions::default(),
+                    )
+                    .unwrap();
+                // Each component is byte swapped independently
+                assert_eq!(encoded.to_vec(), expected);
+                let decoded = codec
+                    .decode(encoded, &chunk_representation, &CodecOptions::default())
+                    .unwrap();
+                // NaN components are preserved bit-exactly
+                assert_eq!(bytes, decoded);
+            }
+        }
+    }
+
     #[test]
     fn codec_bytes_partial_decode() {
         let chunk_shape: ChunkShape = vec![4, 4].try_into().unwrap();
@@ -304,6 +388,156 @@ mod tests {
         assert_eq!(answer, decoded_partial_chunk);
     }
 
+    #[test]
+    fn codec_bytes_partial_encode() {
+        use crate::{
+            array::ArrayBuilder,
+            storage::{store::MemoryStore, ReadableStorageTraits},
+        };
+
+        let store = Arc::new(MemoryStore::default());
+        let array = ArrayBuilder::new(
+            vec![2, 2],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .array_to_bytes_codec(Arc::new(BytesCodec::big()))
+        .bytes_to_bytes_codecs(vec![])
+        .build(store.clone(), "/")
+        .unwrap();
+        assert!(!array.codecs().any_partial_encoder_encodes_all());
+        let mut options = CodecOptions::default();
+        options.set_experimental_partial_encoding(true);
+        let key = array.chunk_key(&[0, 0]);
+
+        // A new chunk is encoded in its entirety
+        array
+            .store_array_subset_elements_opt::<u16>(
+                &ArraySubset::new_with_ranges(&[0..1, 0..1]),
+                &[1],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            store.get(&key).unwrap().unwrap().to_vec(),
+            vec![0, 1, 0, 0, 0, 0, 0, 0]
+        );
+
+        // An existing chunk is updated in place
+        array
+            .store_array_subset_elements_opt::<u16>(
+                &ArraySubset::new_with_ranges(&[1..2, 0..2]),
+                &[2, 3],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            store.get(&key).unwrap().unwrap().to_vec(),
+            vec![0, 1, 0, 0, 0, 2, 0, 3]
+        );
+        assert_eq!(
+            array.retrieve_chunk_elements::<u16>(&[0, 0]).unwrap(),
+            vec![1, 0, 2, 3]
+        );
+
+        // A chunk that becomes entirely the fill value is erased
+        array
+            .store_array_subset_elements_opt::<u16>(
+                &ArraySubset::new_with_ranges(&[0..1, 0..1]),
+                &[0],
+                &options,
+            )
+            .unwrap();
+        assert_eq!(
+            store.get(&key).unwrap().unwrap().to_vec(),
+            vec![0, 0, 0, 0, 0, 2, 0, 3]
+        );
+        array
+            .store_array_subset_elements_opt::<u16>(
+                &ArraySubset::new_with_ranges(&[1..2, 0..2]),
+                &[0, 0],
+                &options,
+            )
+            .unwrap();
+        assert!(store.get(&key).unwrap().is_none());
+    }
+
+    #[test]
+    fn codec_bytes_partial_encode_routing() {
+        use crate::{
+            array::ArrayBuilder,
+            storage::{
+                storage_adapter::metered::{
+                    InMemoryStorageMetrics, MeteredStorageAdapter, StorageOperation,
+                },
+                store::MemoryStore,
+            },
+        };
+
+        let metrics = Arc::new(InMemoryStorageMetrics::new());
+        let store = Arc::new(MeteredStorageAdapter::new(
+            Arc::new(MemoryStore::default()),
+            metrics.clone(),
+        ));
+        let array = ArrayBuilder::new(
+            vec![2, 2],
+            DataType::UInt16,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u16),
+        )
+        .array_to_bytes_codec(Arc::new(BytesCodec::little()))
+        .bytes_to_bytes_codecs(vec![])
+        .build(store.clone(), "/bytes")
+        .unwrap();
+        assert!(!CodecOptions::default().experimental_partial_encoding());
+
+        // A subset of an existing chunk is written in place without experimental partial encoding
+        array
+            .store_chunk_elements::<u16>(&[0, 0], &[1, 2, 3, 4])
+            .unwrap();
+        array
+            .store_array_subset_elements::<u16>(&ArraySubset::new_with_ranges(&[1..2, 1..2]), &[5])
+            .unwrap();
+        assert_eq!(metrics.requests(StorageOperation::SetPartial), 1);
+        assert_eq!(
+            array.retrieve_chunk_elements::<u16>(&[0, 0]).unwrap(),
+            vec![1, 2, 3, 5]
+        );
+
+        // A codec that encodes an entire chunk re-encodes the chunk
+        #[cfg(feature = "gzip")]
+        {
+            use crate::array::codec::GzipCodec;
+
+            let array = ArrayBuilder::new(
+                vec![2, 2],
+                DataType::UInt16,
+                vec![2, 2].try_into().unwrap(),
+                FillValue::from(0u16),
+            )
+            .array_to_bytes_codec(Arc::new(BytesCodec::little()))
+            .bytes_to_bytes_codecs(vec![Arc::new(GzipCodec::new(5).unwrap())])
+            .build(store, "/gzip")
+            .unwrap();
+            assert!(array.codecs().any_partial_encoder_encodes_all());
+            array
+                .store_chunk_elements::<u16>(&[0, 0], &[1, 2, 3, 4])
+                .unwrap();
+            array
+                .store_array_subset_elements::<u16>(
+                    &ArraySubset::new_with_ranges(&[1..2, 1..2]),
+                    &[5],
+                )
+                .unwrap();
+            assert_eq!(metrics.requests(StorageOperation::SetPartial), 1);
+            assert_eq!(
+                array.retrieve_chunk_elements::<u16>(&[0, 0]).unwrap(),
+                vec![1, 2, 3, 5]
+            );
+        }
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn codec_bytes_async_partial_decode() {
@@ -5,13 +5,12 @@ use std::sync::Arc;
 use crate::{
     array::{
         codec::{
-            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderDefault,
-            ArrayPartialEncoderTraits, ArrayToBytesCodecTraits, BytesPartialDecoderTraits,
-            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits,
-            RecommendedConcurrency,
+            ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderTraits,
+            ArrayToBytesCodecTraits, BytesPartialDecoderTraits, BytesPartialEncoderTraits,
+            CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
         },
-        ArrayBytes, ArrayMetadataOptions, BytesRepresentation, ChunkRepresentation, DataTypeSize,
-        RawBytes,
+        ArrayBytes, ArrayMetadataOptions, BytesRepresentation, ChunkRepresentation, DataType,
+        DataTypeSize, RawBytes,
     },
     metadata::v3::MetadataV3,
 };
@@ -20,8 +19,8 @@ use crate::{
 use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecoderTraits};
 
 use super::{
-    bytes_partial_decoder, reverse_endianness, BytesCodecConfiguration, BytesCodecConfigurationV1,
-    Endianness,
+    bytes_partial_decoder, bytes_partial_encoder, reverse_endianness, BytesCodecConfiguration,
+    BytesCodecConfigurationV1, Endianness,
 };
 
 /// A `bytes` codec implementation.
@@ -83,7 +82,10 @@ impl BytesCodec {
                         value.len(),
                         array_size,
                     ));
-                } else if data_type_size > 1 && self.endian.is_none() {
+                } else if data_type_size > 1
+                    && self.endian.is_none()
+                    && !matches!(decoded_representation.data_type(), DataType::RawBits(_))
+                {
                     return Err(CodecError::Other(format!(
                         "tried to encode an array with element size {data_type_size} with endianness None"
                     )));
@@ -118,6 +120,10 @@ impl CodecTraits for BytesCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         false
     }
+
+    fn partial_encoder_encodes_all(&self) -> bool {
+        false
+    }
 }
 
 impl ArrayCodecTraits for BytesCodec {
@@ -192,10 +198,11 @@ impl ArrayToBytesCodecTraits for BytesCodec {
         decoded_representation: &ChunkRepresentation,
         _options: &CodecOptions,
     ) -> Result<Arc<dyn ArrayPartialEncoderTraits>, CodecError> {
-        Ok(Arc::new(ArrayPartialEncoderDefault::new(
+        Ok(Arc::new(bytes_partial_encoder::BytesPartialEncoder::new(
             input_handle,
             output_handle,
             decoded_representation.clone(),
+            self.endian,
             self,
         )))
     }
@@ -0,0 +1,122 @@
+use std::{borrow::Cow, sync::Arc};
+
+use zarrs_storage::byte_range::ByteOffset;
+
+use crate::{
+    array::{
+        codec::{
+            ArrayPartialEncoderDefault, ArrayPartialEncoderTraits, ArraySubset,
+            BytesPartialDecoderTraits, BytesPartialEncoderTraits, CodecError, CodecOptions,
+        },
+        ArrayBytes, ChunkRepresentation, DataTypeSize, RawBytes,
+    },
+    array_subset::IncompatibleArraySubsetAndShapeError,
+};
+
+use super::{reverse_endianness, BytesCodec, Endianness};
+
+/// Partial encoder for the `bytes` codec.
+///
+/// The updated regions of an existing chunk are written in place as byte ranges, without decoding or re-encoding the rest of the chunk.
+/// The chunk is encoded in its entirety with [`ArrayPartialEncoderDefault`] if it does not exist yet,
+/// or if it may become entirely the fill value and [`store_empty_chunks`](CodecOptions::store_empty_chunks) is false.
+pub(crate) struct BytesPartialEncoder {
+    input_handle: Arc<dyn BytesPartialDecoderTraits>,
+    output_handle: Arc<dyn BytesPartialEncoderTraits>,
+    decoded_representation: ChunkRepresentation,
+    endian: Option<Endianness>,
+    default_encoder: ArrayPartialEncoderDefault,
+}
+
+impl BytesPartialEncoder {
+    /// Create a new partial encoder for the `bytes` codec.
+    pub(crate) fn new(
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        output_handle: Arc<dyn BytesPartialEncoderTraits>,
+        decoded_representation: ChunkRepresentation,
+        endian: Option<Endianness>,
+        codec: Arc<BytesCodec>,
+    ) -> Self {
+        let default_encoder = ArrayPartialEncoderDefault::new(
+            input_handle.clone(),
+            output_handle.clone(),
+            decoded_representation.clone(),
+            codec,
+        );
+        Self {
+            input_handle,
+            output_handle,
+            decoded_representation,
+            endian,
+            default_encoder,
+        }
+    }
+
+    /// Returns true if the encoded chunk exists.
+    fn chunk_exists(&self, options: &CodecOptions) -> Result<bool, CodecError> {
+        Ok(self.input_handle.size(options)?.is_some())
+    }
+}
+
+impl ArrayPartialEncoderTraits for BytesPartialEncoder {
+    fn erase(&self) -> Result<(), CodecError> {
+        self.output_handle.erase()
+    }
+
+    fn partial_encode(
+        &self,
+        subsets_and_bytes: &[(&ArraySubset, ArrayBytes<'_>)],
+        options: &CodecOptions,
+    ) -> Result<(), CodecError> {
+        let data_type = self.decoded_representation.data_type();
+        let DataTypeSize::Fixed(data_type_size) = data_type.size() else {
+            return Err(CodecError::UnsupportedDataType(
+                data_type.clone(),
+                super::IDENTIFIER.to_string(),
+            ));
+        };
+
+        let may_become_fill_value = !options.store_empty_chunks()
+            && subsets_and_bytes.iter().all(|(_, chunk_subset_bytes)| {
+                chunk_subset_bytes.is_fill_value(self.decoded_representation.fill_value())
+            });
+        if may_become_fill_value || !self.chunk_exists(options)? {
+            return self
+                .default_encoder
+                .partial_encode(subsets_and_bytes, options);
+        }
+
+        let chunk_shape = self.decoded_representation.shape_u64();
+        let mut offsets_and_bytes: Vec<(ByteOffset, RawBytes<'_>)> = Vec::new();
+        for (chunk_subset, chunk_subset_bytes) in subsets_and_bytes {
+            chunk_subset_bytes.validate(chunk_subset.num_elements(), data_type.size())?;
+            let mut chunk_subset_bytes = chunk_subset_bytes.clone().into_fixed()?;
+            if let Some(endian) = &self.endian {
+                if !endian.is_native() {
+                    reverse_endianness(chunk_subset_bytes.to_mut(), data_type);
+                }
+            }
+
+            // Write each contiguous run of elements of the subset to its offset in the chunk
+            let contiguous_indices = chunk_subset
+                .contiguous_linearised_indices(&chunk_shape)
+                .map_err(|_| {
+                    IncompatibleArraySubsetAndShapeError::from((
+                        (*chunk_subset).clone(),
+                        chunk_shape.clone(),
+                    ))
+                })?;
+            let length = contiguous_indices.contiguous_elements_usize() * data_type_size;
+            for (i, array_index) in contiguous_indices.iter().enumerate() {
+                let bytes = &chunk_subset_bytes[i * length..(i + 1) * length];
+                offsets_and_bytes.push((
+                    array_index * data_type_size as u64,
+                    Cow::Owned(bytes.to_vec()),
+                ));
+            }
+        }
+
+        self.output_handle
+            .partial_encode(&offsets_and_bytes, options)
+    }
+}
@@ -1,6 +1,6 @@
 //! An array to bytes codec formed by joining an array to array sequence, array to bytes, and bytes to bytes sequence of codecs.
 
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use unsafe_cell_slice::UnsafeCellSlice;
 
@@ -12,12 +12,13 @@ use crate::{
             ArrayPartialEncoderTraits, ArrayToArrayCodecTraits, ArrayToBytesCodecTraits,
             BytesPartialDecoderCache, BytesPartialDecoderTraits, BytesPartialEncoderTraits,
             BytesToBytesCodecTraits, Codec, CodecError, CodecOptions, CodecTraits,
+            CodecTransformInfo,
         },
         concurrency::RecommendedConcurrency,
-        ArrayBytes, ArrayMetadataOptions, BytesRepresentation, ChunkRepresentation, ChunkShape,
-        RawBytes,
+        ArrayBytes, ArrayMetadataOptions, ArraySize, BytesRepresentation, ChunkRepresentation,
+        ChunkShape, RawBytes,
     },
-    array_subset::ArraySubset,
+    array_subset::{ArraySubset, IncompatibleArraySubsetAndShapeError},
     metadata::v3::MetadataV3,
     plugin::PluginCreateError,
 };
@@ -32,6 +33,9 @@ use crate::array::codec::{AsyncArrayPartialDecoderTraits, AsyncBytesPartialDecod
 /// If decoding (i.e. going backwards through a codec chain), then a cache may be inserted
 ///    - following the last codec with [`partial_decoder_decodes_all`](crate::array::codec::CodecTraits::partial_decoder_decodes_all) true, or
 ///    - preceding the first codec with [`partial_decoder_should_cache_input`](crate::array::codec::CodecTraits::partial_decoder_should_cache_input), whichever is further.
+///
+/// Codec chains are compared and hashed by their codec metadata (see [`create_metadatas`](CodecChain::create_metadatas)), which has all defaults applied.
+/// Codec chains with logically identical configurations are equal and hash equal, regardless of the key order of the JSON metadata they were created from.
 #[derive(Debug, Clone)]
 pub struct CodecChain {
     array_to_array: Vec<Arc<dyn ArrayToArrayCodecTraits>>,
@@ -181,6 +185,92 @@ impl CodecChain {
         &self.bytes_to_bytes
     }
 
+    /// Returns true if any codec in the chain must decode an entire chunk when partial decoding.
+    fn any_partial_decoder_decodes_all(&self) -> bool {
+        self.array_to_array
+            .iter()
+            .any(|codec| codec.partial_decoder_decodes_all())
+            || self.array_to_bytes.partial_decoder_decodes_all()
+            || self
+                .bytes_to_bytes
+                .iter()
+                .any(|codec| codec.partial_decoder_decodes_all())
+    }
+
+    /// Returns true if any codec in the chain must decode and re-encode an entire chunk when partial encoding.
+    #[must_use]
+    pub fn any_partial_encoder_encodes_all(&self) -> bool {
+        self.array_to_array
+            .iter()
+            .any(|codec| codec.partial_encoder_encodes_all())
+            || self.array_to_bytes.partial_encoder_encodes_all()
+            || self
+                .bytes_to_bytes
+                .iter()
+                .any(|codec| codec.partial_encoder_encodes_all())
+    }
+
+    /// Partially decode the `array_subset` of a chunk from `input_handle`.
+    ///
+    /// The partial decoders of each codec in the chain are composed so that only the data needed for `array_subset` is read and decoded.
+    /// If any codec in the chain cannot partially decode (see [`partial_decoder_decodes_all`](CodecTraits::partial_decoder_decodes_all)) or `array_subset` spans the entire chunk, the chunk is fully decoded and the subset is extracted instead.
+    /// If the chunk is missing, the output is filled with the fill value.
+    ///
+    /// # Errors
+    /// Returns [`CodecError`] if `array_subset` is incompatible with the decoded representation or a codec fails.
+    pub fn partial_decode(
+        self: &Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        decoded_representation: &ChunkRepresentation,
+        array_subset: &ArraySubset,
+        options: &CodecOptions,
+    ) -> Result<ArrayBytes<'static>, CodecError> {
+        let chunk_shape = decoded_representation.shape_u64();
+        if array_subset.dimensionality() != chunk_shape.len() {
+            return Err(CodecError::InvalidArraySubsetDimensionalityError(
+                array_subset.clone(),
+                chunk_shape.len(),
+            ));
+        } else if !array_subset.inbounds(&chunk_shape) {
+            return Err(IncompatibleArraySubsetAndShapeError::new(
+                array_subset.clone(),
+                chunk_shape,
+            )
+            .into());
+        }
+
+        let is_entire_chunk = array_subset.start().iter().all(|&start| start == 0)
+            && array_subset.shape() == chunk_shape;
+        if is_entire_chunk || self.any_partial_decoder_decodes_all() {
+            let Some(encoded_value) = input_handle.decode(options)? else {
+                return Ok(ArrayBytes::new_fill_value(
+                    ArraySize::new(
+                        decoded_representation.data_type().size(),
+                        array_subset.num_elements(),
+                    ),
+                    decoded_representation.fill_value(),
+                ));
+            };
+            let decoded_value = self.decode(encoded_value, decoded_representation, options)?;
+            if is_entire_chunk {
+                Ok(decoded_value.into_owned())
+            } else {
+                Ok(decoded_value
+                    .extract_array_subset(
+                        array_subset,
+                        &chunk_shape,
+                        decoded_representation.data_type(),
+                    )?
+                    .into_owned())
+            }
+        } else {
+            let partial_decoder =
+                Arc::clone(self).partial_decoder(input_handle, decoded_representation, options)?;
+            let mut decoded = partial_decoder.partial_decode(&[array_subset.clone()], options)?;
+            Ok(decoded.remove(0).into_owned())
+        }
+    }
+
     fn get_array_representations(
         &self,
         decoded_representation: ChunkRepresentation,
@@ -211,6 +301,20 @@ impl CodecChain {
     }
 }
 
+impl PartialEq for CodecChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.create_metadatas() == other.create_metadatas()
+    }
+}
+
+impl Eq for CodecChain {}
+
+impl std::hash::Hash for CodecChain {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.create_metadatas().hash(state);
+    }
+}
+
 impl CodecTraits for CodecChain {
     /// Returns [`None`] since a codec chain does not have standard codec metadata.
     ///
@@ -226,6 +330,27 @@ impl CodecTraits for CodecChain {
     fn partial_decoder_decodes_all(&self) -> bool {
         false
     }
+
+    fn partial_encoder_encodes_all(&self) -> bool {
+        self.any_partial_encoder_encodes_all()
+    }
+
+    /// Describe the transform of each codec in the chain in sequence.
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        let mut info = CodecTransformInfo::new_same_size(input_len);
+        for codec in &self.array_to_array {
+            info = info.then(&codec.describe_transform(info.estimated_output_len()));
+        }
+        info = info.then(
+            &self
+                .array_to_bytes
+                .describe_transform(info.estimated_output_len()),
+        );
+        for codec in &self.bytes_to_bytes {
+            info = info.then(&codec.describe_transform(info.estimated_output_len()));
+        }
+        info
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -276,6 +401,7 @@ impl ArrayToBytesCodecTraits for CodecChain {
         decoded_representation: &ChunkRepresentation,
         options: &CodecOptions,
     ) -> Result<ArrayBytes<'a>, CodecError> {
+        let options = &decode_options(decoded_representation, options);
         let array_representations =
             self.get_array_representations(decoded_representation.clone())?;
         let bytes_representations =
@@ -318,6 +444,7 @@ impl ArrayToBytesCodecTraits for CodecChain {
         output_subset: &ArraySubset,
         options: &CodecOptions,
     ) -> Result<(), CodecError> {
+        let options = &decode_options(decoded_representation, options);
         let array_representations =
             self.get_array_representations(decoded_representation.clone())?;
         let bytes_representations =
@@ -679,6 +806,22 @@ impl ArrayCodecTraits for CodecChain {
     }
 }
 
+/// Return `options` with a concurrent target of one if the decoded representation has fewer elements than [`CodecOptions::decode_parallel_min_elements`].
+fn decode_options<'a>(
+    decoded_representation: &ChunkRepresentation,
+    options: &'a CodecOptions,
+) -> Cow<'a, CodecOptions> {
+    if options.concurrent_target() != 1
+        && decoded_representation.num_elements() < options.decode_parallel_min_elements()
+    {
+        let mut options = options.clone();
+        options.set_concurrent_target(1);
+        Cow::Owned(options)
+    } else {
+        Cow::Borrowed(options)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU64;
@@ -830,7 +973,7 @@ mod tests {
         // }
         // assert_eq!(bytes, decoded);
 
-        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let input_handle = Arc::new(std::io::Cursor::new(encoded.clone()));
         let partial_decoder = codec
             .clone()
             .partial_decoder(
@@ -854,9 +997,94 @@ mod tests {
         println!("decoded_partial_chunk {decoded_partial_chunk:?}");
         assert_eq!(decoded_partial_chunk_true, decoded_partial_chunk);
 
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let decoded_partial_chunk: Vec<f32> = decoded_regions
+            .iter()
+            .map(|decoded_region| {
+                codec
+                    .partial_decode(
+                        input_handle.clone(),
+                        &chunk_representation,
+                        decoded_region,
+                        &CodecOptions::default(),
+                    )
+                    .unwrap()
+                    .into_fixed()
+                    .unwrap()
+                    .to_vec()
+            })
+            .flatten()
+            .collect::<Vec<_>>()
+            .chunks(std::mem::size_of::<f32>())
+            .map(|b| f32::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(decoded_partial_chunk_true, decoded_partial_chunk);
+
+        let entire_chunk = ArraySubset::new_with_shape(chunk_representation.shape_u64());
+        let decoded = codec
+            .partial_decode(
+                input_handle,
+                &chunk_representation,
+                &entire_chunk,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded);
+
         // println!("{} {}", encoded_chunk.len(), decoded_chunk.len());
     }
 
+    #[cfg(all(feature = "transpose", feature = "gzip", feature = "crc32c"))]
+    #[test]
+    fn codec_chain_describe_transform() {
+        let codec_chain = |jsons: &[&str]| {
+            CodecChain::from_metadata(
+                &jsons
+                    .iter()
+                    .map(|json| serde_json::from_str(json).unwrap())
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap()
+        };
+
+        let info = codec_chain(&[JSON_TRANSPOSE1, JSON_BYTES]).describe_transform(64);
+        assert_eq!(info, CodecTransformInfo::new_same_size(64));
+
+        let info = codec_chain(&[JSON_BYTES, JSON_CRC32C]).describe_transform(64);
+        assert!(info.changes_size());
+        assert!(!info.is_lossy());
+        assert_eq!(info.estimated_output_len(), 68);
+
+        let info = codec_chain(&[JSON_BYTES, JSON_GZIP, JSON_CRC32C]).describe_transform(64);
+        assert_eq!(info, CodecTransformInfo::new(true, false, 68));
+    }
+
+    #[cfg(feature = "blosc")]
+    #[test]
+    fn codec_chain_eq_hash() {
+        let codec_chain = |json_bytes: &str, json_blosc: &str| {
+            CodecChain::from_metadata(&[
+                serde_json::from_str(json_bytes).unwrap(),
+                serde_json::from_str(json_blosc).unwrap(),
+            ])
+            .unwrap()
+        };
+        let a = codec_chain(JSON_BYTES, JSON_BLOSC);
+        let b = codec_chain(
+            JSON_BYTES,
+            r#"{"configuration": {"blocksize": 0, "typesize": 2, "shuffle": "shuffle", "clevel": 5, "cname": "lz4"}, "name": "blosc"}"#,
+        );
+        let c = codec_chain(
+            JSON_BYTES,
+            r#"{"name": "blosc", "configuration": {"cname": "lz4", "clevel": 6, "shuffle": "shuffle", "typesize": 2, "blocksize": 0}}"#,
+        );
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let codec_chains = std::collections::HashSet::from([a, b, c]);
+        assert_eq!(codec_chains.len(), 2);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn codec_chain_round_trip_bytes() {
@@ -907,4 +1135,33 @@ mod tests {
             decoded_partial_chunk_true,
         );
     }
+
+    #[test]
+    fn codec_chain_decode_options() {
+        let options = CodecOptions::builder()
+            .concurrent_target(8)
+            .decode_parallel_min_elements(64)
+            .build();
+        let representation = |shape: Vec<u64>| {
+            ChunkRepresentation::new(
+                shape
+                    .into_iter()
+                    .map(|s| NonZeroU64::new(s).unwrap())
+                    .collect(),
+                DataType::UInt8,
+                FillValue::from(0u8),
+            )
+            .unwrap()
+        };
+
+        // Small chunks take the serial path
+        let small = decode_options(&representation(vec![4, 4]), &options);
+        assert!(matches!(small, Cow::Owned(_)));
+        assert_eq!(small.concurrent_target(), 1);
+
+        // Large chunks use the concurrent target
+        let large = decode_options(&representation(vec![8, 8]), &options);
+        assert!(matches!(large, Cow::Borrowed(_)));
+        assert_eq!(large.concurrent_target(), 8);
+    }
 }
@@ -7,8 +7,8 @@ use crate::{
         codec::{
             ArrayBytes, ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderDefault,
             ArrayPartialEncoderTraits, ArrayToBytesCodecTraits, BytesPartialDecoderTraits,
-            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits, RawBytes,
-            RecommendedConcurrency,
+            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RawBytes, RecommendedConcurrency,
         },
         convert_from_bytes_slice, transmute_to_bytes_vec, ArrayMetadataOptions,
         BytesRepresentation, ChunkRepresentation, DataType,
@@ -109,6 +109,10 @@ impl CodecTraits for PcodecCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 impl ArrayCodecTraits for PcodecCodec {
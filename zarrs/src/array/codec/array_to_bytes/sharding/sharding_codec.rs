@@ -11,7 +11,8 @@ use crate::{
         codec::{
             ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderTraits,
             ArrayToBytesCodecTraits, BytesPartialDecoderTraits, BytesPartialEncoderTraits,
-            CodecChain, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            CodecChain, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         concurrency::calc_concurrency_outer_inner,
         transmute_to_bytes_vec, unravel_index, ArrayBytes, ArrayMetadataOptions, ArraySize,
@@ -102,6 +103,12 @@ impl CodecTraits for ShardingCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         false
     }
+
+    /// The estimated output length excludes the shard index, since the number of inner chunks depends on the shard shape.
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        let inner = self.inner_codecs.describe_transform(input_len);
+        CodecTransformInfo::new(true, inner.is_lossy(), inner.estimated_output_len())
+    }
 }
 
 /// Repeat the fill value into a contiguous vec
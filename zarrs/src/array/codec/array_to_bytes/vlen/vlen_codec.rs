@@ -6,7 +6,7 @@ use crate::{
             ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderDefault,
             ArrayPartialEncoderTraits, ArrayToBytesCodecTraits, BytesCodec,
             BytesPartialDecoderTraits, BytesPartialEncoderTraits, CodecError, CodecOptions,
-            CodecTraits, RecommendedConcurrency,
+            CodecTraits, CodecTransformInfo, RecommendedConcurrency,
         },
         transmute_to_bytes_vec, ArrayBytes, ArrayMetadataOptions, BytesRepresentation,
         ChunkRepresentation, CodecChain, DataType, DataTypeSize, Endianness, FillValue, RawBytes,
@@ -108,6 +108,11 @@ impl CodecTraits for VlenCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true // TODO: Vlen could do partial decoding, but needs coalescing etc
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        // The encoded size depends on the element lengths and the index/data codecs
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 impl ArrayCodecTraits for VlenCodec {
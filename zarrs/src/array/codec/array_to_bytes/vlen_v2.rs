@@ -1,11 +1,16 @@
 //! The `vlen_v2` array to bytes codec.
+//!
+//! Encodes variable-length elements in the `numcodecs` `VLenUTF8`/`VLenBytes` layout: the number of elements followed by each element prefixed by its length, as little-endian 32-bit integers.
+//!
+//! This codec is also identified by the `vlen-utf8` name used by `zarr-python` for Zarr V3 [`string`](crate::array::DataType::String) arrays.
+//! Set the `vlen_v2` name in [`Config::experimental_codec_names_mut`](crate::config::Config::experimental_codec_names_mut) to `vlen-utf8` to write arrays that can be read by `zarr-python`.
 
 mod vlen_v2_codec;
 mod vlen_v2_partial_decoder;
 
 use std::{mem::size_of, sync::Arc};
 
-pub use vlen_v2::IDENTIFIER;
+pub use vlen_v2::{IDENTIFIER, IDENTIFIER_VLEN_UTF8};
 
 pub use crate::metadata::v3::array::codec::vlen_v2::{
     VlenV2CodecConfiguration, VlenV2CodecConfigurationV1,
@@ -31,6 +36,7 @@ inventory::submit! {
 
 fn is_name_vlen_v2(name: &str) -> bool {
     name.eq(IDENTIFIER)
+        || name.eq(IDENTIFIER_VLEN_UTF8)
         || name
             == global_config()
                 .experimental_codec_names()
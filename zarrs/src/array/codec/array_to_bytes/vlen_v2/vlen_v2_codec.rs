@@ -7,7 +7,7 @@ use crate::{
         codec::{
             ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderDefault,
             ArrayPartialEncoderTraits, ArrayToBytesCodecTraits, BytesPartialDecoderTraits,
-            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits,
+            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
             RecommendedConcurrency,
         },
         ArrayBytes, ArrayMetadataOptions, BytesRepresentation, ChunkRepresentation, DataTypeSize,
@@ -63,6 +63,11 @@ impl CodecTraits for VlenV2Codec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true // TODO: Vlen could do partial decoding, but needs coalescing etc
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        // The encoded size depends on the element lengths
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 impl ArrayCodecTraits for VlenV2Codec {
@@ -14,8 +14,8 @@ use crate::{
         codec::{
             ArrayBytes, ArrayCodecTraits, ArrayPartialDecoderTraits, ArrayPartialEncoderDefault,
             ArrayPartialEncoderTraits, ArrayToBytesCodecTraits, BytesPartialDecoderTraits,
-            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits, RawBytes,
-            RecommendedConcurrency,
+            BytesPartialEncoderTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RawBytes, RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, ChunkRepresentation, DataType,
     },
@@ -146,6 +146,10 @@ impl CodecTraits for ZfpCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, !matches!(self.mode, ZfpMode::Reversible))
+    }
 }
 
 impl ArrayCodecTraits for ZfpCodec {
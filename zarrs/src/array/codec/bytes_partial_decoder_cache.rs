@@ -73,6 +73,10 @@ impl BytesPartialDecoderTraits for BytesPartialDecoderCache<'_> {
             None => None,
         })
     }
+
+    fn size(&self, _options: &CodecOptions) -> Result<Option<u64>, CodecError> {
+        Ok(self.cache.as_ref().map(|bytes| bytes.len() as u64))
+    }
 }
 
 #[cfg(feature = "async")]
@@ -1,7 +1,11 @@
 //! Bytes to bytes codecs.
 
+#[cfg(feature = "adler32")]
+pub mod adler32;
 #[cfg(feature = "blosc")]
 pub mod blosc;
+#[cfg(feature = "brotli")]
+pub mod brotli;
 #[cfg(feature = "bz2")]
 pub mod bz2;
 #[cfg(feature = "crc32c")]
@@ -10,8 +14,33 @@ pub mod crc32c;
 pub mod gdeflate;
 #[cfg(feature = "gzip")]
 pub mod gzip;
+#[cfg(feature = "lz4")]
+pub mod lz4;
 #[cfg(feature = "zstd")]
 pub mod zstd;
 
 #[cfg(test)]
 pub mod test_unbounded;
+
+#[cfg(any(feature = "blosc", feature = "gzip", feature = "zstd"))]
+use crate::array::codec::{CodecError, CodecOptions};
+
+/// Strip any trailing bytes following an encoded stream of length `stream_length`.
+///
+/// # Errors
+/// Returns [`CodecError::TrailingBytes`] if `encoded_value` has trailing bytes and they are not allowed by [`CodecOptions::allow_trailing_bytes()`].
+#[cfg(any(feature = "blosc", feature = "gzip", feature = "zstd"))]
+fn strip_trailing_bytes<'a>(
+    encoded_value: &'a [u8],
+    stream_length: usize,
+    options: &CodecOptions,
+) -> Result<&'a [u8], CodecError> {
+    let trailing_bytes = encoded_value.len().saturating_sub(stream_length);
+    if trailing_bytes == 0 {
+        Ok(encoded_value)
+    } else if options.allow_trailing_bytes() {
+        Ok(&encoded_value[..stream_length])
+    } else {
+        Err(CodecError::TrailingBytes(trailing_bytes))
+    }
+}
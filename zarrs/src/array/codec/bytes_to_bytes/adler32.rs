@@ -0,0 +1,228 @@
+//! The `adler32` (Adler-32 checksum) bytes to bytes codec.
+//!
+//! <div class="warning">
+//! This codec is experimental and is incompatible with other Zarr V3 implementations.
+//! </div>
+//!
+//! This codec requires the `adler32` feature, which is disabled by default.
+//!
+//! Appends an [Adler-32](https://datatracker.ietf.org/doc/html/rfc1950#section-8) checksum of the input bytestream as a little-endian 32-bit integer.
+//! This mirrors the [`crc32c`](super::crc32c) codec.
+
+mod adler32_codec;
+mod adler32_partial_decoder;
+
+use std::sync::Arc;
+
+pub use crate::metadata::v3::array::codec::adler32::{
+    Adler32CodecConfiguration, Adler32CodecConfigurationV1,
+};
+pub use adler32_codec::Adler32Codec;
+
+use crate::{
+    array::codec::{Codec, CodecError, CodecOptions, CodecPlugin},
+    config::global_config,
+    metadata::v3::{array::codec::adler32, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use adler32::IDENTIFIER;
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_adler32, create_codec_adler32)
+}
+
+fn is_name_adler32(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+        || name
+            == global_config()
+                .experimental_codec_names()
+                .get(IDENTIFIER)
+                .expect("experimental codec identifier in global map")
+}
+
+pub(crate) fn create_codec_adler32(metadata: &MetadataV3) -> Result<Codec, PluginCreateError> {
+    let configuration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Arc::new(Adler32Codec::new_with_configuration(&configuration));
+    Ok(Codec::BytesToBytes(codec))
+}
+
+const CHECKSUM_SIZE: usize = core::mem::size_of::<u32>();
+
+/// Return the Adler-32 checksum of `bytes`.
+fn adler32_checksum(bytes: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    ::adler32::RollingAdler32::from_buffer(bytes)
+        .hash()
+        .to_le_bytes()
+}
+
+/// Return the length of the decoded value of `encoded_value`, validating its checksum if enabled in `options`.
+fn adler32_decoded_len(encoded_value: &[u8], options: &CodecOptions) -> Result<usize, CodecError> {
+    let Some(decoded_len) = encoded_value.len().checked_sub(CHECKSUM_SIZE) else {
+        return Err(CodecError::Other(
+            "adler32 decoder expects a 32 bit input".to_string(),
+        ));
+    };
+    if options.validate_checksums()
+        && adler32_checksum(&encoded_value[..decoded_len]) != encoded_value[decoded_len..]
+    {
+        return Err(CodecError::InvalidChecksum);
+    }
+    Ok(decoded_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, sync::Arc};
+
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            BytesRepresentation,
+        },
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    const JSON1: &str = r#"{}"#;
+
+    #[test]
+    fn codec_adler32() {
+        let bytes: Vec<u8> = b"Wikipedia".to_vec();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: Adler32CodecConfiguration = serde_json::from_str(JSON1).unwrap();
+        let codec = Adler32Codec::new_with_configuration(&codec_configuration);
+
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        // The Adler-32 checksum of "Wikipedia" is 0x11E60398
+        assert_eq!(encoded[bytes.len()..], 0x11E6_0398u32.to_le_bytes());
+        let decoded = codec
+            .decode(
+                encoded.clone(),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+
+        // A corrupted value fails validation
+        let mut corrupted = encoded.to_vec();
+        corrupted[0] ^= 1;
+        assert!(matches!(
+            codec.decode(
+                Cow::Owned(corrupted.clone()),
+                &bytes_representation,
+                &CodecOptions::default()
+            ),
+            Err(CodecError::InvalidChecksum)
+        ));
+        let options = CodecOptions::builder().validate_checksums(false).build();
+        assert!(codec
+            .decode(Cow::Owned(corrupted), &bytes_representation, &options)
+            .is_ok());
+        assert!(codec
+            .decode(
+                Cow::Owned(vec![0; 3]),
+                &bytes_representation,
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn codec_adler32_partial_decode() {
+        let bytes: Vec<u8> = (0..32).collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = Arc::new(Adler32Codec::new());
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions = [
+            ByteRange::FromStart(3, Some(2)),
+            ByteRange::FromStart(30, None),
+            ByteRange::Suffix(1),
+        ];
+        let input_handle = Arc::new(std::io::Cursor::new(encoded.to_vec()));
+        let partial_decoder = codec
+            .clone()
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        let answer: &[Vec<u8>] = &[vec![3, 4], vec![30, 31], vec![31]];
+        assert_eq!(
+            answer,
+            decoded_partial_chunk
+                .into_iter()
+                .map(|v| v.to_vec())
+                .collect::<Vec<_>>()
+        );
+
+        // The checksum of the full value is validated before serving any byte range
+        let mut corrupted = encoded.to_vec();
+        corrupted[20] ^= 1;
+        let input_handle = Arc::new(std::io::Cursor::new(corrupted));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(matches!(
+            partial_decoder.partial_decode(&decoded_regions[..1], &CodecOptions::default()),
+            Err(CodecError::InvalidChecksum)
+        ));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn codec_adler32_async_partial_decode() {
+        let bytes: Vec<u8> = (0..32).collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = Arc::new(Adler32Codec::new());
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions = [ByteRange::FromStart(3, Some(2))];
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded_partial_chunk = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+        let answer: &[Vec<u8>] = &[vec![3, 4]];
+        assert_eq!(
+            answer,
+            decoded_partial_chunk
+                .into_iter()
+                .map(|v| v.to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+}
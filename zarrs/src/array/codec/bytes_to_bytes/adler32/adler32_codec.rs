@@ -0,0 +1,164 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
+        },
+        ArrayMetadataOptions, BytesRepresentation, RawBytes,
+    },
+    config::global_config,
+    metadata::v3::MetadataV3,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    adler32_checksum, adler32_decoded_len, adler32_partial_decoder, Adler32CodecConfiguration,
+    Adler32CodecConfigurationV1, CHECKSUM_SIZE, IDENTIFIER,
+};
+
+/// An `adler32` (Adler-32 checksum) codec implementation.
+#[derive(Clone, Debug, Default)]
+pub struct Adler32Codec;
+
+impl Adler32Codec {
+    /// Create a new `adler32` codec.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Create a new `adler32` codec.
+    #[must_use]
+    pub const fn new_with_configuration(_configuration: &Adler32CodecConfiguration) -> Self {
+        Self {}
+    }
+}
+
+impl CodecTraits for Adler32Codec {
+    fn create_metadata_opt(&self, _options: &ArrayMetadataOptions) -> Option<MetadataV3> {
+        let configuration = Adler32CodecConfigurationV1 {};
+        Some(
+            MetadataV3::new_with_serializable_configuration(
+                global_config()
+                    .experimental_codec_names()
+                    .get(IDENTIFIER)
+                    .expect("experimental codec identifier in global map"),
+                &configuration,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new(true, false, input_len + CHECKSUM_SIZE)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for Adler32Codec {
+    fn dynamic(self: Arc<Self>) -> Arc<dyn BytesToBytesCodecTraits> {
+        self as Arc<dyn BytesToBytesCodecTraits>
+    }
+
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode<'a>(
+        &self,
+        decoded_value: RawBytes<'a>,
+        _options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        let checksum = adler32_checksum(&decoded_value);
+        let mut encoded_value: Vec<u8> = Vec::with_capacity(decoded_value.len() + checksum.len());
+        encoded_value.extend_from_slice(&decoded_value);
+        encoded_value.extend_from_slice(&checksum);
+        Ok(Cow::Owned(encoded_value))
+    }
+
+    fn decode<'a>(
+        &self,
+        encoded_value: RawBytes<'a>,
+        _decoded_representation: &BytesRepresentation,
+        options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        let decoded_len = adler32_decoded_len(&encoded_value, options)?;
+        // Strip the checksum without copying the decoded bytes
+        match encoded_value {
+            Cow::Borrowed(encoded_value) => Ok(Cow::Borrowed(&encoded_value[..decoded_len])),
+            Cow::Owned(mut encoded_value) => {
+                encoded_value.truncate(decoded_len);
+                Ok(Cow::Owned(encoded_value))
+            }
+        }
+    }
+
+    fn partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(
+            adler32_partial_decoder::Adler32PartialDecoder::new(input_handle),
+        ))
+    }
+
+    fn partial_encoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        output_handle: Arc<dyn BytesPartialEncoderTraits>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialEncoderTraits>, CodecError> {
+        Ok(Arc::new(BytesPartialEncoderDefault::new(
+            input_handle,
+            output_handle,
+            *decoded_representation,
+            self,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn AsyncBytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(
+            adler32_partial_decoder::AsyncAdler32PartialDecoder::new(input_handle),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        match decoded_representation {
+            BytesRepresentation::FixedSize(size) => {
+                BytesRepresentation::FixedSize(size + CHECKSUM_SIZE as u64)
+            }
+            BytesRepresentation::BoundedSize(size) => {
+                BytesRepresentation::BoundedSize(size + CHECKSUM_SIZE as u64)
+            }
+            BytesRepresentation::UnboundedSize => BytesRepresentation::UnboundedSize,
+        }
+    }
+}
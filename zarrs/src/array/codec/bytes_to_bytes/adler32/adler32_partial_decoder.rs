@@ -0,0 +1,88 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+        RawBytes,
+    },
+    byte_range::{extract_byte_ranges, ByteRange},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::adler32_decoded_len;
+
+/// Validate the checksum of `encoded_value` and extract the `decoded_regions` of its decoded value.
+fn adler32_decode_regions(
+    encoded_value: &[u8],
+    decoded_regions: &[ByteRange],
+    options: &CodecOptions,
+) -> Result<Vec<RawBytes<'static>>, CodecError> {
+    let decoded_len = adler32_decoded_len(encoded_value, options)?;
+    Ok(
+        extract_byte_ranges(&encoded_value[..decoded_len], decoded_regions)
+            .map_err(CodecError::InvalidByteRangeError)?
+            .into_iter()
+            .map(Cow::Owned)
+            .collect(),
+    )
+}
+
+/// Partial decoder for the `adler32` (Adler-32 checksum) codec.
+///
+/// The entire encoded value is retrieved so that its checksum can be validated.
+pub(crate) struct Adler32PartialDecoder<'a> {
+    input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+}
+
+impl<'a> Adler32PartialDecoder<'a> {
+    /// Create a new partial decoder for the `adler32` codec.
+    pub(crate) fn new(input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+impl BytesPartialDecoderTraits for Adler32PartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+        adler32_decode_regions(&encoded_value, decoded_regions, options).map(Some)
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `adler32` (Adler-32 checksum) codec.
+pub(crate) struct AsyncAdler32PartialDecoder {
+    input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncAdler32PartialDecoder {
+    /// Create a new partial decoder for the `adler32` codec.
+    pub(crate) fn new(input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>) -> Self {
+        Self { input_handle }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncAdler32PartialDecoder {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+        adler32_decode_regions(&encoded_value, decoded_regions, options).map(Some)
+    }
+}
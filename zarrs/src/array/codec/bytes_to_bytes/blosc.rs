@@ -15,6 +15,21 @@ mod blosc_partial_decoder;
 /// Otherwise, these functions will use one thread regardless of the `numinternalthreads` parameter.
 const MIN_PARALLEL_LENGTH: usize = 4_000_000;
 
+/// The length of a `blosc` header.
+const BLOSC_MIN_HEADER_LENGTH: usize = 16;
+
+/// The `blosc` header flag indicating that byte-wise shuffling was applied.
+const BLOSC_DOSHUFFLE: u8 = 0x1;
+
+/// The `blosc` header flag indicating that the data was stored verbatim (without compression or shuffling).
+const BLOSC_MEMCPYED: u8 = 0x2;
+
+/// The `blosc` header flag indicating that bit-wise shuffling was applied.
+const BLOSC_DOBITSHUFFLE: u8 = 0x4;
+
+/// The latest `blosc` format version that can be decoded.
+pub const BLOSC_VERSION_FORMAT: u8 = 2;
+
 use std::{
     ffi::{c_char, c_int, c_void},
     sync::Arc,
@@ -27,13 +42,15 @@ pub use crate::metadata::v3::array::codec::blosc::{
 pub use blosc_codec::BloscCodec;
 use blosc_sys::{
     blosc_cbuffer_metainfo, blosc_cbuffer_sizes, blosc_cbuffer_validate, blosc_compress_ctx,
-    blosc_decompress_ctx, blosc_getitem, BLOSC_MAX_OVERHEAD, BLOSC_MAX_THREADS,
+    blosc_decompress_ctx, blosc_get_complib_info, blosc_getitem, BLOSC_MAX_OVERHEAD,
+    BLOSC_MAX_THREADS,
 };
 use derive_more::From;
 use thiserror::Error;
 
 use crate::{
-    array::codec::{Codec, CodecPlugin},
+    array::codec::{Codec, CodecError, CodecPlugin},
+    byte_range::{ByteRange, InvalidByteRangeError},
     metadata::v3::{array::codec::blosc, MetadataV3},
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
@@ -78,6 +95,19 @@ const fn compressor_as_cstr(compressor: BloscCompressor) -> *const u8 {
     }
 }
 
+/// Returns true if `compressor` was compiled into the linked `blosc` library.
+#[must_use]
+pub fn blosc_compressor_available(compressor: BloscCompressor) -> bool {
+    let support = unsafe {
+        blosc_get_complib_info(
+            compressor_as_cstr(compressor).cast::<c_char>(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    support >= 0
+}
+
 fn blosc_compress_bytes(
     src: &[u8],
     clevel: BloscCompressionLevel,
@@ -124,6 +154,15 @@ fn blosc_compress_bytes(
     }
 }
 
+/// Returns the decoded size in bytes of a `blosc` encoded value, as recorded in its header.
+///
+/// The header is validated but nothing is decompressed, so this can be used to preallocate an output buffer or to enforce a decoded size limit before decoding.
+/// Returns [`None`] if `src` is not a valid `blosc` encoded value.
+#[must_use]
+pub fn blosc_decoded_size(src: &[u8]) -> Option<usize> {
+    blosc_validate(src)
+}
+
 fn blosc_validate(src: &[u8]) -> Option<usize> {
     let mut destsize: usize = 0;
     let valid = unsafe {
@@ -152,12 +191,140 @@ fn blosc_typesize(src: &[u8]) -> Option<usize> {
     (typesize != 0).then_some(typesize)
 }
 
-/// Returns the length of the uncompress bytes of a `blosc` buffer.
+/// Returns the verbatim payload of a `blosc` buffer that was stored without compression or shuffling.
 ///
-/// # Safety
+/// Returns [`None`] if `src` is not a valid `blosc` buffer or the data is compressed.
+fn blosc_memcpyed_payload(src: &[u8]) -> Option<&[u8]> {
+    let destsize = blosc_validate(src)?;
+    if !BloscHeader::new(src)?.memcpyed() {
+        return None;
+    }
+    src.get(BLOSC_MIN_HEADER_LENGTH..BLOSC_MIN_HEADER_LENGTH + destsize)
+}
+
+/// The header of a `blosc` encoded value.
 ///
-/// Validate first
-fn blosc_nbytes(src: &[u8]) -> Option<usize> {
+/// The header is read from the first 16 bytes of an encoded value without validating or decompressing it.
+/// This can be used to check the compatibility of an encoded value (e.g. its [format version](BloscHeader::version)) before decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BloscHeader {
+    version: u8,
+    versionlz: u8,
+    flags: u8,
+    typesize: u8,
+    nbytes: u32,
+    blocksize: u32,
+    cbytes: u32,
+}
+
+impl BloscHeader {
+    /// Read the header of the `blosc` encoded value `src`.
+    ///
+    /// Returns [`None`] if `src` is too short to contain a `blosc` header.
+    #[must_use]
+    pub fn new(src: &[u8]) -> Option<Self> {
+        let header = src.get(..BLOSC_MIN_HEADER_LENGTH)?;
+        let u32_at = |offset: usize| {
+            u32::from_le_bytes([
+                header[offset],
+                header[offset + 1],
+                header[offset + 2],
+                header[offset + 3],
+            ])
+        };
+        Some(Self {
+            version: header[0],
+            versionlz: header[1],
+            flags: header[2],
+            typesize: header[3],
+            nbytes: u32_at(4),
+            blocksize: u32_at(8),
+            cbytes: u32_at(12),
+        })
+    }
+
+    /// Return the `blosc` format version.
+    ///
+    /// Encoded values with a version newer than [`BLOSC_VERSION_FORMAT`] cannot be decoded.
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Return the format version of the internal compressor.
+    #[must_use]
+    pub const fn versionlz(&self) -> u8 {
+        self.versionlz
+    }
+
+    /// Return the raw flag bits.
+    #[must_use]
+    pub const fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Returns true if byte-wise shuffling was applied.
+    #[must_use]
+    pub const fn shuffle(&self) -> bool {
+        self.flags & BLOSC_DOSHUFFLE != 0
+    }
+
+    /// Returns true if bit-wise shuffling was applied.
+    #[must_use]
+    pub const fn bitshuffle(&self) -> bool {
+        self.flags & BLOSC_DOBITSHUFFLE != 0
+    }
+
+    /// Returns true if the data was stored verbatim (without compression or shuffling).
+    #[must_use]
+    pub const fn memcpyed(&self) -> bool {
+        self.flags & BLOSC_MEMCPYED != 0
+    }
+
+    /// Return the code of the internal compressor format, stored in the upper 3 bits of the flags.
+    #[must_use]
+    pub const fn compressor_format(&self) -> u8 {
+        self.flags >> 5
+    }
+
+    /// Return the type size in bytes.
+    #[must_use]
+    pub const fn typesize(&self) -> u8 {
+        self.typesize
+    }
+
+    /// Return the decoded size in bytes.
+    #[must_use]
+    pub const fn nbytes(&self) -> u32 {
+        self.nbytes
+    }
+
+    /// Return the block size in bytes.
+    #[must_use]
+    pub const fn blocksize(&self) -> u32 {
+        self.blocksize
+    }
+
+    /// Return the encoded size in bytes, including the header.
+    #[must_use]
+    pub const fn cbytes(&self) -> u32 {
+        self.cbytes
+    }
+
+    /// Returns true if the format version can be decoded.
+    #[must_use]
+    pub const fn is_version_supported(&self) -> bool {
+        self.version <= BLOSC_VERSION_FORMAT
+    }
+}
+
+/// Returns the length of a `blosc` buffer as recorded in its header.
+///
+/// Returns [`None`] if `src` is too short to contain a `blosc` header.
+fn blosc_cbytes(src: &[u8]) -> Option<usize> {
+    if src.len() < BLOSC_MIN_HEADER_LENGTH {
+        return None;
+    }
     let mut uncompressed_bytes: usize = 0;
     let mut cbytes: usize = 0;
     let mut blocksize: usize = 0;
@@ -169,7 +336,7 @@ fn blosc_nbytes(src: &[u8]) -> Option<usize> {
             std::ptr::addr_of_mut!(blocksize),
         );
     };
-    (uncompressed_bytes > 0 && cbytes > 0 && blocksize > 0).then_some(uncompressed_bytes)
+    (cbytes > 0).then_some(cbytes)
 }
 
 fn blosc_decompress_bytes(
@@ -204,15 +371,134 @@ fn blosc_decompress_bytes(
     }
 }
 
-fn blosc_decompress_bytes_partial(
+/// Decompress the `byte_range` of the decoded value of a `blosc` encoded value `src`.
+///
+/// Only the `blosc` blocks intersecting `byte_range` are decompressed.
+/// The `blosc` header of `src` and `byte_range` are validated before `src` is passed to the `blosc` library, so invalid input returns an error rather than panicking.
+///
+/// # Errors
+/// Returns a [`CodecError`] if
+///  - `src` is not a valid `blosc` encoded value,
+///  - `byte_range` exceeds the decoded size recorded in the `blosc` header, or
+///  - decompression fails.
+pub fn blosc_decompress_bytes_partial(
     src: &[u8],
-    offset: usize,
-    length: usize,
+    byte_range: &ByteRange,
+) -> Result<Vec<u8>, CodecError> {
+    blosc_decompress_bytes_partial_opt(src, byte_range, None)
+}
+
+/// Decompress the `byte_range` of the decoded value of a `blosc` encoded value `src` with a `typesize` override.
+///
+/// This is [`blosc_decompress_bytes_partial`], except that the `blosc` header of `src` may record a typesize of 0 (unknown) if `typesize` is a positive integer.
+/// `typesize` should be the element size of the array data type.
+/// The items of such a value cannot be addressed individually, so it is decompressed in its entirety and then sliced.
+/// `typesize` is ignored if the header records a positive typesize.
+///
+/// # Errors
+/// Returns a [`CodecError`] if
+///  - `src` is not a valid `blosc` encoded value,
+///  - the header records a typesize of 0 and `typesize` is [`None`] or 0,
+///  - `byte_range` exceeds the decoded size recorded in the `blosc` header, or
+///  - decompression fails.
+pub fn blosc_decompress_bytes_partial_opt(
+    src: &[u8],
+    byte_range: &ByteRange,
+    typesize: Option<usize>,
+) -> Result<Vec<u8>, CodecError> {
+    let invalid = || CodecError::from("blosc encoded value is invalid");
+    let nbytes = blosc_decoded_size(src).ok_or_else(invalid)?;
+    let header_typesize = blosc_typesize(src);
+    if header_typesize.is_none() && typesize.unwrap_or_default() == 0 {
+        return Err(CodecError::from(
+            "blosc encoded value has an unknown typesize and no typesize override",
+        ));
+    }
+
+    // Validate the byte range against the decoded size
+    let nbytes_u64 = nbytes as u64;
+    let (offset, length) = match *byte_range {
+        ByteRange::FromStart(offset, Some(length)) => (offset, length),
+        ByteRange::FromStart(offset, None) => (offset, nbytes_u64.saturating_sub(offset)),
+        ByteRange::Suffix(length) => (nbytes_u64.saturating_sub(length), length),
+    };
+    if offset
+        .checked_add(length)
+        .map_or(true, |end| end > nbytes_u64)
+    {
+        return Err(InvalidByteRangeError::new(*byte_range, nbytes_u64).into());
+    }
+    // offset + length <= nbytes, so these conversions cannot fail
+    let offset = usize::try_from(offset).unwrap();
+    let length = usize::try_from(length).unwrap();
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+
+    let Some(typesize) = header_typesize else {
+        // blosc_getitem addresses items with the typesize in the header, so decode everything
+        if let Some(payload) = blosc_memcpyed_payload(src) {
+            return Ok(payload[offset..offset + length].to_vec());
+        }
+        let decoded = blosc_decompress_bytes(src, nbytes, 1)
+            .map_err(|err: BloscError| CodecError::Other(err.to_string()))?;
+        return Ok(decoded[offset..offset + length].to_vec());
+    };
+
+    // Decode whole items spanning the byte range
+    // blosc_getitem cannot retrieve the bytes after the last whole item, so they are copied from the final block
+    let item_start = offset / typesize;
+    let item_end = (offset + length).div_ceil(typesize);
+    let item_end_whole = item_end.min(nbytes / typesize);
+    let mut dest = if item_end_whole > item_start {
+        blosc_getitems(src, item_start, item_end_whole - item_start, typesize)?
+    } else {
+        Vec::new()
+    };
+    if item_end > item_end_whole {
+        let tail_offset = item_end_whole * typesize;
+        if let Some(payload) = blosc_memcpyed_payload(src) {
+            dest.extend_from_slice(&payload[tail_offset..]);
+        } else {
+            let header = BloscHeader::new(src).ok_or_else(invalid)?;
+            let blocksize = (header.blocksize() as usize).max(1);
+            let block = (nbytes - 1) / blocksize;
+            let block_bytes = blosc_decompress_block(src, &header, block)?;
+            let block_tail = tail_offset
+                .checked_sub(block * blocksize)
+                .and_then(|block_tail| block_bytes.get(block_tail..))
+                .ok_or_else(invalid)?;
+            dest.extend_from_slice(block_tail);
+        }
+    }
+
+    let dest_offset = offset - item_start * typesize;
+    if dest_offset == 0 && length == dest.len() {
+        Ok(dest)
+    } else {
+        Ok(dest[dest_offset..dest_offset + length].to_vec())
+    }
+}
+
+/// Decompress `nitems` items starting at item `item_start` of a `blosc` encoded value `src` with `blosc_getitem`.
+///
+/// # Safety
+///
+/// Validate first
+fn blosc_getitems(
+    src: &[u8],
+    item_start: usize,
+    nitems: usize,
     typesize: usize,
-) -> Result<Vec<u8>, BloscError> {
-    let start = i32::try_from(offset / typesize).unwrap();
-    let nitems = i32::try_from(length / typesize).unwrap();
-    let mut dest: Vec<u8> = Vec::with_capacity(length);
+) -> Result<Vec<u8>, CodecError> {
+    let (Ok(start), Ok(nitems)) = (i32::try_from(item_start), i32::try_from(nitems)) else {
+        return Err(CodecError::Other(format!(
+            "blosc partial decode of {nitems} items from item {item_start} exceeds the supported number of items"
+        )));
+    };
+    #[allow(clippy::cast_sign_loss)]
+    let items_length = nitems as usize * typesize;
+    let mut dest: Vec<u8> = Vec::with_capacity(items_length);
     let destsize = unsafe {
         blosc_getitem(
             src.as_ptr().cast::<c_void>(),
@@ -221,32 +507,204 @@ fn blosc_decompress_bytes_partial(
             dest.as_mut_ptr().cast::<c_void>(),
         )
     };
-    if destsize <= 0 {
-        Err(BloscError::from(format!(
+    if usize::try_from(destsize).ok() != Some(items_length) {
+        return Err(CodecError::Other(format!(
             "blosc_getitem(src: len {}, start: {start}, nitems: {nitems}) -> {destsize} (failure)",
             src.len()
-        )))
-    } else {
-        unsafe {
-            #[allow(clippy::cast_sign_loss)]
-            dest.set_len(destsize as usize);
+        )));
+    }
+    unsafe {
+        dest.set_len(items_length);
+    }
+    Ok(dest)
+}
+
+/// Decompress the block with index `block` of a compressed (not memcpyed) `blosc` encoded value `src` with `header`.
+///
+/// A standalone `blosc` value holding only that block is assembled and decompressed, so memory usage is bounded by the block size.
+/// Its header records the length of the block as both the decoded size and the block size.
+/// A final block shorter than the block size is always compressed without splitting, so the `dont_split` flag is set for it.
+///
+/// # Safety
+///
+/// Validate first
+fn blosc_decompress_block(
+    src: &[u8],
+    header: &BloscHeader,
+    block: usize,
+) -> Result<Vec<u8>, CodecError> {
+    const BLOSC_DONT_SPLIT: u8 = 0x10;
+    let invalid = || CodecError::from("blosc encoded value is invalid");
+    let nbytes = header.nbytes() as usize;
+    let blocksize = header.blocksize() as usize;
+    let cbytes = (header.cbytes() as usize).min(src.len());
+    if blocksize == 0 {
+        return Err(invalid());
+    }
+    let nblocks = nbytes.div_ceil(blocksize);
+    let block_offset = block * blocksize;
+    if block >= nblocks {
+        return Err(invalid());
+    }
+    let block_length = blocksize.min(nbytes - block_offset);
+
+    // The compressed block extends from its start to the start of the next block in the buffer (not necessarily the next block index)
+    let bstarts_end = BLOSC_MIN_HEADER_LENGTH + nblocks * std::mem::size_of::<i32>();
+    let bstarts: Vec<usize> = src
+        .get(BLOSC_MIN_HEADER_LENGTH..bstarts_end)
+        .ok_or_else(invalid)?
+        .chunks_exact(std::mem::size_of::<i32>())
+        .map(|bstart| u32::from_le_bytes(bstart.try_into().unwrap()) as usize)
+        .collect();
+    let block_start = bstarts[block];
+    let block_end = bstarts
+        .iter()
+        .copied()
+        .filter(|&bstart| bstart > block_start)
+        .min()
+        .unwrap_or(cbytes);
+    if block_start < bstarts_end || block_end > cbytes {
+        return Err(invalid());
+    }
+    let block_src = &src[block_start..block_end];
+
+    let mut flags = header.flags();
+    if block_length < blocksize {
+        flags |= BLOSC_DONT_SPLIT;
+    }
+    let block_length_u32 = u32::try_from(block_length).unwrap();
+    let block_start_u32 =
+        u32::try_from(BLOSC_MIN_HEADER_LENGTH + std::mem::size_of::<i32>()).unwrap();
+    let block_cbytes = BLOSC_MIN_HEADER_LENGTH + std::mem::size_of::<i32>() + block_src.len();
+    let mut block_value = Vec::with_capacity(block_cbytes);
+    block_value.extend_from_slice(&[
+        header.version(),
+        header.versionlz(),
+        flags,
+        header.typesize(),
+    ]);
+    block_value.extend_from_slice(&block_length_u32.to_le_bytes());
+    block_value.extend_from_slice(&block_length_u32.to_le_bytes());
+    block_value.extend_from_slice(
+        &u32::try_from(block_cbytes)
+            .map_err(|_| invalid())?
+            .to_le_bytes(),
+    );
+    block_value.extend_from_slice(&block_start_u32.to_le_bytes());
+    block_value.extend_from_slice(block_src);
+
+    blosc_validate(&block_value).ok_or_else(invalid)?;
+    blosc_decompress_bytes(&block_value, block_length, 1)
+        .map_err(|err: BloscError| CodecError::Other(err.to_string()))
+}
+
+/// Returns the block size in bytes of a `blosc` encoded value, as recorded in its header.
+///
+/// # Safety
+///
+/// Validate first
+fn blosc_blocksize(src: &[u8]) -> usize {
+    let mut uncompressed_bytes: usize = 0;
+    let mut cbytes: usize = 0;
+    let mut blocksize: usize = 0;
+    unsafe {
+        blosc_cbuffer_sizes(
+            src.as_ptr().cast::<c_void>(),
+            std::ptr::addr_of_mut!(uncompressed_bytes),
+            std::ptr::addr_of_mut!(cbytes),
+            std::ptr::addr_of_mut!(blocksize),
+        );
+    };
+    blocksize
+}
+
+/// An iterator over the decompressed blocks of a `blosc` encoded value.
+///
+/// Each block is decompressed independently when it is yielded (using [`blosc_decompress_bytes_partial`]), so memory usage is bounded by the block size rather than the decoded size.
+/// Every block has the block size recorded in the `blosc` header, except the final block which may be shorter.
+/// The concatenation of the blocks is the decoded value.
+#[derive(Debug, Clone)]
+pub struct BloscBlocks<'a> {
+    src: &'a [u8],
+    nbytes: usize,
+    blocksize: usize,
+    offset: usize,
+}
+
+impl<'a> BloscBlocks<'a> {
+    /// Create an iterator over the decompressed blocks of the `blosc` encoded value `src`.
+    ///
+    /// # Errors
+    /// Returns a [`CodecError`] if `src` is not a valid `blosc` encoded value.
+    pub fn new(src: &'a [u8]) -> Result<Self, CodecError> {
+        let invalid = || CodecError::from("blosc encoded value is invalid");
+        let nbytes = blosc_decoded_size(src).ok_or_else(invalid)?;
+        let blocksize = blosc_blocksize(src);
+        if blocksize == 0 && nbytes > 0 {
+            return Err(invalid());
         }
-        dest.shrink_to_fit();
-        Ok(dest)
+        Ok(Self {
+            src,
+            nbytes,
+            blocksize,
+            offset: 0,
+        })
+    }
+
+    /// Return the block size in bytes.
+    #[must_use]
+    pub const fn blocksize(&self) -> usize {
+        self.blocksize
+    }
+
+    /// Return the decoded size in bytes.
+    #[must_use]
+    pub const fn decoded_size(&self) -> usize {
+        self.nbytes
     }
 }
 
+impl Iterator for BloscBlocks<'_> {
+    type Item = Result<Vec<u8>, CodecError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.nbytes {
+            return None;
+        }
+        let length = std::cmp::min(self.blocksize, self.nbytes - self.offset);
+        let block = blosc_decompress_bytes_partial(
+            self.src,
+            &ByteRange::FromStart(self.offset as u64, Some(length as u64)),
+        );
+        self.offset += length;
+        if block.is_err() {
+            // Do not attempt to decode any subsequent blocks
+            self.offset = self.nbytes;
+        }
+        Some(block)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let num_blocks = (self.nbytes - self.offset).div_ceil(self.blocksize.max(1));
+        (num_blocks, Some(num_blocks))
+    }
+}
+
+impl ExactSizeIterator for BloscBlocks<'_> {}
+
+impl std::iter::FusedIterator for BloscBlocks<'_> {}
+
 #[cfg(test)]
 mod tests {
     use std::{borrow::Cow, sync::Arc};
 
     use crate::{
         array::{
-            codec::{BytesToBytesCodecTraits, CodecOptions},
+            codec::{BytesToBytesCodecTraits, CodecError, CodecOptions},
             ArrayRepresentation, BytesRepresentation, DataType, FillValue,
         },
         array_subset::ArraySubset,
-        byte_range::ByteRange,
+        byte_range::{extract_byte_ranges, ByteRange},
     };
 
     use super::*;
@@ -303,6 +761,56 @@ mod tests {
         assert_eq!(bytes, decoded.to_vec());
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_compressors() {
+        for (cname, compressor) in [
+            ("blosclz", BloscCompressor::BloscLZ),
+            ("lz4", BloscCompressor::LZ4),
+            ("lz4hc", BloscCompressor::LZ4HC),
+            ("snappy", BloscCompressor::Snappy),
+            ("zlib", BloscCompressor::Zlib),
+            ("zstd", BloscCompressor::Zstd),
+        ] {
+            let json = format!(
+                r#"{{"cname": "{cname}", "clevel": 5, "shuffle": "shuffle", "typesize": 2, "blocksize": 0}}"#
+            );
+            let configuration: BloscCodecConfiguration = serde_json::from_str(&json).unwrap();
+            let BloscCodecConfiguration::V1(configuration_v1) = &configuration;
+            assert_eq!(configuration_v1.cname, compressor);
+            if blosc_compressor_available(compressor) {
+                codec_blosc_round_trip(&json);
+            } else {
+                let err = BloscCodec::new_with_configuration(&configuration).unwrap_err();
+                assert!(err.to_string().contains("not available"));
+            }
+        }
+        assert!(blosc_compressor_available(BloscCompressor::BloscLZ));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_decoded_size() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let configuration: BloscCodecConfiguration = serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = BloscCodec::new_with_configuration(&configuration).unwrap();
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        assert_eq!(blosc_decoded_size(&encoded), Some(bytes.len()));
+        assert_eq!(blosc_decoded_size(&encoded[..8]), None);
+
+        // The decoded size must not exceed the decoded representation
+        assert!(codec
+            .decode(
+                encoded,
+                &BytesRepresentation::BoundedSize(bytes.len() as u64 - 1),
+                &CodecOptions::default()
+            )
+            .is_err());
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn codec_blosc_round_trip1() {
@@ -334,6 +842,242 @@ mod tests {
         codec_blosc_round_trip(json);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_decode_trailing_bytes() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: BloscCodecConfiguration = serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = BloscCodec::new_with_configuration(&configuration).unwrap();
+
+        let mut encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .to_vec();
+        encoded.extend_from_slice(&[0; 3]);
+
+        let options = CodecOptions::builder().allow_trailing_bytes(false).build();
+        assert!(matches!(
+            codec.decode(Cow::Borrowed(&encoded), &bytes_representation, &options),
+            Err(CodecError::TrailingBytes(3))
+        ));
+
+        let options = CodecOptions::builder().allow_trailing_bytes(true).build();
+        let decoded = codec
+            .decode(Cow::Borrowed(&encoded), &bytes_representation, &options)
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_header() {
+        let elements: Vec<u16> = (0..1024).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: BloscCodecConfiguration = serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = BloscCodec::new_with_configuration(&configuration).unwrap();
+        let mut encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .to_vec();
+
+        let header = BloscHeader::new(&encoded).unwrap();
+        assert_eq!(header.version(), BLOSC_VERSION_FORMAT);
+        assert!(header.is_version_supported());
+        assert_eq!(header.typesize(), 2);
+        assert_eq!(header.nbytes() as usize, bytes.len());
+        assert_eq!(header.cbytes() as usize, encoded.len());
+        assert!(header.shuffle());
+        assert!(!header.bitshuffle());
+        assert!(BloscHeader::new(&encoded[..15]).is_none());
+
+        // A newer format version is rejected
+        encoded[0] = BLOSC_VERSION_FORMAT + 1;
+        let header = BloscHeader::new(&encoded).unwrap();
+        assert!(!header.is_version_supported());
+        assert!(codec
+            .decode(
+                Cow::Borrowed(&encoded),
+                &bytes_representation,
+                &CodecOptions::default()
+            )
+            .is_err_and(|err| err.to_string().contains("format version")));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_decode_memcpyed_borrowed() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: BloscCodecConfiguration = serde_json::from_str(
+            r#"{"cname": "lz4", "clevel": 0, "shuffle": "noshuffle", "blocksize": 0}"#,
+        )
+        .unwrap();
+        let codec = BloscCodec::new_with_configuration(&configuration).unwrap();
+
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .to_vec();
+        let decoded = codec
+            .decode(
+                Cow::Borrowed(&encoded),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(matches!(decoded, Cow::Borrowed(_)));
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_decompress_bytes_partial_bounds() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let configuration: BloscCodecConfiguration = serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = BloscCodec::new_with_configuration(&configuration).unwrap();
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+
+        let decode = |byte_range| blosc_decompress_bytes_partial(&encoded, &byte_range);
+        assert_eq!(
+            decode(ByteRange::FromStart(4, Some(4))).unwrap(),
+            &bytes[4..8]
+        );
+        assert_eq!(
+            decode(ByteRange::FromStart(3, Some(3))).unwrap(),
+            &bytes[3..6]
+        );
+        assert_eq!(decode(ByteRange::Suffix(4)).unwrap(), &bytes[60..]);
+        assert!(decode(ByteRange::FromStart(64, None)).unwrap().is_empty());
+        assert!(matches!(
+            decode(ByteRange::FromStart(62, Some(4))),
+            Err(CodecError::InvalidByteRangeError(_))
+        ));
+        assert!(decode(ByteRange::FromStart(u64::MAX, Some(2))).is_err());
+        assert!(decode(ByteRange::FromStart(65, None)).is_err());
+        assert!(decode(ByteRange::Suffix(65)).is_err());
+        assert!(blosc_decompress_bytes_partial(&[0; 4], &ByteRange::FromStart(0, None)).is_err());
+        assert!(blosc_decompress_bytes_partial(&encoded[..20], &ByteRange::Suffix(2)).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_decompress_bytes_partial_typesize_override() {
+        let bytes: Vec<u8> = (0..64).collect();
+        for clevel in [0u8, 5] {
+            let codec = BloscCodec::new(
+                BloscCompressor::BloscLZ,
+                BloscCompressionLevel::try_from(clevel).unwrap(),
+                None,
+                BloscShuffleMode::NoShuffle,
+                Some(2),
+            )
+            .unwrap();
+            let mut encoded = codec
+                .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+                .unwrap()
+                .to_vec();
+            // Clear the typesize in the header
+            encoded[3] = 0;
+            assert_eq!(BloscHeader::new(&encoded).unwrap().typesize(), 0);
+
+            let byte_range = ByteRange::FromStart(3, Some(5));
+            assert!(blosc_decompress_bytes_partial(&encoded, &byte_range).is_err());
+            assert!(blosc_decompress_bytes_partial_opt(&encoded, &byte_range, Some(0)).is_err());
+            assert_eq!(
+                blosc_decompress_bytes_partial_opt(&encoded, &byte_range, Some(2)).unwrap(),
+                bytes[3..8]
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_blocks() {
+        // The decoded size is not a multiple of the blocksize or typesize
+        let bytes: Vec<u8> = (0..2002u32).map(|i| (i / 16) as u8).collect();
+        let codec = BloscCodec::new(
+            BloscCompressor::LZ4,
+            BloscCompressionLevel::try_from(5u8).unwrap(),
+            Some(256),
+            BloscShuffleMode::Shuffle,
+            Some(4),
+        )
+        .unwrap();
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        assert!(!BloscHeader::new(&encoded).unwrap().memcpyed());
+
+        let blocks = BloscBlocks::new(&encoded).unwrap();
+        assert_eq!(blocks.blocksize(), 256);
+        assert_eq!(blocks.decoded_size(), 2002);
+        assert_eq!(blocks.len(), 8);
+        let blocks: Vec<Vec<u8>> = blocks.map(Result::unwrap).collect();
+        assert!(blocks[..7].iter().all(|block| block.len() == 256));
+        assert_eq!(blocks[7].len(), 2002 - 7 * 256);
+        assert_eq!(blocks.concat(), bytes);
+
+        assert!(BloscBlocks::new(&encoded[..8]).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_partial_decode_partial_item() {
+        // The bytes after the last whole item are decoded from the final block only
+        for (blocksize, shuffle_mode) in [
+            (256, BloscShuffleMode::Shuffle),
+            (1024, BloscShuffleMode::Shuffle),
+            (1024, BloscShuffleMode::BitShuffle),
+            (1024, BloscShuffleMode::NoShuffle),
+        ] {
+            let bytes: Vec<u8> = (0..4002u32).map(|i| (i / 16) as u8).collect();
+            let codec = BloscCodec::new(
+                BloscCompressor::LZ4,
+                BloscCompressionLevel::try_from(5u8).unwrap(),
+                Some(blocksize),
+                shuffle_mode,
+                Some(4),
+            )
+            .unwrap();
+            let encoded = codec
+                .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+                .unwrap();
+            let header = BloscHeader::new(&encoded).unwrap();
+            assert!(!header.memcpyed());
+            assert_eq!(header.blocksize() as usize, blocksize);
+
+            let last_block = (bytes.len() - 1) / blocksize;
+            assert_eq!(
+                blosc_decompress_block(&encoded, &header, last_block).unwrap(),
+                bytes[last_block * blocksize..]
+            );
+            for byte_range in [
+                ByteRange::Suffix(1),
+                ByteRange::Suffix(2),
+                ByteRange::FromStart(3990, Some(12)),
+                ByteRange::FromStart(3999, None),
+            ] {
+                assert_eq!(
+                    blosc_decompress_bytes_partial(&encoded, &byte_range).unwrap(),
+                    extract_byte_ranges(&bytes, &[byte_range])
+                        .unwrap()
+                        .remove(0)
+                );
+            }
+        }
+    }
+
     #[test]
     #[should_panic]
     #[cfg_attr(miri, ignore)]
@@ -387,6 +1131,45 @@ mod tests {
         assert_eq!(answer, decoded);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_blosc_partial_decode_empty_regions() {
+        let elements: Vec<u16> = (0..8).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: BloscCodecConfiguration =
+            serde_json::from_str(JSON_VALID2).unwrap();
+        let codec = Arc::new(BloscCodec::new_with_configuration(&codec_configuration).unwrap());
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        let partial_decoder = codec
+            .partial_decoder(
+                Arc::new(std::io::Cursor::new(encoded)),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(
+                &[
+                    ByteRange::FromStart(2, Some(2)),
+                    ByteRange::FromStart(4, Some(0)),
+                    ByteRange::Suffix(0),
+                    ByteRange::FromStart(0, Some(2)),
+                ],
+                &CodecOptions::default(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.len(), 4);
+        assert_eq!(decoded[0], bytes[2..4]);
+        assert!(decoded[1].is_empty());
+        assert!(decoded[2].is_empty());
+        assert_eq!(decoded[3], bytes[0..2]);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     #[cfg_attr(miri, ignore)]
@@ -1,12 +1,13 @@
-use std::{borrow::Cow, ffi::c_char, sync::Arc};
+use std::{borrow::Cow, sync::Arc};
 
-use blosc_sys::{blosc_get_complib_info, BLOSC_MAX_OVERHEAD};
+use blosc_sys::BLOSC_MAX_OVERHEAD;
 
 use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes,
     },
@@ -18,9 +19,10 @@ use crate::{
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
 use super::{
-    blosc_compress_bytes, blosc_decompress_bytes, blosc_partial_decoder, blosc_validate,
-    compressor_as_cstr, BloscCodecConfiguration, BloscCodecConfigurationV1, BloscCompressionLevel,
-    BloscCompressor, BloscError, BloscShuffleMode, IDENTIFIER,
+    super::strip_trailing_bytes, blosc_cbytes, blosc_compress_bytes, blosc_compressor_available,
+    blosc_decoded_size, blosc_decompress_bytes, blosc_memcpyed_payload, blosc_partial_decoder,
+    BloscCodecConfiguration, BloscCodecConfigurationV1, BloscCompressionLevel, BloscCompressor,
+    BloscError, BloscHeader, BloscShuffleMode, BLOSC_VERSION_FORMAT, IDENTIFIER,
 };
 
 /// A `blosc` codec implementation.
@@ -42,7 +44,7 @@ impl BloscCodec {
     /// # Errors
     ///
     /// Returns [`PluginCreateError`] if
-    ///  - the compressor is not supported, or
+    ///  - the compressor is not available in the linked `blosc` library (see [`blosc_compressor_available`](super::blosc_compressor_available)), or
     ///  - `typesize` is [`None`] and shuffling is enabled.
     pub fn new(
         cname: BloscCompressor,
@@ -60,16 +62,9 @@ impl BloscCodec {
         }
 
         // Check that the compressor is available
-        let support = unsafe {
-            blosc_get_complib_info(
-                compressor_as_cstr(cname).cast::<c_char>(),
-                std::ptr::null_mut(),
-                std::ptr::null_mut(),
-            )
-        };
-        if support < 0 {
+        if !blosc_compressor_available(cname) {
             return Err(PluginCreateError::from(format!(
-                "compressor {cname:?} is not supported."
+                "blosc compressor {cname:?} is not available in the linked blosc library"
             )));
         }
 
@@ -119,14 +114,44 @@ impl BloscCodec {
         .map_err(|err: BloscError| CodecError::Other(err.to_string()))
     }
 
-    fn do_decode(encoded_value: &[u8], n_threads: usize) -> Result<Vec<u8>, CodecError> {
-        blosc_validate(encoded_value).map_or_else(
-            || Err(CodecError::from("blosc encoded value is invalid")),
-            |destsize| {
-                blosc_decompress_bytes(encoded_value, destsize, n_threads)
-                    .map_err(|e| CodecError::from(e.to_string()))
-            },
-        )
+    fn do_decode<'a>(
+        encoded_value: &'a [u8],
+        decoded_representation: &BytesRepresentation,
+        n_threads: usize,
+        options: &CodecOptions,
+    ) -> Result<Cow<'a, [u8]>, CodecError> {
+        let encoded_value = match blosc_cbytes(encoded_value) {
+            Some(cbytes) => strip_trailing_bytes(encoded_value, cbytes, options)?,
+            None => encoded_value,
+        };
+        if let Some(header) = BloscHeader::new(encoded_value) {
+            if !header.is_version_supported() {
+                return Err(CodecError::Other(format!(
+                    "blosc format version {} is newer than the latest supported version {BLOSC_VERSION_FORMAT}",
+                    header.version()
+                )));
+            }
+        }
+        let destsize = blosc_decoded_size(encoded_value)
+            .ok_or_else(|| CodecError::from("blosc encoded value is invalid"))?;
+        match decoded_representation {
+            BytesRepresentation::FixedSize(size) | BytesRepresentation::BoundedSize(size)
+                if destsize as u64 > *size =>
+            {
+                return Err(CodecError::Other(format!(
+                    "blosc decoded size {destsize} exceeds the expected decoded size {size}"
+                )));
+            }
+            _ => {}
+        }
+        if let Some(payload) = blosc_memcpyed_payload(encoded_value) {
+            // Data stored verbatim can be borrowed from the input
+            return Ok(Cow::Borrowed(payload));
+        }
+        Ok(Cow::Owned(
+            blosc_decompress_bytes(encoded_value, destsize, n_threads)
+                .map_err(|e| CodecError::from(e.to_string()))?,
+        ))
     }
 }
 
@@ -155,6 +180,10 @@ impl CodecTraits for BloscCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -188,8 +217,8 @@ impl BytesToBytesCodecTraits for BloscCodec {
     fn decode<'a>(
         &self,
         encoded_value: RawBytes<'a>,
-        _decoded_representation: &BytesRepresentation,
-        _options: &CodecOptions,
+        decoded_representation: &BytesRepresentation,
+        options: &CodecOptions,
     ) -> Result<RawBytes<'a>, CodecError> {
         // let n_threads = std::cmp::min(
         //     options.concurrent_limit(),
@@ -197,7 +226,15 @@ impl BytesToBytesCodecTraits for BloscCodec {
         // )
         // .get();
         let n_threads = 1;
-        Ok(Cow::Owned(Self::do_decode(&encoded_value, n_threads)?))
+        match encoded_value {
+            Cow::Borrowed(encoded_value) => {
+                Self::do_decode(encoded_value, decoded_representation, n_threads, options)
+            }
+            Cow::Owned(encoded_value) => Ok(Cow::Owned(
+                Self::do_decode(&encoded_value, decoded_representation, n_threads, options)?
+                    .into_owned(),
+            )),
+        }
     }
 
     fn partial_decoder(
@@ -208,6 +245,7 @@ impl BytesToBytesCodecTraits for BloscCodec {
     ) -> Result<Arc<dyn BytesPartialDecoderTraits>, CodecError> {
         Ok(Arc::new(blosc_partial_decoder::BloscPartialDecoder::new(
             input_handle,
+            self.typesize,
         )))
     }
 
@@ -234,7 +272,7 @@ impl BytesToBytesCodecTraits for BloscCodec {
         _parallel: &CodecOptions,
     ) -> Result<Arc<dyn AsyncBytesPartialDecoderTraits>, CodecError> {
         Ok(Arc::new(
-            blosc_partial_decoder::AsyncBloscPartialDecoder::new(input_handle),
+            blosc_partial_decoder::AsyncBloscPartialDecoder::new(input_handle, self.typesize),
         ))
     }
 
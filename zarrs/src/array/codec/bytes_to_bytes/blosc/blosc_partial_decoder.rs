@@ -2,10 +2,7 @@ use std::{borrow::Cow, sync::Arc};
 
 use crate::{
     array::{
-        codec::{
-            bytes_to_bytes::blosc::blosc_nbytes, BytesPartialDecoderTraits, CodecError,
-            CodecOptions,
-        },
+        codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
         RawBytes,
     },
     byte_range::ByteRange,
@@ -14,16 +11,42 @@ use crate::{
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
-use super::{blosc_decompress_bytes_partial, blosc_typesize, blosc_validate};
+use super::{super::strip_trailing_bytes, blosc_cbytes, blosc_decompress_bytes_partial_opt};
+
+/// Decode the `byte_range` of a `blosc` encoded value.
+///
+/// An empty byte range is decoded without calling into `blosc`, so the decoded regions still align with the requested regions.
+/// `typesize` overrides a typesize of 0 (unknown) in the `blosc` header.
+fn blosc_decode_region(
+    encoded_value: &[u8],
+    byte_range: &ByteRange,
+    typesize: Option<usize>,
+) -> Result<RawBytes<'static>, CodecError> {
+    if matches!(
+        byte_range,
+        ByteRange::FromStart(_, Some(0)) | ByteRange::Suffix(0)
+    ) {
+        Ok(Cow::Owned(Vec::new()))
+    } else {
+        blosc_decompress_bytes_partial_opt(encoded_value, byte_range, typesize).map(Cow::Owned)
+    }
+}
 
 /// Partial decoder for the `blosc` codec.
 pub(crate) struct BloscPartialDecoder<'a> {
     input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+    typesize: Option<usize>,
 }
 
 impl<'a> BloscPartialDecoder<'a> {
-    pub(crate) fn new(input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>) -> Self {
-        Self { input_handle }
+    pub(crate) fn new(
+        input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+        typesize: Option<usize>,
+    ) -> Self {
+        Self {
+            input_handle,
+            typesize,
+        }
     }
 }
 
@@ -38,29 +61,16 @@ impl BytesPartialDecoderTraits for BloscPartialDecoder<'_> {
             return Ok(None);
         };
 
-        if let Some(_destsize) = blosc_validate(&encoded_value) {
-            let nbytes = blosc_nbytes(&encoded_value);
-            let typesize = blosc_typesize(&encoded_value);
-            if let (Some(nbytes), Some(typesize)) = (nbytes, typesize) {
-                let mut decoded_byte_ranges = Vec::with_capacity(decoded_regions.len());
-                for byte_range in decoded_regions {
-                    let start = usize::try_from(byte_range.start(nbytes as u64)).unwrap();
-                    let end = usize::try_from(byte_range.end(nbytes as u64)).unwrap();
-                    decoded_byte_ranges.push(
-                        blosc_decompress_bytes_partial(
-                            &encoded_value,
-                            start,
-                            end - start,
-                            typesize,
-                        )
-                        .map(Cow::Owned)
-                        .map_err(|err| CodecError::from(err.to_string()))?,
-                    );
-                }
-                return Ok(Some(decoded_byte_ranges));
-            }
-        }
-        Err(CodecError::from("blosc encoded value is invalid"))
+        let encoded_value = match blosc_cbytes(&encoded_value) {
+            Some(cbytes) => strip_trailing_bytes(&encoded_value, cbytes, options)?,
+            None => &encoded_value,
+        };
+
+        decoded_regions
+            .iter()
+            .map(|byte_range| blosc_decode_region(encoded_value, byte_range, self.typesize))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
     }
 }
 
@@ -68,12 +78,19 @@ impl BytesPartialDecoderTraits for BloscPartialDecoder<'_> {
 /// Asynchronous partial decoder for the `blosc` codec.
 pub(crate) struct AsyncBloscPartialDecoder {
     input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+    typesize: Option<usize>,
 }
 
 #[cfg(feature = "async")]
 impl AsyncBloscPartialDecoder {
-    pub(crate) fn new(input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>) -> Self {
-        Self { input_handle }
+    pub(crate) fn new(
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        typesize: Option<usize>,
+    ) -> Self {
+        Self {
+            input_handle,
+            typesize,
+        }
     }
 }
 
@@ -90,28 +107,15 @@ impl AsyncBytesPartialDecoderTraits for AsyncBloscPartialDecoder {
             return Ok(None);
         };
 
-        if let Some(_destsize) = blosc_validate(&encoded_value) {
-            let nbytes = blosc_nbytes(&encoded_value);
-            let typesize = blosc_typesize(&encoded_value);
-            if let (Some(nbytes), Some(typesize)) = (nbytes, typesize) {
-                let mut decoded_byte_ranges = Vec::with_capacity(decoded_regions.len());
-                for byte_range in decoded_regions {
-                    let start = usize::try_from(byte_range.start(nbytes as u64)).unwrap();
-                    let end = usize::try_from(byte_range.end(nbytes as u64)).unwrap();
-                    decoded_byte_ranges.push(
-                        blosc_decompress_bytes_partial(
-                            &encoded_value,
-                            start,
-                            end - start,
-                            typesize,
-                        )
-                        .map(Cow::Owned)
-                        .map_err(|err| CodecError::from(err.to_string()))?,
-                    );
-                }
-                return Ok(Some(decoded_byte_ranges));
-            }
-        }
-        Err(CodecError::from("blosc encoded value is invalid"))
+        let encoded_value = match blosc_cbytes(&encoded_value) {
+            Some(cbytes) => strip_trailing_bytes(&encoded_value, cbytes, options)?,
+            None => &encoded_value,
+        };
+
+        decoded_regions
+            .iter()
+            .map(|byte_range| blosc_decode_region(encoded_value, byte_range, self.typesize))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some)
     }
 }
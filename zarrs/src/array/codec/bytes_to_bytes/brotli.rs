@@ -0,0 +1,240 @@
+//! The `brotli` bytes to bytes codec.
+//!
+//! <div class="warning">
+//! This codec is experimental and is incompatible with other Zarr V3 implementations.
+//! </div>
+//!
+//! This codec requires the `brotli` feature, which is disabled by default.
+//!
+//! Applies [Brotli](https://datatracker.ietf.org/doc/html/rfc7932) compression.
+//!
+//! See [`BrotliCodecConfigurationV1`] for example `JSON` metadata.
+
+mod brotli_codec;
+mod brotli_partial_decoder;
+
+use std::{io::Read, sync::Arc};
+
+use crate::{
+    array::codec::{Codec, CodecPlugin},
+    config::global_config,
+    metadata::v3::{array::codec::brotli, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use crate::metadata::v3::array::codec::brotli::{
+    BrotliCodecConfiguration, BrotliCodecConfigurationV1,
+};
+
+pub use self::brotli_codec::BrotliCodec;
+
+pub use brotli::IDENTIFIER;
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_brotli, create_codec_brotli)
+}
+
+fn is_name_brotli(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+        || name
+            == global_config()
+                .experimental_codec_names()
+                .get(IDENTIFIER)
+                .expect("experimental codec identifier in global map")
+}
+
+pub(crate) fn create_codec_brotli(metadata: &MetadataV3) -> Result<Codec, PluginCreateError> {
+    let configuration: BrotliCodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Arc::new(BrotliCodec::new_with_configuration(&configuration)?);
+    Ok(Codec::BytesToBytes(codec))
+}
+
+/// The internal buffer size of the `brotli` compressor and decompressor.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+/// Decompress a `brotli` encoded value.
+fn brotli_decompress(encoded_value: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let mut decompressed = Vec::new();
+    ::brotli::Decompressor::new(encoded_value, BROTLI_BUFFER_SIZE)
+        .read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, sync::Arc};
+
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            ArrayRepresentation, BytesRepresentation, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    const JSON_VALID1: &str = r#"
+{
+    "quality": 9,
+    "window_size": 22
+}"#;
+
+    const JSON_INVALID_QUALITY: &str = r#"
+{
+    "quality": 12,
+    "window_size": 22
+}"#;
+
+    const JSON_INVALID_WINDOW_SIZE: &str = r#"
+{
+    "quality": 9,
+    "window_size": 25
+}"#;
+
+    #[test]
+    fn codec_brotli_configuration_invalid() {
+        let codec_configuration: BrotliCodecConfiguration =
+            serde_json::from_str(JSON_INVALID_QUALITY).unwrap();
+        assert!(BrotliCodec::new_with_configuration(&codec_configuration).is_err());
+        let codec_configuration: BrotliCodecConfiguration =
+            serde_json::from_str(JSON_INVALID_WINDOW_SIZE).unwrap();
+        assert!(BrotliCodec::new_with_configuration(&codec_configuration).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_brotli_round_trip1() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: BrotliCodecConfiguration =
+            serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = BrotliCodec::new_with_configuration(&codec_configuration).unwrap();
+
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded = codec
+            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_brotli_round_trip_quality() {
+        let bytes: Vec<u8> = "the quick brown fox jumps over the lazy dog "
+            .repeat(64)
+            .into_bytes();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        for quality in [0, 1, 5, 9, 11] {
+            let codec = BrotliCodec::new(quality, 22).unwrap();
+            let encoded = codec
+                .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+                .unwrap();
+            assert!(encoded.len() < bytes.len());
+            let decoded = codec
+                .decode(encoded, &bytes_representation, &CodecOptions::default())
+                .unwrap();
+            assert_eq!(bytes, decoded.to_vec());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_brotli_partial_decode() {
+        let array_representation =
+            ArrayRepresentation::new(vec![2, 2, 2], DataType::UInt16, FillValue::from(0u16))
+                .unwrap();
+        let data_type_size = array_representation.data_type().fixed_size().unwrap();
+        let array_size = array_representation.num_elements_usize() * data_type_size;
+        let bytes_representation = BytesRepresentation::FixedSize(array_size as u64);
+
+        let elements: Vec<u16> = (0..array_representation.num_elements() as u16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Arc::new(BrotliCodec::new(5, 22).unwrap());
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions: Vec<ByteRange> = ArraySubset::new_with_ranges(&[0..2, 1..2, 0..1])
+            .byte_ranges(array_representation.shape(), data_type_size)
+            .unwrap();
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode_concat(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let decoded: Vec<u16> = decoded
+            .to_vec()
+            .chunks_exact(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let answer: Vec<u16> = vec![2, 6];
+        assert_eq!(answer, decoded);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn codec_brotli_async_partial_decode() {
+        let array_representation =
+            ArrayRepresentation::new(vec![2, 2, 2], DataType::UInt16, FillValue::from(0u16))
+                .unwrap();
+        let data_type_size = array_representation.data_type().fixed_size().unwrap();
+        let array_size = array_representation.num_elements_usize() * data_type_size;
+        let bytes_representation = BytesRepresentation::FixedSize(array_size as u64);
+
+        let elements: Vec<u16> = (0..array_representation.num_elements() as u16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Arc::new(BrotliCodec::new(5, 22).unwrap());
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions: Vec<ByteRange> = ArraySubset::new_with_ranges(&[0..2, 1..2, 0..1])
+            .byte_ranges(array_representation.shape(), data_type_size)
+            .unwrap();
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode_concat(&decoded_regions, &CodecOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let decoded: Vec<u16> = decoded
+            .to_vec()
+            .chunks_exact(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let answer: Vec<u16> = vec![2, 6];
+        assert_eq!(answer, decoded);
+    }
+}
@@ -0,0 +1,205 @@
+use std::{borrow::Cow, io::Write, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
+        },
+        ArrayMetadataOptions, BytesRepresentation, RawBytes,
+    },
+    config::global_config,
+    metadata::v3::MetadataV3,
+    plugin::PluginCreateError,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    brotli_decompress, brotli_partial_decoder, BrotliCodecConfiguration,
+    BrotliCodecConfigurationV1, BROTLI_BUFFER_SIZE,
+};
+
+/// The maximum `brotli` quality.
+const BROTLI_MAX_QUALITY: u32 = 11;
+
+/// The minimum `brotli` window size (`lgwin`).
+const BROTLI_MIN_WINDOW_SIZE: u32 = 10;
+
+/// The maximum `brotli` window size (`lgwin`).
+const BROTLI_MAX_WINDOW_SIZE: u32 = 24;
+
+/// A `brotli` codec implementation.
+#[derive(Clone, Debug)]
+pub struct BrotliCodec {
+    quality: u32,
+    window_size: u32,
+}
+
+impl BrotliCodec {
+    /// Create a new `brotli` codec.
+    ///
+    /// # Errors
+    /// Returns an error if `quality` is not between 0-11 or `window_size` is not between 10-24.
+    pub fn new(quality: u32, window_size: u32) -> Result<Self, PluginCreateError> {
+        if quality > BROTLI_MAX_QUALITY {
+            return Err(PluginCreateError::from(format!(
+                "brotli quality {quality} is invalid, must be 0-{BROTLI_MAX_QUALITY}"
+            )));
+        }
+        if !(BROTLI_MIN_WINDOW_SIZE..=BROTLI_MAX_WINDOW_SIZE).contains(&window_size) {
+            return Err(PluginCreateError::from(format!(
+                "brotli window size {window_size} is invalid, must be {BROTLI_MIN_WINDOW_SIZE}-{BROTLI_MAX_WINDOW_SIZE}"
+            )));
+        }
+        Ok(Self {
+            quality,
+            window_size,
+        })
+    }
+
+    /// Create a new `brotli` codec from configuration.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration is not supported.
+    pub fn new_with_configuration(
+        configuration: &BrotliCodecConfiguration,
+    ) -> Result<Self, PluginCreateError> {
+        let BrotliCodecConfiguration::V1(configuration) = configuration;
+        Self::new(configuration.quality, configuration.window_size)
+    }
+
+    /// Return the compression quality.
+    #[must_use]
+    pub const fn quality(&self) -> u32 {
+        self.quality
+    }
+
+    /// Return the window size (`lgwin`).
+    #[must_use]
+    pub const fn window_size(&self) -> u32 {
+        self.window_size
+    }
+}
+
+impl CodecTraits for BrotliCodec {
+    fn create_metadata_opt(&self, _options: &ArrayMetadataOptions) -> Option<MetadataV3> {
+        let configuration = BrotliCodecConfigurationV1 {
+            quality: self.quality,
+            window_size: self.window_size,
+        };
+        Some(
+            MetadataV3::new_with_serializable_configuration(
+                global_config()
+                    .experimental_codec_names()
+                    .get(super::IDENTIFIER)
+                    .expect("experimental codec identifier in global map"),
+                &configuration,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for BrotliCodec {
+    fn dynamic(self: Arc<Self>) -> Arc<dyn BytesToBytesCodecTraits> {
+        self as Arc<dyn BytesToBytesCodecTraits>
+    }
+
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        // brotli compression is single threaded
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode<'a>(
+        &self,
+        decoded_value: RawBytes<'a>,
+        _options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        let mut encoder = brotli::CompressorWriter::new(
+            Vec::new(),
+            BROTLI_BUFFER_SIZE,
+            self.quality,
+            self.window_size,
+        );
+        encoder.write_all(&decoded_value)?;
+        Ok(Cow::Owned(encoder.into_inner()))
+    }
+
+    fn decode<'a>(
+        &self,
+        encoded_value: RawBytes<'a>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        Ok(Cow::Owned(brotli_decompress(&encoded_value)?))
+    }
+
+    fn partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(brotli_partial_decoder::BrotliPartialDecoder::new(
+            input_handle,
+        )))
+    }
+
+    fn partial_encoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        output_handle: Arc<dyn BytesPartialEncoderTraits>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialEncoderTraits>, CodecError> {
+        Ok(Arc::new(BytesPartialEncoderDefault::new(
+            input_handle,
+            output_handle,
+            *decoded_representation,
+            self,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        _decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn AsyncBytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(
+            brotli_partial_decoder::AsyncBrotliPartialDecoder::new(input_handle),
+        ))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        decoded_representation
+            .size()
+            .map_or(BytesRepresentation::UnboundedSize, |size| {
+                // BrotliEncoderMaxCompressedSize
+                BytesRepresentation::BoundedSize(size + 4 * (size >> 14) + 6)
+            })
+    }
+}
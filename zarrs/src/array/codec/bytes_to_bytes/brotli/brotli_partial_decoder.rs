@@ -0,0 +1,84 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+        RawBytes,
+    },
+    byte_range::{extract_byte_ranges, ByteRange},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::brotli_decompress;
+
+/// Partial decoder for the `brotli` codec.
+pub(crate) struct BrotliPartialDecoder<'a> {
+    input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+}
+
+impl<'a> BrotliPartialDecoder<'a> {
+    pub(crate) fn new(input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>) -> Self {
+        Self { input_handle }
+    }
+}
+
+impl BytesPartialDecoderTraits for BrotliPartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = brotli_decompress(&encoded_value)?;
+        Ok(Some(
+            extract_byte_ranges(&decompressed, decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `brotli` codec.
+pub(crate) struct AsyncBrotliPartialDecoder {
+    input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncBrotliPartialDecoder {
+    pub(crate) fn new(input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>) -> Self {
+        Self { input_handle }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncBrotliPartialDecoder {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = brotli_decompress(&encoded_value)?;
+        Ok(Some(
+            extract_byte_ranges(&decompressed, decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+        ))
+    }
+}
@@ -8,7 +8,8 @@ use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes,
     },
@@ -70,6 +71,10 @@ impl CodecTraits for Bz2Codec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -98,6 +98,40 @@ mod tests {
         assert_eq!(checksum, &[20, 133, 9, 65]);
     }
 
+    #[test]
+    fn codec_crc32c_decode_borrowed() {
+        let bytes: Vec<u8> = (0..6).collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+        let codec = Crc32cCodec::new();
+
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .into_owned();
+
+        // A borrowed input is decoded without copying
+        let decoded = codec
+            .decode(
+                Cow::Borrowed(&encoded),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(matches!(decoded, Cow::Borrowed(_)));
+        assert_eq!(decoded.as_ref(), bytes.as_slice());
+
+        // An owned input is truncated in place
+        let decoded = codec
+            .decode(
+                Cow::Owned(encoded),
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert!(matches!(decoded, Cow::Owned(_)));
+        assert_eq!(decoded.as_ref(), bytes.as_slice());
+    }
+
     #[test]
     fn codec_crc32c_partial_decode() {
         let elements: Vec<u8> = (0..32).collect();
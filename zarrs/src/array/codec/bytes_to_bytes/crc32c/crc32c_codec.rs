@@ -4,7 +4,8 @@ use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes,
     },
@@ -50,6 +51,10 @@ impl CodecTraits for Crc32cCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         false
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new(true, false, input_len + CHECKSUM_SIZE)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -91,8 +96,15 @@ impl BytesToBytesCodecTraits for Crc32cCodec {
                     return Err(CodecError::InvalidChecksum);
                 }
             }
-            let decoded_value = encoded_value[..encoded_value.len() - CHECKSUM_SIZE].to_vec();
-            Ok(Cow::Owned(decoded_value))
+            // Strip the checksum without copying the decoded bytes
+            let decoded_len = encoded_value.len() - CHECKSUM_SIZE;
+            match encoded_value {
+                Cow::Borrowed(encoded_value) => Ok(Cow::Borrowed(&encoded_value[..decoded_len])),
+                Cow::Owned(mut encoded_value) => {
+                    encoded_value.truncate(decoded_len);
+                    Ok(Cow::Owned(encoded_value))
+                }
+            }
         } else {
             Err(CodecError::Other(
                 "crc32c decoder expects a 32 bit input".to_string(),
@@ -5,7 +5,7 @@ use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes, RecommendedConcurrency,
     },
@@ -62,6 +62,10 @@ impl CodecTraits for GDeflateCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
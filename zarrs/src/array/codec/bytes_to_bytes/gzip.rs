@@ -7,7 +7,9 @@
 mod gzip_codec;
 mod gzip_partial_decoder;
 
-use std::sync::Arc;
+use std::{io::Read, sync::Arc};
+
+use flate2::bufread::GzDecoder;
 
 pub use crate::metadata::v3::array::codec::gzip::{
     GzipCodecConfiguration, GzipCodecConfigurationV1, GzipCompressionLevel,
@@ -16,11 +18,13 @@ pub use crate::metadata::v3::array::codec::gzip::{
 pub use gzip_codec::GzipCodec;
 
 use crate::{
-    array::codec::{Codec, CodecPlugin},
+    array::codec::{Codec, CodecError, CodecOptions, CodecPlugin},
     metadata::v3::{array::codec::gzip, MetadataV3},
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
 
+use super::strip_trailing_bytes;
+
 pub use gzip::IDENTIFIER;
 
 // Register the codec.
@@ -40,6 +44,36 @@ pub(crate) fn create_codec_gzip(metadata: &MetadataV3) -> Result<Codec, PluginCr
     Ok(Codec::BytesToBytes(codec))
 }
 
+/// Decompress a `gzip` encoded value.
+///
+/// Trailing bytes after the `gzip` member are handled according to [`CodecOptions::allow_trailing_bytes()`].
+fn gzip_decode(encoded_value: &[u8], options: &CodecOptions) -> Result<Vec<u8>, CodecError> {
+    let mut decoder = GzDecoder::new(encoded_value);
+    let mut decoded_value = Vec::new();
+    decoder.read_to_end(&mut decoded_value)?;
+    let stream_length = encoded_value.len() - decoder.into_inner().len();
+    strip_trailing_bytes(encoded_value, stream_length, options)?;
+    Ok(decoded_value)
+}
+
+/// Decompress the first `decoded_len` bytes of a `gzip` encoded value, or the entire value if `decoded_len` is [`None`].
+///
+/// Decompression stops once `decoded_len` bytes have been produced, so trailing bytes are only checked if `decoded_len` is [`None`].
+fn gzip_decode_prefix(
+    encoded_value: &[u8],
+    decoded_len: Option<u64>,
+    options: &CodecOptions,
+) -> Result<Vec<u8>, CodecError> {
+    let Some(decoded_len) = decoded_len else {
+        return gzip_decode(encoded_value, options);
+    };
+    let mut decoded_value = Vec::new();
+    GzDecoder::new(encoded_value)
+        .take(decoded_len)
+        .read_to_end(&mut decoded_value)?;
+    Ok(decoded_value)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{borrow::Cow, sync::Arc};
@@ -97,6 +131,34 @@ mod tests {
         assert_eq!(bytes, decoded.to_vec());
     }
 
+    #[test]
+    fn codec_gzip_decode_trailing_bytes() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let configuration: GzipCodecConfiguration = serde_json::from_str(JSON_VALID).unwrap();
+        let codec = GzipCodec::new_with_configuration(&configuration);
+
+        let mut encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .to_vec();
+        encoded.extend_from_slice(&[0; 3]);
+
+        let options = CodecOptions::builder().allow_trailing_bytes(false).build();
+        assert!(matches!(
+            codec.decode(Cow::Borrowed(&encoded), &bytes_representation, &options),
+            Err(CodecError::TrailingBytes(3))
+        ));
+
+        let options = CodecOptions::builder().allow_trailing_bytes(true).build();
+        let decoded = codec
+            .decode(Cow::Borrowed(&encoded), &bytes_representation, &options)
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
     #[test]
     fn codec_gzip_partial_decode() {
         let elements: Vec<u16> = (0..8).collect();
@@ -136,6 +198,61 @@ mod tests {
         assert_eq!(answer, decoded_partial_chunk);
     }
 
+    #[test]
+    fn codec_gzip_partial_decode_prefix() {
+        let bytes: Vec<u8> = (0..1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec = Arc::new(GzipCodec::new(5).unwrap());
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap()
+            .to_vec();
+
+        // Decompression stops at the end of the last requested byte range
+        let decoded_regions = [
+            ByteRange::FromStart(8, Some(4)),
+            ByteRange::FromStart(0, Some(16)),
+        ];
+        assert_eq!(
+            gzip_partial_decoder::decoded_regions_end(&decoded_regions),
+            Some(16)
+        );
+        let decoded = gzip_decode_prefix(&encoded, Some(16), &CodecOptions::default()).unwrap();
+        assert_eq!(decoded, bytes[..16]);
+        assert_eq!(
+            gzip_partial_decoder::decoded_regions_end(&[
+                ByteRange::FromStart(0, Some(16)),
+                ByteRange::Suffix(4),
+            ]),
+            None
+        );
+        assert_eq!(
+            gzip_partial_decoder::decoded_regions_end(&[ByteRange::FromStart(1, Some(u64::MAX))]),
+            None
+        );
+
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded[0].as_ref(), &bytes[8..12]);
+        assert_eq!(decoded[1].as_ref(), &bytes[..16]);
+        let decoded = partial_decoder
+            .partial_decode(&[ByteRange::Suffix(4)], &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded[0].as_ref(), &bytes[bytes.len() - 4..]);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn codec_gzip_async_partial_decode() {
@@ -4,13 +4,14 @@ use std::{
     sync::Arc,
 };
 
-use flate2::bufread::{GzDecoder, GzEncoder};
+use flate2::bufread::GzEncoder;
 
 use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes,
     },
@@ -21,8 +22,8 @@ use crate::{
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
 use super::{
-    gzip_partial_decoder, GzipCodecConfiguration, GzipCodecConfigurationV1, GzipCompressionLevel,
-    GzipCompressionLevelError, IDENTIFIER,
+    gzip_decode, gzip_partial_decoder, GzipCodecConfiguration, GzipCodecConfigurationV1,
+    GzipCompressionLevel, GzipCompressionLevelError, IDENTIFIER,
 };
 
 /// A `gzip` codec implementation.
@@ -66,6 +67,10 @@ impl CodecTraits for GzipCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -99,12 +104,9 @@ impl BytesToBytesCodecTraits for GzipCodec {
         &self,
         encoded_value: RawBytes<'a>,
         _decoded_representation: &BytesRepresentation,
-        _options: &CodecOptions,
+        options: &CodecOptions,
     ) -> Result<RawBytes<'a>, CodecError> {
-        let mut decoder = GzDecoder::new(Cursor::new(encoded_value));
-        let mut out: Vec<u8> = Vec::new();
-        decoder.read_to_end(&mut out)?;
-        Ok(Cow::Owned(out))
+        Ok(Cow::Owned(gzip_decode(&encoded_value, options)?))
     }
 
     fn partial_decoder(
@@ -1,10 +1,4 @@
-use std::{
-    borrow::Cow,
-    io::{Cursor, Read},
-    sync::Arc,
-};
-
-use flate2::bufread::GzDecoder;
+use std::{borrow::Cow, sync::Arc};
 
 use crate::{
     array::{
@@ -17,6 +11,22 @@ use crate::{
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
+use super::gzip_decode_prefix;
+
+/// Return the end of the last of `decoded_regions`, or [`None`] if a region extends to the end of the decoded value or its end overflows.
+///
+/// `gzip` streams cannot be seeked, but decompression can stop once this many bytes have been produced.
+pub(super) fn decoded_regions_end(decoded_regions: &[ByteRange]) -> Option<u64> {
+    decoded_regions
+        .iter()
+        .try_fold(0, |end, decoded_region| match decoded_region {
+            ByteRange::FromStart(offset, Some(length)) => {
+                Some(end.max(offset.checked_add(*length)?))
+            }
+            ByteRange::FromStart(_, None) | ByteRange::Suffix(_) => None,
+        })
+}
+
 /// Partial decoder for the `gzip` codec.
 pub(crate) struct GzipPartialDecoder<'a> {
     input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
@@ -40,9 +50,11 @@ impl BytesPartialDecoderTraits for GzipPartialDecoder<'_> {
             return Ok(None);
         };
 
-        let mut decoder = GzDecoder::new(Cursor::new(&encoded_value));
-        let mut decompressed = Vec::new();
-        decoder.read_to_end(&mut decompressed)?;
+        let decompressed = gzip_decode_prefix(
+            &encoded_value,
+            decoded_regions_end(decoded_regions),
+            options,
+        )?;
 
         Ok(Some(
             extract_byte_ranges(&decompressed, decoded_regions)
@@ -81,9 +93,11 @@ impl AsyncBytesPartialDecoderTraits for AsyncGzipPartialDecoder {
             return Ok(None);
         };
 
-        let mut decoder = GzDecoder::new(Cursor::new(&encoded_value));
-        let mut decompressed = Vec::new();
-        decoder.read_to_end(&mut decompressed)?;
+        let decompressed = gzip_decode_prefix(
+            &encoded_value,
+            decoded_regions_end(decoded_regions),
+            options,
+        )?;
 
         Ok(Some(
             extract_byte_ranges(&decompressed, decoded_regions)
@@ -0,0 +1,279 @@
+//! The `lz4` bytes to bytes codec.
+//!
+//! <div class="warning">
+//! This codec is experimental and is incompatible with other Zarr V3 implementations.
+//! </div>
+//!
+//! This codec requires the `lz4` feature, which is disabled by default.
+//!
+//! Applies [LZ4](https://lz4.org/) compression.
+//! The encoded value is an LZ4 frame as defined by the [LZ4 frame format](https://github.com/lz4/lz4/blob/dev/doc/lz4_Frame_format.md).
+//! The frame header records the decoded size.
+//!
+//! Note that this is not compatible with the `numcodecs` `LZ4` codec, which encodes a size-prefixed LZ4 block.
+//!
+//! See [`Lz4CodecConfigurationV1`] for example `JSON` metadata.
+
+mod lz4_codec;
+mod lz4_partial_decoder;
+
+use std::{io::Read, sync::Arc};
+
+use crate::{
+    array::codec::{Codec, CodecError, CodecPlugin},
+    config::global_config,
+    metadata::v3::{array::codec::lz4, MetadataV3},
+    plugin::{PluginCreateError, PluginMetadataInvalidError},
+};
+
+pub use crate::metadata::v3::array::codec::lz4::{Lz4CodecConfiguration, Lz4CodecConfigurationV1};
+
+pub use self::lz4_codec::Lz4Codec;
+
+pub use lz4::IDENTIFIER;
+
+// Register the codec.
+inventory::submit! {
+    CodecPlugin::new(IDENTIFIER, is_name_lz4, create_codec_lz4)
+}
+
+fn is_name_lz4(name: &str) -> bool {
+    name.eq(IDENTIFIER)
+        || name
+            == global_config()
+                .experimental_codec_names()
+                .get(IDENTIFIER)
+                .expect("experimental codec identifier in global map")
+}
+
+pub(crate) fn create_codec_lz4(metadata: &MetadataV3) -> Result<Codec, PluginCreateError> {
+    let configuration: Lz4CodecConfiguration = metadata
+        .to_configuration()
+        .map_err(|_| PluginMetadataInvalidError::new(IDENTIFIER, "codec", metadata.clone()))?;
+    let codec = Arc::new(Lz4Codec::new_with_configuration(&configuration));
+    Ok(Codec::BytesToBytes(codec))
+}
+
+/// Compress a value into an `lz4` frame.
+///
+/// LZ4 treats a negative compression level as an acceleration factor.
+/// The `lz4` crate only accepts an unsigned compression level, so the negated acceleration is passed as its two's complement.
+fn lz4_compress(decoded_value: &[u8], acceleration: i32) -> Result<Vec<u8>, std::io::Error> {
+    let level = if acceleration > 1 {
+        u32::from_ne_bytes(acceleration.saturating_neg().to_ne_bytes())
+    } else {
+        0
+    };
+    let mut encoder = ::lz4::EncoderBuilder::new()
+        .level(level)
+        .content_size(decoded_value.len() as u64)
+        .build(Vec::with_capacity(decoded_value.len()))?;
+    std::io::Write::write_all(&mut encoder, decoded_value)?;
+    let (encoded_value, result) = encoder.finish();
+    result?;
+    Ok(encoded_value)
+}
+
+/// Decompress an `lz4` frame.
+///
+/// If `decoded_size_limit` is set, the decoded value is read into a buffer of that size and decoding fails if the frame decodes to more bytes.
+fn lz4_decompress(
+    encoded_value: &[u8],
+    decoded_size_limit: Option<u64>,
+) -> Result<Vec<u8>, CodecError> {
+    let mut decoder = ::lz4::Decoder::new(encoded_value)?;
+    let mut decoded_value = Vec::new();
+    if let Some(limit) = decoded_size_limit {
+        let capacity = usize::try_from(limit).map_err(|_| {
+            CodecError::Other(format!(
+                "lz4 decoded size {limit} exceeds the addressable memory"
+            ))
+        })?;
+        decoded_value.reserve_exact(capacity);
+        (&mut decoder)
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut decoded_value)?;
+        if decoded_value.len() > capacity {
+            return Err(CodecError::Other(format!(
+                "lz4 decoded size exceeds the expected decoded size {limit}"
+            )));
+        }
+    } else {
+        decoder.read_to_end(&mut decoded_value)?;
+    }
+    let (_, result) = decoder.finish();
+    result?;
+    Ok(decoded_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, sync::Arc};
+
+    use crate::{
+        array::{
+            codec::{BytesToBytesCodecTraits, CodecOptions},
+            ArrayRepresentation, BytesRepresentation, DataType, FillValue,
+        },
+        array_subset::ArraySubset,
+        byte_range::ByteRange,
+    };
+
+    use super::*;
+
+    const JSON_VALID1: &str = r#"
+{
+    "acceleration": 1
+}"#;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_lz4_round_trip1() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+        let bytes_representation = BytesRepresentation::FixedSize(bytes.len() as u64);
+
+        let codec_configuration: Lz4CodecConfiguration = serde_json::from_str(JSON_VALID1).unwrap();
+        let codec = Lz4Codec::new_with_configuration(&codec_configuration);
+
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        // The lz4 frame magic number
+        assert_eq!(encoded[..4], 0x184D_2204u32.to_le_bytes());
+        let decoded = codec
+            .decode(encoded, &bytes_representation, &CodecOptions::default())
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_lz4_round_trip_acceleration() {
+        let elements: Vec<u16> = (0..1024).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Lz4Codec::new(8);
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded = codec
+            .decode(
+                encoded,
+                &BytesRepresentation::UnboundedSize,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        assert_eq!(bytes, decoded.to_vec());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_lz4_decoded_size_limit() {
+        let elements: Vec<u16> = (0..32).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Lz4Codec::new(1);
+        let encoded = codec
+            .encode(Cow::Borrowed(&bytes), &CodecOptions::default())
+            .unwrap();
+        assert!(codec
+            .decode(
+                encoded,
+                &BytesRepresentation::FixedSize(32),
+                &CodecOptions::default(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_lz4_partial_decode() {
+        let array_representation =
+            ArrayRepresentation::new(vec![2, 2, 2], DataType::UInt16, FillValue::from(0u16))
+                .unwrap();
+        let data_type_size = array_representation.data_type().fixed_size().unwrap();
+        let array_size = array_representation.num_elements_usize() * data_type_size;
+        let bytes_representation = BytesRepresentation::FixedSize(array_size as u64);
+
+        let elements: Vec<u16> = (0..array_representation.num_elements() as u16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Arc::new(Lz4Codec::new(1));
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions: Vec<ByteRange> = ArraySubset::new_with_ranges(&[0..2, 1..2, 0..1])
+            .byte_ranges(array_representation.shape(), data_type_size)
+            .unwrap();
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode_concat(&decoded_regions, &CodecOptions::default())
+            .unwrap()
+            .unwrap();
+
+        let decoded: Vec<u16> = decoded
+            .to_vec()
+            .chunks_exact(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let answer: Vec<u16> = vec![2, 6];
+        assert_eq!(answer, decoded);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn codec_lz4_async_partial_decode() {
+        let array_representation =
+            ArrayRepresentation::new(vec![2, 2, 2], DataType::UInt16, FillValue::from(0u16))
+                .unwrap();
+        let data_type_size = array_representation.data_type().fixed_size().unwrap();
+        let array_size = array_representation.num_elements_usize() * data_type_size;
+        let bytes_representation = BytesRepresentation::FixedSize(array_size as u64);
+
+        let elements: Vec<u16> = (0..array_representation.num_elements() as u16).collect();
+        let bytes = crate::array::transmute_to_bytes_vec(elements);
+
+        let codec = Arc::new(Lz4Codec::new(1));
+
+        let encoded = codec
+            .encode(Cow::Owned(bytes), &CodecOptions::default())
+            .unwrap();
+        let decoded_regions: Vec<ByteRange> = ArraySubset::new_with_ranges(&[0..2, 1..2, 0..1])
+            .byte_ranges(array_representation.shape(), data_type_size)
+            .unwrap();
+        let input_handle = Arc::new(std::io::Cursor::new(encoded));
+        let partial_decoder = codec
+            .async_partial_decoder(
+                input_handle,
+                &bytes_representation,
+                &CodecOptions::default(),
+            )
+            .await
+            .unwrap();
+        let decoded = partial_decoder
+            .partial_decode_concat(&decoded_regions, &CodecOptions::default())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let decoded: Vec<u16> = decoded
+            .to_vec()
+            .chunks_exact(std::mem::size_of::<u16>())
+            .map(|b| u16::from_ne_bytes(b.try_into().unwrap()))
+            .collect();
+
+        let answer: Vec<u16> = vec![2, 6];
+        assert_eq!(answer, decoded);
+    }
+}
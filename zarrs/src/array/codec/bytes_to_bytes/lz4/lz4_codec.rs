@@ -0,0 +1,177 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{
+            BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
+        },
+        ArrayMetadataOptions, BytesRepresentation, RawBytes,
+    },
+    config::global_config,
+    metadata::v3::MetadataV3,
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::{
+    lz4_compress, lz4_decompress, lz4_partial_decoder, Lz4CodecConfiguration,
+    Lz4CodecConfigurationV1,
+};
+
+/// The maximum size of the frame header, end mark, and content checksum of an `lz4` frame.
+const FRAME_OVERHEAD: u64 = 19 + 4 + 4;
+
+/// The maximum size of an `lz4` frame block (the default block size).
+const FRAME_BLOCK_SIZE: u64 = 64 * 1024;
+
+/// An `lz4` codec implementation.
+#[derive(Clone, Debug)]
+pub struct Lz4Codec {
+    acceleration: i32,
+}
+
+impl Lz4Codec {
+    /// Create a new `lz4` codec.
+    ///
+    /// An `acceleration` less than or equal to zero uses the default acceleration of `1`.
+    #[must_use]
+    pub const fn new(acceleration: i32) -> Self {
+        Self { acceleration }
+    }
+
+    /// Create a new `lz4` codec from configuration.
+    #[must_use]
+    pub const fn new_with_configuration(configuration: &Lz4CodecConfiguration) -> Self {
+        let Lz4CodecConfiguration::V1(configuration) = configuration;
+        Self::new(configuration.acceleration)
+    }
+
+    /// Return the acceleration factor.
+    #[must_use]
+    pub const fn acceleration(&self) -> i32 {
+        self.acceleration
+    }
+}
+
+impl CodecTraits for Lz4Codec {
+    fn create_metadata_opt(&self, _options: &ArrayMetadataOptions) -> Option<MetadataV3> {
+        let configuration = Lz4CodecConfigurationV1 {
+            acceleration: self.acceleration,
+        };
+        Some(
+            MetadataV3::new_with_serializable_configuration(
+                global_config()
+                    .experimental_codec_names()
+                    .get(super::IDENTIFIER)
+                    .expect("experimental codec identifier in global map"),
+                &configuration,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn partial_decoder_should_cache_input(&self) -> bool {
+        false
+    }
+
+    fn partial_decoder_decodes_all(&self) -> bool {
+        true
+    }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
+}
+
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+impl BytesToBytesCodecTraits for Lz4Codec {
+    fn dynamic(self: Arc<Self>) -> Arc<dyn BytesToBytesCodecTraits> {
+        self as Arc<dyn BytesToBytesCodecTraits>
+    }
+
+    fn recommended_concurrency(
+        &self,
+        _decoded_representation: &BytesRepresentation,
+    ) -> Result<RecommendedConcurrency, CodecError> {
+        // lz4 frame compression is single threaded
+        Ok(RecommendedConcurrency::new_maximum(1))
+    }
+
+    fn encode<'a>(
+        &self,
+        decoded_value: RawBytes<'a>,
+        _options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        Ok(Cow::Owned(lz4_compress(&decoded_value, self.acceleration)?))
+    }
+
+    fn decode<'a>(
+        &self,
+        encoded_value: RawBytes<'a>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<RawBytes<'a>, CodecError> {
+        Ok(Cow::Owned(lz4_decompress(
+            &encoded_value,
+            decoded_representation.size(),
+        )?))
+    }
+
+    fn partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(lz4_partial_decoder::Lz4PartialDecoder::new(
+            input_handle,
+            *decoded_representation,
+        )))
+    }
+
+    fn partial_encoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn BytesPartialDecoderTraits>,
+        output_handle: Arc<dyn BytesPartialEncoderTraits>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn BytesPartialEncoderTraits>, CodecError> {
+        Ok(Arc::new(BytesPartialEncoderDefault::new(
+            input_handle,
+            output_handle,
+            *decoded_representation,
+            self,
+        )))
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_partial_decoder(
+        self: Arc<Self>,
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        decoded_representation: &BytesRepresentation,
+        _options: &CodecOptions,
+    ) -> Result<Arc<dyn AsyncBytesPartialDecoderTraits>, CodecError> {
+        Ok(Arc::new(lz4_partial_decoder::AsyncLz4PartialDecoder::new(
+            input_handle,
+            *decoded_representation,
+        )))
+    }
+
+    fn compute_encoded_size(
+        &self,
+        decoded_representation: &BytesRepresentation,
+    ) -> BytesRepresentation {
+        decoded_representation
+            .size()
+            .map_or(BytesRepresentation::UnboundedSize, |size| {
+                // LZ4_COMPRESSBOUND plus the frame header, block headers, end mark, and checksum
+                let blocks = size / FRAME_BLOCK_SIZE + 1;
+                BytesRepresentation::BoundedSize(
+                    size + size / 255 + 16 + blocks * 4 + FRAME_OVERHEAD,
+                )
+            })
+    }
+}
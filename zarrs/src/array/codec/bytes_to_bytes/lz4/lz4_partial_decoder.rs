@@ -0,0 +1,98 @@
+use std::{borrow::Cow, sync::Arc};
+
+use crate::{
+    array::{
+        codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
+        BytesRepresentation, RawBytes,
+    },
+    byte_range::{extract_byte_ranges, ByteRange},
+};
+
+#[cfg(feature = "async")]
+use crate::array::codec::AsyncBytesPartialDecoderTraits;
+
+use super::lz4_decompress;
+
+/// Partial decoder for the `lz4` codec.
+pub(crate) struct Lz4PartialDecoder<'a> {
+    input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+    decoded_representation: BytesRepresentation,
+}
+
+impl<'a> Lz4PartialDecoder<'a> {
+    pub(crate) fn new(
+        input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: BytesRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
+    }
+}
+
+impl BytesPartialDecoderTraits for Lz4PartialDecoder<'_> {
+    fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options)?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = lz4_decompress(&encoded_value, self.decoded_representation.size())?;
+        Ok(Some(
+            extract_byte_ranges(&decompressed, decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(feature = "async")]
+/// Asynchronous partial decoder for the `lz4` codec.
+pub(crate) struct AsyncLz4PartialDecoder {
+    input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+    decoded_representation: BytesRepresentation,
+}
+
+#[cfg(feature = "async")]
+impl AsyncLz4PartialDecoder {
+    pub(crate) fn new(
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        decoded_representation: BytesRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl AsyncBytesPartialDecoderTraits for AsyncLz4PartialDecoder {
+    async fn partial_decode(
+        &self,
+        decoded_regions: &[ByteRange],
+        options: &CodecOptions,
+    ) -> Result<Option<Vec<RawBytes<'_>>>, CodecError> {
+        let encoded_value = self.input_handle.decode(options).await?;
+        let Some(encoded_value) = encoded_value else {
+            return Ok(None);
+        };
+
+        let decompressed = lz4_decompress(&encoded_value, self.decoded_representation.size())?;
+        Ok(Some(
+            extract_byte_ranges(&decompressed, decoded_regions)
+                .map_err(CodecError::InvalidByteRangeError)?
+                .into_iter()
+                .map(Cow::Owned)
+                .collect(),
+        ))
+    }
+}
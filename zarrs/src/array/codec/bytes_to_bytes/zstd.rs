@@ -15,11 +15,13 @@ pub use crate::metadata::v3::array::codec::zstd::{
 pub use zstd_codec::ZstdCodec;
 
 use crate::{
-    array::codec::{Codec, CodecPlugin},
+    array::codec::{Codec, CodecError, CodecOptions, CodecPlugin},
     metadata::v3::{array::codec::zstd, MetadataV3},
     plugin::{PluginCreateError, PluginMetadataInvalidError},
 };
 
+use super::strip_trailing_bytes;
+
 pub use zstd::IDENTIFIER;
 
 // Register the codec.
@@ -39,6 +41,64 @@ pub(crate) fn create_codec_zstd(metadata: &MetadataV3) -> Result<Codec, PluginCr
     Ok(Codec::BytesToBytes(codec))
 }
 
+/// Returns the length of the complete `zstd` frames at the start of `encoded_value`.
+fn zstd_frames_length(encoded_value: &[u8]) -> usize {
+    let mut length = 0;
+    while length < encoded_value.len() {
+        match ::zstd::zstd_safe::find_frame_compressed_size(&encoded_value[length..]) {
+            Ok(frame_size) if frame_size > 0 => length += frame_size,
+            _ => break,
+        }
+    }
+    length
+}
+
+/// Decompress a `zstd` encoded value.
+///
+/// Trailing bytes after the last complete frame are handled according to [`CodecOptions::allow_trailing_bytes()`].
+/// If `decoded_size_limit` is set and the frame header records the frame content size, the content size is checked against the limit and the frame is decoded directly into a buffer of that size.
+/// Otherwise, the value is decoded with a streaming decoder and the decoded size is checked afterwards.
+/// The frame content size is untrusted, so it is never used to size a buffer without a limit.
+fn zstd_decode(
+    encoded_value: &[u8],
+    decoded_size_limit: Option<u64>,
+    options: &CodecOptions,
+) -> Result<Vec<u8>, CodecError> {
+    // If there is no complete frame, the value is decoded as is so that the decoder reports the error
+    let frames_length = zstd_frames_length(encoded_value);
+    let encoded_value = if frames_length > 0 {
+        strip_trailing_bytes(encoded_value, frames_length, options)?
+    } else {
+        encoded_value
+    };
+
+    let check_limit = |decoded_size: u64| match decoded_size_limit {
+        Some(limit) if decoded_size > limit => Err(CodecError::Other(format!(
+            "zstd decoded size {decoded_size} exceeds the expected decoded size {limit}"
+        ))),
+        _ => Ok(()),
+    };
+
+    // The fast path only applies to a single frame, since only the content size of the first frame is known
+    let single_frame = ::zstd::zstd_safe::find_frame_compressed_size(encoded_value)
+        .is_ok_and(|frame_size| frame_size == encoded_value.len());
+    if single_frame && decoded_size_limit.is_some() {
+        if let Ok(Some(content_size)) = ::zstd::zstd_safe::get_frame_content_size(encoded_value) {
+            check_limit(content_size)?;
+            let capacity = usize::try_from(content_size).map_err(|_| {
+                CodecError::Other(format!(
+                    "zstd frame content size {content_size} exceeds the addressable memory"
+                ))
+            })?;
+            return ::zstd::bulk::decompress(encoded_value, capacity).map_err(CodecError::IOError);
+        }
+    }
+
+    let decoded_value = ::zstd::decode_all(encoded_value).map_err(CodecError::IOError)?;
+    check_limit(decoded_value.len() as u64)?;
+    Ok(decoded_value)
+}
+
 #[cfg(test)]
 mod tests {
     use std::{borrow::Cow, sync::Arc};
@@ -117,6 +177,60 @@ mod tests {
         assert_eq!(answer, decoded_partial_chunk);
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zstd_decode_frame_content_size() {
+        let bytes: Vec<u8> = (0..64).collect();
+        let options = CodecOptions::default();
+
+        // Frame content size recorded in the frame header
+        let encoded = ::zstd::bulk::compress(&bytes, 1).unwrap();
+        assert_eq!(
+            ::zstd::zstd_safe::get_frame_content_size(&encoded).ok(),
+            Some(Some(64))
+        );
+        assert_eq!(zstd_decode(&encoded, Some(64), &options).unwrap(), bytes);
+        assert_eq!(zstd_decode(&encoded, None, &options).unwrap(), bytes);
+        assert!(zstd_decode(&encoded, Some(32), &options).is_err());
+
+        // Frame content size not recorded, fall back to a streaming decode
+        let mut encoded = Vec::new();
+        let mut encoder = ::zstd::Encoder::new(&mut encoded, 1).unwrap();
+        encoder.include_contentsize(false).unwrap();
+        std::io::copy(&mut std::io::Cursor::new(&bytes), &mut encoder).unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(
+            ::zstd::zstd_safe::get_frame_content_size(&encoded).ok(),
+            Some(None)
+        );
+        assert_eq!(zstd_decode(&encoded, Some(64), &options).unwrap(), bytes);
+        assert!(zstd_decode(&encoded, Some(32), &options).is_err());
+
+        // Multiple frames
+        let encoded = [
+            ::zstd::bulk::compress(&bytes[..32], 1).unwrap(),
+            ::zstd::bulk::compress(&bytes[32..], 1).unwrap(),
+        ]
+        .concat();
+        assert_eq!(zstd_decode(&encoded, Some(64), &options).unwrap(), bytes);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn codec_zstd_decode_trailing_bytes() {
+        let bytes: Vec<u8> = (0..64).collect();
+        let encoded = [::zstd::bulk::compress(&bytes, 1).unwrap(), vec![0; 5]].concat();
+
+        let options = CodecOptions::builder().allow_trailing_bytes(false).build();
+        assert!(matches!(
+            zstd_decode(&encoded, Some(64), &options),
+            Err(CodecError::TrailingBytes(5))
+        ));
+
+        let options = CodecOptions::builder().allow_trailing_bytes(true).build();
+        assert_eq!(zstd_decode(&encoded, Some(64), &options).unwrap(), bytes);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     #[cfg_attr(miri, ignore)]
@@ -6,7 +6,8 @@ use crate::{
     array::{
         codec::{
             BytesPartialDecoderTraits, BytesPartialEncoderDefault, BytesPartialEncoderTraits,
-            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, RecommendedConcurrency,
+            BytesToBytesCodecTraits, CodecError, CodecOptions, CodecTraits, CodecTransformInfo,
+            RecommendedConcurrency,
         },
         ArrayMetadataOptions, BytesRepresentation, RawBytes,
     },
@@ -16,7 +17,9 @@ use crate::{
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
-use super::{zstd_partial_decoder, ZstdCodecConfiguration, ZstdCodecConfigurationV1, IDENTIFIER};
+use super::{
+    zstd_decode, zstd_partial_decoder, ZstdCodecConfiguration, ZstdCodecConfigurationV1, IDENTIFIER,
+};
 
 /// A `zstd` codec implementation.
 #[derive(Clone, Debug)]
@@ -62,6 +65,10 @@ impl CodecTraits for ZstdCodec {
     fn partial_decoder_decodes_all(&self) -> bool {
         true
     }
+
+    fn describe_transform(&self, input_len: usize) -> CodecTransformInfo {
+        CodecTransformInfo::new_compressor(input_len, false)
+    }
 }
 
 #[cfg_attr(feature = "async", async_trait::async_trait)]
@@ -86,6 +93,8 @@ impl BytesToBytesCodecTraits for ZstdCodec {
         let mut result = Vec::<u8>::new();
         let mut encoder = zstd::Encoder::new(&mut result, self.compression)?;
         encoder.include_checksum(self.checksum)?;
+        // Record the frame content size so that decoders can decode into a correctly sized buffer
+        encoder.set_pledged_src_size(Some(decoded_value.len() as u64))?;
         // if parallel {
         //     let n_threads = std::thread::available_parallelism().unwrap().get();
         //     encoder.multithread(u32::try_from(n_threads).unwrap())?; // TODO: Check overhead of zstd par_encode
@@ -98,21 +107,22 @@ impl BytesToBytesCodecTraits for ZstdCodec {
     fn decode<'a>(
         &self,
         encoded_value: RawBytes<'a>,
-        _decoded_representation: &BytesRepresentation,
-        _options: &CodecOptions,
+        decoded_representation: &BytesRepresentation,
+        options: &CodecOptions,
     ) -> Result<RawBytes<'a>, CodecError> {
-        zstd::decode_all(std::io::Cursor::new(&encoded_value))
-            .map_err(CodecError::IOError)
-            .map(Cow::Owned)
+        zstd_decode(&encoded_value, decoded_representation.size(), options).map(Cow::Owned)
     }
 
     fn partial_decoder(
         self: Arc<Self>,
         r: Arc<dyn BytesPartialDecoderTraits>,
-        _decoded_representation: &BytesRepresentation,
+        decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Arc<dyn BytesPartialDecoderTraits>, CodecError> {
-        Ok(Arc::new(zstd_partial_decoder::ZstdPartialDecoder::new(r)))
+        Ok(Arc::new(zstd_partial_decoder::ZstdPartialDecoder::new(
+            r,
+            *decoded_representation,
+        )))
     }
 
     fn partial_encoder(
@@ -134,11 +144,11 @@ impl BytesToBytesCodecTraits for ZstdCodec {
     async fn async_partial_decoder(
         self: Arc<Self>,
         r: Arc<dyn AsyncBytesPartialDecoderTraits>,
-        _decoded_representation: &BytesRepresentation,
+        decoded_representation: &BytesRepresentation,
         _options: &CodecOptions,
     ) -> Result<Arc<dyn AsyncBytesPartialDecoderTraits>, CodecError> {
         Ok(Arc::new(
-            zstd_partial_decoder::AsyncZstdPartialDecoder::new(r),
+            zstd_partial_decoder::AsyncZstdPartialDecoder::new(r, *decoded_representation),
         ))
     }
 
@@ -1,27 +1,36 @@
 use std::{borrow::Cow, sync::Arc};
 
-use bytes::Buf;
-
 use crate::{
     array::{
         codec::{BytesPartialDecoderTraits, CodecError, CodecOptions},
-        RawBytes,
+        BytesRepresentation, RawBytes,
     },
     byte_range::{extract_byte_ranges, ByteRange},
 };
 
+use super::zstd_decode;
+
 #[cfg(feature = "async")]
 use crate::array::codec::AsyncBytesPartialDecoderTraits;
 
 /// Partial decoder for the `zstd` codec.
+///
+/// The frame content size is used to decode into a correctly sized buffer if it is recorded in the frame header.
 pub(crate) struct ZstdPartialDecoder<'a> {
     input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+    decoded_representation: BytesRepresentation,
 }
 
 impl<'a> ZstdPartialDecoder<'a> {
     /// Create a new partial decoder for the `zstd` codec.
-    pub(crate) fn new(input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>) -> Self {
-        Self { input_handle }
+    pub(crate) fn new(
+        input_handle: Arc<dyn BytesPartialDecoderTraits + 'a>,
+        decoded_representation: BytesRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
     }
 }
 
@@ -36,7 +45,8 @@ impl BytesPartialDecoderTraits for ZstdPartialDecoder<'_> {
             return Ok(None);
         };
 
-        let decompressed = zstd::decode_all(encoded_value.reader()).map_err(CodecError::IOError)?;
+        let decompressed =
+            zstd_decode(&encoded_value, self.decoded_representation.size(), options)?;
 
         Ok(Some(
             extract_byte_ranges(&decompressed, decoded_regions)
@@ -52,13 +62,20 @@ impl BytesPartialDecoderTraits for ZstdPartialDecoder<'_> {
 /// Asynchronous partial decoder for the `zstd` codec.
 pub(crate) struct AsyncZstdPartialDecoder {
     input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+    decoded_representation: BytesRepresentation,
 }
 
 #[cfg(feature = "async")]
 impl AsyncZstdPartialDecoder {
     /// Create a new partial decoder for the `zstd` codec.
-    pub(crate) fn new(input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>) -> Self {
-        Self { input_handle }
+    pub(crate) fn new(
+        input_handle: Arc<dyn AsyncBytesPartialDecoderTraits>,
+        decoded_representation: BytesRepresentation,
+    ) -> Self {
+        Self {
+            input_handle,
+            decoded_representation,
+        }
     }
 }
 
@@ -75,7 +92,8 @@ impl AsyncBytesPartialDecoderTraits for AsyncZstdPartialDecoder {
             return Ok(None);
         };
 
-        let decompressed = zstd::decode_all(encoded_value.reader()).map_err(CodecError::IOError)?;
+        let decompressed =
+            zstd_decode(&encoded_value, self.decoded_representation.size(), options)?;
 
         Ok(Some(
             extract_byte_ranges(&decompressed, decoded_regions)
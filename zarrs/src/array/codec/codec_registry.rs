@@ -0,0 +1,118 @@
+//! A runtime registry of codec plugins.
+//!
+//! The registry is initialised once, on first use, with the codec plugins registered at compile time with [`inventory::submit!`].
+//! Further plugins can be registered at runtime with [`register_codec`], which is safe to call from multiple threads.
+
+use std::sync::{OnceLock, RwLock};
+
+use thiserror::Error;
+
+use super::CodecPlugin;
+
+/// A codec with the same identifier is already registered.
+#[derive(Debug, Error)]
+#[error("codec {_0} is already registered")]
+pub struct CodecAlreadyRegisteredError(&'static str);
+
+fn codec_registry() -> &'static RwLock<Vec<&'static CodecPlugin>> {
+    static CODEC_REGISTRY: OnceLock<RwLock<Vec<&'static CodecPlugin>>> = OnceLock::new();
+    CODEC_REGISTRY.get_or_init(|| RwLock::new(inventory::iter::<CodecPlugin>.into_iter().collect()))
+}
+
+/// Register a codec plugin at runtime.
+///
+/// Registered codecs are matched in registration order, after the codecs registered at compile time.
+/// If a codec with the same [identifier](crate::plugin::Plugin::identifier) is already registered, it is replaced in place if `replace` is true.
+///
+/// # Errors
+/// Returns a [`CodecAlreadyRegisteredError`] if a codec with the same identifier is already registered and `replace` is false.
+///
+/// # Panics
+/// Panics if the registry lock is poisoned.
+pub fn register_codec(
+    plugin: &'static CodecPlugin,
+    replace: bool,
+) -> Result<(), CodecAlreadyRegisteredError> {
+    let mut registry = codec_registry().write().unwrap();
+    if let Some(existing) = registry
+        .iter_mut()
+        .find(|existing| existing.identifier() == plugin.identifier())
+    {
+        if replace {
+            *existing = plugin;
+            Ok(())
+        } else {
+            Err(CodecAlreadyRegisteredError(plugin.identifier()))
+        }
+    } else {
+        registry.push(plugin);
+        Ok(())
+    }
+}
+
+/// Return the identifiers of all registered codec plugins.
+///
+/// # Panics
+/// Panics if the registry lock is poisoned.
+#[must_use]
+pub fn registered_codec_identifiers() -> Vec<&'static str> {
+    codec_registry()
+        .read()
+        .unwrap()
+        .iter()
+        .map(|plugin| plugin.identifier())
+        .collect()
+}
+
+/// Return the first registered codec plugin matching `name`.
+pub(crate) fn find_codec_plugin(name: &str) -> Option<&'static CodecPlugin> {
+    codec_registry()
+        .read()
+        .unwrap()
+        .iter()
+        .find(|plugin| plugin.match_name(name))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin::PluginCreateError;
+
+    use super::*;
+
+    fn leak_plugin(identifier: String) -> &'static CodecPlugin {
+        Box::leak(Box::new(CodecPlugin::new(
+            Box::leak(identifier.into_boxed_str()),
+            |_| false,
+            |_| Err(PluginCreateError::from("test codec")),
+        )))
+    }
+
+    #[test]
+    fn codec_registry_concurrent() {
+        let identifiers: Vec<String> = (0..64).map(|i| format!("test_codec_{i}")).collect();
+        std::thread::scope(|scope| {
+            for identifier in &identifiers {
+                scope
+                    .spawn(move || register_codec(leak_plugin(identifier.clone()), false).unwrap());
+            }
+        });
+        let registered = registered_codec_identifiers();
+        for identifier in &identifiers {
+            assert!(registered.contains(&identifier.as_str()));
+        }
+    }
+
+    #[test]
+    fn codec_registry_duplicate() {
+        register_codec(leak_plugin("test_codec_duplicate".to_string()), false).unwrap();
+        assert!(register_codec(leak_plugin("test_codec_duplicate".to_string()), false).is_err());
+        let num_registered = registered_codec_identifiers().len();
+        register_codec(leak_plugin("test_codec_duplicate".to_string()), true).unwrap();
+        assert_eq!(registered_codec_identifiers().len(), num_registered);
+
+        #[cfg(all(feature = "gzip", not(miri)))]
+        assert!(registered_codec_identifiers()
+            .contains(&crate::array::codec::bytes_to_bytes::gzip::IDENTIFIER));
+    }
+}
@@ -0,0 +1,67 @@
+/// A description of how a codec transforms its input, returned by [`CodecTraits::describe_transform`](super::CodecTraits::describe_transform).
+///
+/// This is metadata about a codec that can be used to model a codec pipeline (e.g. the size flow shown in a progress indicator) without encoding any data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CodecTransformInfo {
+    changes_size: bool,
+    lossy: bool,
+    estimated_output_len: usize,
+}
+
+impl CodecTransformInfo {
+    /// Create a new codec transform description.
+    #[must_use]
+    pub const fn new(changes_size: bool, lossy: bool, estimated_output_len: usize) -> Self {
+        Self {
+            changes_size,
+            lossy,
+            estimated_output_len,
+        }
+    }
+
+    /// Create a description of a lossless codec that does not change the size of its input (e.g. `bytes` or `transpose`).
+    #[must_use]
+    pub const fn new_same_size(input_len: usize) -> Self {
+        Self::new(false, false, input_len)
+    }
+
+    /// Create a description of a compressor.
+    ///
+    /// The compression ratio depends on the data, so the estimated output length is the input length (i.e. incompressible data, excluding any header).
+    #[must_use]
+    pub const fn new_compressor(input_len: usize, lossy: bool) -> Self {
+        Self::new(true, lossy, input_len)
+    }
+
+    /// Return a description of this transform followed by the `next` transform.
+    ///
+    /// The `next` transform should be described with the [estimated output length](CodecTransformInfo::estimated_output_len) of this transform as its input length.
+    #[must_use]
+    pub const fn then(&self, next: &Self) -> Self {
+        Self::new(
+            self.changes_size || next.changes_size,
+            self.lossy || next.lossy,
+            next.estimated_output_len,
+        )
+    }
+
+    /// Returns true if the codec can change the size of its input.
+    ///
+    /// If false, the [estimated output length](CodecTransformInfo::estimated_output_len) is exact.
+    #[must_use]
+    pub const fn changes_size(&self) -> bool {
+        self.changes_size
+    }
+
+    /// Returns true if the codec is lossy.
+    #[must_use]
+    pub const fn is_lossy(&self) -> bool {
+        self.lossy
+    }
+
+    /// Return the estimated encoded length of the input.
+    #[must_use]
+    pub const fn estimated_output_len(&self) -> usize {
+        self.estimated_output_len
+    }
+}
@@ -1,6 +1,8 @@
 //! Codec options for encoding and decoding.
 
-use crate::config::global_config;
+use std::time::Duration;
+
+use crate::{array::FillValue, config::global_config};
 
 /// Codec options for encoding/decoding.
 ///
@@ -10,7 +12,15 @@ pub struct CodecOptions {
     validate_checksums: bool,
     store_empty_chunks: bool,
     concurrent_target: usize,
+    decode_parallel_min_elements: u64,
     experimental_partial_encoding: bool,
+    allow_trailing_bytes: bool,
+    async_read_timeout: Option<Duration>,
+    retrieve_size_limit: u64,
+    retrieve_element_limit: u64,
+    assume_new_chunks: bool,
+    new_chunk_fill_value: Option<FillValue>,
+    allow_oob_fill: bool,
 }
 
 impl Default for CodecOptions {
@@ -19,7 +29,15 @@ impl Default for CodecOptions {
             validate_checksums: global_config().validate_checksums(),
             store_empty_chunks: global_config().store_empty_chunks(),
             concurrent_target: global_config().codec_concurrent_target(),
+            decode_parallel_min_elements: global_config().decode_parallel_min_elements(),
             experimental_partial_encoding: global_config().experimental_partial_encoding(),
+            allow_trailing_bytes: global_config().allow_trailing_bytes(),
+            async_read_timeout: global_config().async_read_timeout(),
+            retrieve_size_limit: global_config().retrieve_size_limit(),
+            retrieve_element_limit: global_config().retrieve_element_limit(),
+            assume_new_chunks: false,
+            new_chunk_fill_value: None,
+            allow_oob_fill: false,
         }
     }
 }
@@ -38,7 +56,15 @@ impl CodecOptions {
             validate_checksums: self.validate_checksums,
             store_empty_chunks: self.store_empty_chunks,
             concurrent_target: self.concurrent_target,
+            decode_parallel_min_elements: self.decode_parallel_min_elements,
             experimental_partial_encoding: self.experimental_partial_encoding,
+            allow_trailing_bytes: self.allow_trailing_bytes,
+            async_read_timeout: self.async_read_timeout,
+            retrieve_size_limit: self.retrieve_size_limit,
+            retrieve_element_limit: self.retrieve_element_limit,
+            assume_new_chunks: self.assume_new_chunks,
+            new_chunk_fill_value: self.new_chunk_fill_value.clone(),
+            allow_oob_fill: self.allow_oob_fill,
         }
     }
 
@@ -78,6 +104,23 @@ impl CodecOptions {
         self
     }
 
+    /// Return the decode parallel minimum elements.
+    #[must_use]
+    pub fn decode_parallel_min_elements(&self) -> u64 {
+        self.decode_parallel_min_elements
+    }
+
+    /// Set the minimum number of elements in a chunk for a codec chain to decode it in parallel.
+    ///
+    /// Smaller chunks are decoded with a concurrent target of one.
+    pub fn set_decode_parallel_min_elements(
+        &mut self,
+        decode_parallel_min_elements: u64,
+    ) -> &mut Self {
+        self.decode_parallel_min_elements = decode_parallel_min_elements;
+        self
+    }
+
     /// Return the experimental partial encoding setting.
     #[must_use]
     pub fn experimental_partial_encoding(&self) -> bool {
@@ -92,6 +135,112 @@ impl CodecOptions {
         self.experimental_partial_encoding = experimental_partial_encoding;
         self
     }
+
+    /// Return the allow trailing bytes setting.
+    #[must_use]
+    pub fn allow_trailing_bytes(&self) -> bool {
+        self.allow_trailing_bytes
+    }
+
+    /// Set whether or not to ignore trailing bytes after an encoded stream when decoding.
+    pub fn set_allow_trailing_bytes(&mut self, allow_trailing_bytes: bool) -> &mut Self {
+        self.allow_trailing_bytes = allow_trailing_bytes;
+        self
+    }
+
+    /// Return the async read timeout.
+    #[must_use]
+    pub fn async_read_timeout(&self) -> Option<Duration> {
+        self.async_read_timeout
+    }
+
+    /// Set the timeout of each storage read made by an async operation, or [`None`] for no timeout.
+    pub fn set_async_read_timeout(&mut self, async_read_timeout: Option<Duration>) -> &mut Self {
+        self.async_read_timeout = async_read_timeout;
+        self
+    }
+
+    /// Return the retrieve size limit.
+    #[must_use]
+    pub fn retrieve_size_limit(&self) -> u64 {
+        self.retrieve_size_limit
+    }
+
+    /// Set the maximum decoded size in bytes of an array read with [`Array::retrieve_all_opt`](crate::array::Array::retrieve_all_opt) and its variants.
+    ///
+    /// See the [retrieve size limit](crate::config::Config#retrieve-size-limit) configuration.
+    pub fn set_retrieve_size_limit(&mut self, retrieve_size_limit: u64) -> &mut Self {
+        self.retrieve_size_limit = retrieve_size_limit;
+        self
+    }
+
+    /// Return the retrieve element limit.
+    #[must_use]
+    pub fn retrieve_element_limit(&self) -> u64 {
+        self.retrieve_element_limit
+    }
+
+    /// Set the maximum number of elements of an array or array subset read with [`Array::retrieve_all_opt`](crate::array::Array::retrieve_all_opt) or [`Array::retrieve_array_subset_opt`](crate::array::Array::retrieve_array_subset_opt) and their variants.
+    ///
+    /// See the [retrieve element limit](crate::config::Config#retrieve-element-limit) configuration.
+    pub fn set_retrieve_element_limit(&mut self, retrieve_element_limit: u64) -> &mut Self {
+        self.retrieve_element_limit = retrieve_element_limit;
+        self
+    }
+
+    /// Return the assume new chunks setting.
+    #[must_use]
+    pub fn assume_new_chunks(&self) -> bool {
+        self.assume_new_chunks
+    }
+
+    /// Set whether or not to assume that chunks partially written by an array or chunk subset store do not exist.
+    ///
+    /// If `true`, a partially written chunk is padded with the fill value instead of being retrieved and updated.
+    /// This avoids a read-modify-write of the edge chunks of a region, which is faster for initial bulk loads.
+    /// The written subset must start on a chunk boundary.
+    ///
+    /// **Existing data in a partially written chunk is overwritten with the fill value.**
+    /// This option defaults to `false` and is not configurable globally.
+    pub fn set_assume_new_chunks(&mut self, assume_new_chunks: bool) -> &mut Self {
+        self.assume_new_chunks = assume_new_chunks;
+        self
+    }
+
+    /// Return the new chunk fill value override.
+    #[must_use]
+    pub fn new_chunk_fill_value(&self) -> Option<&FillValue> {
+        self.new_chunk_fill_value.as_ref()
+    }
+
+    /// Set the value that fills the unwritten portion of chunks created by an array or chunk subset store, or [`None`] to use the array fill value.
+    ///
+    /// This only affects chunks that do not exist before the store, and existing chunks are updated as usual.
+    /// The fill value must be compatible with the data type of the array.
+    pub fn set_new_chunk_fill_value(
+        &mut self,
+        new_chunk_fill_value: Option<FillValue>,
+    ) -> &mut Self {
+        self.new_chunk_fill_value = new_chunk_fill_value;
+        self
+    }
+
+    /// Return the allow out-of-bounds fill setting.
+    #[must_use]
+    pub fn allow_oob_fill(&self) -> bool {
+        self.allow_oob_fill
+    }
+
+    /// Set whether or not an array subset retrieve may extend beyond the bounds of the array.
+    ///
+    /// If `true`, the read is clamped to the array and the out-of-bounds region is padded with the fill value, so the output always has the shape of the requested subset.
+    /// This simplifies fixed-size tile extraction at the edges of an array.
+    /// The number of padded elements is reported by [`Array::num_oob_elements`](crate::array::Array::num_oob_elements).
+    /// This option defaults to `false` and is not configurable globally.
+    pub fn set_allow_oob_fill(&mut self, allow_oob_fill: bool) -> &mut Self {
+        self.allow_oob_fill = allow_oob_fill;
+        self
+    }
 }
 
 /// Builder for [`CodecOptions`].
@@ -102,7 +251,15 @@ pub struct CodecOptionsBuilder {
     validate_checksums: bool,
     store_empty_chunks: bool,
     concurrent_target: usize,
+    decode_parallel_min_elements: u64,
     experimental_partial_encoding: bool,
+    allow_trailing_bytes: bool,
+    async_read_timeout: Option<Duration>,
+    retrieve_size_limit: u64,
+    retrieve_element_limit: u64,
+    assume_new_chunks: bool,
+    new_chunk_fill_value: Option<FillValue>,
+    allow_oob_fill: bool,
 }
 
 impl Default for CodecOptionsBuilder {
@@ -119,7 +276,15 @@ impl CodecOptionsBuilder {
             validate_checksums: global_config().validate_checksums(),
             store_empty_chunks: global_config().store_empty_chunks(),
             concurrent_target: global_config().codec_concurrent_target(),
+            decode_parallel_min_elements: global_config().decode_parallel_min_elements(),
             experimental_partial_encoding: global_config().experimental_partial_encoding(),
+            allow_trailing_bytes: global_config().allow_trailing_bytes(),
+            async_read_timeout: global_config().async_read_timeout(),
+            retrieve_size_limit: global_config().retrieve_size_limit(),
+            retrieve_element_limit: global_config().retrieve_element_limit(),
+            assume_new_chunks: false,
+            new_chunk_fill_value: None,
+            allow_oob_fill: false,
         }
     }
 
@@ -130,7 +295,15 @@ impl CodecOptionsBuilder {
             validate_checksums: self.validate_checksums,
             store_empty_chunks: self.store_empty_chunks,
             concurrent_target: self.concurrent_target,
+            decode_parallel_min_elements: self.decode_parallel_min_elements,
             experimental_partial_encoding: self.experimental_partial_encoding,
+            allow_trailing_bytes: self.allow_trailing_bytes,
+            async_read_timeout: self.async_read_timeout,
+            retrieve_size_limit: self.retrieve_size_limit,
+            retrieve_element_limit: self.retrieve_element_limit,
+            assume_new_chunks: self.assume_new_chunks,
+            new_chunk_fill_value: self.new_chunk_fill_value.clone(),
+            allow_oob_fill: self.allow_oob_fill,
         }
     }
 
@@ -155,10 +328,77 @@ impl CodecOptionsBuilder {
         self
     }
 
+    /// Set the minimum number of elements in a chunk for a codec chain to decode it in parallel.
+    #[must_use]
+    pub fn decode_parallel_min_elements(mut self, decode_parallel_min_elements: u64) -> Self {
+        self.decode_parallel_min_elements = decode_parallel_min_elements;
+        self
+    }
+
     /// Set whether or not to use experimental partial encoding.
     #[must_use]
     pub fn experimental_partial_encoding(mut self, experimental_partial_encoding: bool) -> Self {
         self.experimental_partial_encoding = experimental_partial_encoding;
         self
     }
+
+    /// Set whether or not to ignore trailing bytes after an encoded stream when decoding.
+    #[must_use]
+    pub fn allow_trailing_bytes(mut self, allow_trailing_bytes: bool) -> Self {
+        self.allow_trailing_bytes = allow_trailing_bytes;
+        self
+    }
+
+    /// Set the timeout of each storage read made by an async operation, or [`None`] for no timeout.
+    #[must_use]
+    pub fn async_read_timeout(mut self, async_read_timeout: Option<Duration>) -> Self {
+        self.async_read_timeout = async_read_timeout;
+        self
+    }
+
+    /// Set the maximum decoded size in bytes of an array read with `retrieve_all`.
+    ///
+    /// See [`CodecOptions::set_retrieve_size_limit`].
+    #[must_use]
+    pub fn retrieve_size_limit(mut self, retrieve_size_limit: u64) -> Self {
+        self.retrieve_size_limit = retrieve_size_limit;
+        self
+    }
+
+    /// Set the maximum number of elements of an array or array subset read.
+    ///
+    /// See [`CodecOptions::set_retrieve_element_limit`].
+    #[must_use]
+    pub fn retrieve_element_limit(mut self, retrieve_element_limit: u64) -> Self {
+        self.retrieve_element_limit = retrieve_element_limit;
+        self
+    }
+
+    /// Set whether or not to assume that chunks partially written by an array or chunk subset store do not exist.
+    ///
+    /// See [`CodecOptions::set_assume_new_chunks`].
+    /// **Existing data in a partially written chunk is overwritten with the fill value.**
+    #[must_use]
+    pub fn assume_new_chunks(mut self, assume_new_chunks: bool) -> Self {
+        self.assume_new_chunks = assume_new_chunks;
+        self
+    }
+
+    /// Set the value that fills the unwritten portion of chunks created by an array or chunk subset store, or [`None`] to use the array fill value.
+    ///
+    /// See [`CodecOptions::set_new_chunk_fill_value`].
+    #[must_use]
+    pub fn new_chunk_fill_value(mut self, new_chunk_fill_value: Option<FillValue>) -> Self {
+        self.new_chunk_fill_value = new_chunk_fill_value;
+        self
+    }
+
+    /// Set whether or not an array subset retrieve may extend beyond the bounds of the array.
+    ///
+    /// See [`CodecOptions::set_allow_oob_fill`].
+    #[must_use]
+    pub fn allow_oob_fill(mut self, allow_oob_fill: bool) -> Self {
+        self.allow_oob_fill = allow_oob_fill;
+        self
+    }
 }
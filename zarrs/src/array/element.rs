@@ -69,6 +69,8 @@ impl Element for bool {
     }
 }
 
+/// Each `bool` element is stored as one byte (matching `NumPy` `bool_`).
+/// A byte that is not `0` or `1` (e.g. written by another implementation) is read as `true`.
 impl ElementOwned for bool {
     fn from_array_bytes(
         data_type: &DataType,
@@ -76,17 +78,17 @@ impl ElementOwned for bool {
     ) -> Result<Vec<Self>, ArrayError> {
         Self::validate_data_type(data_type)?;
         let bytes = bytes.into_fixed()?;
-        let elements_u8 = convert_from_bytes_slice::<u8>(&bytes);
-        if elements_u8.iter().all(|&u| u <= 1) {
-            let length: usize = elements_u8.len();
-            let capacity: usize = elements_u8.capacity();
-            let mut manual_drop_vec = ManuallyDrop::new(elements_u8);
-            let vec_ptr: *mut u8 = manual_drop_vec.as_mut_ptr();
-            let ptr: *mut Self = vec_ptr.cast::<Self>();
-            Ok(unsafe { Vec::from_raw_parts(ptr, length, capacity) })
-        } else {
-            Err(ArrayError::InvalidElementValue)
+        let mut elements_u8 = convert_from_bytes_slice::<u8>(&bytes);
+        // Treat any nonzero byte as true, so the bytes are valid `bool`s
+        for element in &mut elements_u8 {
+            *element = u8::from(*element != 0);
         }
+        let length: usize = elements_u8.len();
+        let capacity: usize = elements_u8.capacity();
+        let mut manual_drop_vec = ManuallyDrop::new(elements_u8);
+        let vec_ptr: *mut u8 = manual_drop_vec.as_mut_ptr();
+        let ptr: *mut Self = vec_ptr.cast::<Self>();
+        Ok(unsafe { Vec::from_raw_parts(ptr, length, capacity) })
     }
 }
 
@@ -2,9 +2,16 @@
 //!
 //! See <https://zarr-specs.readthedocs.io/en/latest/v3/core/v3.0.html#fill-value>.
 
+use super::DataType;
+
 /// The fill value of the Zarr array.
 ///
 /// Provides an element value to use for uninitialised portions of the Zarr array.
+///
+/// A fill value is the native endian byte representation of an element.
+/// It can be created from a scalar with [`FillValue::from_scalar`] (or [`From`]) and read back with [`FillValue::as_scalar`].
+/// Conversion to and from fill value metadata, including the special floating point values of the Zarr V3 specification (`"NaN"`, `"Infinity"`, `"-Infinity"`, and hex strings such as `"0x7fc00001"`), is handled by [`DataType::metadata_fill_value`](crate::array::DataType::metadata_fill_value) and [`DataType::fill_value_from_metadata`](crate::array::DataType::fill_value_from_metadata).
+/// Floating point values are stored bit-exactly, so NaN payloads and signed zeros are preserved.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct FillValue(Vec<u8>);
 
@@ -153,6 +160,29 @@ impl FillValue {
         Self(bytes)
     }
 
+    /// Create a fill value from a scalar `value`.
+    ///
+    /// This is equivalent to [`FillValue::from`], and is supported for all scalar types of the supported data types.
+    #[must_use]
+    pub fn from_scalar<T>(value: T) -> Self
+    where
+        Self: From<T>,
+    {
+        Self::from(value)
+    }
+
+    /// Return the fill value as a scalar of type `T`, or [`None`] if the size of `T` does not match the size of the fill value.
+    ///
+    /// The bytes are reinterpreted as `T` without validation against the data type of an array.
+    #[must_use]
+    pub fn as_scalar<T: bytemuck::Pod>(&self) -> Option<T> {
+        if self.0.len() == std::mem::size_of::<T>() {
+            Some(bytemuck::pod_read_unaligned(&self.0))
+        } else {
+            None
+        }
+    }
+
     /// Returns the size in bytes of the fill value.
     #[must_use]
     pub fn size(&self) -> usize {
@@ -165,6 +195,42 @@ impl FillValue {
         &self.0
     }
 
+    /// Check if the bytes of an element of `data_type` are equal to the fill value.
+    ///
+    /// Floating point elements (including the components of complex elements) are also equal if both are NaN, irrespective of the NaN payload.
+    /// Otherwise, elements are compared bytewise.
+    #[must_use]
+    pub fn equals_element(&self, data_type: &DataType, element: &[u8]) -> bool {
+        fn eq_or_nan<const N: usize>(a: &[u8], b: &[u8], is_nan: impl Fn([u8; N]) -> bool) -> bool {
+            a.len() == b.len()
+                && std::iter::zip(a.chunks_exact(N), b.chunks_exact(N)).all(|(a, b)| {
+                    a == b
+                        || (is_nan(a.try_into().unwrap_or([0; N]))
+                            && is_nan(b.try_into().unwrap_or([0; N])))
+                })
+        }
+        let fill_value = self.0.as_slice();
+        match data_type {
+            DataType::Float16 => eq_or_nan(fill_value, element, |bytes: [u8; 2]| {
+                half::f16::from_ne_bytes(bytes).is_nan()
+            }),
+            DataType::BFloat16 => eq_or_nan(fill_value, element, |bytes: [u8; 2]| {
+                half::bf16::from_ne_bytes(bytes).is_nan()
+            }),
+            DataType::Float32 | DataType::Complex64 => {
+                eq_or_nan(fill_value, element, |bytes: [u8; 4]| {
+                    f32::from_ne_bytes(bytes).is_nan()
+                })
+            }
+            DataType::Float64 | DataType::Complex128 => {
+                eq_or_nan(fill_value, element, |bytes: [u8; 8]| {
+                    f64::from_ne_bytes(bytes).is_nan()
+                })
+            }
+            _ => fill_value == element,
+        }
+    }
+
     /// Check if the bytes are equal to a sequence of the fill value.
     #[allow(clippy::missing_panics_doc)]
     #[must_use]
@@ -237,6 +303,96 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn fill_value_equals_element() {
+        let fill_value = FillValue::from(f32::NAN);
+        let nan_payload = f32::from_bits(0x7fc0_0001);
+        assert!(fill_value.equals_element(&DataType::Float32, &f32::NAN.to_ne_bytes()));
+        assert!(fill_value.equals_element(&DataType::Float32, &nan_payload.to_ne_bytes()));
+        assert!(!fill_value.equals_element(&DataType::Float32, &1.0f32.to_ne_bytes()));
+        // A NaN is only special for floating point data types
+        assert!(!fill_value.equals_element(&DataType::UInt32, &nan_payload.to_ne_bytes()));
+
+        let fill_value = FillValue::from(num::complex::Complex64::new(f64::NAN, 0.0));
+        assert!(fill_value.equals_element(
+            &DataType::Complex128,
+            &[(-f64::NAN).to_ne_bytes(), 0.0f64.to_ne_bytes()].concat()
+        ));
+        assert!(!fill_value.equals_element(
+            &DataType::Complex128,
+            &[f64::NAN.to_ne_bytes(), 1.0f64.to_ne_bytes()].concat()
+        ));
+
+        let fill_value = FillValue::from("");
+        assert!(fill_value.equals_element(&DataType::String, b""));
+        assert!(!fill_value.equals_element(&DataType::String, b"a"));
+    }
+
+    #[test]
+    fn fill_value_scalar() {
+        assert_eq!(FillValue::from_scalar(1u8).as_scalar::<u8>(), Some(1u8));
+        assert_eq!(
+            FillValue::from_scalar(-1i16).as_scalar::<i16>(),
+            Some(-1i16)
+        );
+        assert_eq!(FillValue::from_scalar(1u32).as_scalar::<u32>(), Some(1u32));
+        assert_eq!(
+            FillValue::from_scalar(-1i64).as_scalar::<i64>(),
+            Some(-1i64)
+        );
+        assert_eq!(
+            FillValue::from_scalar(half::f16::ONE).as_scalar::<half::f16>(),
+            Some(half::f16::ONE)
+        );
+        assert_eq!(
+            FillValue::from_scalar(half::bf16::ONE).as_scalar::<half::bf16>(),
+            Some(half::bf16::ONE)
+        );
+        assert_eq!(
+            FillValue::from_scalar(num::complex::Complex64::new(1.0, -2.0))
+                .as_scalar::<num::complex::Complex64>(),
+            Some(num::complex::Complex64::new(1.0, -2.0))
+        );
+        assert_eq!(FillValue::from_scalar(1u32).as_scalar::<u64>(), None);
+
+        // Special floats round trip exactly, including through metadata
+        let nan_payload = f32::from_bits(0x7fc0_0001);
+        for value in [
+            f32::NAN,
+            nan_payload,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            -0.0,
+            0.1,
+        ] {
+            let fill_value = FillValue::from_scalar(value);
+            assert_eq!(
+                fill_value.as_scalar::<f32>().unwrap().to_bits(),
+                value.to_bits()
+            );
+            let metadata = DataType::Float32.metadata_fill_value(&fill_value);
+            assert_eq!(
+                DataType::Float32
+                    .fill_value_from_metadata(&metadata)
+                    .unwrap(),
+                fill_value
+            );
+        }
+        for value in [f64::NAN, f64::from_bits(0x7ff8_0000_0000_0001), -0.0, 0.1] {
+            let fill_value = FillValue::from_scalar(value);
+            let metadata = DataType::Float64.metadata_fill_value(&fill_value);
+            assert_eq!(
+                DataType::Float64
+                    .fill_value_from_metadata(&metadata)
+                    .unwrap()
+                    .as_scalar::<f64>()
+                    .unwrap()
+                    .to_bits(),
+                value.to_bits()
+            );
+        }
+    }
+
     #[test]
     fn fill_value() {
         assert_eq!(
@@ -23,7 +23,7 @@ mod contiguous_linearised_indices_iterator;
 mod indices_iterator;
 mod linearised_indices_iterator;
 
-pub use chunks_iterator::{Chunks, ChunksIterator};
+pub use chunks_iterator::{BoundedChunk, BoundedChunksIterator, Chunks, ChunksIterator};
 pub use contiguous_indices_iterator::{ContiguousIndices, ContiguousIndicesIterator};
 pub use contiguous_linearised_indices_iterator::{
     ContiguousLinearisedIndices, ContiguousLinearisedIndicesIterator,
@@ -204,4 +204,47 @@ mod tests {
             (vec![2, 1], ArraySubset::new_with_ranges(&[4..6, 3..6])),
         ]);
     }
+
+    #[test]
+    fn array_subset_iter_chunks_bounded() {
+        let array_shape = [5, 5];
+        let subset = ArraySubset::new_with_shape(array_shape.to_vec());
+        let chunk_shape = [NonZeroU64::new(2).unwrap(), NonZeroU64::new(3).unwrap()];
+        let chunks = subset.chunks(&chunk_shape).unwrap();
+        assert!(chunks.iter_bounded(&[5]).is_err());
+        let chunks = chunks
+            .iter_bounded(&array_shape)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 6);
+
+        let full = &chunks[0];
+        assert_eq!(full.chunk_indices, vec![0, 0]);
+        assert!(!full.is_edge());
+        assert_eq!(
+            full.bounded_subset(),
+            &ArraySubset::new_with_ranges(&[0..2, 0..3])
+        );
+
+        let edge = &chunks[1];
+        assert_eq!(edge.chunk_indices, vec![0, 1]);
+        assert!(edge.is_edge());
+        assert_eq!(
+            edge.chunk_subset,
+            ArraySubset::new_with_ranges(&[0..2, 3..6])
+        );
+        assert_eq!(
+            edge.edge_subset,
+            Some(ArraySubset::new_with_ranges(&[0..2, 3..5]))
+        );
+
+        let corner = chunks.last().unwrap();
+        assert_eq!(corner.chunk_indices, vec![2, 1]);
+        assert_eq!(
+            corner.bounded_subset(),
+            &ArraySubset::new_with_ranges(&[4..5, 3..5])
+        );
+
+        assert_eq!(chunks.iter().filter(|chunk| chunk.is_edge()).count(), 4);
+    }
 }
@@ -110,6 +110,54 @@ impl Chunks {
     pub fn iter(&self) -> ChunksIterator<'_> {
         <&Self as IntoIterator>::into_iter(self)
     }
+
+    /// Create a new serial iterator that identifies edge chunks extending beyond `array_shape`.
+    ///
+    /// # Errors
+    /// Returns [`IncompatibleDimensionalityError`] if `array_shape` does not match the dimensionality of the chunks.
+    pub fn iter_bounded<'a>(
+        &'a self,
+        array_shape: &'a [u64],
+    ) -> Result<BoundedChunksIterator<'a>, IncompatibleDimensionalityError> {
+        if array_shape.len() == self.chunk_shape.len() {
+            Ok(BoundedChunksIterator {
+                inner: self.iter(),
+                array_shape,
+            })
+        } else {
+            Err(IncompatibleDimensionalityError(
+                array_shape.len(),
+                self.chunk_shape.len(),
+            ))
+        }
+    }
+}
+
+/// A chunk and its extent within an array.
+///
+/// See [`Chunks::iter_bounded`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedChunk {
+    /// The chunk indices.
+    pub chunk_indices: ArrayIndices,
+    /// The array subset of the chunk, which may extend beyond the array shape.
+    pub chunk_subset: ArraySubset,
+    /// The array subset of the chunk cropped to the array shape if it is an edge chunk, otherwise [`None`].
+    pub edge_subset: Option<ArraySubset>,
+}
+
+impl BoundedChunk {
+    /// Returns true if the chunk extends beyond the array shape.
+    #[must_use]
+    pub fn is_edge(&self) -> bool {
+        self.edge_subset.is_some()
+    }
+
+    /// Return the array subset of the chunk bounded by the array shape.
+    #[must_use]
+    pub fn bounded_subset(&self) -> &ArraySubset {
+        self.edge_subset.as_ref().unwrap_or(&self.chunk_subset)
+    }
 }
 
 impl<'a> IntoIterator for &'a Chunks {
@@ -182,6 +230,55 @@ impl ExactSizeIterator for ChunksIterator<'_> {}
 
 impl FusedIterator for ChunksIterator<'_> {}
 
+/// Serial bounded chunks iterator.
+///
+/// See [`Chunks::iter_bounded`].
+pub struct BoundedChunksIterator<'a> {
+    inner: ChunksIterator<'a>,
+    array_shape: &'a [u64],
+}
+
+impl BoundedChunksIterator<'_> {
+    fn bounded_chunk(
+        &self,
+        (chunk_indices, chunk_subset): (ArrayIndices, ArraySubset),
+    ) -> BoundedChunk {
+        let is_edge = std::iter::zip(chunk_subset.end_exc(), self.array_shape)
+            .any(|(end, &shape)| end > shape);
+        let edge_subset =
+            is_edge.then(|| unsafe { chunk_subset.bound_unchecked(self.array_shape) });
+        BoundedChunk {
+            chunk_indices,
+            chunk_subset,
+            edge_subset,
+        }
+    }
+}
+
+impl Iterator for BoundedChunksIterator<'_> {
+    type Item = BoundedChunk;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|chunk| self.bounded_chunk(chunk))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for BoundedChunksIterator<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|chunk| self.bounded_chunk(chunk))
+    }
+}
+
+impl ExactSizeIterator for BoundedChunksIterator<'_> {}
+
+impl FusedIterator for BoundedChunksIterator<'_> {}
+
 /// Parallel chunks iterator.
 ///
 /// See [`Chunks`].
@@ -2,14 +2,22 @@
 //!
 //! See [`Config`] for the list of options.
 
-use crate::metadata::v3::array::codec;
+use crate::{
+    array::{
+        codec::{ArrayToBytesCodecTraits, BytesToBytesCodecTraits},
+        ArrayMetadataOptions, ChunkKeySeparator,
+    },
+    metadata::v3::array::codec,
+    plugin::PluginCreateError,
+};
 use std::{
     collections::HashMap,
-    sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{Arc, OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Duration,
 };
 
 #[cfg(doc)]
-use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
+use crate::array::{codec::CodecOptions, ArrayBuilder};
 
 /// Global configuration options for the `zarrs` crate.
 ///
@@ -34,6 +42,15 @@ use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
 /// This incurs a computational overhead as each element must be tested for equality to the fill value before a chunk is encoded.
 /// If `true`, the aforementioned test is skipped and all chunks are stored.
 ///
+/// ### Allow Trailing Bytes
+///  > default: [`false`]
+///
+/// [`CodecOptions::allow_trailing_bytes()`] defaults to [`Config::allow_trailing_bytes()`].
+///
+/// If `true`, trailing bytes following a complete compressed stream are ignored when decoding with the `blosc`, `gzip`, and `zstd` codecs.
+/// If `false`, decoding fails with [`CodecError::TrailingBytes`](crate::array::codec::CodecError::TrailingBytes), which reports the number of trailing bytes.
+/// Some producers pad encoded chunks, and this option permits reading such data.
+///
 /// ### Codec Concurrent Target
 /// > default: [`std::thread::available_parallelism`]`()`
 ///
@@ -53,6 +70,107 @@ use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
 /// For example, `array_store_chunks` will concurrently encode and store up to four chunks at a time by default.
 /// The concurrency of internal codecs is adjusted to accomodate for the chunk concurrency in accordance with the concurrent target set in the [`CodecOptions`] parameter of an encode or decode method.
 ///
+/// ### Decode Parallel Minimum Elements
+/// > default: `16384`
+///
+/// [`CodecOptions::decode_parallel_min_elements()`] defaults to [`Config::decode_parallel_min_elements()`].
+///
+/// The minimum number of elements in a chunk for a codec chain to decode it in parallel.
+/// Smaller chunks are decoded serially (with a concurrent target of one), since spawning parallel tasks for tiny chunks costs more than it saves.
+/// The [codec concurrent target](#codec-concurrent-target) is then an upper bound that is only used if worthwhile.
+/// Set to zero to always permit parallel decoding.
+///
+/// ### Async Read Timeout
+/// > default: [`None`] (no timeout)
+///
+/// [`CodecOptions::async_read_timeout()`] defaults to [`Config::async_read_timeout()`].
+///
+/// If set, each storage read made by the async [`Array`](crate::array::Array) retrieve methods must complete within the timeout, otherwise it fails with a [`StorageError::Timeout`](crate::storage::StorageError::Timeout).
+/// This enables a retrying storage adapter to react to stalled requests.
+/// The timeout does not apply to sync stores.
+///
+/// ### Retrieve Size Limit
+/// > default: `4294967296` (4 GiB)
+///
+/// The maximum decoded size in bytes of an array read with [`Array::retrieve_all`](crate::array::Array::retrieve_all) and its variants.
+/// Reading an array that exceeds the limit fails with [`ArrayError::RetrieveSizeLimitExceeded`](crate::array::ArrayError::RetrieveSizeLimitExceeded) before anything is retrieved.
+/// This guards against accidentally allocating an absurd amount of memory.
+/// The limit only applies to data types with a fixed size.
+/// It can be overridden per call with [`CodecOptions::set_retrieve_size_limit`](crate::array::codec::CodecOptions::set_retrieve_size_limit).
+///
+/// ### Retrieve Element Limit
+/// > default: `4294967296` (2<sup>32</sup> elements)
+///
+/// The maximum number of elements of an array read with [`Array::retrieve_all`](crate::array::Array::retrieve_all) or an array subset read with [`Array::retrieve_array_subset`](crate::array::Array::retrieve_array_subset) and their variants.
+/// Reading more elements fails with [`ArrayError::RetrieveElementLimitExceeded`](crate::array::ArrayError::RetrieveElementLimitExceeded) before anything is retrieved or allocated.
+/// Unlike the retrieve size limit, this applies to subsets (e.g. with a mistyped shape) and to data types with a variable size.
+/// It can be overridden per call with [`CodecOptions::set_retrieve_element_limit`](crate::array::codec::CodecOptions::set_retrieve_element_limit).
+///
+/// ### Chunk Size Limit
+/// > default: `4294967296` (4 GiB)
+///
+/// The maximum decoded size in bytes of a chunk of an array created with an [`ArrayBuilder`].
+/// [`ArrayBuilder::build`] fails with [`ArrayCreateError::ChunkTooLarge`](crate::array::ArrayCreateError::ChunkTooLarge) if a chunk exceeds the limit.
+/// This catches a common misconfiguration, such as a chunk shape equal to the shape of a huge array, before chunks are read into memory.
+/// The limit only applies to data types with a fixed size and does not apply to opened arrays.
+/// Only the chunk at the origin of the chunk grid is checked, which covers every chunk of a regular chunk grid but not the other chunks of an irregular chunk grid (e.g. `rectangular`).
+///
+/// ### Duplicate Dimension Names
+/// > default: [`DuplicateDimensionNames::Allow`]
+///
+/// The behaviour of [`ArrayBuilder::build`] if the dimension names of an array contain a duplicate non-null name (e.g. `["y", "x", "x"]`).
+/// Duplicate dimension names are permitted by the Zarr V3 specification, but are usually a user error and are not supported by some consumers (e.g. `xarray`).
+/// Multiple unnamed (`null`) dimensions are always permitted.
+///
+/// ### Must Understand Level
+/// > default: [`MustUnderstandLevel::Strict`]
+///
+/// The enforcement of additional fields in array and group metadata that must be understood (i.e. without `"must_understand": false`).
+/// `zarrs` does not understand any additional fields, so these are typically written by a newer Zarr implementation or an extension.
+///  - [`MustUnderstandLevel::Strict`]: creating or opening an array or group fails.
+///  - [`MustUnderstandLevel::LenientRead`]: arrays and groups can be opened for inspection (e.g. of their metadata), but accessing array data fails.
+///  - [`MustUnderstandLevel::Permissive`]: the fields are ignored with a warning.
+///
+/// ### Default Missing Fill Value
+/// > default: [`false`]
+///
+/// The Zarr V3 specification requires a `fill_value` in array metadata, but some non-conformant producers omit it.
+/// If false, opening an array without a `fill_value` fails.
+/// If true, [`Array::open`](crate::array::Array::open) and variants apply a default fill value matching the data type with a warning.
+/// The default fill value has all bytes zero (e.g. `0` for numeric data types, `false` for `bool`), or is empty for variable-sized data types (e.g. `""` for `string`).
+///
+/// ### Lenient Group Metadata
+/// > default: [`false`]
+///
+/// The handling of Zarr V3 group metadata (`zarr.json`) with a mismatched `node_type` or `zarr_format` (e.g. array metadata) when opening a group.
+/// If false, [`Group::open`](crate::group::Group::open) and variants fail.
+/// If true, the group is opened with a warning for inspection of corrupted or mislabelled hierarchies.
+/// The entire metadata document becomes the attributes of the group, and the mismatch is reported by [`Group::metadata_mismatch`](crate::group::Group::metadata_mismatch).
+///
+/// ### Default Array To Bytes Codec
+/// > default: [`None`] (the `bytes` codec with native endian encoding)
+///
+/// The array to bytes codec used by an [`ArrayBuilder`] for data types with a fixed size if it is not explicitly set with [`ArrayBuilder::array_to_bytes_codec`].
+/// Variable-size data types always default to the `vlen` codec.
+///
+/// The codec is validated when it is set, and must produce codec metadata.
+///
+/// ### Default Bytes To Bytes Codecs
+/// > default: `[]` (no compression)
+///
+/// The bytes to bytes codecs used by an [`ArrayBuilder`] if they are not explicitly set with [`ArrayBuilder::bytes_to_bytes_codecs`].
+/// For example, an application could set a `zstd` codec here once rather than configuring each array builder.
+///
+/// The codecs are validated when they are set, and must each produce codec metadata.
+///
+/// ### Default Chunk Key Separator
+/// > default: [`ChunkKeySeparator::Slash`] (`/`)
+///
+/// The separator of the `default` chunk key encoding used by an [`ArrayBuilder`] if the chunk key encoding is not explicitly set with [`ArrayBuilder::chunk_key_encoding`] or [`ArrayBuilder::chunk_key_encoding_default_separator`].
+/// The `/` separator places each chunk index in a separate path component, which is friendly to object store prefixes and listing.
+///
+/// Opened arrays are unaffected, their chunk key encoding is always read from their metadata.
+///
 /// ## Metadata Options
 ///
 /// ### Experimental Codec Store Metadata If Encode Only
@@ -90,6 +208,23 @@ use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
 ///  }
 /// ```
 ///
+/// ### Serialize Empty Attributes
+/// > default: [`false`]
+///
+/// [`ArrayMetadataOptions::serialize_empty_attributes`](crate::array::ArrayMetadataOptions::serialize_empty_attributes) and [`GroupMetadataOptions::serialize_empty_attributes`](crate::group::GroupMetadataOptions::serialize_empty_attributes) default to [`Config::serialize_empty_attributes`].
+///
+/// If true, empty attributes are written by the `store_metadata` methods of [`crate::array::Array`] and [`crate::group::Group`] (`"attributes": {}` for Zarr V3, an empty `.zattrs` for Zarr V2) rather than omitted.
+/// Parsing is unaffected, missing attributes are always interpreted as empty.
+///
+/// ### Stage Metadata Writes
+/// > default: [`false`]
+///
+/// [`ArrayMetadataOptions::stage_metadata_writes`](crate::array::ArrayMetadataOptions::stage_metadata_writes) and [`GroupMetadataOptions::stage_metadata_writes`](crate::group::GroupMetadataOptions::stage_metadata_writes) default to [`Config::stage_metadata_writes`].
+///
+/// If true, the `store_metadata` methods of [`crate::array::Array`] and [`crate::group::Group`] write metadata to a unique temporary key (e.g. `.zarr.json.{pid}.{n}.tmp`) and then rename it to the final key.
+/// This prevents concurrent readers from observing partially written metadata.
+/// Metadata is written to the final key directly if the store does not [support renaming](crate::storage::WritableStorageTraits::supports_rename), which offers no such guarantee.
+///
 /// ### Experimental Codec Names
 /// > default: See the [crate root documentation](crate#array-support).
 ///
@@ -101,6 +236,7 @@ use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
 ///
 /// If `true`, [`Array::store_chunk_subset`](crate::array::Array::store_chunk_subset) and [`Array::store_array_subset`](crate::array::Array::store_array_subset) and variants can use partial encoding.
 /// This is relevant when using the sharding codec, as it enables inner chunks to be written without reading and writing entire shards.
+/// Partial encoding is always used if no codec needs to encode an entire chunk (e.g. the `bytes` codec without any bytes to bytes codecs), regardless of this setting.
 ///
 /// This is an experimental feature for now until it has more comprehensively tested and support is added in the async API.
 #[derive(Debug)]
@@ -108,12 +244,27 @@ use crate::array::{codec::CodecOptions, ArrayMetadataOptions};
 pub struct Config {
     validate_checksums: bool,
     store_empty_chunks: bool,
+    allow_trailing_bytes: bool,
     codec_concurrent_target: usize,
     chunk_concurrent_minimum: usize,
+    decode_parallel_min_elements: u64,
+    async_read_timeout: Option<Duration>,
+    retrieve_size_limit: u64,
+    retrieve_element_limit: u64,
+    chunk_size_limit: u64,
+    duplicate_dimension_names: DuplicateDimensionNames,
+    must_understand_level: MustUnderstandLevel,
+    default_missing_fill_value: bool,
+    lenient_group_metadata: bool,
+    default_array_to_bytes_codec: Option<Arc<dyn ArrayToBytesCodecTraits>>,
+    default_bytes_to_bytes_codecs: Vec<Arc<dyn BytesToBytesCodecTraits>>,
+    default_chunk_key_separator: ChunkKeySeparator,
     experimental_codec_store_metadata_if_encode_only: bool,
     metadata_convert_version: MetadataConvertVersion,
     metadata_erase_version: MetadataEraseVersion,
     include_zarrs_metadata: bool,
+    serialize_empty_attributes: bool,
+    stage_metadata_writes: bool,
     experimental_codec_names: HashMap<&'static str, String>,
     experimental_partial_encoding: bool,
 }
@@ -126,6 +277,10 @@ impl Default for Config {
             // Array to array
             #[cfg(feature = "bitround")]
             (codec::bitround::IDENTIFIER, "https://codec.zarrs.dev/array_to_array/bitround".to_string()),
+            #[cfg(feature = "delta")]
+            (codec::delta::IDENTIFIER, "https://codec.zarrs.dev/array_to_array/delta".to_string()),
+            #[cfg(feature = "fixedscaleoffset")]
+            (codec::fixedscaleoffset::IDENTIFIER, "https://codec.zarrs.dev/array_to_array/fixedscaleoffset".to_string()),
             // Array to bytes
             #[cfg(feature = "zfp")]
             (codec::zfp::IDENTIFIER, "https://codec.zarrs.dev/array_to_bytes/zfp".to_string()),
@@ -134,8 +289,14 @@ impl Default for Config {
             (codec::vlen::IDENTIFIER, "https://codec.zarrs.dev/array_to_bytes/vlen".to_string()),
             (codec::vlen_v2::IDENTIFIER, "https://codec.zarrs.dev/array_to_bytes/vlen_v2".to_string()),
             // Bytes to bytes
+            #[cfg(feature = "adler32")]
+            (codec::adler32::IDENTIFIER, "https://codec.zarrs.dev/bytes_to_bytes/adler32".to_string()),
+            #[cfg(feature = "brotli")]
+            (codec::brotli::IDENTIFIER, "https://codec.zarrs.dev/bytes_to_bytes/brotli".to_string()),
             #[cfg(feature = "bz2")]
             (codec::bz2::IDENTIFIER, "https://codec.zarrs.dev/bytes_to_bytes/bz2".to_string()),
+            #[cfg(feature = "lz4")]
+            (codec::lz4::IDENTIFIER, "https://codec.zarrs.dev/bytes_to_bytes/lz4".to_string()),
         ]);
 
         let concurrency_multiply = 1;
@@ -143,14 +304,29 @@ impl Default for Config {
         Self {
             validate_checksums: true,
             store_empty_chunks: false,
+            allow_trailing_bytes: false,
             codec_concurrent_target: std::thread::available_parallelism().unwrap().get()
                 * concurrency_multiply
                 + concurrency_add,
             chunk_concurrent_minimum: 4,
+            decode_parallel_min_elements: 16384,
+            async_read_timeout: None,
+            retrieve_size_limit: 4 * 1024 * 1024 * 1024,
+            retrieve_element_limit: 1 << 32,
+            chunk_size_limit: 4 * 1024 * 1024 * 1024,
+            duplicate_dimension_names: DuplicateDimensionNames::Allow,
+            must_understand_level: MustUnderstandLevel::Strict,
+            default_missing_fill_value: false,
+            lenient_group_metadata: false,
+            default_array_to_bytes_codec: None,
+            default_bytes_to_bytes_codecs: Vec::default(),
+            default_chunk_key_separator: ChunkKeySeparator::Slash,
             experimental_codec_store_metadata_if_encode_only: false,
             metadata_convert_version: MetadataConvertVersion::Default,
             metadata_erase_version: MetadataEraseVersion::Default,
             include_zarrs_metadata: true,
+            serialize_empty_attributes: false,
+            stage_metadata_writes: false,
             experimental_codec_names,
             experimental_partial_encoding: false,
         }
@@ -182,6 +358,18 @@ impl Config {
         self
     }
 
+    /// Get the [allow trailing bytes](#allow-trailing-bytes) configuration.
+    #[must_use]
+    pub fn allow_trailing_bytes(&self) -> bool {
+        self.allow_trailing_bytes
+    }
+
+    /// Set the [allow trailing bytes](#allow-trailing-bytes) configuration.
+    pub fn set_allow_trailing_bytes(&mut self, allow_trailing_bytes: bool) -> &mut Self {
+        self.allow_trailing_bytes = allow_trailing_bytes;
+        self
+    }
+
     /// Get the [codec concurrent target](#codec-concurrent-target) configuration.
     #[must_use]
     pub fn codec_concurrent_target(&self) -> usize {
@@ -206,6 +394,193 @@ impl Config {
         self
     }
 
+    /// Get the [decode parallel minimum elements](#decode-parallel-minimum-elements) configuration.
+    #[must_use]
+    pub fn decode_parallel_min_elements(&self) -> u64 {
+        self.decode_parallel_min_elements
+    }
+
+    /// Set the [decode parallel minimum elements](#decode-parallel-minimum-elements) configuration.
+    pub fn set_decode_parallel_min_elements(
+        &mut self,
+        decode_parallel_min_elements: u64,
+    ) -> &mut Self {
+        self.decode_parallel_min_elements = decode_parallel_min_elements;
+        self
+    }
+
+    /// Get the [async read timeout](#async-read-timeout) configuration.
+    #[must_use]
+    pub fn async_read_timeout(&self) -> Option<Duration> {
+        self.async_read_timeout
+    }
+
+    /// Set the [async read timeout](#async-read-timeout) configuration.
+    pub fn set_async_read_timeout(&mut self, async_read_timeout: Option<Duration>) -> &mut Self {
+        self.async_read_timeout = async_read_timeout;
+        self
+    }
+
+    /// Get the [retrieve size limit](#retrieve-size-limit) configuration.
+    #[must_use]
+    pub fn retrieve_size_limit(&self) -> u64 {
+        self.retrieve_size_limit
+    }
+
+    /// Set the [retrieve size limit](#retrieve-size-limit) configuration.
+    pub fn set_retrieve_size_limit(&mut self, retrieve_size_limit: u64) -> &mut Self {
+        self.retrieve_size_limit = retrieve_size_limit;
+        self
+    }
+
+    /// Get the [retrieve element limit](#retrieve-element-limit) configuration.
+    #[must_use]
+    pub fn retrieve_element_limit(&self) -> u64 {
+        self.retrieve_element_limit
+    }
+
+    /// Set the [retrieve element limit](#retrieve-element-limit) configuration.
+    pub fn set_retrieve_element_limit(&mut self, retrieve_element_limit: u64) -> &mut Self {
+        self.retrieve_element_limit = retrieve_element_limit;
+        self
+    }
+
+    /// Get the [chunk size limit](#chunk-size-limit) configuration.
+    #[must_use]
+    pub fn chunk_size_limit(&self) -> u64 {
+        self.chunk_size_limit
+    }
+
+    /// Set the [chunk size limit](#chunk-size-limit) configuration.
+    pub fn set_chunk_size_limit(&mut self, chunk_size_limit: u64) -> &mut Self {
+        self.chunk_size_limit = chunk_size_limit;
+        self
+    }
+
+    /// Get the [duplicate dimension names](#duplicate-dimension-names) configuration.
+    #[must_use]
+    pub fn duplicate_dimension_names(&self) -> DuplicateDimensionNames {
+        self.duplicate_dimension_names
+    }
+
+    /// Set the [duplicate dimension names](#duplicate-dimension-names) configuration.
+    pub fn set_duplicate_dimension_names(
+        &mut self,
+        duplicate_dimension_names: DuplicateDimensionNames,
+    ) -> &mut Self {
+        self.duplicate_dimension_names = duplicate_dimension_names;
+        self
+    }
+
+    /// Get the [must understand level](#must-understand-level) configuration.
+    #[must_use]
+    pub fn must_understand_level(&self) -> MustUnderstandLevel {
+        self.must_understand_level
+    }
+
+    /// Set the [must understand level](#must-understand-level) configuration.
+    pub fn set_must_understand_level(
+        &mut self,
+        must_understand_level: MustUnderstandLevel,
+    ) -> &mut Self {
+        self.must_understand_level = must_understand_level;
+        self
+    }
+
+    /// Get the [default missing fill value](#default-missing-fill-value) configuration.
+    #[must_use]
+    pub fn default_missing_fill_value(&self) -> bool {
+        self.default_missing_fill_value
+    }
+
+    /// Set the [default missing fill value](#default-missing-fill-value) configuration.
+    pub fn set_default_missing_fill_value(
+        &mut self,
+        default_missing_fill_value: bool,
+    ) -> &mut Self {
+        self.default_missing_fill_value = default_missing_fill_value;
+        self
+    }
+
+    /// Get the [lenient group metadata](#lenient-group-metadata) configuration.
+    #[must_use]
+    pub fn lenient_group_metadata(&self) -> bool {
+        self.lenient_group_metadata
+    }
+
+    /// Set the [lenient group metadata](#lenient-group-metadata) configuration.
+    pub fn set_lenient_group_metadata(&mut self, lenient_group_metadata: bool) -> &mut Self {
+        self.lenient_group_metadata = lenient_group_metadata;
+        self
+    }
+
+    /// Get the [default array to bytes codec](#default-array-to-bytes-codec) configuration.
+    #[must_use]
+    pub fn default_array_to_bytes_codec(&self) -> Option<&Arc<dyn ArrayToBytesCodecTraits>> {
+        self.default_array_to_bytes_codec.as_ref()
+    }
+
+    /// Set the [default array to bytes codec](#default-array-to-bytes-codec) configuration.
+    ///
+    /// # Errors
+    /// Returns a [`PluginCreateError`] if the codec does not produce codec metadata.
+    /// The configuration is unchanged on error.
+    pub fn set_default_array_to_bytes_codec(
+        &mut self,
+        codec: Option<Arc<dyn ArrayToBytesCodecTraits>>,
+    ) -> Result<&mut Self, PluginCreateError> {
+        if let Some(codec) = &codec {
+            let options = ArrayMetadataOptions::from_config(self);
+            if codec.create_metadata_opt(&options).is_none() {
+                return Err(PluginCreateError::from(format!(
+                    "default array to bytes codec {codec:?} does not produce codec metadata"
+                )));
+            }
+        }
+        self.default_array_to_bytes_codec = codec;
+        Ok(self)
+    }
+
+    /// Get the [default bytes to bytes codecs](#default-bytes-to-bytes-codecs) configuration.
+    #[must_use]
+    pub fn default_bytes_to_bytes_codecs(&self) -> &[Arc<dyn BytesToBytesCodecTraits>] {
+        &self.default_bytes_to_bytes_codecs
+    }
+
+    /// Set the [default bytes to bytes codecs](#default-bytes-to-bytes-codecs) configuration.
+    ///
+    /// # Errors
+    /// Returns a [`PluginCreateError`] if any codec does not produce codec metadata.
+    /// The configuration is unchanged on error.
+    pub fn set_default_bytes_to_bytes_codecs(
+        &mut self,
+        codecs: Vec<Arc<dyn BytesToBytesCodecTraits>>,
+    ) -> Result<&mut Self, PluginCreateError> {
+        let options = ArrayMetadataOptions::from_config(self);
+        if let Some(codec) = codecs
+            .iter()
+            .find(|codec| codec.create_metadata_opt(&options).is_none())
+        {
+            return Err(PluginCreateError::from(format!(
+                "default bytes to bytes codec {codec:?} does not produce codec metadata"
+            )));
+        }
+        self.default_bytes_to_bytes_codecs = codecs;
+        Ok(self)
+    }
+
+    /// Get the [default chunk key separator](#default-chunk-key-separator) configuration.
+    #[must_use]
+    pub fn default_chunk_key_separator(&self) -> ChunkKeySeparator {
+        self.default_chunk_key_separator
+    }
+
+    /// Set the [default chunk key separator](#default-chunk-key-separator) configuration.
+    pub fn set_default_chunk_key_separator(&mut self, separator: ChunkKeySeparator) -> &mut Self {
+        self.default_chunk_key_separator = separator;
+        self
+    }
+
     /// Get the [experimental codec store metadata if encode only](#experimental-codec-store-metadata-if-encode-only) configuration.
     #[must_use]
     pub fn experimental_codec_store_metadata_if_encode_only(&self) -> bool {
@@ -257,6 +632,33 @@ impl Config {
         self
     }
 
+    /// Get the [serialize empty attributes](#serialize-empty-attributes) configuration.
+    #[must_use]
+    pub fn serialize_empty_attributes(&self) -> bool {
+        self.serialize_empty_attributes
+    }
+
+    /// Set the [serialize empty attributes](#serialize-empty-attributes) configuration.
+    pub fn set_serialize_empty_attributes(
+        &mut self,
+        serialize_empty_attributes: bool,
+    ) -> &mut Self {
+        self.serialize_empty_attributes = serialize_empty_attributes;
+        self
+    }
+
+    /// Get the [stage metadata writes](#stage-metadata-writes) configuration.
+    #[must_use]
+    pub fn stage_metadata_writes(&self) -> bool {
+        self.stage_metadata_writes
+    }
+
+    /// Set the [stage metadata writes](#stage-metadata-writes) configuration.
+    pub fn set_stage_metadata_writes(&mut self, stage_metadata_writes: bool) -> &mut Self {
+        self.stage_metadata_writes = stage_metadata_writes;
+        self
+    }
+
     /// Get the [experimental codec names](#experimental-codec-names) configuration.
     #[must_use]
     pub fn experimental_codec_names(&self) -> &HashMap<&'static str, String> {
@@ -320,6 +722,32 @@ pub enum MetadataRetrieveVersion {
     V2,
 }
 
+/// The behaviour of [`ArrayBuilder::build`] if an array has duplicate non-null dimension names.
+///
+/// See the [duplicate dimension names](Config#duplicate-dimension-names) configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateDimensionNames {
+    /// Permit duplicate dimension names.
+    Allow,
+    /// Permit duplicate dimension names, but log a warning.
+    Warn,
+    /// Fail with [`ArrayCreateError::DuplicateDimensionName`](crate::array::ArrayCreateError::DuplicateDimensionName).
+    Error,
+}
+
+/// The enforcement of additional fields in array and group metadata that must be understood.
+///
+/// See the [must understand level](Config#must-understand-level) configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MustUnderstandLevel {
+    /// Fail to create an array or group with [`UnsupportedAdditionalFieldError`](crate::metadata::v3::UnsupportedAdditionalFieldError).
+    Strict,
+    /// Permit creating an array or group, but fail to access array data with [`ArrayError::UnsupportedAdditionalFieldError`](crate::array::ArrayError::UnsupportedAdditionalFieldError).
+    LenientRead,
+    /// Permit creating an array or group and accessing array data, but log a warning.
+    Permissive,
+}
+
 /// Version options for [`Array::store_metadata`](crate::array::Array::store_metadata) and [`Group::store_metadata`](crate::group::Group::store_metadata), and their async variants.
 #[derive(Debug, Clone, Copy)]
 pub enum MetadataConvertVersion {
@@ -347,10 +775,48 @@ mod tests {
     use super::*;
 
     #[test]
-    fn config_validate_checksums() {
-        assert!(global_config().validate_checksums());
-        global_config_mut().set_validate_checksums(false);
-        assert!(!global_config().validate_checksums());
-        global_config_mut().set_validate_checksums(true);
+    fn config_default_codecs() {
+        let mut config = Config::default();
+        assert!(config.default_array_to_bytes_codec().is_none());
+        assert!(config.default_bytes_to_bytes_codecs().is_empty());
+
+        // A codec chain has no codec metadata of its own
+        let codec_chain = Arc::new(crate::array::CodecChain::new(
+            vec![],
+            Arc::new(crate::array::codec::BytesCodec::default()),
+            vec![],
+        ));
+        assert!(config
+            .set_default_array_to_bytes_codec(Some(codec_chain))
+            .is_err());
+        assert!(config.default_array_to_bytes_codec().is_none());
+
+        config
+            .set_default_array_to_bytes_codec(Some(
+                Arc::new(crate::array::codec::BytesCodec::big()),
+            ))
+            .unwrap();
+        assert!(config.default_array_to_bytes_codec().is_some());
+
+        #[cfg(feature = "zstd")]
+        {
+            config
+                .set_default_bytes_to_bytes_codecs(vec![Arc::new(
+                    crate::array::codec::ZstdCodec::new(3, false),
+                )])
+                .unwrap();
+            assert_eq!(config.default_bytes_to_bytes_codecs().len(), 1);
+        }
+    }
+
+    #[test]
+    fn config_default_chunk_key_separator() {
+        let mut config = Config::default();
+        assert_eq!(
+            config.default_chunk_key_separator(),
+            ChunkKeySeparator::Slash
+        );
+        config.set_default_chunk_key_separator(ChunkKeySeparator::Dot);
+        assert_eq!(config.default_chunk_key_separator(), ChunkKeySeparator::Dot);
     }
 }
@@ -32,20 +32,33 @@ use derive_more::Display;
 use thiserror::Error;
 
 use crate::{
+    array::{Array, ArrayCreateError, ArrayMetadata},
     config::{
         global_config, MetadataConvertVersion, MetadataEraseVersion, MetadataRetrieveVersion,
+        MustUnderstandLevel,
     },
     metadata::{
         v2::GroupMetadataV2,
         v2_to_v3::group_metadata_v2_to_v3,
-        v3::{AdditionalFields, UnsupportedAdditionalFieldError},
+        v3::{AdditionalField, AdditionalFields, UnsupportedAdditionalFieldError},
+    },
+    node::{
+        get_child_nodes, meta_key_v2_attributes, meta_key_v2_consolidated, meta_key_v2_group,
+        meta_key_v3, metadata_v3_to_json, node_type_v3, set_metadata, strip_bom,
+        ConsolidatedMetadataSource, Node, NodeMetadata, NodePath, NodePathError,
+        CONSOLIDATED_METADATA_FIELD,
+    },
+    storage::{
+        ListableStorageTraits, ReadableStorageTraits, StorageError, StorageHandle, StoreKey,
+        StoreKeys, StorePrefix, WritableStorageTraits,
     },
-    node::{meta_key_v2_attributes, meta_key_v2_group, meta_key_v3, NodePath, NodePathError},
-    storage::{ReadableStorageTraits, StorageError, StorageHandle, WritableStorageTraits},
 };
 
 #[cfg(feature = "async")]
-use crate::storage::{AsyncReadableStorageTraits, AsyncWritableStorageTraits};
+use crate::{
+    node::{async_descendants_stream, async_get_child_nodes, async_set_metadata},
+    storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits},
+};
 
 pub use self::group_builder::GroupBuilder;
 pub use crate::metadata::{v3::GroupMetadataV3, GroupMetadata};
@@ -59,13 +72,59 @@ pub use group_metadata_options::GroupMetadataOptions;
 )]
 pub struct Group<TStorage: ?Sized> {
     /// The storage.
-    #[allow(dead_code)]
     storage: Arc<TStorage>,
     /// The path of the group in the store.
     #[allow(dead_code)]
     path: NodePath,
     /// The metadata.
     metadata: GroupMetadata,
+    /// The metadata mismatch if opened with [lenient group metadata](crate::config::Config#lenient-group-metadata).
+    metadata_mismatch: Option<GroupMetadataMismatch>,
+}
+
+/// A mismatch in the Zarr V3 metadata of a group opened with [lenient group metadata](crate::config::Config#lenient-group-metadata).
+#[derive(Clone, Debug, PartialEq, Eq, Display)]
+pub enum GroupMetadataMismatch {
+    /// The `node_type` is not `group` (e.g. the metadata is array metadata).
+    #[display("node_type is {_0}, expected \"group\"")]
+    NodeType(serde_json::Value),
+    /// The `zarr_format` is not `3`.
+    #[display("zarr_format is {_0}, expected 3")]
+    ZarrFormat(serde_json::Value),
+}
+
+/// Deserialize Zarr V3 group metadata, applying the [lenient group metadata](crate::config::Config#lenient-group-metadata) configuration.
+fn group_metadata_v3_from_slice(
+    key: &StoreKey,
+    metadata: &[u8],
+) -> Result<(GroupMetadataV3, Option<GroupMetadataMismatch>), StorageError> {
+    let metadata = strip_bom(metadata);
+    let err = match serde_json::from_slice::<GroupMetadataV3>(metadata) {
+        Ok(metadata) => return Ok((metadata, None)),
+        Err(err) => StorageError::InvalidMetadata(key.clone(), err.to_string()),
+    };
+    if !global_config().lenient_group_metadata() {
+        return Err(err);
+    }
+    let Ok(document) =
+        serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(metadata)
+    else {
+        return Err(err);
+    };
+    let field = |name: &str| document.get(name).cloned().unwrap_or_default();
+    let mismatch = if document.get("node_type") != Some(&serde_json::Value::from("group")) {
+        GroupMetadataMismatch::NodeType(field("node_type"))
+    } else if document.get("zarr_format") != Some(&serde_json::Value::from(3u64)) {
+        GroupMetadataMismatch::ZarrFormat(field("zarr_format"))
+    } else {
+        // The metadata is invalid for another reason
+        return Err(err);
+    };
+    log::warn!("group metadata at {key} has a mismatch ({mismatch}), opening leniently");
+    Ok((
+        GroupMetadataV3::new(document, AdditionalFields::default()),
+        Some(mismatch),
+    ))
 }
 
 impl<TStorage: ?Sized> Group<TStorage> {
@@ -75,19 +134,97 @@ impl<TStorage: ?Sized> Group<TStorage> {
     /// # Errors
     ///
     /// Returns [`GroupCreateError`] if any metadata is invalid.
+    /// See the [must understand level](crate::config::Config#must-understand-level) configuration for the handling of additional fields.
     pub fn new_with_metadata(
         storage: Arc<TStorage>,
         path: &str,
         metadata: GroupMetadata,
     ) -> Result<Self, GroupCreateError> {
         let path = NodePath::new(path)?;
+        let additional_fields = match &metadata {
+            GroupMetadata::V3(metadata) => &metadata.additional_fields,
+            GroupMetadata::V2(metadata) => &metadata.additional_fields,
+        };
+        // Consolidated metadata is understood by zarrs
+        if let Err(err) = additional_fields
+            .iter()
+            .filter(|(name, _)| *name != CONSOLIDATED_METADATA_FIELD)
+            .try_for_each(|(name, field)| field.validate(name))
+        {
+            match global_config().must_understand_level() {
+                MustUnderstandLevel::Strict => {
+                    return Err(GroupCreateError::UnsupportedAdditionalFieldError(err));
+                }
+                // A group has no data to access
+                MustUnderstandLevel::LenientRead => {}
+                MustUnderstandLevel::Permissive => {
+                    log::warn!("group at {path} has an ignored {err}");
+                }
+            }
+        }
         Ok(Self {
             storage,
             path,
             metadata,
+            metadata_mismatch: None,
         })
     }
 
+    /// Replace the metadata of the group with that of `reloaded`, preserving the storage and path of this handle.
+    fn replace_metadata(&mut self, reloaded: Self) {
+        let Self {
+            storage: _,
+            path: _,
+            metadata,
+            metadata_mismatch,
+        } = reloaded;
+        self.metadata = metadata;
+        self.metadata_mismatch = metadata_mismatch;
+    }
+
+    /// Create the arrays in `nodes`, ignoring any groups.
+    ///
+    /// This can be used with the output of [`Group::children`] to avoid listing the children of a group more than once.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if the metadata of any array is invalid.
+    pub fn arrays_from_nodes(
+        &self,
+        nodes: &[Node],
+    ) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+        nodes
+            .iter()
+            .filter_map(|node| match node.metadata() {
+                NodeMetadata::Array(metadata) => Some(Array::new_with_metadata(
+                    self.storage.clone(),
+                    node.path().as_str(),
+                    metadata.clone(),
+                )),
+                NodeMetadata::Group(_) => None,
+            })
+            .collect()
+    }
+
+    /// Create the groups in `nodes`, ignoring any arrays.
+    ///
+    /// This can be used with the output of [`Group::children`] to avoid listing the children of a group more than once.
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if the metadata of any group is invalid.
+    pub fn groups_from_nodes(&self, nodes: &[Node]) -> Result<Vec<Self>, GroupCreateError> {
+        nodes
+            .iter()
+            .filter_map(|node| match node.metadata() {
+                NodeMetadata::Group(metadata) => Some(Self::new_with_metadata(
+                    self.storage.clone(),
+                    node.path().as_str(),
+                    metadata.clone(),
+                )),
+                NodeMetadata::Array(_) => None,
+            })
+            .collect()
+    }
+
     /// Get path.
     #[must_use]
     pub const fn path(&self) -> &NodePath {
@@ -135,6 +272,31 @@ impl<TStorage: ?Sized> Group<TStorage> {
         &self.metadata
     }
 
+    /// Return the metadata mismatch if the group was opened with [lenient group metadata](crate::config::Config#lenient-group-metadata).
+    ///
+    /// If this is [`Some`], the stored metadata is not valid group metadata and the [attributes](Group::attributes) hold the entire metadata document.
+    /// Such a group is only suitable for inspection, and its metadata cannot be stored.
+    #[must_use]
+    pub const fn metadata_mismatch(&self) -> Option<&GroupMetadataMismatch> {
+        self.metadata_mismatch.as_ref()
+    }
+
+    /// Return the Zarr format version of the group metadata (`2` or `3`).
+    #[must_use]
+    pub fn zarr_format(&self) -> u8 {
+        self.metadata.zarr_format()
+    }
+
+    /// Return an error if the group was opened with a [metadata mismatch](Group::metadata_mismatch), so the stored metadata is not overwritten.
+    fn check_metadata_mismatch(&self) -> Result<(), StorageError> {
+        self.metadata_mismatch.as_ref().map_or(Ok(()), |mismatch| {
+            Err(StorageError::InvalidMetadata(
+                meta_key_v3(self.path()),
+                format!("group was opened leniently with a metadata mismatch ({mismatch})"),
+            ))
+        })
+    }
+
     /// Return a new [`GroupMetadata`] with [`GroupMetadataOptions`] applied.
     ///
     /// This method is used internally by [`Group::store_metadata`] and [`Group::store_metadata_opt`].
@@ -153,6 +315,23 @@ impl<TStorage: ?Sized> Group<TStorage> {
 }
 
 impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {
+    /// Return true if a group exists in `storage` at `path`.
+    ///
+    /// This checks for Zarr V3 group metadata (`zarr.json` with a `group` node type) and then Zarr V2 group metadata (`.zgroup`).
+    /// At most two storage requests are made, the store is not listed, and the group is not constructed.
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if `path` is invalid, there is a storage error, or `zarr.json` is invalid.
+    /// An absent group is not an error.
+    pub fn exists(storage: &Arc<TStorage>, path: &str) -> Result<bool, GroupCreateError> {
+        let node_path: NodePath = path.try_into()?;
+        let key_v3 = meta_key_v3(&node_path);
+        if let Some(metadata) = storage.get(&key_v3)? {
+            return Ok(node_type_v3(key_v3, &metadata)? == "group");
+        }
+        Ok(storage.size_key(&meta_key_v2_group(&node_path))?.is_some())
+    }
+
     /// Open a group in `storage` at `path` with [`MetadataRetrieveVersion`].
     /// The metadata is read from the store.
     ///
@@ -178,9 +357,12 @@ impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {
             // Try Zarr V3
             let key_v3 = meta_key_v3(&node_path);
             if let Some(metadata) = storage.get(&key_v3)? {
-                let metadata: GroupMetadataV3 = serde_json::from_slice(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
-                return Self::new_with_metadata(storage, path, GroupMetadata::V3(metadata));
+                let (metadata, metadata_mismatch) =
+                    group_metadata_v3_from_slice(&key_v3, &metadata)?;
+                let mut group =
+                    Self::new_with_metadata(storage, path, GroupMetadata::V3(metadata))?;
+                group.metadata_mismatch = metadata_mismatch;
+                return Ok(group);
             }
         }
 
@@ -188,14 +370,15 @@ impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {
             // Try Zarr V2
             let key_v2 = meta_key_v2_group(&node_path);
             if let Some(metadata) = storage.get(&key_v2)? {
-                let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
                 let attributes_key = meta_key_v2_attributes(&node_path);
                 let attributes = storage.get(&attributes_key)?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Self::new_with_metadata(storage, path, GroupMetadata::V2(metadata));
             }
@@ -204,10 +387,65 @@ impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {
         // No metadata has been found
         Err(GroupCreateError::MissingMetadata)
     }
+
+    /// Open a group in `storage` at `path`, first consulting `consolidated` metadata.
+    ///
+    /// If `consolidated` holds the metadata of the group, the group is opened without a storage request.
+    /// Otherwise, the metadata is read from the store as in [`open`](Group::open).
+    ///
+    /// Zarr V2 (`.zmetadata`) and Zarr V3 (inline `consolidated_metadata`) consolidated metadata can be retrieved with [`ConsolidatedMetadata::retrieve`](crate::node::ConsolidatedMetadata::retrieve).
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if there is a storage error, the consolidated metadata cannot be loaded, or any metadata is invalid.
+    pub fn open_with_consolidated(
+        storage: Arc<TStorage>,
+        path: &str,
+        consolidated: &dyn ConsolidatedMetadataSource,
+    ) -> Result<Self, GroupCreateError> {
+        let node_path = path.try_into()?;
+        if let Some(metadata) = consolidated
+            .consolidated_group_metadata(&node_path, &MetadataRetrieveVersion::Default)?
+        {
+            return Self::new_with_metadata(storage, path, metadata);
+        }
+        Self::open(storage, path)
+    }
+
+    /// Reload the group metadata from the store, replacing the in-memory metadata.
+    ///
+    /// This refreshes a group handle after its metadata is updated externally (e.g. by another process).
+    /// The storage and path of the group are preserved.
+    /// The metadata is retrieved as in [`open`](Group::open).
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if there is a storage error or any metadata is invalid.
+    /// The group is unchanged if reloading fails.
+    pub fn reload(&mut self) -> Result<(), GroupCreateError> {
+        let reloaded = Self::open(self.storage.clone(), self.path.as_str())?;
+        self.replace_metadata(reloaded);
+        Ok(())
+    }
 }
 
 #[cfg(feature = "async")]
 impl<TStorage: ?Sized + AsyncReadableStorageTraits> Group<TStorage> {
+    /// Async variant of [`exists`](Group::exists).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_exists(
+        storage: &Arc<TStorage>,
+        path: &str,
+    ) -> Result<bool, GroupCreateError> {
+        let node_path: NodePath = path.try_into()?;
+        let key_v3 = meta_key_v3(&node_path);
+        if let Some(metadata) = storage.get(&key_v3).await? {
+            return Ok(node_type_v3(key_v3, &metadata)? == "group");
+        }
+        Ok(storage
+            .size_key(&meta_key_v2_group(&node_path))
+            .await?
+            .is_some())
+    }
+
     /// Async variant of [`open`](Group::open).
     #[allow(clippy::missing_errors_doc)]
     pub async fn async_open(storage: Arc<TStorage>, path: &str) -> Result<Self, GroupCreateError> {
@@ -227,9 +465,12 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> Group<TStorage> {
             // Try Zarr V3
             let key_v3 = meta_key_v3(&node_path);
             if let Some(metadata) = storage.get(&key_v3).await? {
-                let metadata: GroupMetadataV3 = serde_json::from_slice(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
-                return Self::new_with_metadata(storage, path, GroupMetadata::V3(metadata));
+                let (metadata, metadata_mismatch) =
+                    group_metadata_v3_from_slice(&key_v3, &metadata)?;
+                let mut group =
+                    Self::new_with_metadata(storage, path, GroupMetadata::V3(metadata))?;
+                group.metadata_mismatch = metadata_mismatch;
+                return Ok(group);
             }
         }
 
@@ -237,14 +478,15 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> Group<TStorage> {
             // Try Zarr V2
             let key_v2 = meta_key_v2_group(&node_path);
             if let Some(metadata) = storage.get(&key_v2).await? {
-                let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
                 let attributes_key = meta_key_v2_attributes(&node_path);
                 let attributes = storage.get(&attributes_key).await?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Self::new_with_metadata(storage, path, GroupMetadata::V2(metadata));
             }
@@ -253,6 +495,14 @@ impl<TStorage: ?Sized + AsyncReadableStorageTraits> Group<TStorage> {
         // No metadata has been found
         Err(GroupCreateError::MissingMetadata)
     }
+
+    /// Async variant of [`reload`](Group::reload).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_reload(&mut self) -> Result<(), GroupCreateError> {
+        let reloaded = Self::async_open(self.storage.clone(), self.path.as_str()).await?;
+        self.replace_metadata(reloaded);
+        Ok(())
+    }
 }
 
 /// A group creation error.
@@ -272,13 +522,80 @@ pub enum GroupCreateError {
     MissingMetadata,
 }
 
-impl<TStorage: ?Sized + ReadableStorageTraits> Group<TStorage> {}
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits> Group<TStorage> {
+    /// Return the children of the group.
+    ///
+    /// The children are sorted by path.
+    /// Child prefixes without node metadata (e.g. stray directories) are skipped.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`] if there is an underlying store error or the metadata of a child is invalid.
+    pub fn children(&self) -> Result<Vec<Node>, StorageError> {
+        get_child_nodes(&self.storage, &self.path)
+    }
+
+    /// Return the children of the group that are arrays.
+    ///
+    /// Use [`Group::children`] and [`Group::arrays_from_nodes`] if the groups are also needed.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is an underlying store error or the metadata of a child is invalid.
+    pub fn arrays(&self) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+        self.arrays_from_nodes(&self.children()?)
+    }
+
+    /// Return the children of the group that are groups.
+    ///
+    /// Use [`Group::children`] and [`Group::groups_from_nodes`] if the arrays are also needed.
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if there is an underlying store error or the metadata of a child is invalid.
+    pub fn groups(&self) -> Result<Vec<Self>, GroupCreateError> {
+        self.groups_from_nodes(&self.children()?)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits> Group<TStorage> {
+    /// Async variant of [`children`](Group::children).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_children(&self) -> Result<Vec<Node>, StorageError> {
+        async_get_child_nodes(&self.storage, &self.path).await
+    }
+
+    /// Async variant of [`arrays`](Group::arrays).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_arrays(&self) -> Result<Vec<Array<TStorage>>, ArrayCreateError> {
+        self.arrays_from_nodes(&self.async_children().await?)
+    }
+
+    /// Async variant of [`groups`](Group::groups).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_groups(&self) -> Result<Vec<Self>, GroupCreateError> {
+        self.groups_from_nodes(&self.async_children().await?)
+    }
+
+    /// Return a stream of the descendant nodes of this group.
+    ///
+    /// The hierarchy is walked breadth-first, retrieving the metadata of up to `concurrency` nodes at a time.
+    /// Nodes are yielded as they are retrieved, so the memory usage is bounded by the breadth of the hierarchy rather than its size.
+    /// The yielded nodes do not have their [`children`](Node::children) populated.
+    ///
+    /// Nodes are yielded in completion order, which is only deterministic (breadth-first, in lexicographical order within a group) if `concurrency` is `1`.
+    /// A node with missing or invalid metadata is yielded as an [`Err`] without stopping the stream, but its descendants are not visited.
+    pub fn async_descendants_stream(
+        &self,
+        concurrency: usize,
+    ) -> impl futures::Stream<Item = Result<Node, StorageError>> + Send + '_ {
+        async_descendants_stream(&self.storage, &self.path, concurrency)
+    }
+}
 
 impl<TStorage: ?Sized + WritableStorageTraits> Group<TStorage> {
     /// Store metadata with default [`GroupMetadataOptions`].
     ///
     /// # Errors
-    /// Returns [`StorageError`] if there is an underlying store error.
+    /// Returns [`StorageError`] if there is an underlying store error or the group has a [metadata mismatch](Group::metadata_mismatch).
     pub fn store_metadata(&self) -> Result<(), StorageError> {
         self.store_metadata_opt(&GroupMetadataOptions::default())
     }
@@ -286,10 +603,12 @@ impl<TStorage: ?Sized + WritableStorageTraits> Group<TStorage> {
     /// Store metadata with non-default [`GroupMetadataOptions`].
     ///
     /// # Errors
-    /// Returns [`StorageError`] if there is an underlying store error.
+    /// Returns [`StorageError`] if there is an underlying store error or the group has a [metadata mismatch](Group::metadata_mismatch).
     pub fn store_metadata_opt(&self, options: &GroupMetadataOptions) -> Result<(), StorageError> {
         let storage_handle = Arc::new(StorageHandle::new(self.storage.clone()));
 
+        self.check_metadata_mismatch()?;
+
         // Get the metadata with options applied and store
         let metadata = self.metadata_opt(options);
 
@@ -298,20 +617,30 @@ impl<TStorage: ?Sized + WritableStorageTraits> Group<TStorage> {
         match metadata {
             GroupMetadata::V3(metadata) => {
                 let key = meta_key_v3(path);
-                let json = serde_json::to_vec_pretty(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_handle.set(&key, json.into())
+                let json =
+                    metadata_v3_to_json(&key, &metadata, options.serialize_empty_attributes())?;
+                set_metadata(
+                    &*storage_handle,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
             }
             GroupMetadata::V2(metadata) => {
                 let mut metadata = metadata.clone();
 
-                if !metadata.attributes.is_empty() {
+                if !metadata.attributes.is_empty() || options.serialize_empty_attributes() {
                     // Store .zgroup
                     let key = meta_key_v2_attributes(path);
                     let json = serde_json::to_vec_pretty(&metadata.attributes).map_err(|err| {
                         StorageError::InvalidMetadata(key.clone(), err.to_string())
                     })?;
-                    storage_handle.set(&key, json.into())?;
+                    set_metadata(
+                        &*storage_handle,
+                        &key,
+                        json.into(),
+                        options.stage_metadata_writes(),
+                    )?;
 
                     metadata.attributes = serde_json::Map::default();
                 }
@@ -320,7 +649,12 @@ impl<TStorage: ?Sized + WritableStorageTraits> Group<TStorage> {
                 let key = meta_key_v2_group(path);
                 let json = serde_json::to_vec_pretty(&metadata)
                     .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_handle.set(&key, json.into())?;
+                set_metadata(
+                    &*storage_handle,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )?;
                 Ok(())
             }
         }
@@ -384,6 +718,8 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> Group<TStorage> {
     ) -> Result<(), StorageError> {
         let storage_handle = StorageHandle::new(self.storage.clone());
 
+        self.check_metadata_mismatch()?;
+
         // Get the metadata with options applied and store
         let metadata = self.metadata_opt(options);
 
@@ -392,20 +728,32 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> Group<TStorage> {
         match metadata {
             GroupMetadata::V3(metadata) => {
                 let key = meta_key_v3(path);
-                let json = serde_json::to_vec_pretty(&metadata)
-                    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_handle.set(&key, json.into()).await
+                let json =
+                    metadata_v3_to_json(&key, &metadata, options.serialize_empty_attributes())?;
+                async_set_metadata(
+                    &storage_handle,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
+                .await
             }
             GroupMetadata::V2(metadata) => {
                 let mut metadata = metadata.clone();
 
-                if !metadata.attributes.is_empty() {
+                if !metadata.attributes.is_empty() || options.serialize_empty_attributes() {
                     // Store .zgroup
                     let key = meta_key_v2_attributes(path);
                     let json = serde_json::to_vec_pretty(&metadata.attributes).map_err(|err| {
                         StorageError::InvalidMetadata(key.clone(), err.to_string())
                     })?;
-                    storage_handle.set(&key, json.into()).await?;
+                    async_set_metadata(
+                        &storage_handle,
+                        &key,
+                        json.into(),
+                        options.stage_metadata_writes(),
+                    )
+                    .await?;
 
                     metadata.attributes = serde_json::Map::default();
                 }
@@ -414,8 +762,13 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> Group<TStorage> {
                 let key = meta_key_v2_group(path);
                 let json = serde_json::to_vec_pretty(&metadata)
                     .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
-                storage_handle.set(&key, json.into()).await?;
-                Ok(())
+                async_set_metadata(
+                    &storage_handle,
+                    &key,
+                    json.into(),
+                    options.stage_metadata_writes(),
+                )
+                .await
             }
         }
     }
@@ -468,6 +821,203 @@ impl<TStorage: ?Sized + AsyncWritableStorageTraits> Group<TStorage> {
     }
 }
 
+impl<TStorage: ?Sized + ListableStorageTraits> Group<TStorage> {
+    /// Return the keys that [`remove`](Group::remove) would erase, without erasing anything.
+    ///
+    /// These are all keys under the path of the group, including the metadata (Zarr V3 `zarr.json`, Zarr V2 `.zgroup`, `.zarray`, and `.zattrs`) and chunks of the group and all of its descendants.
+    /// Keys of unrecognised nodes or other data under the group path are also included.
+    /// Note that the root group includes every key in the store.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn remove_dry_run(&self) -> Result<StoreKeys, StorageError> {
+        let prefix: StorePrefix = self.path().try_into()?;
+        self.storage.list_prefix(&prefix)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits + WritableStorageTraits> Group<TStorage> {
+    /// Remove the group and all of its descendants from the store.
+    ///
+    /// Every key listed by [`remove_dry_run`](Group::remove_dry_run) is erased.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn remove(&self) -> Result<(), StorageError> {
+        let keys = self.remove_dry_run()?;
+        self.storage.erase_values(&keys)
+    }
+}
+
+/// Serialize the consolidated metadata of the node with metadata `key`.
+fn consolidated_value<T: serde::Serialize>(
+    key: &StoreKey,
+    metadata: &T,
+) -> Result<serde_json::Value, StorageError> {
+    serde_json::to_value(metadata)
+        .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))
+}
+
+/// Insert the metadata of `nodes` and their descendants into the consolidated `metadata` of the group at `prefix`.
+///
+/// Zarr V3 metadata is keyed by the node path relative to the group (e.g. `array`).
+/// Zarr V2 metadata is keyed by the metadata key relative to the group (e.g. `array/.zarray` and `array/.zattrs`).
+/// Nodes with a different Zarr version to the group are skipped.
+fn consolidate_nodes(
+    nodes: &[Node],
+    prefix: &StorePrefix,
+    zarr_format: u8,
+    metadata: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<(), StorageError> {
+    for node in nodes {
+        let node_prefix: StorePrefix = node.path().try_into()?;
+        let relative_path = node_prefix
+            .as_str()
+            .strip_prefix(prefix.as_str())
+            .unwrap_or(node_prefix.as_str())
+            .trim_end_matches('/');
+        let key = meta_key_v3(node.path());
+        match (node.metadata(), zarr_format) {
+            (NodeMetadata::Array(ArrayMetadata::V3(node_metadata)), 3) => {
+                let value = consolidated_value(&key, node_metadata)?;
+                metadata.insert(relative_path.to_string(), value);
+            }
+            (NodeMetadata::Group(GroupMetadata::V3(node_metadata)), 3) => {
+                // Nested consolidated metadata is redundant
+                let mut node_metadata = node_metadata.clone();
+                node_metadata
+                    .additional_fields
+                    .remove(CONSOLIDATED_METADATA_FIELD);
+                let value = consolidated_value(&key, &node_metadata)?;
+                metadata.insert(relative_path.to_string(), value);
+            }
+            (NodeMetadata::Array(ArrayMetadata::V2(node_metadata)), 2) => {
+                let mut node_metadata = node_metadata.clone();
+                let attributes = std::mem::take(&mut node_metadata.attributes);
+                let value = consolidated_value(&key, &node_metadata)?;
+                metadata.insert(format!("{relative_path}/.zarray"), value);
+                if !attributes.is_empty() {
+                    metadata.insert(format!("{relative_path}/.zattrs"), attributes.into());
+                }
+            }
+            (NodeMetadata::Group(GroupMetadata::V2(node_metadata)), 2) => {
+                let mut node_metadata = node_metadata.clone();
+                let attributes = std::mem::take(&mut node_metadata.attributes);
+                let value = consolidated_value(&key, &node_metadata)?;
+                metadata.insert(format!("{relative_path}/.zgroup"), value);
+                if !attributes.is_empty() {
+                    metadata.insert(format!("{relative_path}/.zattrs"), attributes.into());
+                }
+            }
+            _ => {
+                log::warn!(
+                    "skipping consolidation of node {} with a different Zarr version to the group",
+                    node.path()
+                );
+                continue;
+            }
+        }
+        consolidate_nodes(node.children(), prefix, zarr_format, metadata)?;
+    }
+    Ok(())
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + WritableStorageTraits>
+    Group<TStorage>
+{
+    /// Consolidate the metadata of all descendants of the group and store it.
+    ///
+    /// For a Zarr V3 group, the metadata of each descendant is stored inline in the `consolidated_metadata` field of the group `zarr.json`, keyed by its path relative to the group.
+    /// This follows the consolidated metadata convention of `zarr-python`.
+    /// The group metadata in the store is rewritten, but the in-memory metadata of this group is unchanged.
+    ///
+    /// For a Zarr V2 group, the metadata of the group and its descendants is stored in `.zmetadata`.
+    ///
+    /// Either form can be read with [`ConsolidatedMetadata`](crate::node::ConsolidatedMetadata).
+    ///
+    /// Descendants with a different Zarr version to the group are not consolidated.
+    /// Consolidated metadata is a snapshot, it must be stored again after the hierarchy changes.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`] if there is an underlying store error, the metadata of a descendant is invalid, or the group has a [metadata mismatch](Group::metadata_mismatch).
+    pub fn store_consolidated_metadata(&self) -> Result<(), StorageError> {
+        self.check_metadata_mismatch()?;
+        let prefix: StorePrefix = self.path().try_into()?;
+        let nodes = get_child_nodes(&self.storage, self.path())?;
+        let mut metadata = serde_json::Map::new();
+        match &self.metadata {
+            GroupMetadata::V3(group_metadata) => {
+                let key = meta_key_v3(self.path());
+                consolidate_nodes(&nodes, &prefix, 3, &mut metadata)?;
+                let mut group_metadata = group_metadata.clone();
+                group_metadata.additional_fields.insert(
+                    CONSOLIDATED_METADATA_FIELD.to_string(),
+                    AdditionalField::from(serde_json::Map::from_iter([
+                        ("kind".to_string(), "inline".into()),
+                        ("metadata".to_string(), metadata.into()),
+                    ])),
+                );
+                let json = metadata_v3_to_json(
+                    &key,
+                    &group_metadata,
+                    global_config().serialize_empty_attributes(),
+                )?;
+                set_metadata(
+                    &*self.storage,
+                    &key,
+                    json.into(),
+                    global_config().stage_metadata_writes(),
+                )
+            }
+            GroupMetadata::V2(group_metadata) => {
+                let key = meta_key_v2_consolidated(self.path());
+                let mut group_metadata = group_metadata.clone();
+                let attributes = std::mem::take(&mut group_metadata.attributes);
+                metadata.insert(
+                    ".zgroup".to_string(),
+                    consolidated_value(&key, &group_metadata)?,
+                );
+                if !attributes.is_empty() {
+                    metadata.insert(".zattrs".to_string(), attributes.into());
+                }
+                consolidate_nodes(&nodes, &prefix, 2, &mut metadata)?;
+                let document = serde_json::json!({
+                    "metadata": metadata,
+                    "zarr_consolidated_format": 1,
+                });
+                let json = serde_json::to_vec_pretty(&document)
+                    .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+                set_metadata(
+                    &*self.storage,
+                    &key,
+                    json.into(),
+                    global_config().stage_metadata_writes(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> Group<TStorage> {
+    /// Async variant of [`remove_dry_run`](Group::remove_dry_run).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_remove_dry_run(&self) -> Result<StoreKeys, StorageError> {
+        let prefix: StorePrefix = self.path().try_into()?;
+        self.storage.list_prefix(&prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TStorage: ?Sized + AsyncListableStorageTraits + AsyncWritableStorageTraits> Group<TStorage> {
+    /// Async variant of [`remove`](Group::remove).
+    #[allow(clippy::missing_errors_doc)]
+    pub async fn async_remove(&self) -> Result<(), StorageError> {
+        let keys = self.async_remove_dry_run().await?;
+        self.storage.erase_values(&keys).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::storage::{store::MemoryStore, StoreKey};
@@ -510,6 +1060,37 @@ mod tests {
         Group::new_with_metadata(store.into(), "/", GroupMetadata::V3(group_metadata)).unwrap();
     }
 
+    #[test]
+    fn group_metadata_v3_consolidated_metadata() {
+        // consolidated_metadata must be understood, which zarrs does
+        let group_metadata: GroupMetadataV3 = serde_json::from_str(
+            r#"{
+            "zarr_format": 3,
+            "node_type": "group",
+            "consolidated_metadata": {
+                "kind": "inline",
+                "metadata": {}
+            }
+        }"#,
+        )
+        .unwrap();
+        let store = std::sync::Arc::new(MemoryStore::default());
+        Group::new_with_metadata(store.clone(), "/", GroupMetadata::V3(group_metadata)).unwrap();
+
+        let group_metadata: GroupMetadataV3 = serde_json::from_str(
+            r#"{
+            "zarr_format": 3,
+            "node_type": "group",
+            "unknown": {}
+        }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            Group::new_with_metadata(store, "/", GroupMetadata::V3(group_metadata)),
+            Err(GroupCreateError::UnsupportedAdditionalFieldError(_))
+        ));
+    }
+
     #[test]
     fn group_metadata_v3_invalid_format() {
         let group_metadata = serde_json::from_str::<GroupMetadataV3>(
@@ -579,6 +1160,56 @@ mod tests {
         // );
     }
 
+    #[test]
+    fn group_metadata_serialize_empty_attributes() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let group = GroupBuilder::new().build(store.clone(), "/v3").unwrap();
+        let mut options = GroupMetadataOptions::default();
+        group.store_metadata_opt(&options).unwrap();
+        let metadata: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("v3/zarr.json").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(metadata.get("attributes").is_none());
+
+        options.set_serialize_empty_attributes(true);
+        group.store_metadata_opt(&options).unwrap();
+        let metadata: serde_json::Value = serde_json::from_slice(
+            &store
+                .get(&StoreKey::new("v3/zarr.json").unwrap())
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(metadata["attributes"], serde_json::json!({}));
+        assert_eq!(
+            Group::open(store.clone(), "/v3").unwrap().metadata(),
+            group.metadata()
+        );
+
+        let metadata: GroupMetadataV2 = serde_json::from_str(r#"{"zarr_format":2}"#).unwrap();
+        let group =
+            Group::new_with_metadata(store.clone(), "/v2", GroupMetadata::V2(metadata)).unwrap();
+        group
+            .store_metadata_opt(&GroupMetadataOptions::default())
+            .unwrap();
+        assert!(store
+            .get(&StoreKey::new("v2/.zattrs").unwrap())
+            .unwrap()
+            .is_none());
+        group.store_metadata_opt(&options).unwrap();
+        assert_eq!(
+            store
+                .get(&StoreKey::new("v2/.zattrs").unwrap())
+                .unwrap()
+                .unwrap(),
+            b"{}".as_slice()
+        );
+    }
+
     #[test]
     fn group_metadata_invalid_path() {
         let group_metadata: GroupMetadata = serde_json::from_str(JSON_VALID1).unwrap();
@@ -612,6 +1243,318 @@ mod tests {
         );
     }
 
+    #[test]
+    fn group_open_with_consolidated_v2() {
+        use crate::node::ConsolidatedMetadata;
+
+        let store: std::sync::Arc<MemoryStore> = std::sync::Arc::new(MemoryStore::new());
+        let zmetadata = serde_json::json!({
+            "metadata": {
+                ".zgroup": {"zarr_format": 2},
+                ".zattrs": {"spam": "ham"},
+                "array/.zarray": {
+                    "zarr_format": 2,
+                    "shape": [4],
+                    "chunks": [2],
+                    "dtype": "<i4",
+                    "compressor": null,
+                    "fill_value": 0,
+                    "order": "C",
+                    "filters": null
+                },
+                "array/.zattrs": {"units": "m"}
+            },
+            "zarr_consolidated_format": 1
+        });
+        store
+            .set(
+                &StoreKey::new("root/.zmetadata").unwrap(),
+                serde_json::to_vec(&zmetadata).unwrap().into(),
+            )
+            .unwrap();
+
+        // The per-node metadata is absent from the store
+        let root_path: NodePath = "/root".try_into().unwrap();
+        let consolidated = ConsolidatedMetadata::retrieve(&store, &root_path)
+            .unwrap()
+            .unwrap();
+        assert!(consolidated
+            .metadata()
+            .contains_key(&StoreKey::new("root/array/.zarray").unwrap()));
+        let group = Group::open_with_consolidated(store.clone(), "/root", &consolidated).unwrap();
+        assert!(matches!(group.metadata(), GroupMetadata::V2(_)));
+        assert_eq!(group.attributes()["spam"], "ham");
+        let array =
+            Array::open_with_consolidated(store.clone(), "/root/array", &consolidated).unwrap();
+        assert_eq!(array.shape(), &[4]);
+        assert_eq!(array.attributes()["units"], "m");
+
+        // Nodes that are not consolidated are read from the store
+        assert!(Group::open_with_consolidated(store.clone(), "/other", &consolidated).is_err());
+        GroupBuilder::new()
+            .build(store.clone(), "/other")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        assert!(Group::open_with_consolidated(store.clone(), "/other", &consolidated).is_ok());
+
+        // Missing and unsupported consolidated metadata
+        assert!(ConsolidatedMetadata::retrieve(&store, &NodePath::root())
+            .unwrap()
+            .is_none());
+        assert!(ConsolidatedMetadata::from_slice(
+            &root_path,
+            br#"{"metadata": {}, "zarr_consolidated_format": 2}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn group_store_consolidated_metadata_v3() {
+        let consolidated_metadata = |group: &Group<MemoryStore>| {
+            let GroupMetadata::V3(metadata) = group.metadata() else {
+                panic!("expected Zarr V3 group metadata")
+            };
+            metadata.additional_fields["consolidated_metadata"]
+                .as_map()
+                .clone()
+        };
+
+        let store = std::sync::Arc::new(MemoryStore::new());
+        for path in ["/", "/a", "/a/b", "/empty"] {
+            GroupBuilder::new()
+                .build(store.clone(), path)
+                .unwrap()
+                .store_metadata()
+                .unwrap();
+        }
+        crate::array::ArrayBuilder::new(
+            vec![4],
+            crate::array::DataType::UInt8,
+            vec![2].try_into().unwrap(),
+            crate::array::FillValue::from(0u8),
+        )
+        .build(store.clone(), "/a/b/c")
+        .unwrap()
+        .store_metadata()
+        .unwrap();
+
+        // An empty group
+        let empty = Group::open(store.clone(), "/empty").unwrap();
+        empty.store_consolidated_metadata().unwrap();
+        let empty = Group::open(store.clone(), "/empty").unwrap();
+        assert_eq!(
+            consolidated_metadata(&empty).get("metadata"),
+            Some(&serde_json::json!({}))
+        );
+
+        let root = Group::open(store.clone(), "/").unwrap();
+        root.store_consolidated_metadata().unwrap();
+        // The metadata of the group handle is unchanged
+        let GroupMetadata::V3(metadata) = root.metadata() else {
+            panic!("expected Zarr V3 group metadata")
+        };
+        assert!(!metadata
+            .additional_fields
+            .contains_key("consolidated_metadata"));
+        let root = Group::open(store.clone(), "/").unwrap();
+        let consolidated = consolidated_metadata(&root);
+        assert_eq!(consolidated["kind"], "inline");
+        let metadata = consolidated["metadata"].as_object().unwrap();
+        assert_eq!(
+            metadata.keys().collect::<Vec<_>>(),
+            vec!["a", "a/b", "a/b/c", "empty"]
+        );
+        assert_eq!(metadata["a/b/c"]["node_type"], "array");
+        assert_eq!(metadata["a/b/c"]["shape"], serde_json::json!([4]));
+        // Nested consolidated metadata is not repeated
+        assert!(metadata["empty"].get("consolidated_metadata").is_none());
+
+        // The consolidated metadata is read back in the Zarr V3 form
+        let consolidated = crate::node::ConsolidatedMetadata::retrieve(&store, &NodePath::root())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            consolidated
+                .metadata()
+                .keys()
+                .map(StoreKey::as_str)
+                .collect::<Vec<_>>(),
+            vec![
+                "a/b/c/zarr.json",
+                "a/b/zarr.json",
+                "a/zarr.json",
+                "empty/zarr.json",
+                "zarr.json"
+            ]
+        );
+        let group = Group::open_with_consolidated(store.clone(), "/a/b", &consolidated).unwrap();
+        assert!(matches!(group.metadata(), GroupMetadata::V3(_)));
+        let array = Array::open_with_consolidated(store.clone(), "/a/b/c", &consolidated).unwrap();
+        assert_eq!(array.shape(), &[4]);
+        assert!(
+            crate::node::ConsolidatedMetadata::retrieve(&store, &"/a".try_into().unwrap())
+                .unwrap()
+                .is_none()
+        );
+
+        // An unrecognised consolidated metadata format
+        assert!(crate::node::ConsolidatedMetadata::from_slice(
+            &NodePath::root(),
+            br#"{"zarr_format": 3, "node_type": "group"}"#
+        )
+        .is_err());
+        assert!(crate::node::ConsolidatedMetadata::from_slice(
+            &NodePath::root(),
+            br#"{"zarr_format": 3, "node_type": "group", "consolidated_metadata": {"kind": "other", "metadata": {}}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn group_store_consolidated_metadata_v2() {
+        use crate::node::ConsolidatedMetadata;
+
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let set = |key: &str, value: serde_json::Value| {
+            store
+                .set(
+                    &StoreKey::new(key).unwrap(),
+                    serde_json::to_vec(&value).unwrap().into(),
+                )
+                .unwrap();
+        };
+        let zgroup = serde_json::json!({"zarr_format": 2});
+        set("root/.zgroup", zgroup.clone());
+        set("root/.zattrs", serde_json::json!({"spam": "ham"}));
+        set("root/a/.zgroup", zgroup.clone());
+        set("root/a/b/.zgroup", zgroup);
+        set(
+            "root/a/b/c/.zarray",
+            serde_json::json!({
+                "zarr_format": 2,
+                "shape": [4],
+                "chunks": [2],
+                "dtype": "|u1",
+                "compressor": null,
+                "fill_value": 0,
+                "order": "C",
+                "filters": null
+            }),
+        );
+        set("root/a/b/c/.zattrs", serde_json::json!({"units": "m"}));
+
+        let root = Group::open(store.clone(), "/root").unwrap();
+        root.store_consolidated_metadata().unwrap();
+
+        let root_path: NodePath = "/root".try_into().unwrap();
+        let consolidated = ConsolidatedMetadata::retrieve(&store, &root_path)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            consolidated
+                .metadata()
+                .keys()
+                .map(StoreKey::as_str)
+                .collect::<Vec<_>>(),
+            vec![
+                "root/.zattrs",
+                "root/.zgroup",
+                "root/a/.zgroup",
+                "root/a/b/.zgroup",
+                "root/a/b/c/.zarray",
+                "root/a/b/c/.zattrs",
+            ]
+        );
+        assert_eq!(
+            consolidated.metadata()[&StoreKey::new("root/.zgroup").unwrap()],
+            serde_json::json!({"zarr_format": 2})
+        );
+
+        // The hierarchy opens from the consolidated metadata alone
+        for key in store.list().unwrap() {
+            if key.as_str() != "root/.zmetadata" {
+                store.erase(&key).unwrap();
+            }
+        }
+        let root = Group::open_with_consolidated(store.clone(), "/root", &consolidated).unwrap();
+        assert_eq!(root.attributes()["spam"], "ham");
+        let array =
+            Array::open_with_consolidated(store.clone(), "/root/a/b/c", &consolidated).unwrap();
+        assert_eq!(array.shape(), &[4]);
+        assert_eq!(array.attributes()["units"], "m");
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn group_async_descendants_stream() {
+        use futures::StreamExt;
+
+        let store = std::sync::Arc::new(zarrs_object_store::AsyncObjectStore::new(
+            object_store::memory::InMemory::new(),
+        ));
+        let group = GroupBuilder::new().build(store.clone(), "/group").unwrap();
+        group.async_store_metadata().await.unwrap();
+        GroupBuilder::new()
+            .build(store.clone(), "/group/a")
+            .unwrap()
+            .async_store_metadata()
+            .await
+            .unwrap();
+        for path in ["/group/a/x", "/group/b"] {
+            crate::array::ArrayBuilder::new(
+                vec![1],
+                crate::array::DataType::UInt8,
+                vec![1].try_into().unwrap(),
+                crate::array::FillValue::from(0u8),
+            )
+            .build(store.clone(), path)
+            .unwrap()
+            .async_store_metadata()
+            .await
+            .unwrap();
+        }
+        crate::storage::AsyncWritableStorageTraits::set(
+            &*store,
+            &crate::storage::StoreKey::new("group/c/zarr.json").unwrap(),
+            bytes::Bytes::from_static(b"invalid"),
+        )
+        .await
+        .unwrap();
+
+        // Deterministic breadth-first order
+        let nodes: Vec<_> = group.async_descendants_stream(1).collect().await;
+        assert_eq!(nodes.len(), 4);
+        let paths: Vec<_> = nodes
+            .iter()
+            .map(|node| {
+                node.as_ref()
+                    .map(|node| node.path().as_str().to_string())
+                    .ok()
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                Some("/group/a".to_string()),
+                Some("/group/b".to_string()),
+                None,
+                Some("/group/a/x".to_string())
+            ]
+        );
+
+        // Concurrent
+        let mut paths: Vec<_> = group
+            .async_descendants_stream(4)
+            .filter_map(
+                |node| async move { node.ok().map(|node| node.path().as_str().to_string()) },
+            )
+            .collect()
+            .await;
+        paths.sort();
+        assert_eq!(paths, ["/group/a", "/group/a/x", "/group/b"]);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn group_metadata_write_read_async() {
@@ -628,6 +1571,177 @@ mod tests {
         assert_eq!(group_copy.metadata(), group.metadata());
     }
 
+    #[test]
+    fn group_children() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let group = GroupBuilder::new().build(store.clone(), "/").unwrap();
+        group.store_metadata().unwrap();
+        GroupBuilder::new()
+            .build(store.clone(), "/subgroup")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        crate::array::ArrayBuilder::new(
+            vec![4],
+            crate::array::DataType::UInt8,
+            vec![2].try_into().unwrap(),
+            crate::array::FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap()
+        .store_metadata()
+        .unwrap();
+
+        let children = group.children().unwrap();
+        assert_eq!(children.len(), 2);
+
+        let arrays = group.arrays().unwrap();
+        assert_eq!(arrays.len(), 1);
+        assert_eq!(arrays[0].path().as_str(), "/array");
+        assert_eq!(arrays[0].shape(), &[4]);
+
+        let groups = group.groups().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].path().as_str(), "/subgroup");
+
+        assert_eq!(group.arrays_from_nodes(&children).unwrap().len(), 1);
+        assert_eq!(group.groups_from_nodes(&children).unwrap().len(), 1);
+
+        // A prefix without node metadata is skipped
+        store
+            .set(&StoreKey::new("stray/data.bin").unwrap(), vec![0].into())
+            .unwrap();
+        let paths = |nodes: &[Node]| nodes.iter().map(Node::path).cloned().collect::<Vec<_>>();
+        assert_eq!(paths(&group.children().unwrap()), paths(&children));
+
+        // A child with invalid metadata is an error
+        store
+            .set(&StoreKey::new("invalid/zarr.json").unwrap(), vec![0].into())
+            .unwrap();
+        assert!(group.children().is_err());
+        assert!(group.arrays().is_err());
+        assert!(group.groups().is_err());
+    }
+
+    #[test]
+    fn group_remove() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        GroupBuilder::new()
+            .build(store.clone(), "/")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        let group = GroupBuilder::new().build(store.clone(), "/group").unwrap();
+        group.store_metadata().unwrap();
+        let array = crate::array::ArrayBuilder::new(
+            vec![4],
+            crate::array::DataType::UInt8,
+            vec![2].try_into().unwrap(),
+            crate::array::FillValue::from(0u8),
+        )
+        .build(store.clone(), "/group/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array.store_chunk_elements::<u8>(&[1], &[1, 2]).unwrap();
+        let group_v2 = Group::new_with_metadata(
+            store.clone(),
+            "/group/group_v2",
+            GroupMetadata::V2(serde_json::from_str(r#"{"zarr_format": 2}"#).unwrap()),
+        )
+        .unwrap();
+        group_v2.store_metadata().unwrap();
+        let attributes_key = StoreKey::new("group/group_v2/.zattrs").unwrap();
+        store.set(&attributes_key, b"{}".to_vec().into()).unwrap();
+        // A key which shares a prefix with the group name but is not in the group
+        let other_key = StoreKey::new("group_other/zarr.json").unwrap();
+        store.set(&other_key, vec![].into()).unwrap();
+
+        let keys = group.remove_dry_run().unwrap();
+        assert_eq!(
+            keys,
+            [
+                "group/array/c/1",
+                "group/array/zarr.json",
+                "group/group_v2/.zattrs",
+                "group/group_v2/.zgroup",
+                "group/zarr.json",
+            ]
+            .map(|key| StoreKey::new(key).unwrap())
+        );
+        assert_eq!(store.list().unwrap().len(), 7);
+
+        group.remove().unwrap();
+        assert!(group.remove_dry_run().unwrap().is_empty());
+        assert_eq!(
+            store.list().unwrap(),
+            [
+                StoreKey::new("group_other/zarr.json").unwrap(),
+                meta_key_v3(&NodePath::root())
+            ]
+        );
+    }
+
+    #[test]
+    fn group_exists() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        assert!(!Group::exists(&store, "/group").unwrap());
+        GroupBuilder::new()
+            .build(store.clone(), "/group")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        assert!(Group::exists(&store, "/group").unwrap());
+
+        store
+            .set(
+                &StoreKey::new("group_v2/.zgroup").unwrap(),
+                b"{}".to_vec().into(),
+            )
+            .unwrap();
+        assert!(Group::exists(&store, "/group_v2").unwrap());
+
+        crate::array::ArrayBuilder::new(
+            vec![4],
+            crate::array::DataType::UInt8,
+            vec![2].try_into().unwrap(),
+            crate::array::FillValue::from(0u8),
+        )
+        .build(store.clone(), "/array")
+        .unwrap()
+        .store_metadata()
+        .unwrap();
+        assert!(!Group::exists(&store, "/array").unwrap());
+
+        store
+            .set(&StoreKey::new("invalid/zarr.json").unwrap(), vec![].into())
+            .unwrap();
+        assert!(Group::exists(&store, "/invalid").is_err());
+        assert!(Group::exists(&store, "invalid").is_err());
+    }
+
+    #[test]
+    fn group_reload() {
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let mut group = GroupBuilder::new().build(store.clone(), "/group").unwrap();
+        group.store_metadata().unwrap();
+
+        let mut group_other = Group::open(store.clone(), "/group").unwrap();
+        let mut attributes = serde_json::Map::new();
+        attributes.insert("key".to_string(), "value".into());
+        *group_other.attributes_mut() = attributes.clone();
+        group_other.store_metadata().unwrap();
+
+        assert!(group.attributes().is_empty());
+        group.reload().unwrap();
+        assert_eq!(group.attributes(), &attributes);
+        assert_eq!(group.path().as_str(), "/group");
+        assert!(std::sync::Arc::ptr_eq(&group.storage, &store));
+
+        group.erase_metadata().unwrap();
+        assert!(group.reload().is_err());
+        assert_eq!(group.attributes(), &attributes);
+    }
+
     /// Implicit group support is removed since implicit groups were removed from the Zarr V3 spec
     #[test]
     fn group_implicit() {
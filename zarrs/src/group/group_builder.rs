@@ -1,8 +1,11 @@
 use std::sync::Arc;
 
-use crate::metadata::{
-    v3::{AdditionalFields, GroupMetadataV3},
-    GroupMetadata,
+use crate::{
+    config::{global_config, MustUnderstandLevel},
+    metadata::{
+        v3::{AdditionalFields, GroupMetadataV3},
+        GroupMetadata,
+    },
 };
 
 use super::{Group, GroupCreateError};
@@ -54,11 +57,39 @@ impl GroupBuilder {
         self
     }
 
+    /// Validate the group configuration without building the group.
+    ///
+    /// This runs every check performed by [`build`](GroupBuilder::build), except for path validation, and collects all of the errors rather than returning the first.
+    /// The storage is not accessed.
+    ///
+    /// # Errors
+    /// Returns a [`GroupCreateError`] for each unsupported additional field if the [must understand level](crate::config::Config#must-understand-level) is strict.
+    pub fn validate(&self) -> Result<(), Vec<GroupCreateError>> {
+        if global_config().must_understand_level() != MustUnderstandLevel::Strict {
+            return Ok(());
+        }
+        let additional_fields = match &self.metadata {
+            GroupMetadata::V3(metadata) => &metadata.additional_fields,
+            GroupMetadata::V2(metadata) => &metadata.additional_fields,
+        };
+        let errors: Vec<_> = additional_fields
+            .iter()
+            .filter_map(|(name, field)| field.validate(name).err())
+            .map(GroupCreateError::UnsupportedAdditionalFieldError)
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Build into a [`Group`].
     ///
     /// # Errors
     ///
     /// Returns [`GroupCreateError`] if the group could not be created.
+    /// Use [`validate`](GroupBuilder::validate) to get every problem with the group configuration.
     pub fn build<TStorage: ?Sized>(
         &self,
         storage: Arc<TStorage>,
@@ -96,4 +127,19 @@ mod tests {
         assert_eq!(group.attributes_mut(), &attributes);
         assert_eq!(group.additional_fields_mut(), &additional_fields);
     }
+
+    #[test]
+    fn group_builder_validate() {
+        let mut builder = GroupBuilder::default();
+        assert!(builder.validate().is_ok());
+
+        let must_understand: AdditionalFields = serde_json::from_str(
+            r#"{"a": {"must_understand": true}, "b": {}, "c": {"must_understand": false}}"#,
+        )
+        .unwrap();
+        builder.additional_fields(must_understand);
+        let errors = builder.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(builder.build(Arc::new(MemoryStore::new()), "/").is_err());
+    }
 }
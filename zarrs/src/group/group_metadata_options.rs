@@ -4,12 +4,17 @@ use crate::config::{global_config, MetadataConvertVersion};
 #[derive(Debug, Clone)]
 pub struct GroupMetadataOptions {
     convert_version: MetadataConvertVersion,
+    serialize_empty_attributes: bool,
+    stage_metadata_writes: bool,
 }
 
 impl Default for GroupMetadataOptions {
     fn default() -> Self {
+        let config = global_config();
         Self {
-            convert_version: global_config().metadata_convert_version(),
+            convert_version: config.metadata_convert_version(),
+            serialize_empty_attributes: config.serialize_empty_attributes(),
+            stage_metadata_writes: config.stage_metadata_writes(),
         }
     }
 }
@@ -29,4 +34,31 @@ impl GroupMetadataOptions {
         self.convert_version = convert_version;
         self
     }
+
+    /// Get the [serialize empty attributes](crate::config::Config#serialize-empty-attributes) configuration.
+    #[must_use]
+    pub fn serialize_empty_attributes(&self) -> bool {
+        self.serialize_empty_attributes
+    }
+
+    /// Set the [serialize empty attributes](crate::config::Config#serialize-empty-attributes) configuration.
+    pub fn set_serialize_empty_attributes(
+        &mut self,
+        serialize_empty_attributes: bool,
+    ) -> &mut Self {
+        self.serialize_empty_attributes = serialize_empty_attributes;
+        self
+    }
+
+    /// Get the [stage metadata writes](crate::config::Config#stage-metadata-writes) configuration.
+    #[must_use]
+    pub fn stage_metadata_writes(&self) -> bool {
+        self.stage_metadata_writes
+    }
+
+    /// Set the [stage metadata writes](crate::config::Config#stage-metadata-writes) configuration.
+    pub fn set_stage_metadata_writes(&mut self, stage_metadata_writes: bool) -> &mut Self {
+        self.stage_metadata_writes = stage_metadata_writes;
+        self
+    }
 }
@@ -177,7 +177,8 @@
 //!  - `async`: an **experimental** asynchronous API for [`stores`](storage), [`Array`](crate::array::Array), and [`Group`](group::Group).
 //!    - The async API is runtime-agnostic. This has some limitations that are detailed in the [`Array`](crate::array::Array) docs.
 //!    - The async API is not as performant as the sync API.
-//!  - Codecs: `bitround`, `bz2`, `pcodec`, `zfp`, `zstd`.
+//!  - Codecs: `adler32`, `bitround`, `brotli`, `bz2`, `delta`, `fixedscaleoffset`, `lz4`, `pcodec`, `zfp`, `zstd`.
+//!  - `transport_decompress`: the [`TransportDecompressStorageAdapter`](storage::storage_adapter::transport_decompress::TransportDecompressStorageAdapter) for stores that wrap values in a transport `gzip` layer.
 //!
 //! ## `zarrs` Ecosystem
 #![doc = include_str!("../doc/ecosystem.md")]
@@ -15,17 +15,35 @@ pub use node_path::{NodePath, NodePathError};
 mod node_sync;
 pub use node_sync::{get_child_nodes, node_exists, node_exists_listable};
 
+mod copy;
+pub use copy::{copy_hierarchy, CopyHierarchyError, CopyHierarchyMode};
+
+mod consolidated_metadata;
+pub(crate) use consolidated_metadata::CONSOLIDATED_METADATA_FIELD;
+pub use consolidated_metadata::{
+    ConsolidatedMetadata, ConsolidatedMetadataMap, ConsolidatedMetadataSource,
+};
+
+mod hierarchy;
+pub use hierarchy::Hierarchy;
+
 mod key;
 pub use key::{
-    data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group, meta_key_v3,
+    data_key, meta_key, meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_consolidated,
+    meta_key_v2_group, meta_key_v3,
 };
 
 #[cfg(feature = "async")]
 mod node_async;
 #[cfg(feature = "async")]
+pub(crate) use node_async::async_descendants_stream;
+#[cfg(feature = "async")]
 pub use node_async::{async_get_child_nodes, async_node_exists, async_node_exists_listable};
 
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 pub use crate::metadata::NodeMetadata;
 use thiserror::Error;
@@ -37,11 +55,16 @@ use crate::{
         v2::{ArrayMetadataV2, GroupMetadataV2},
         GroupMetadata,
     },
-    storage::{ListableStorageTraits, ReadableStorageTraits, StorageError},
+    storage::{
+        Bytes, ListableStorageTraits, ReadableStorageTraits, StorageError, StoreKey,
+        WritableStorageTraits,
+    },
 };
 
 #[cfg(feature = "async")]
-use crate::storage::{AsyncListableStorageTraits, AsyncReadableStorageTraits};
+use crate::storage::{
+    AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+};
 
 /// A Zarr hierarchy node.
 ///
@@ -85,7 +108,7 @@ impl Node {
             // Try a Zarr V3 group/array
             let key_v3 = meta_key_v3(path);
             if let Some(metadata) = storage.get(&key_v3)? {
-                let metadata: NodeMetadata = serde_json::from_slice(&metadata)
+                let metadata: NodeMetadata = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
                 match metadata {
                     NodeMetadata::Array(ArrayMetadata::V3(_))
@@ -103,13 +126,14 @@ impl Node {
             let array_key = meta_key_v2_array(path);
             let attributes_key = meta_key_v2_attributes(path);
             if let Some(metadata) = storage.get(&array_key)? {
-                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(array_key, err.to_string()))?;
                 let attributes = storage.get(&attributes_key)?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Ok(NodeMetadata::Array(ArrayMetadata::V2(metadata)));
             }
@@ -117,13 +141,14 @@ impl Node {
             // Try a Zarr V2 group
             let group_key = meta_key_v2_group(path);
             if let Some(metadata) = storage.get(&group_key)? {
-                let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(group_key, err.to_string()))?;
                 let attributes = storage.get(&attributes_key)?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Ok(NodeMetadata::Group(GroupMetadata::V2(metadata)));
             }
@@ -147,7 +172,7 @@ impl Node {
             // Try a Zarr V3 group/array
             let key_v3 = meta_key_v3(path);
             if let Some(metadata) = storage.get(&key_v3).await? {
-                let metadata: NodeMetadata = serde_json::from_slice(&metadata)
+                let metadata: NodeMetadata = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
                 match metadata {
                     NodeMetadata::Array(ArrayMetadata::V3(_))
@@ -165,13 +190,14 @@ impl Node {
             let array_key = meta_key_v2_array(path);
             let attributes_key = meta_key_v2_attributes(path);
             if let Some(metadata) = storage.get(&array_key).await? {
-                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(array_key, err.to_string()))?;
                 let attributes = storage.get(&attributes_key).await?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Ok(NodeMetadata::Array(ArrayMetadata::V2(metadata)));
             }
@@ -179,13 +205,14 @@ impl Node {
             // Try a Zarr V2 group
             let group_key = meta_key_v2_group(path);
             if let Some(metadata) = storage.get(&group_key).await? {
-                let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+                let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
                     .map_err(|err| StorageError::InvalidMetadata(group_key, err.to_string()))?;
                 let attributes = storage.get(&attributes_key).await?;
                 if let Some(attributes) = attributes {
-                    metadata.attributes = serde_json::from_slice(&attributes).map_err(|err| {
-                        StorageError::InvalidMetadata(attributes_key, err.to_string())
-                    })?;
+                    metadata.attributes =
+                        serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
+                            StorageError::InvalidMetadata(attributes_key, err.to_string())
+                        })?;
                 }
                 return Ok(NodeMetadata::Group(GroupMetadata::V2(metadata)));
             }
@@ -312,58 +339,182 @@ impl Node {
 
     /// Return a tree representation of a hierarchy as a string.
     ///
-    /// Arrays are annotated with their shape and data type.
+    /// Nodes are drawn with box-drawing characters and siblings are sorted by name.
+    /// Arrays are annotated with their shape and data type, and groups with their number of children.
     /// For example:
     /// ```text
-    /// a
-    ///   baz [10000, 1000] float64
-    ///   foo [10000, 1000] float64
-    /// b
+    /// / (2 children)
+    /// ├── a (2 children)
+    /// │   ├── baz [10000, 1000] float64
+    /// │   └── foo [10000, 1000] float64
+    /// └── b (0 children)
     /// ```
     #[must_use]
     pub fn hierarchy_tree(&self) -> String {
-        fn print_metadata(name: &str, string: &mut String, metadata: &NodeMetadata) {
-            match metadata {
-                NodeMetadata::Array(array_metadata) => {
-                    let s = match array_metadata {
-                        ArrayMetadata::V3(array_metadata) => {
-                            format!(
-                                "{} {:?} {}",
-                                name, array_metadata.shape, array_metadata.data_type
-                            )
-                        }
-                        ArrayMetadata::V2(array_metadata) => {
-                            format!(
-                                "{} {:?} {:?}",
-                                name, array_metadata.shape, array_metadata.dtype
-                            )
-                        }
-                    };
-                    string.push_str(&s);
-                }
+        fn print_node(name: &str, string: &mut String, node: &Node) {
+            let s = match &node.metadata {
+                NodeMetadata::Array(array_metadata) => match array_metadata {
+                    ArrayMetadata::V3(array_metadata) => {
+                        format!(
+                            "{} {:?} {}",
+                            name, array_metadata.shape, array_metadata.data_type
+                        )
+                    }
+                    ArrayMetadata::V2(array_metadata) => {
+                        format!(
+                            "{} {:?} {:?}",
+                            name, array_metadata.shape, array_metadata.dtype
+                        )
+                    }
+                },
                 NodeMetadata::Group(_) => {
-                    string.push_str(name);
+                    let num_children = node.children.len();
+                    let children = if num_children == 1 {
+                        "child"
+                    } else {
+                        "children"
+                    };
+                    format!("{name} ({num_children} {children})")
                 }
             };
+            string.push_str(&s);
             string.push('\n');
         }
 
-        fn update_tree(string: &mut String, children: &[Node], depth: usize) {
-            for child in children {
-                let name = child.name();
-                string.push_str(&" ".repeat(depth * 2));
-                print_metadata(name.as_str(), string, &child.metadata);
-                update_tree(string, &child.children, depth + 1);
+        fn update_tree(string: &mut String, children: &[Node], indent: &str) {
+            let mut children: Vec<&Node> = children.iter().collect();
+            children.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
+            let num_children = children.len();
+            for (i, child) in children.into_iter().enumerate() {
+                let last = i + 1 == num_children;
+                string.push_str(indent);
+                string.push_str(if last { "└── " } else { "├── " });
+                print_node(child.name().as_str(), string, child);
+                let indent = format!("{indent}{}", if last { "    " } else { "│   " });
+                update_tree(string, &child.children, &indent);
             }
         }
 
         let mut string = String::default();
-        print_metadata("/", &mut string, &self.metadata);
-        update_tree(&mut string, &self.children, 1);
+        print_node("/", &mut string, self);
+        update_tree(&mut string, &self.children, "");
         string
     }
 }
 
+/// Strip a leading UTF-8 byte order mark from a metadata document.
+///
+/// Some tools write metadata with a BOM, which is not valid JSON.
+/// Surrounding whitespace is already permitted by JSON.
+pub(crate) fn strip_bom(metadata: &[u8]) -> &[u8] {
+    metadata.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(metadata)
+}
+
+/// Return the `node_type` of the Zarr V3 node metadata at `key` without parsing the rest of the metadata.
+pub(crate) fn node_type_v3(key: StoreKey, metadata: &[u8]) -> Result<String, StorageError> {
+    #[derive(serde::Deserialize)]
+    struct NodeTypeV3 {
+        node_type: String,
+    }
+    serde_json::from_slice::<NodeTypeV3>(strip_bom(metadata))
+        .map(|metadata| metadata.node_type)
+        .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))
+}
+
+/// Serialise the Zarr V3 node metadata at `key` to pretty JSON.
+///
+/// Empty attributes are omitted unless `serialize_empty_attributes` is true.
+pub(crate) fn metadata_v3_to_json<T: serde::Serialize>(
+    key: &StoreKey,
+    metadata: &T,
+    serialize_empty_attributes: bool,
+) -> Result<Vec<u8>, StorageError> {
+    let to_storage_error =
+        |err: serde_json::Error| StorageError::InvalidMetadata(key.clone(), err.to_string());
+    if serialize_empty_attributes {
+        let mut metadata = serde_json::to_value(metadata).map_err(to_storage_error)?;
+        if let Some(metadata) = metadata.as_object_mut() {
+            metadata
+                .entry("attributes")
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::default()));
+        }
+        serde_json::to_vec_pretty(&metadata)
+    } else {
+        serde_json::to_vec_pretty(metadata)
+    }
+    .map_err(to_storage_error)
+}
+
+/// Return a unique temporary key alongside the metadata `key` (e.g. `array/.zarr.json.{pid}.{n}.tmp`).
+///
+/// The key is unique across concurrent writers in this process and, through the process id, across processes.
+fn temporary_metadata_key(key: &StoreKey) -> Result<StoreKey, StorageError> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let (parent, name) = key.as_str().rsplit_once('/').unwrap_or(("", key.as_str()));
+    let name = format!(
+        ".{name}.{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    Ok(StoreKey::new(if parent.is_empty() {
+        name
+    } else {
+        format!("{parent}/{name}")
+    })?)
+}
+
+/// Store the node metadata `value` at `key`.
+///
+/// If `stage` is true and the store [supports renaming](WritableStorageTraits::supports_rename), `value` is written to a unique temporary key and then [renamed](WritableStorageTraits::rename) to `key`, so readers never observe partially written metadata.
+/// The temporary key is erased if staging fails.
+/// Otherwise, `value` is written to `key` directly.
+pub(crate) fn set_metadata<TStorage: ?Sized + WritableStorageTraits>(
+    storage: &TStorage,
+    key: &StoreKey,
+    value: Bytes,
+    stage: bool,
+) -> Result<(), StorageError> {
+    if stage && storage.supports_rename() {
+        let temp_key = temporary_metadata_key(key)?;
+        let result = storage
+            .set(&temp_key, value)
+            .and_then(|()| storage.rename(&temp_key, key));
+        if result.is_err() {
+            // Best effort cleanup, the original error is more informative
+            let _ = storage.erase(&temp_key);
+        }
+        result
+    } else {
+        storage.set(key, value)
+    }
+}
+
+/// Asynchronously store the node metadata `value` at `key`.
+///
+/// See [`set_metadata`].
+#[cfg(feature = "async")]
+pub(crate) async fn async_set_metadata<TStorage: ?Sized + AsyncWritableStorageTraits>(
+    storage: &TStorage,
+    key: &StoreKey,
+    value: AsyncBytes,
+    stage: bool,
+) -> Result<(), StorageError> {
+    if stage && storage.supports_rename() {
+        let temp_key = temporary_metadata_key(key)?;
+        let mut result = storage.set(&temp_key, value).await;
+        if result.is_ok() {
+            result = storage.rename(&temp_key, key).await;
+        }
+        if result.is_err() {
+            // Best effort cleanup, the original error is more informative
+            let _ = storage.erase(&temp_key).await;
+        }
+        result
+    } else {
+        storage.set(key, value).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -496,6 +647,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn node_metadata_bom() {
+        let store: std::sync::Arc<MemoryStore> = std::sync::Arc::new(MemoryStore::new());
+        let mut metadata = b"\xEF\xBB\xBF\n ".to_vec();
+        metadata.extend_from_slice(br#"{"zarr_format":3,"node_type":"group"}"#);
+        metadata.extend_from_slice(b" \r\n");
+        store
+            .set(&StoreKey::new("node/zarr.json").unwrap(), metadata.into())
+            .unwrap();
+        assert!(Node::open(&store, "/node").is_ok());
+        assert!(crate::group::Group::open(store.clone(), "/node").is_ok());
+
+        // A BOM does not mask malformed metadata
+        store
+            .set(
+                &StoreKey::new("node/zarr.json").unwrap(),
+                b"\xEF\xBB\xBF{".to_vec().into(),
+            )
+            .unwrap();
+        assert!(Node::open(&store, "/node").is_err());
+    }
+
     #[test]
     fn node_invalid_child() {
         let store: std::sync::Arc<MemoryStore> = std::sync::Arc::new(MemoryStore::new());
@@ -515,6 +688,88 @@ mod tests {
         );
     }
 
+    /// A store which does not support renaming.
+    struct NoRenameStore(MemoryStore);
+
+    impl WritableStorageTraits for NoRenameStore {
+        fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+            self.0.set(key, value)
+        }
+
+        fn set_partial_values(
+            &self,
+            key_offset_values: &[crate::storage::StoreKeyOffsetValue],
+        ) -> Result<(), StorageError> {
+            self.0.set_partial_values(key_offset_values)
+        }
+
+        fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+            self.0.erase(key)
+        }
+
+        fn erase_prefix(&self, prefix: &crate::storage::StorePrefix) -> Result<(), StorageError> {
+            self.0.erase_prefix(prefix)
+        }
+    }
+
+    #[test]
+    fn node_temporary_metadata_key() {
+        let key = StoreKey::new("node/zarr.json").unwrap();
+        let temp_key_a = temporary_metadata_key(&key).unwrap();
+        let temp_key_b = temporary_metadata_key(&key).unwrap();
+        assert_ne!(temp_key_a, temp_key_b);
+        assert!(temp_key_a.as_str().starts_with("node/.zarr.json."));
+        assert!(temp_key_a.as_str().ends_with(".tmp"));
+        let root_key = StoreKey::new("zarr.json").unwrap();
+        assert!(temporary_metadata_key(&root_key)
+            .unwrap()
+            .as_str()
+            .starts_with(".zarr.json."));
+    }
+
+    #[test]
+    fn node_set_metadata_staged() {
+        use crate::storage::storage_adapter::metered::{
+            InMemoryStorageMetrics, MeteredStorageAdapter, StorageOperation,
+        };
+
+        let key = StoreKey::new("node/zarr.json").unwrap();
+
+        // Staged through a temporary key if the store supports renaming
+        let store = std::sync::Arc::new(MemoryStore::new());
+        let metrics = std::sync::Arc::new(InMemoryStorageMetrics::new());
+        let adapter = MeteredStorageAdapter::new(store.clone(), metrics.clone());
+        set_metadata(&adapter, &key, vec![1].into(), true).unwrap();
+        assert_eq!(store.get(&key).unwrap().unwrap(), vec![1]);
+        assert_eq!(store.list().unwrap(), &[key.clone()]);
+        assert_eq!(metrics.requests(StorageOperation::Set), 1);
+        assert_eq!(metrics.requests(StorageOperation::Rename), 1);
+
+        // Written directly if the store does not support renaming
+        let store = std::sync::Arc::new(NoRenameStore(MemoryStore::new()));
+        let metrics = std::sync::Arc::new(InMemoryStorageMetrics::new());
+        let adapter = MeteredStorageAdapter::new(store.clone(), metrics.clone());
+        set_metadata(&adapter, &key, vec![2].into(), true).unwrap();
+        assert_eq!(store.0.get(&key).unwrap().unwrap(), vec![2]);
+        assert_eq!(store.0.list().unwrap(), &[key.clone()]);
+        assert_eq!(metrics.requests(StorageOperation::Set), 1);
+        assert_eq!(metrics.requests(StorageOperation::Rename), 0);
+        assert_eq!(metrics.requests(StorageOperation::Erase), 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn node_async_set_metadata_staged() {
+        let store =
+            zarrs_object_store::AsyncObjectStore::new(object_store::memory::InMemory::new());
+        let key = StoreKey::new("node/zarr.json").unwrap();
+        async_set_metadata(&store, &key, vec![1].into(), true)
+            .await
+            .unwrap();
+        assert_eq!(store.get(&key).await.unwrap().unwrap(), vec![1]);
+        assert_eq!(store.list().await.unwrap(), &[key.clone()]);
+    }
+
     #[test]
     fn node_root() {
         let node = Node::new_with_metadata(
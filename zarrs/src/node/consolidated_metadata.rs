@@ -0,0 +1,315 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    array::ArrayMetadata,
+    config::MetadataRetrieveVersion,
+    group::GroupMetadata,
+    metadata::{
+        v2::{ArrayMetadataV2, GroupMetadataV2},
+        v3::{ArrayMetadataV3, GroupMetadataV3},
+    },
+    storage::{ReadableStorageTraits, StorageError, StoreKey, StorePrefix},
+};
+
+use super::{
+    meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_consolidated, meta_key_v2_group,
+    meta_key_v3, strip_bom, NodePath,
+};
+
+/// A source of consolidated metadata.
+///
+/// Consolidated metadata maps the metadata keys of the nodes in a hierarchy (e.g. `array/zarr.json`, or `array/.zarray` and `array/.zattrs` for Zarr V2) to their JSON values.
+/// It enables nodes to be opened without a storage request per node.
+///
+/// A consolidated metadata source can be an in-memory document, such as a [`ConsolidatedMetadataMap`], or a loader that retrieves a consolidated document lazily on first use.
+pub trait ConsolidatedMetadataSource: Send + Sync {
+    /// Return the consolidated value of the metadata `key`, or [`None`] if `key` is not consolidated.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the consolidated metadata cannot be loaded.
+    fn consolidated_metadata(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<serde_json::Value>, StorageError>;
+
+    /// Return the consolidated metadata of the array at `path`, or [`None`] if it is not consolidated.
+    ///
+    /// Zarr V3 metadata is prioritised over Zarr V2 metadata if `version` is [`MetadataRetrieveVersion::Default`].
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the consolidated metadata cannot be loaded or is invalid.
+    fn consolidated_array_metadata(
+        &self,
+        path: &NodePath,
+        version: &MetadataRetrieveVersion,
+    ) -> Result<Option<ArrayMetadata>, StorageError> {
+        if let MetadataRetrieveVersion::Default | MetadataRetrieveVersion::V3 = version {
+            let key_v3 = meta_key_v3(path);
+            if let Some(metadata) = self.consolidated_metadata(&key_v3)? {
+                let metadata: ArrayMetadataV3 = serde_json::from_value(metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
+                return Ok(Some(ArrayMetadata::V3(metadata)));
+            }
+        }
+
+        if let MetadataRetrieveVersion::Default | MetadataRetrieveVersion::V2 = version {
+            let key_v2 = meta_key_v2_array(path);
+            if let Some(metadata) = self.consolidated_metadata(&key_v2)? {
+                let mut metadata: ArrayMetadataV2 = serde_json::from_value(metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
+
+                let attributes_key = meta_key_v2_attributes(path);
+                if let Some(attributes) = self.consolidated_metadata(&attributes_key)? {
+                    metadata.attributes = serde_json::from_value(attributes).map_err(|err| {
+                        StorageError::InvalidMetadata(attributes_key, err.to_string())
+                    })?;
+                }
+                return Ok(Some(ArrayMetadata::V2(metadata)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Return the consolidated metadata of the group at `path`, or [`None`] if it is not consolidated.
+    ///
+    /// Zarr V3 metadata is prioritised over Zarr V2 metadata if `version` is [`MetadataRetrieveVersion::Default`].
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the consolidated metadata cannot be loaded or is invalid.
+    fn consolidated_group_metadata(
+        &self,
+        path: &NodePath,
+        version: &MetadataRetrieveVersion,
+    ) -> Result<Option<GroupMetadata>, StorageError> {
+        if let MetadataRetrieveVersion::Default | MetadataRetrieveVersion::V3 = version {
+            let key_v3 = meta_key_v3(path);
+            if let Some(metadata) = self.consolidated_metadata(&key_v3)? {
+                let metadata: GroupMetadataV3 = serde_json::from_value(metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key_v3, err.to_string()))?;
+                return Ok(Some(GroupMetadata::V3(metadata)));
+            }
+        }
+
+        if let MetadataRetrieveVersion::Default | MetadataRetrieveVersion::V2 = version {
+            let key_v2 = meta_key_v2_group(path);
+            if let Some(metadata) = self.consolidated_metadata(&key_v2)? {
+                let mut metadata: GroupMetadataV2 = serde_json::from_value(metadata)
+                    .map_err(|err| StorageError::InvalidMetadata(key_v2, err.to_string()))?;
+
+                let attributes_key = meta_key_v2_attributes(path);
+                if let Some(attributes) = self.consolidated_metadata(&attributes_key)? {
+                    metadata.attributes = serde_json::from_value(attributes).map_err(|err| {
+                        StorageError::InvalidMetadata(attributes_key, err.to_string())
+                    })?;
+                }
+                return Ok(Some(GroupMetadata::V2(metadata)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// An in-memory consolidated metadata map from metadata keys to their JSON values.
+pub type ConsolidatedMetadataMap = BTreeMap<StoreKey, serde_json::Value>;
+
+impl ConsolidatedMetadataSource for ConsolidatedMetadataMap {
+    fn consolidated_metadata(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<serde_json::Value>, StorageError> {
+        Ok(self.get(key).cloned())
+    }
+}
+
+/// Consolidated metadata in the Zarr V2 (`.zmetadata`) or Zarr V3 (inline `consolidated_metadata`) form, as written by `zarr-python`.
+///
+/// A Zarr V2 `.zmetadata` document holds the metadata of every node in the hierarchy under a group, keyed by metadata key relative to that group:
+/// ```json
+/// {
+///     "metadata": {
+///         ".zgroup": {"zarr_format": 2},
+///         "array/.zarray": {"zarr_format": 2, "shape": [100], ...},
+///         "array/.zattrs": {"units": "m"}
+///     },
+///     "zarr_consolidated_format": 1
+/// }
+/// ```
+///
+/// A Zarr V3 group `zarr.json` holds the metadata of every node in the hierarchy under the group inline, keyed by node path relative to that group:
+/// ```json
+/// {
+///     "zarr_format": 3,
+///     "node_type": "group",
+///     "consolidated_metadata": {
+///         "kind": "inline",
+///         "metadata": {
+///             "array": {"zarr_format": 3, "node_type": "array", ...}
+///         }
+///     }
+/// }
+/// ```
+///
+/// The keys are resolved to the store keys of the nodes (e.g. `array/.zarray` or `array/zarr.json`) relative to the path of the group holding the document, so they can be looked up with the store keys of the nodes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConsolidatedMetadata {
+    metadata: ConsolidatedMetadataMap,
+}
+
+/// The supported `zarr_consolidated_format` of Zarr V2 consolidated metadata.
+const ZARR_CONSOLIDATED_FORMAT: u64 = 1;
+
+/// The name of the Zarr V3 group metadata field holding consolidated metadata.
+pub(crate) const CONSOLIDATED_METADATA_FIELD: &str = "consolidated_metadata";
+
+/// The supported `kind` of Zarr V3 consolidated metadata.
+const CONSOLIDATED_METADATA_KIND: &str = "inline";
+
+/// Returns true if a Zarr V3 group metadata `document` has a non-null `consolidated_metadata` field.
+fn has_consolidated_metadata_field(document: &serde_json::Map<String, serde_json::Value>) -> bool {
+    document
+        .get(CONSOLIDATED_METADATA_FIELD)
+        .is_some_and(|consolidated| !consolidated.is_null())
+}
+
+impl ConsolidatedMetadata {
+    /// Parse a consolidated metadata document of the group at `path`.
+    ///
+    /// The format of the document is detected:
+    ///  - a Zarr V2 `.zmetadata` document has a `zarr_consolidated_format` field, and
+    ///  - a Zarr V3 group `zarr.json` document has a `consolidated_metadata` field.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the document is not valid consolidated metadata or its format is not recognised.
+    pub fn from_slice(path: &NodePath, document: &[u8]) -> Result<Self, StorageError> {
+        let key_v2 = meta_key_v2_consolidated(path);
+        let document: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_slice(strip_bom(document))
+                .map_err(|err| StorageError::InvalidMetadata(key_v2.clone(), err.to_string()))?;
+        if document.contains_key("zarr_consolidated_format") {
+            Self::from_document_v2(path, document)
+        } else if has_consolidated_metadata_field(&document) {
+            Self::from_document_v3(path, document)
+        } else {
+            Err(StorageError::InvalidMetadata(
+                key_v2,
+                "unrecognised consolidated metadata format, expected a Zarr V2 .zmetadata document or a Zarr V3 group with consolidated_metadata".to_string(),
+            ))
+        }
+    }
+
+    fn from_document_v2(
+        path: &NodePath,
+        document: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Self, StorageError> {
+        #[derive(serde::Deserialize)]
+        struct ConsolidatedMetadataDocument {
+            metadata: serde_json::Map<String, serde_json::Value>,
+            zarr_consolidated_format: u64,
+        }
+
+        let key = meta_key_v2_consolidated(path);
+        let document: ConsolidatedMetadataDocument = serde_json::from_value(document.into())
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+        if document.zarr_consolidated_format != ZARR_CONSOLIDATED_FORMAT {
+            return Err(StorageError::InvalidMetadata(
+                key,
+                format!(
+                    "unsupported zarr_consolidated_format {}",
+                    document.zarr_consolidated_format
+                ),
+            ));
+        }
+
+        let prefix: StorePrefix = path.try_into()?;
+        let metadata = document
+            .metadata
+            .into_iter()
+            .map(|(key, value)| Ok((StoreKey::new(format!("{}{key}", prefix.as_str()))?, value)))
+            .collect::<Result<_, StorageError>>()?;
+        Ok(Self { metadata })
+    }
+
+    fn from_document_v3(
+        path: &NodePath,
+        mut document: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Self, StorageError> {
+        #[derive(serde::Deserialize)]
+        struct ConsolidatedMetadataField {
+            kind: String,
+            metadata: serde_json::Map<String, serde_json::Value>,
+        }
+
+        let key = meta_key_v3(path);
+        let consolidated = document
+            .remove(CONSOLIDATED_METADATA_FIELD)
+            .unwrap_or_default();
+        let consolidated: ConsolidatedMetadataField = serde_json::from_value(consolidated)
+            .map_err(|err| StorageError::InvalidMetadata(key.clone(), err.to_string()))?;
+        if consolidated.kind != CONSOLIDATED_METADATA_KIND {
+            return Err(StorageError::InvalidMetadata(
+                key,
+                format!(
+                    "unsupported consolidated_metadata kind {}",
+                    consolidated.kind
+                ),
+            ));
+        }
+
+        let prefix: StorePrefix = path.try_into()?;
+        let mut metadata: ConsolidatedMetadataMap = consolidated
+            .metadata
+            .into_iter()
+            .map(|(relative_path, value)| {
+                let relative_path = relative_path.trim_matches('/');
+                Ok((
+                    StoreKey::new(format!("{}{relative_path}/zarr.json", prefix.as_str()))?,
+                    value,
+                ))
+            })
+            .collect::<Result<_, StorageError>>()?;
+        metadata.insert(key, document.into());
+        Ok(Self { metadata })
+    }
+
+    /// Retrieve the consolidated metadata of the group at `path` in `storage`, or [`None`] if it does not exist.
+    ///
+    /// The Zarr V3 group metadata (`zarr.json`) is checked for consolidated metadata first, then the Zarr V2 consolidated metadata (`.zmetadata`).
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error or the document is not valid consolidated metadata.
+    pub fn retrieve<TStorage: ?Sized + ReadableStorageTraits>(
+        storage: &Arc<TStorage>,
+        path: &NodePath,
+    ) -> Result<Option<Self>, StorageError> {
+        if let Some(document) = storage.get(&meta_key_v3(path))? {
+            let key = meta_key_v3(path);
+            let group: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_slice(strip_bom(&document))
+                    .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
+            if has_consolidated_metadata_field(&group) {
+                return Self::from_document_v3(path, group).map(Some);
+            }
+        }
+        storage
+            .get(&meta_key_v2_consolidated(path))?
+            .map(|document| Self::from_slice(path, &document))
+            .transpose()
+    }
+
+    /// Return the consolidated metadata map, keyed by store key.
+    #[must_use]
+    pub fn metadata(&self) -> &ConsolidatedMetadataMap {
+        &self.metadata
+    }
+}
+
+impl ConsolidatedMetadataSource for ConsolidatedMetadata {
+    fn consolidated_metadata(
+        &self,
+        key: &StoreKey,
+    ) -> Result<Option<serde_json::Value>, StorageError> {
+        self.metadata.consolidated_metadata(key)
+    }
+}
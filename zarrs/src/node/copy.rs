@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::{
+    array::{Array, ArrayCreateError, ArrayError},
+    array_subset::ArraySubset,
+    group::{Group, GroupCreateError},
+    storage::{
+        ListableStorageTraits, ReadableStorageTraits, StorageError, StoreKey, StorePrefix,
+        WritableStorageTraits,
+    },
+};
+
+use super::{Node, NodeCreateError, NodeMetadata, NodePath, NodePathError};
+
+/// The mode of [`copy_hierarchy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyHierarchyMode {
+    /// Copy every key below the source path byte-for-byte.
+    ///
+    /// This is a pure storage copy that does not parse metadata or decode chunks, so it never needs codec support.
+    /// Any keys that are not part of the hierarchy (e.g. consolidated metadata or orphaned chunks) are copied too.
+    #[default]
+    Verbatim,
+    /// Open each node and copy its metadata, and decode and re-encode each array chunk.
+    ///
+    /// Every codec in the hierarchy must be supported.
+    /// Only nodes and chunks that are part of the hierarchy are copied.
+    Transcode,
+}
+
+/// A [`copy_hierarchy`] error.
+#[derive(Debug, Error)]
+pub enum CopyHierarchyError {
+    /// An invalid node path.
+    #[error(transparent)]
+    NodePathError(#[from] NodePathError),
+    /// A storage error.
+    #[error(transparent)]
+    StorageError(#[from] StorageError),
+    /// A node creation error.
+    #[error(transparent)]
+    NodeCreateError(#[from] NodeCreateError),
+    /// An array creation error.
+    #[error(transparent)]
+    ArrayCreateError(#[from] ArrayCreateError),
+    /// A group creation error.
+    #[error(transparent)]
+    GroupCreateError(#[from] GroupCreateError),
+    /// An array error.
+    #[error(transparent)]
+    ArrayError(#[from] ArrayError),
+}
+
+/// Copy the hierarchy at `src_path` of `src_storage` to `dst_path` of `dst_storage`.
+///
+/// The [`CopyHierarchyMode::Verbatim`] mode copies the keys of the hierarchy byte-for-byte, and should be used to relocate a hierarchy with codecs that are not supported.
+/// The [`CopyHierarchyMode::Transcode`] mode decodes and re-encodes each chunk.
+///
+/// # Errors
+/// Returns a [`CopyHierarchyError`] if a path is invalid, there is an underlying error with either store, or a node cannot be transcoded.
+pub fn copy_hierarchy<
+    TSrcStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static,
+    TDstStorage: ?Sized + WritableStorageTraits + 'static,
+>(
+    src_storage: &Arc<TSrcStorage>,
+    src_path: &str,
+    dst_storage: &Arc<TDstStorage>,
+    dst_path: &str,
+    mode: CopyHierarchyMode,
+) -> Result<(), CopyHierarchyError> {
+    let src_path = NodePath::new(src_path)?;
+    let dst_path = NodePath::new(dst_path)?;
+    match mode {
+        CopyHierarchyMode::Verbatim => {
+            let src_prefix: StorePrefix = (&src_path).try_into().map_err(StorageError::from)?;
+            let dst_prefix: StorePrefix = (&dst_path).try_into().map_err(StorageError::from)?;
+            for src_key in src_storage.list_prefix(&src_prefix)? {
+                let Some(value) = src_storage.get(&src_key)? else {
+                    continue;
+                };
+                let relative_key = src_path
+                    .relative_key(&src_key)
+                    .unwrap_or_else(|| src_key.as_str().to_string());
+                let dst_key = StoreKey::new(dst_prefix.as_str().to_string() + &relative_key)
+                    .map_err(StorageError::from)?;
+                dst_storage.set(&dst_key, value)?;
+            }
+        }
+        CopyHierarchyMode::Transcode => {
+            let node = Node::open(src_storage, src_path.as_str())?;
+            transcode_node(src_storage, &src_path, dst_storage, &dst_path, &node)?;
+        }
+    }
+    Ok(())
+}
+
+fn transcode_node<
+    TSrcStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits + 'static,
+    TDstStorage: ?Sized + WritableStorageTraits + 'static,
+>(
+    src_storage: &Arc<TSrcStorage>,
+    src_path: &NodePath,
+    dst_storage: &Arc<TDstStorage>,
+    dst_path: &NodePath,
+    node: &Node,
+) -> Result<(), CopyHierarchyError> {
+    let node_dst_path = relocate_path(src_path, dst_path, node.path())?;
+    match node.metadata() {
+        NodeMetadata::Array(_) => {
+            let src_array = Array::open(src_storage.clone(), node.path().as_str())?;
+            let dst_array = Array::new_with_metadata(
+                dst_storage.clone(),
+                node_dst_path.as_str(),
+                src_array.metadata().clone(),
+            )?;
+            dst_array.store_metadata()?;
+            if let Some(chunk_grid_shape) = src_array.chunk_grid_shape() {
+                for chunk_indices in &ArraySubset::new_with_shape(chunk_grid_shape).indices() {
+                    if let Some(bytes) = src_array.retrieve_chunk_if_exists(&chunk_indices)? {
+                        dst_array.store_chunk(&chunk_indices, bytes)?;
+                    }
+                }
+            }
+        }
+        NodeMetadata::Group(metadata) => {
+            Group::new_with_metadata(
+                dst_storage.clone(),
+                node_dst_path.as_str(),
+                metadata.clone(),
+            )?
+            .store_metadata()?;
+            for child in node.children() {
+                transcode_node(src_storage, src_path, dst_storage, dst_path, child)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Map `path` below `src_path` to the same relative path below `dst_path`.
+fn relocate_path(
+    src_path: &NodePath,
+    dst_path: &NodePath,
+    path: &NodePath,
+) -> Result<NodePath, NodePathError> {
+    let relative = path
+        .as_str()
+        .strip_prefix(src_path.as_str())
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    if relative.is_empty() {
+        Ok(dst_path.clone())
+    } else {
+        NodePath::new(&format!(
+            "{}/{relative}",
+            dst_path.as_str().trim_end_matches('/')
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, DataType, FillValue},
+        group::GroupBuilder,
+        storage::store::MemoryStore,
+    };
+
+    use super::*;
+
+    #[test]
+    fn copy_hierarchy_modes() {
+        let src_storage = Arc::new(MemoryStore::new());
+        GroupBuilder::new()
+            .build(src_storage.clone(), "/group")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        let array = ArrayBuilder::new(
+            vec![4, 4],
+            DataType::UInt8,
+            vec![2, 2].try_into().unwrap(),
+            FillValue::from(0u8),
+        )
+        .build(src_storage.clone(), "/group/array")
+        .unwrap();
+        array.store_metadata().unwrap();
+        array
+            .store_chunk_elements::<u8>(&[0, 1], &[1, 2, 3, 4])
+            .unwrap();
+
+        // An array with an unsupported codec
+        let unsupported_key = StoreKey::new("group/unsupported/zarr.json").unwrap();
+        let mut unsupported_metadata = serde_json::to_value(array.metadata()).unwrap();
+        unsupported_metadata["codecs"][0]["name"] = "unsupported_codec".into();
+        src_storage
+            .set(
+                &unsupported_key,
+                serde_json::to_vec(&unsupported_metadata).unwrap().into(),
+            )
+            .unwrap();
+        let unsupported_chunk_key = StoreKey::new("group/unsupported/c/0/0").unwrap();
+        src_storage
+            .set(&unsupported_chunk_key, vec![0, 1, 2].into())
+            .unwrap();
+
+        // Verbatim copies every key without decoding
+        let dst_storage = Arc::new(MemoryStore::new());
+        copy_hierarchy(
+            &src_storage,
+            "/group",
+            &dst_storage,
+            "/copy",
+            CopyHierarchyMode::default(),
+        )
+        .unwrap();
+        assert_eq!(dst_storage.list().unwrap().len(), 5);
+        assert_eq!(
+            dst_storage
+                .get(&StoreKey::new("copy/unsupported/c/0/0").unwrap())
+                .unwrap()
+                .unwrap(),
+            vec![0, 1, 2]
+        );
+        let copy = Array::open(dst_storage.clone(), "/copy/array").unwrap();
+        assert_eq!(
+            copy.retrieve_chunk_elements::<u8>(&[0, 1]).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+
+        // Transcode requires codec support
+        let dst_storage = Arc::new(MemoryStore::new());
+        assert!(copy_hierarchy(
+            &src_storage,
+            "/group",
+            &dst_storage,
+            "/copy",
+            CopyHierarchyMode::Transcode,
+        )
+        .is_err());
+        src_storage
+            .erase_prefix(&StorePrefix::new("group/unsupported/").unwrap())
+            .unwrap();
+        copy_hierarchy(
+            &src_storage,
+            "/group",
+            &dst_storage,
+            "/copy",
+            CopyHierarchyMode::Transcode,
+        )
+        .unwrap();
+        let copy = Array::open(dst_storage.clone(), "/copy/array").unwrap();
+        assert_eq!(
+            copy.retrieve_chunk_elements::<u8>(&[0, 1]).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert!(copy.retrieve_chunk_if_exists(&[0, 0]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use crate::{
+    array::{
+        Array, ArrayCreateError, CachedArray, ChunkCacheDecodedSharedArray,
+        ChunkCacheDecodedSharedSizeLimit,
+    },
+    group::{Group, GroupCreateError},
+    storage::ReadableStorageTraits,
+};
+
+use super::ConsolidatedMetadataSource;
+
+/// A Zarr hierarchy context, holding the storage and state shared by the nodes of a hierarchy.
+///
+/// Arrays and groups opened with [`open_array`](Hierarchy::open_array) and [`open_group`](Hierarchy::open_group) share:
+///  - the storage, so storage adapters (e.g. retry or caching) are configured once for the entire hierarchy,
+///  - an optional [`ConsolidatedMetadataSource`], so nodes are opened without a storage request per node, and
+///  - an optional [`ChunkCacheDecodedSharedSizeLimit`], so every array draws on one chunk cache budget.
+///
+/// Arrays are opened as a [`CachedArray`] that retrieves chunks through the chunk cache of the hierarchy and invalidates it on writes.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use zarrs::array::{ArrayBuilder, ChunkCacheDecodedSharedSizeLimit, DataType, FillValue};
+/// # use zarrs::node::Hierarchy;
+/// # let store = Arc::new(zarrs_storage::store::MemoryStore::new());
+/// # let array = ArrayBuilder::new(vec![4, 4], DataType::UInt8, vec![2, 2].try_into()?, FillValue::from(0u8))
+/// #     .build(store.clone(), "/array")?;
+/// # array.store_metadata()?;
+/// let hierarchy = Hierarchy::new(store)
+///     .with_chunk_cache(ChunkCacheDecodedSharedSizeLimit::new(64 * 1024 * 1024));
+/// let array = hierarchy.open_array("/array")?;
+/// let subset_all = array.array().subset_all();
+/// let elements = array.retrieve_array_subset_elements::<u8>(&subset_all)?;
+/// // The chunks are now served from the shared chunk cache
+/// let elements = array.retrieve_array_subset_elements::<u8>(&subset_all)?;
+/// assert_eq!(array.cache_hits(), 4);
+/// # assert_eq!(elements, vec![0u8; 16]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Hierarchy<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    consolidated_metadata: Option<Arc<dyn ConsolidatedMetadataSource>>,
+    chunk_cache: Option<ChunkCacheDecodedSharedSizeLimit>,
+}
+
+impl<TStorage: ?Sized> Clone for Hierarchy<TStorage> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            consolidated_metadata: self.consolidated_metadata.clone(),
+            chunk_cache: self.chunk_cache.clone(),
+        }
+    }
+}
+
+impl<TStorage: ?Sized> Hierarchy<TStorage> {
+    /// Create a new hierarchy context with `storage`, no consolidated metadata, and no chunk cache.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self {
+            storage,
+            consolidated_metadata: None,
+            chunk_cache: None,
+        }
+    }
+
+    /// Set the consolidated metadata consulted when opening nodes.
+    #[must_use]
+    pub fn with_consolidated_metadata(
+        mut self,
+        consolidated_metadata: Arc<dyn ConsolidatedMetadataSource>,
+    ) -> Self {
+        self.consolidated_metadata = Some(consolidated_metadata);
+        self
+    }
+
+    /// Set the chunk cache shared by the arrays of the hierarchy.
+    #[must_use]
+    pub fn with_chunk_cache(mut self, chunk_cache: ChunkCacheDecodedSharedSizeLimit) -> Self {
+        self.chunk_cache = Some(chunk_cache);
+        self
+    }
+
+    /// Return the storage of the hierarchy.
+    #[must_use]
+    pub fn storage(&self) -> Arc<TStorage> {
+        self.storage.clone()
+    }
+
+    /// Return the consolidated metadata consulted when opening nodes.
+    #[must_use]
+    pub fn consolidated_metadata(&self) -> Option<&Arc<dyn ConsolidatedMetadataSource>> {
+        self.consolidated_metadata.as_ref()
+    }
+
+    /// Return the chunk cache shared by the arrays of the hierarchy.
+    #[must_use]
+    pub fn chunk_cache(&self) -> Option<&ChunkCacheDecodedSharedSizeLimit> {
+        self.chunk_cache.as_ref()
+    }
+
+    /// Return the chunk cache of `array` in the shared chunk cache, or [`None`] if the hierarchy has no chunk cache.
+    #[must_use]
+    pub fn array_chunk_cache<TArrayStorage: ?Sized>(
+        &self,
+        array: &Array<TArrayStorage>,
+    ) -> Option<ChunkCacheDecodedSharedArray> {
+        self.chunk_cache
+            .as_ref()
+            .map(|chunk_cache| chunk_cache.array_cache(array.path()))
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + 'static> Hierarchy<TStorage> {
+    /// Open the array at `path` in the hierarchy.
+    ///
+    /// The consolidated metadata of the hierarchy is consulted first, if set.
+    /// The chunks of the array are cached in the chunk cache of the hierarchy, if set, otherwise they are not cached.
+    ///
+    /// # Errors
+    /// Returns [`ArrayCreateError`] if there is a storage error, the consolidated metadata cannot be loaded, or any metadata is invalid.
+    pub fn open_array(&self, path: &str) -> Result<CachedArray<TStorage>, ArrayCreateError> {
+        let array = if let Some(consolidated_metadata) = &self.consolidated_metadata {
+            Array::open_with_consolidated(
+                self.storage.clone(),
+                path,
+                consolidated_metadata.as_ref(),
+            )?
+        } else {
+            Array::open(self.storage.clone(), path)?
+        };
+        Ok(if let Some(chunk_cache) = &self.chunk_cache {
+            CachedArray::new_with_shared_cache(array, chunk_cache)
+        } else {
+            CachedArray::new(array, 0)
+        })
+    }
+
+    /// Open the group at `path` in the hierarchy.
+    ///
+    /// The consolidated metadata of the hierarchy is consulted first, if set.
+    ///
+    /// # Errors
+    /// Returns [`GroupCreateError`] if there is a storage error, the consolidated metadata cannot be loaded, or any metadata is invalid.
+    pub fn open_group(&self, path: &str) -> Result<Group<TStorage>, GroupCreateError> {
+        if let Some(consolidated_metadata) = &self.consolidated_metadata {
+            Group::open_with_consolidated(
+                self.storage.clone(),
+                path,
+                consolidated_metadata.as_ref(),
+            )
+        } else {
+            Group::open(self.storage.clone(), path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        array::{ArrayBuilder, ChunkCache, DataType, FillValue},
+        group::GroupBuilder,
+        node::ConsolidatedMetadataMap,
+        storage::{
+            storage_adapter::performance_metrics::PerformanceMetricsStorageAdapter,
+            store::MemoryStore, StoreKey,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn hierarchy_open_shared_cache() {
+        let store = Arc::new(PerformanceMetricsStorageAdapter::new(Arc::new(
+            MemoryStore::new(),
+        )));
+        GroupBuilder::new()
+            .build(store.clone(), "/")
+            .unwrap()
+            .store_metadata()
+            .unwrap();
+        for path in ["/a", "/b"] {
+            let array = ArrayBuilder::new(
+                vec![4, 4],
+                DataType::UInt8,
+                vec![2, 2].try_into().unwrap(),
+                FillValue::from(0u8),
+            )
+            .build(store.clone(), path)
+            .unwrap();
+            array.store_metadata().unwrap();
+            array
+                .store_array_subset_elements(&array.subset_all(), &[1u8; 16])
+                .unwrap();
+        }
+
+        let hierarchy = Hierarchy::new(store.clone())
+            .with_chunk_cache(ChunkCacheDecodedSharedSizeLimit::new(1024));
+        assert!(hierarchy.open_group("/").is_ok());
+        let array_a = hierarchy.open_array("/a").unwrap();
+        let array_b = hierarchy.open_array("/b").unwrap();
+        let cache_a = hierarchy.array_chunk_cache(array_a.array()).unwrap();
+        let cache_b = hierarchy.array_chunk_cache(array_b.array()).unwrap();
+
+        // Arrays opened from the hierarchy use its chunk cache
+        let reads = store.reads();
+        array_a.retrieve_chunk(&[0, 0]).unwrap();
+        array_b.retrieve_chunk(&[0, 0]).unwrap();
+        assert_eq!(store.reads(), reads + 2);
+
+        // Chunks of different arrays with the same indices are cached separately
+        array_a.retrieve_chunk(&[0, 0]).unwrap();
+        assert_eq!(store.reads(), reads + 2);
+        assert_eq!(array_a.cache_hits(), 1);
+        assert_eq!(cache_a.len(), 1);
+        assert_eq!(cache_b.len(), 1);
+        assert_eq!(hierarchy.chunk_cache().unwrap().len(), 2);
+
+        // Writes invalidate the chunks of the written array only
+        array_a.store_chunk_elements(&[0, 0], &[2u8; 4]).unwrap();
+        assert_eq!(cache_a.len(), 0);
+        assert_eq!(cache_b.len(), 1);
+        assert_eq!(
+            array_a.retrieve_chunk_elements::<u8>(&[0, 0]).unwrap(),
+            vec![2u8; 4]
+        );
+        array_a.clear_cache();
+        assert_eq!(cache_a.len(), 0);
+        assert_eq!(cache_b.len(), 1);
+
+        hierarchy.chunk_cache().unwrap().clear();
+        assert!(hierarchy.chunk_cache().unwrap().is_empty());
+
+        // Without a chunk cache, chunks are not cached
+        let array_a = Hierarchy::new(store.clone()).open_array("/a").unwrap();
+        let reads = store.reads();
+        array_a.retrieve_chunk(&[0, 0]).unwrap();
+        array_a.retrieve_chunk(&[0, 0]).unwrap();
+        assert_eq!(store.reads(), reads + 2);
+        assert_eq!(array_a.cache_len(), 0);
+    }
+
+    #[test]
+    fn hierarchy_open_consolidated() {
+        let store = Arc::new(MemoryStore::new());
+        let mut consolidated = ConsolidatedMetadataMap::new();
+        consolidated.insert(
+            StoreKey::new("zarr.json").unwrap(),
+            serde_json::json!({"zarr_format": 3, "node_type": "group"}),
+        );
+        let hierarchy = Hierarchy::new(store).with_consolidated_metadata(Arc::new(consolidated));
+        assert!(hierarchy.open_group("/").is_ok());
+        assert!(hierarchy.open_array("/a").is_err());
+    }
+}
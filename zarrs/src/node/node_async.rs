@@ -1,4 +1,6 @@
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
+
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
 
 use crate::{
     array::{ArrayMetadata, ArrayMetadataV2},
@@ -11,8 +13,8 @@ use crate::{
 };
 
 use super::{
-    meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group, meta_key_v3, Node, NodeMetadata,
-    NodePath, NodePathError,
+    meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group, meta_key_v3, strip_bom, Node,
+    NodeMetadata, NodePath, NodePathError,
 };
 
 async fn get_metadata_v3<
@@ -28,7 +30,7 @@ async fn get_metadata_v3<
     );
     match storage.get(&key).await? {
         Some(metadata) => {
-            let metadata: NodeMetadataV3 = serde_json::from_slice(&metadata)
+            let metadata: NodeMetadataV3 = serde_json::from_slice(strip_bom(&metadata))
                 .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
             Ok(Some(match metadata {
                 NodeMetadataV3::Array(array) => NodeMetadata::Array(ArrayMetadata::V3(array)),
@@ -53,12 +55,12 @@ async fn get_metadata_v2<
     // Try array
     let key_array: StoreKey = meta_key_v2_array(&node_path);
     if let Some(metadata) = storage.get(&key_array).await? {
-        let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+        let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
             .map_err(|err| StorageError::InvalidMetadata(key_array, err.to_string()))?;
         let attributes = storage.get(&attributes_key).await?;
         if let Some(attributes) = attributes {
             let attributes: serde_json::Map<String, serde_json::Value> =
-                serde_json::from_slice(&attributes).map_err(|err| {
+                serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
                     StorageError::InvalidMetadata(attributes_key, err.to_string())
                 })?;
             metadata.attributes = attributes;
@@ -69,12 +71,12 @@ async fn get_metadata_v2<
     // Try group
     let key_group: StoreKey = meta_key_v2_group(&node_path);
     if let Some(metadata) = storage.get(&key_group).await? {
-        let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+        let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
             .map_err(|err| StorageError::InvalidMetadata(key_group, err.to_string()))?;
         let attributes = storage.get(&attributes_key).await?;
         if let Some(attributes) = attributes {
             let attributes: serde_json::Map<String, serde_json::Value> =
-                serde_json::from_slice(&attributes).map_err(|err| {
+                serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
                     StorageError::InvalidMetadata(attributes_key, err.to_string())
                 })?;
             metadata.attributes = attributes;
@@ -87,8 +89,11 @@ async fn get_metadata_v2<
 
 /// Asynchronously get the child nodes.
 ///
+/// The nodes are sorted by path, regardless of the listing order of the store.
+/// Child prefixes without node metadata (e.g. stray directories) are skipped.
+///
 /// # Errors
-/// Returns a [`StorageError`] if there is an underlying error with the store.
+/// Returns a [`StorageError`] if there is an underlying error with the store or the metadata of a child is invalid.
 pub async fn async_get_child_nodes<TStorage>(
     storage: &Arc<TStorage>,
     path: &NodePath,
@@ -106,7 +111,8 @@ where
             child_metadata = get_metadata_v2(storage, prefix).await?;
         }
         let Some(child_metadata) = child_metadata else {
-            return Err(StorageError::MissingMetadata(prefix.clone()));
+            // Not a node (e.g. a stray prefix)
+            continue;
         };
 
         let path: NodePath = prefix
@@ -118,9 +124,84 @@ where
         };
         nodes.push(Node::new_with_metadata(path, child_metadata, children));
     }
+    nodes.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
     Ok(nodes)
 }
 
+/// A node (without children) and the prefixes of its children.
+type DescendantFuture<'a> = BoxFuture<'a, Result<(Option<Node>, Vec<StorePrefix>), StorageError>>;
+
+fn descendant<'a, TStorage>(storage: &'a Arc<TStorage>, prefix: StorePrefix) -> DescendantFuture<'a>
+where
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits,
+{
+    async move {
+        let mut metadata = get_metadata_v3(storage, &prefix).await?;
+        if metadata.is_none() {
+            metadata = get_metadata_v2(storage, &prefix).await?;
+        }
+        let Some(metadata) = metadata else {
+            return Err(StorageError::MissingMetadata(prefix));
+        };
+        let children = match metadata {
+            NodeMetadata::Array(_) => Vec::default(),
+            NodeMetadata::Group(_) => async_discover_children(storage, &prefix).await?,
+        };
+        let path: NodePath = (&prefix)
+            .try_into()
+            .map_err(|err: NodePathError| StorageError::Other(err.to_string()))?;
+        Ok((
+            Some(Node::new_with_metadata(path, metadata, Vec::default())),
+            children,
+        ))
+    }
+    .boxed()
+}
+
+/// Asynchronously stream the descendant nodes of the node at `path` in breadth-first order.
+///
+/// See [`Group::async_descendants_stream`](crate::group::Group::async_descendants_stream).
+pub(crate) fn async_descendants_stream<'a, TStorage>(
+    storage: &'a Arc<TStorage>,
+    path: &NodePath,
+    concurrency: usize,
+) -> impl Stream<Item = Result<Node, StorageError>> + Send + 'a
+where
+    TStorage: ?Sized + AsyncReadableStorageTraits + AsyncListableStorageTraits,
+{
+    let concurrency = concurrency.max(1);
+    let path = path.clone();
+    let root: DescendantFuture<'a> = async move {
+        let prefix: StorePrefix = (&path).try_into()?;
+        Ok((None, async_discover_children(storage, &prefix).await?))
+    }
+    .boxed();
+    let pending = VecDeque::<StorePrefix>::new();
+    let in_flight = FuturesUnordered::from_iter([root]);
+    futures::stream::unfold(
+        (pending, in_flight),
+        move |(mut pending, mut in_flight)| async move {
+            loop {
+                while in_flight.len() < concurrency {
+                    let Some(prefix) = pending.pop_front() else {
+                        break;
+                    };
+                    in_flight.push(descendant(storage, prefix));
+                }
+                match in_flight.next().await? {
+                    Ok((node, children)) => {
+                        pending.extend(children);
+                        if let Some(node) = node {
+                            return Some((Ok(node), (pending, in_flight)));
+                        }
+                    }
+                    Err(err) => return Some((Err(err), (pending, in_flight))),
+                }
+            }
+        },
+    )
+}
+
 /// Asynchronously check if a node exists.
 ///
 /// # Errors
@@ -1,4 +1,4 @@
-use crate::storage::{StorePrefix, StorePrefixError};
+use crate::storage::{StoreKey, StorePrefix, StorePrefixError};
 use derive_more::Display;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -48,6 +48,23 @@ impl NodePath {
         &self.0
     }
 
+    /// Returns the path of `key` relative to this node, or [`None`] if `key` is not below this node.
+    ///
+    /// For example, the key `a/b/zarr.json` relative to the node `/a` is `b/zarr.json`.
+    /// Every key is below the root node, so its relative path is the key itself.
+    /// A key equal to the path of this node (e.g. `a` for `/a`) or sharing only a partial node name (e.g. `ab/zarr.json` for `/a`) is not below this node.
+    #[must_use]
+    pub fn relative_key(&self, key: &StoreKey) -> Option<String> {
+        let path = self.as_str();
+        if path.eq("/") {
+            return Some(key.as_str().to_string());
+        }
+        key.as_str()
+            .strip_prefix(path.strip_prefix('/').unwrap_or(path))?
+            .strip_prefix('/')
+            .map(str::to_string)
+    }
+
     /// Validates a path according to the following rules from the specification:
     /// - A path always starts with `/`, and
     /// - a non-root path cannot end with `/`, because node names must be non-empty and cannot contain `/`.
@@ -108,4 +125,33 @@ mod tests {
         assert!(NodePath::new("/a//b").is_err());
         assert_eq!(NodePath::new("/a/b").unwrap().as_path(), Path::new("/a/b/"));
     }
+
+    #[test]
+    fn node_path_relative_key() {
+        let key = StoreKey::new("a/b/zarr.json").unwrap();
+        assert_eq!(
+            NodePath::root().relative_key(&key).as_deref(),
+            Some("a/b/zarr.json")
+        );
+        assert_eq!(
+            NodePath::new("/a").unwrap().relative_key(&key).as_deref(),
+            Some("b/zarr.json")
+        );
+        assert_eq!(
+            NodePath::new("/a/b").unwrap().relative_key(&key).as_deref(),
+            Some("zarr.json")
+        );
+        assert_eq!(
+            NodePath::new("/a/b/zarr.json").unwrap().relative_key(&key),
+            None
+        );
+        assert_eq!(NodePath::new("/a/c").unwrap().relative_key(&key), None);
+        assert_eq!(NodePath::new("/a/bc").unwrap().relative_key(&key), None);
+        assert_eq!(
+            NodePath::new("/a")
+                .unwrap()
+                .relative_key(&StoreKey::new("ab/zarr.json").unwrap()),
+            None
+        );
+    }
 }
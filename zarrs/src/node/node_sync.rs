@@ -11,8 +11,8 @@ use crate::{
 };
 
 use super::{
-    meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group, meta_key_v3, Node, NodeMetadata,
-    NodePath, NodePathError,
+    meta_key_v2_array, meta_key_v2_attributes, meta_key_v2_group, meta_key_v3, strip_bom, Node,
+    NodeMetadata, NodePath, NodePathError,
 };
 
 fn get_metadata_v3<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits>(
@@ -26,7 +26,7 @@ fn get_metadata_v3<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTra
     );
     match storage.get(&key)? {
         Some(metadata) => {
-            let metadata: NodeMetadataV3 = serde_json::from_slice(&metadata)
+            let metadata: NodeMetadataV3 = serde_json::from_slice(strip_bom(&metadata))
                 .map_err(|err| StorageError::InvalidMetadata(key, err.to_string()))?;
             Ok(Some(match metadata {
                 NodeMetadataV3::Array(array) => NodeMetadata::Array(ArrayMetadata::V3(array)),
@@ -49,12 +49,12 @@ fn get_metadata_v2<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTra
     // Try array
     let key_array: StoreKey = meta_key_v2_array(&node_path);
     if let Some(metadata) = storage.get(&key_array)? {
-        let mut metadata: ArrayMetadataV2 = serde_json::from_slice(&metadata)
+        let mut metadata: ArrayMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
             .map_err(|err| StorageError::InvalidMetadata(key_array, err.to_string()))?;
         let attributes = storage.get(&attributes_key)?;
         if let Some(attributes) = attributes {
             let attributes: serde_json::Map<String, serde_json::Value> =
-                serde_json::from_slice(&attributes).map_err(|err| {
+                serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
                     StorageError::InvalidMetadata(attributes_key, err.to_string())
                 })?;
             metadata.attributes = attributes;
@@ -65,12 +65,12 @@ fn get_metadata_v2<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTra
     // Try group
     let key_group: StoreKey = meta_key_v2_group(&node_path);
     if let Some(metadata) = storage.get(&key_group)? {
-        let mut metadata: GroupMetadataV2 = serde_json::from_slice(&metadata)
+        let mut metadata: GroupMetadataV2 = serde_json::from_slice(strip_bom(&metadata))
             .map_err(|err| StorageError::InvalidMetadata(key_group, err.to_string()))?;
         let attributes = storage.get(&attributes_key)?;
         if let Some(attributes) = attributes {
             let attributes: serde_json::Map<String, serde_json::Value> =
-                serde_json::from_slice(&attributes).map_err(|err| {
+                serde_json::from_slice(strip_bom(&attributes)).map_err(|err| {
                     StorageError::InvalidMetadata(attributes_key, err.to_string())
                 })?;
             metadata.attributes = attributes;
@@ -83,8 +83,11 @@ fn get_metadata_v2<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTra
 
 /// Get the child nodes.
 ///
+/// The nodes are sorted by path, regardless of the listing order of the store.
+/// Child prefixes without node metadata (e.g. stray directories) are skipped.
+///
 /// # Errors
-/// Returns a [`StorageError`] if there is an underlying error with the store.
+/// Returns a [`StorageError`] if there is an underlying error with the store or the metadata of a child is invalid.
 pub fn get_child_nodes<TStorage: ?Sized + ReadableStorageTraits + ListableStorageTraits>(
     storage: &Arc<TStorage>,
     path: &NodePath,
@@ -98,7 +101,8 @@ pub fn get_child_nodes<TStorage: ?Sized + ReadableStorageTraits + ListableStorag
             child_metadata = get_metadata_v2(storage, prefix)?;
         }
         let Some(child_metadata) = child_metadata else {
-            return Err(StorageError::MissingMetadata(prefix.clone()));
+            // Not a node (e.g. a stray prefix)
+            continue;
         };
 
         let path: NodePath = prefix
@@ -110,6 +114,7 @@ pub fn get_child_nodes<TStorage: ?Sized + ReadableStorageTraits + ListableStorag
         };
         nodes.push(Node::new_with_metadata(path, child_metadata, children));
     }
+    nodes.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
     Ok(nodes)
 }
 
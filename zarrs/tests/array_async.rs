@@ -138,6 +138,86 @@ async fn array_async_read_shard_compress() -> Result<(), Box<dyn std::error::Err
     array_async_read(true).await
 }
 
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn array_async_chunk_size_report() -> Result<(), Box<dyn std::error::Error>> {
+    let store = std::sync::Arc::new(zarrs_object_store::AsyncObjectStore::new(InMemory::new()));
+    let mut builder = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(),
+        FillValue::from(0u8),
+    );
+    builder.bytes_to_bytes_codecs(vec![]);
+    let array = builder.build(store, "/array").unwrap();
+    array.async_store_metadata().await?;
+    assert!(array.async_chunk_size_report().await?.is_empty());
+
+    array
+        .async_store_chunk_elements::<u8>(&[1, 1], &[1; 4])
+        .await?;
+    array
+        .async_store_chunk_elements::<u8>(&[0, 1], &[2; 4])
+        .await?;
+    assert_eq!(
+        array.async_chunk_size_report().await?,
+        vec![(vec![0, 1], 4), (vec![1, 1], 4)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn array_async_deduplicate_chunk_reads() -> Result<(), Box<dyn std::error::Error>> {
+    use zarrs::array::chunk_key_encoding::ChunkKeyEncodingTraits;
+    use zarrs::metadata::v3::MetadataV3;
+    use zarrs::storage::StoreKey;
+
+    // Every chunk is stored at the same key
+    #[derive(Debug)]
+    struct BroadcastChunkKeyEncoding;
+
+    impl ChunkKeyEncodingTraits for BroadcastChunkKeyEncoding {
+        fn create_metadata(&self) -> MetadataV3 {
+            MetadataV3::new("broadcast")
+        }
+
+        fn encode(&self, _chunk_grid_indices: &[u64]) -> StoreKey {
+            StoreKey::new("array/c").unwrap()
+        }
+    }
+
+    let store = std::sync::Arc::new(zarrs_object_store::AsyncObjectStore::new(InMemory::new()));
+    let mut array = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(),
+        FillValue::from(0u8),
+    )
+    .chunk_key_encoding(BroadcastChunkKeyEncoding.into())
+    .build(store, "/array")?;
+    array
+        .async_store_chunk_elements::<u8>(&[0, 0], &[1, 2, 3, 4])
+        .await?;
+    array.set_deduplicate_chunk_reads(true);
+
+    let subset = ArraySubset::new_with_shape(vec![4, 4]);
+    assert_eq!(
+        array
+            .async_retrieve_array_subset_elements::<u8>(&subset)
+            .await?,
+        vec![1, 2, 1, 2, 3, 4, 3, 4, 1, 2, 1, 2, 3, 4, 3, 4]
+    );
+    let subset = ArraySubset::new_with_ranges(&[1..3, 0..3]);
+    assert_eq!(
+        array
+            .async_retrieve_array_subset_elements::<u8>(&subset)
+            .await?,
+        vec![3, 4, 3, 1, 2, 1]
+    );
+    Ok(())
+}
+
 async fn array_str_impl(
     array: Array<zarrs_object_store::AsyncObjectStore<InMemory>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
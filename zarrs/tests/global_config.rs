@@ -0,0 +1,218 @@
+//! Tests that modify the global configuration.
+//!
+//! These are kept out of the library unit tests, which read the global configuration concurrently.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use zarrs::array::{
+    Array, ArrayBuilder, ArrayCreateError, ArrayError, ArrayMetadata, DataType, DimensionName,
+    FillValue,
+};
+use zarrs::config::{
+    global_config, global_config_mut, Config, DuplicateDimensionNames, MustUnderstandLevel,
+};
+use zarrs::group::{Group, GroupBuilder, GroupMetadataMismatch};
+use zarrs::storage::store::MemoryStore;
+use zarrs::storage::{
+    ListableStorageTraits, ReadableStorageTraits, StoreKey, WritableStorageTraits,
+};
+
+/// Serialises tests that modify the global configuration, and restores the default configuration when dropped.
+struct GlobalConfigGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl GlobalConfigGuard {
+    fn new() -> Self {
+        static LOCK: Mutex<()> = Mutex::new(());
+        Self {
+            _lock: LOCK.lock().unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+impl Drop for GlobalConfigGuard {
+    fn drop(&mut self) {
+        *global_config_mut() = Config::default();
+    }
+}
+
+#[test]
+fn config_validate_checksums() {
+    let _guard = GlobalConfigGuard::new();
+    assert!(global_config().validate_checksums());
+    global_config_mut().set_validate_checksums(false);
+    assert!(!global_config().validate_checksums());
+    global_config_mut().set_validate_checksums(true);
+}
+
+#[test]
+fn array_builder_duplicate_dimension_names() {
+    let storage = Arc::new(MemoryStore::new());
+    let builder = || {
+        ArrayBuilder::new(
+            vec![8, 8, 8],
+            DataType::Int8,
+            vec![2, 2, 2].try_into().unwrap(),
+            FillValue::from(0i8),
+        )
+    };
+    let mut builder_null = builder();
+    builder_null.dimension_names(Some([
+        DimensionName::default(),
+        "x".into(),
+        DimensionName::default(),
+    ]));
+    let mut builder = builder();
+    builder.dimension_names(["x", "y", "x"].into());
+
+    let _guard = GlobalConfigGuard::new();
+    assert!(builder.build(storage.clone(), "/").is_ok());
+    global_config_mut().set_duplicate_dimension_names(DuplicateDimensionNames::Error);
+    let result = builder.build(storage.clone(), "/");
+    let result_null = builder_null.build(storage.clone(), "/");
+    assert!(matches!(
+        result,
+        Err(ArrayCreateError::DuplicateDimensionName(name)) if name == "x"
+    ));
+    assert!(result_null.is_ok());
+}
+
+#[test]
+fn array_must_understand_level() {
+    let store = Arc::new(MemoryStore::default());
+    let array = ArrayBuilder::new(
+        vec![4, 4],
+        DataType::UInt8,
+        vec![2, 2].try_into().unwrap(),
+        FillValue::from(0u8),
+    )
+    .build(store.clone(), "/")
+    .unwrap();
+    let mut metadata = serde_json::to_value(array.metadata()).unwrap();
+    metadata["future_extension"] = serde_json::json!({"key": "value"});
+    let metadata: ArrayMetadata = serde_json::from_value(metadata).unwrap();
+
+    let _guard = GlobalConfigGuard::new();
+    assert!(matches!(
+        Array::new_with_metadata(store.clone(), "/", metadata.clone()),
+        Err(ArrayCreateError::UnsupportedAdditionalFieldError(err)) if err.name() == "future_extension"
+    ));
+
+    global_config_mut().set_must_understand_level(MustUnderstandLevel::LenientRead);
+    let array = Array::new_with_metadata(store.clone(), "/", metadata.clone());
+    let retrieve = array
+        .as_ref()
+        .map(|array| array.retrieve_chunk_elements::<u8>(&[0, 0]));
+    let store_chunk = array
+        .as_ref()
+        .map(|array| array.store_chunk_elements::<u8>(&[0, 0], &[1; 4]));
+
+    global_config_mut().set_must_understand_level(MustUnderstandLevel::Permissive);
+    let array_permissive = Array::new_with_metadata(store.clone(), "/", metadata);
+    let retrieve_permissive = array_permissive
+        .as_ref()
+        .map(|array| array.retrieve_chunk_elements::<u8>(&[0, 0]));
+
+    let array = array.as_ref().unwrap();
+    assert_eq!(array.shape(), &[4, 4]);
+    assert!(matches!(
+        retrieve.unwrap(),
+        Err(ArrayError::UnsupportedAdditionalFieldError(_))
+    ));
+    assert!(matches!(
+        store_chunk.unwrap(),
+        Err(ArrayError::UnsupportedAdditionalFieldError(_))
+    ));
+    assert!(store.list().unwrap().is_empty());
+    assert_eq!(retrieve_permissive.unwrap().unwrap(), vec![0; 4]);
+}
+
+#[test]
+fn array_default_missing_fill_value() {
+    let store = Arc::new(MemoryStore::default());
+    for (path, data_type, codec) in [("uint8", "uint8", "bytes"), ("string", "string", "vlen_v2")] {
+        let metadata = serde_json::json!({
+            "zarr_format": 3,
+            "node_type": "array",
+            "shape": [4],
+            "data_type": data_type,
+            "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [2]}},
+            "chunk_key_encoding": {"name": "default"},
+            "codecs": [{"name": codec}]
+        });
+        store
+            .set(
+                &StoreKey::new(format!("{path}/zarr.json")).unwrap(),
+                serde_json::to_vec(&metadata).unwrap().into(),
+            )
+            .unwrap();
+    }
+    let _guard = GlobalConfigGuard::new();
+    assert!(Array::open(store.clone(), "/uint8").is_err());
+
+    global_config_mut().set_default_missing_fill_value(true);
+    let array_uint8 = Array::open(store.clone(), "/uint8");
+    let array_string = Array::open(store.clone(), "/string");
+
+    let array_uint8 = array_uint8.unwrap();
+    assert_eq!(array_uint8.fill_value(), &FillValue::from(0u8));
+    assert_eq!(
+        array_uint8.retrieve_chunk_elements::<u8>(&[0]).unwrap(),
+        vec![0, 0]
+    );
+    let array_string = array_string.unwrap();
+    assert_eq!(array_string.fill_value(), &FillValue::from(""));
+}
+
+#[test]
+fn group_lenient_metadata() {
+    let store = Arc::new(MemoryStore::new());
+    let array_metadata = serde_json::json!({
+        "zarr_format": 3,
+        "node_type": "array",
+        "attributes": {"spam": "ham"}
+    });
+    let key = StoreKey::new("array/zarr.json").unwrap();
+    store
+        .set(&key, serde_json::to_vec(&array_metadata).unwrap().into())
+        .unwrap();
+    let format_metadata = serde_json::json!({"zarr_format": 4, "node_type": "group"});
+    store
+        .set(
+            &StoreKey::new("group/zarr.json").unwrap(),
+            serde_json::to_vec(&format_metadata).unwrap().into(),
+        )
+        .unwrap();
+    let _guard = GlobalConfigGuard::new();
+    assert!(Group::open(store.clone(), "/array").is_err());
+
+    global_config_mut().set_lenient_group_metadata(true);
+    let array_group = Group::open(store.clone(), "/array");
+    let format_group = Group::open(store.clone(), "/group");
+
+    let array_group = array_group.unwrap();
+    assert_eq!(
+        array_group.metadata_mismatch(),
+        Some(&GroupMetadataMismatch::NodeType("array".into()))
+    );
+    assert_eq!(
+        &serde_json::Value::Object(array_group.attributes().clone()),
+        &array_metadata
+    );
+    // The stored metadata is not overwritten
+    assert!(array_group.store_metadata().is_err());
+    assert_eq!(
+        store.get(&key).unwrap().unwrap(),
+        serde_json::to_vec(&array_metadata).unwrap()
+    );
+
+    let format_group = format_group.unwrap();
+    assert_eq!(
+        format_group.metadata_mismatch().map(ToString::to_string),
+        Some("zarr_format is 4, expected 3".to_string())
+    );
+
+    let group = GroupBuilder::new().build(store, "/valid").unwrap();
+    assert!(group.metadata_mismatch().is_none());
+}
@@ -5,21 +5,18 @@ use zarrs_filesystem::FilesystemStore;
 
 #[test]
 fn hierarchy_tree() {
-    let store = Arc::new(
-        FilesystemStore::new("./tests/data/hierarchy.zarr")
-            .unwrap()
-            .sorted(),
-    );
+    // Siblings are sorted in the tree even if the store lists them unsorted
+    let store = Arc::new(FilesystemStore::new("./tests/data/hierarchy.zarr").unwrap());
     let node = Node::open(&store, "/").unwrap();
     let tree = node.hierarchy_tree();
     println!("{:?}", tree);
     assert_eq!(
         tree,
-        "/
-  a
-    baz [10000, 1000] float64
-    foo [10000, 1000] float64
-  b
+        "/ (2 children)
+├── a (2 children)
+│   ├── baz [10000, 1000] float64
+│   └── foo [10000, 1000] float64
+└── b (0 children)
 "
     );
 }
@@ -9,9 +9,9 @@
 
 use zarrs_storage::{
     byte_range::{ByteOffset, ByteRange},
-    store_set_partial_values, Bytes, ListableStorageTraits, ReadableStorageTraits, StorageError,
-    StoreKey, StoreKeyError, StoreKeyOffsetValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
-    StorePrefixes, WritableStorageTraits,
+    store_set_partial_values, Bytes, ListContinuationToken, ListableStorageTraits,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyError, StoreKeyOffsetValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, StorePrefixes, WritableStorageTraits,
 };
 
 use bytes::BytesMut;
@@ -21,10 +21,14 @@ use walkdir::WalkDir;
 
 use std::{
     collections::HashMap,
+    ffi::OsString,
     fs::{File, OpenOptions},
     io::{Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
 };
 
 #[cfg(target_os = "linux")]
@@ -64,9 +68,21 @@ fn bytes_aligned(size: usize) -> BytesMut {
 
 /// Options for use with [`FilesystemStore`]
 #[non_exhaustive]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct FilesystemStoreOptions {
     direct_io: bool,
+    atomic_writes: bool,
+    list_page_size: usize,
+}
+
+impl Default for FilesystemStoreOptions {
+    fn default() -> Self {
+        Self {
+            direct_io: false,
+            atomic_writes: true,
+            list_page_size: 1000,
+        }
+    }
 }
 
 impl FilesystemStoreOptions {
@@ -76,10 +92,62 @@ impl FilesystemStoreOptions {
         self.direct_io = direct_io;
         self
     }
+
+    /// Set whether or not values are written atomically. Enabled by default.
+    ///
+    /// If enabled, [`set`](WritableStorageTraits::set) writes a value to a temporary file in the same directory and renames it into place, so readers never observe a partially written value.
+    /// Disable this on filesystems where renaming is expensive or unsupported.
+    pub fn atomic_writes(&mut self, atomic_writes: bool) -> &mut Self {
+        self.atomic_writes = atomic_writes;
+        self
+    }
+
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged). Defaults to 1000.
+    ///
+    /// A page size of zero is treated as one.
+    pub fn list_page_size(&mut self, list_page_size: usize) -> &mut Self {
+        self.list_page_size = list_page_size.max(1);
+        self
+    }
+}
+
+/// Return a unique temporary path in the same directory as `path`.
+fn temporary_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut file_name = OsString::from(".");
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(file_name)
+}
+
+/// Return true if `path` is a temporary path of the form `.{name}.{pid}.{n}.tmp`.
+///
+/// These are written by atomic writes and by staged metadata writes, and are excluded from listings.
+fn is_temporary_path(path: &Path) -> bool {
+    let Some(stem) = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .and_then(|file_name| file_name.strip_prefix('.'))
+        .and_then(|file_name| file_name.strip_suffix(".tmp"))
+    else {
+        return false;
+    };
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit());
+    let mut parts = stem.rsplitn(3, '.');
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(n), Some(pid), Some(_)) if is_number(n) && is_number(pid)
+    )
 }
 
 /// A synchronous file system store.
 ///
+/// Values are written atomically by default, see [`FilesystemStoreOptions::atomic_writes`].
+///
 /// See <https://zarr-specs.readthedocs.io/en/latest/v3/stores/filesystem/v1.0.html>.
 #[derive(Debug)]
 pub struct FilesystemStore {
@@ -205,6 +273,28 @@ impl FilesystemStore {
             }
         }
 
+        if self.options.atomic_writes && offset == 0 && truncate {
+            // Write to a temporary file and rename it into place
+            let temporary_path = temporary_path(&key_path);
+            let result = self
+                .write_file(&temporary_path, value, 0, true)
+                .and_then(|()| Ok(std::fs::rename(&temporary_path, &key_path)?));
+            if result.is_err() {
+                let _ = std::fs::remove_file(&temporary_path);
+            }
+            result
+        } else {
+            self.write_file(&key_path, value, offset, truncate)
+        }
+    }
+
+    fn write_file(
+        &self,
+        path: &Path,
+        value: &[u8],
+        offset: ByteOffset,
+        truncate: bool,
+    ) -> Result<(), StorageError> {
         let mut flags = OpenOptions::new();
         flags.write(true).create(true).truncate(truncate);
 
@@ -221,7 +311,7 @@ impl FilesystemStore {
             flags.custom_flags(O_DIRECT);
         }
 
-        let mut file = flags.open(key_path)?;
+        let mut file = flags.open(path)?;
 
         // Write
         if enable_direct {
@@ -364,6 +454,32 @@ impl WritableStorageTraits for FilesystemStore {
             Ok(())
         }
     }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        if self.readonly {
+            return Err(StorageError::ReadOnly);
+        }
+
+        // Lock in key order to avoid deadlocks with concurrent renames
+        let (first, second) = if from <= to { (from, to) } else { (to, from) };
+        let first = self.get_file_mutex(first);
+        let _first_lock = first.write();
+        let second = (from != to).then(|| self.get_file_mutex(second));
+        let _second_lock = second.as_ref().map(|second| second.write());
+
+        let to_path = self.key_to_fspath(to);
+        if let Some(parent) = to_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+        std::fs::rename(self.key_to_fspath(from), to_path)?;
+        Ok(())
+    }
+
+    fn supports_rename(&self) -> bool {
+        !self.readonly
+    }
 }
 
 impl ListableStorageTraits for FilesystemStore {
@@ -372,7 +488,7 @@ impl ListableStorageTraits for FilesystemStore {
             .sort_by_file_name()
             .into_iter()
             .filter_map(std::result::Result::ok)
-            .filter(|v| v.path().is_file())
+            .filter(|v| v.path().is_file() && !is_temporary_path(v.path()))
             .filter_map(|v| self.fspath_to_key(v.path()).ok())
             .collect())
     }
@@ -382,11 +498,62 @@ impl ListableStorageTraits for FilesystemStore {
             .sort_by_file_name()
             .into_iter()
             .filter_map(std::result::Result::ok)
-            .filter(|v| v.path().is_file())
+            .filter(|v| v.path().is_file() && !is_temporary_path(v.path()))
             .filter_map(|v| self.fspath_to_key(v.path()).ok())
             .collect())
     }
 
+    /// Retrieve a page of keys with a given prefix without walking the entire prefix.
+    ///
+    /// Keys are walked in the same order as [`list_prefix`](ListableStorageTraits::list_prefix), which orders keys by their path components.
+    /// Directories which only contain keys up to the continuation token are skipped.
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let token = continuation_token.map(|token| PathBuf::from(token.as_str()));
+        let relative = |path: &Path| {
+            path.strip_prefix(&self.base_path)
+                .unwrap_or(path)
+                .to_path_buf()
+        };
+        let walk = WalkDir::new(self.prefix_to_fs_path(prefix))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                token.as_ref().map_or(true, |token| {
+                    let path = relative(entry.path());
+                    let n = path.components().count().min(token.components().count());
+                    path.components().take(n).ge(token.components().take(n))
+                })
+            });
+        let mut keys = StoreKeys::new();
+        for entry in walk.filter_map(std::result::Result::ok) {
+            let path = entry.path();
+            if !path.is_file()
+                || is_temporary_path(path)
+                || token
+                    .as_ref()
+                    .is_some_and(|token| relative(path).components().le(token.components()))
+            {
+                continue;
+            }
+            let Ok(key) = self.fspath_to_key(path) else {
+                continue;
+            };
+            if keys.len() == self.options.list_page_size {
+                // There are more keys after this page
+                let continuation_token = keys
+                    .last()
+                    .map(|key| ListContinuationToken::new(key.as_str()));
+                return Ok((keys, continuation_token));
+            }
+            keys.push(key);
+        }
+        Ok((keys, None))
+    }
+
     fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
         let prefix_path = self.prefix_to_fs_path(prefix);
         let mut keys: StoreKeys = vec![];
@@ -397,6 +564,9 @@ impl ListableStorageTraits for FilesystemStore {
                 let entry = entry?;
                 let fs_path = entry.path();
                 let path = fs_path.file_name().unwrap();
+                if is_temporary_path(&fs_path) {
+                    continue;
+                }
                 if fs_path.is_dir() {
                     prefixes.push(StorePrefix::new(
                         prefix.as_str().to_string() + path.to_str().unwrap() + "/",
@@ -421,7 +591,7 @@ impl ListableStorageTraits for FilesystemStore {
             .into_iter()
             .filter_map(std::result::Result::ok)
             .filter_map(|v| {
-                if v.path().is_file() {
+                if v.path().is_file() && !is_temporary_path(v.path()) {
                     Some(std::fs::metadata(v.path()).unwrap().len())
                 } else {
                     None
@@ -466,6 +636,101 @@ mod tests {
         zarrs_storage::store_test::store_write(&store)?;
         zarrs_storage::store_test::store_read(&store)?;
         zarrs_storage::store_test::store_list(&store)?;
+        zarrs_storage::store_test::store_rename(&store)?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_non_atomic() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let mut opts = FilesystemStoreOptions::default();
+        opts.atomic_writes(false);
+
+        let store = FilesystemStore::new_with_options(path.path(), opts)?.sorted();
+        zarrs_storage::store_test::store_write(&store)?;
+        zarrs_storage::store_test::store_read(&store)?;
+        zarrs_storage::store_test::store_list(&store)?;
+        zarrs_storage::store_test::store_rename(&store)?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_atomic_write_cleanup() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = FilesystemStore::new(path.path())?.sorted();
+        store.set(&"a/b".try_into()?, vec![0, 1, 2].into())?;
+        store.set(&"a/b".try_into()?, vec![3, 4].into())?;
+        assert_eq!(store.get(&"a/b".try_into()?)?.unwrap(), vec![3, 4]);
+
+        // Renaming onto a directory fails, and the temporary file is removed
+        store.set(&"a/c/d".try_into()?, vec![].into())?;
+        assert!(store.set(&"a/c".try_into()?, vec![5].into()).is_err());
+        assert_eq!(store.list()?, &["a/b".try_into()?, "a/c/d".try_into()?]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_list_temporary() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = FilesystemStore::new(path.path())?.sorted();
+        store.set(&"a/b".try_into()?, vec![0, 1, 2].into())?;
+        store.set(&"a/.b".try_into()?, vec![3].into())?;
+        store.set(&"a/.b.c.tmp".try_into()?, vec![4].into())?;
+
+        // Leftover temporary files (e.g. from a crashed writer) are not listed
+        std::fs::write(path.path().join("a/.b.123.0.tmp"), [5])?;
+        std::fs::write(path.path().join("a/.zarr.json.123.1.tmp"), [6])?;
+        assert!(is_temporary_path(&path.path().join("a/.b.123.0.tmp")));
+        assert!(!is_temporary_path(&path.path().join("a/.b.c.tmp")));
+        let keys: StoreKeys = vec![
+            "a/.b".try_into()?,
+            "a/.b.c.tmp".try_into()?,
+            "a/b".try_into()?,
+        ];
+        assert_eq!(store.list()?, keys);
+        assert_eq!(store.list_prefix(&"a/".try_into()?)?, keys);
+        assert_eq!(store.list_prefix_paged(&"a/".try_into()?, None)?.0, keys);
+        assert_eq!(store.list_dir(&"a/".try_into()?)?.keys(), &keys);
+        assert_eq!(store.size()?, 5);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn filesystem_list_prefix_paged() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let mut opts = FilesystemStoreOptions::default();
+        opts.list_page_size(2);
+        let store = FilesystemStore::new_with_options(path.path(), opts)?.sorted();
+        for key in ["a/0", "a/1/0", "a/1/1", "a.x", "a/2", "b/0"] {
+            store.set(&StoreKey::new(key)?, vec![0].into())?;
+        }
+
+        let prefix = StorePrefix::root();
+        let mut pages = vec![];
+        let mut continuation_token = None;
+        loop {
+            let (keys, next_continuation_token) =
+                store.list_prefix_paged(&prefix, continuation_token.as_ref())?;
+            pages.push(keys);
+            continuation_token = next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.concat(), store.list_prefix(&prefix)?);
+
+        let prefix = StorePrefix::new("a/")?;
+        let (keys, continuation_token) = store.list_prefix_paged(&prefix, None)?;
+        assert_eq!(keys, [StoreKey::new("a/0")?, StoreKey::new("a/1/0")?]);
+        let (keys, continuation_token) =
+            store.list_prefix_paged(&prefix, continuation_token.as_ref())?;
+        assert_eq!(keys, [StoreKey::new("a/1/1")?, StoreKey::new("a/2")?]);
+        assert!(continuation_token.is_none());
         Ok(())
     }
 
@@ -481,6 +746,7 @@ mod tests {
         zarrs_storage::store_test::store_write(&store)?;
         zarrs_storage::store_test::store_read(&store)?;
         zarrs_storage::store_test::store_list(&store)?;
+        zarrs_storage::store_test::store_rename(&store)?;
         Ok(())
     }
 }
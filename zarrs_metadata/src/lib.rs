@@ -6,6 +6,15 @@
 //!
 //! All known array metadata is defined in this module.
 //! This includes experimental data types, codecs, etc. supported by the `zarrs` crate.
+//!
+//! ## Canonical JSON
+//! [`ArrayMetadata::to_canonical_json`] and [`GroupMetadata::to_canonical_json`] serialise metadata to a canonical form for hashing, signing, or content-addressed storage.
+//! Logically identical metadata produces byte-identical output:
+//!  - object keys are sorted (by UTF-8 byte order) at every level, including in attributes and codec configurations,
+//!  - there is no insignificant whitespace, and
+//!  - numbers are in their shortest round-trip form, so floating point fill values are bit-stable.
+//!
+//! Canonical JSON parses back to the same metadata.
 
 use derive_more::derive::{Display, From};
 use serde::{Deserialize, Serialize};
@@ -21,6 +30,9 @@ pub mod v2;
 /// Zarr V2 to V3 conversion.
 pub mod v2_to_v3;
 
+/// Zarr V3 to V2 conversion.
+pub mod v3_to_v2;
+
 /// An alias for [`v3::MetadataV3`].
 #[deprecated(since = "0.17.0", note = "use v3::MetadataV3 explicitly")]
 pub type Metadata = v3::MetadataV3;
@@ -37,6 +49,39 @@ pub enum ArrayMetadata {
     V2(v2::ArrayMetadataV2),
 }
 
+impl ArrayMetadata {
+    /// Serialise the metadata to canonical JSON.
+    ///
+    /// See [canonical JSON](crate#canonical-json).
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if the metadata cannot be serialised.
+    pub fn to_canonical_json(&self) -> Result<String, serde_json::Error> {
+        Ok(canonical_json(&serde_json::to_value(self)?))
+    }
+
+    /// Return the Zarr format version of the metadata (`2` or `3`).
+    #[must_use]
+    pub fn zarr_format(&self) -> u8 {
+        match self {
+            Self::V3(_) => 3,
+            Self::V2(_) => 2,
+        }
+    }
+
+    /// Return true if the metadata is Zarr V2 array metadata.
+    #[must_use]
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Self::V2(_))
+    }
+
+    /// Return true if the metadata is Zarr V3 array metadata.
+    #[must_use]
+    pub fn is_v3(&self) -> bool {
+        matches!(self, Self::V3(_))
+    }
+}
+
 impl TryFrom<&str> for ArrayMetadata {
     type Error = serde_json::Error;
     fn try_from(metadata_json: &str) -> Result<Self, Self::Error> {
@@ -54,6 +99,39 @@ pub enum GroupMetadata {
     V2(v2::GroupMetadataV2),
 }
 
+impl GroupMetadata {
+    /// Serialise the metadata to canonical JSON.
+    ///
+    /// See [canonical JSON](crate#canonical-json).
+    ///
+    /// # Errors
+    /// Returns a [`serde_json::Error`] if the metadata cannot be serialised.
+    pub fn to_canonical_json(&self) -> Result<String, serde_json::Error> {
+        Ok(canonical_json(&serde_json::to_value(self)?))
+    }
+
+    /// Return the Zarr format version of the metadata (`2` or `3`).
+    #[must_use]
+    pub fn zarr_format(&self) -> u8 {
+        match self {
+            Self::V3(_) => 3,
+            Self::V2(_) => 2,
+        }
+    }
+
+    /// Return true if the metadata is Zarr V2 group metadata.
+    #[must_use]
+    pub fn is_v2(&self) -> bool {
+        matches!(self, Self::V2(_))
+    }
+
+    /// Return true if the metadata is Zarr V3 group metadata.
+    #[must_use]
+    pub fn is_v3(&self) -> bool {
+        matches!(self, Self::V3(_))
+    }
+}
+
 impl TryFrom<&str> for GroupMetadata {
     type Error = serde_json::Error;
     fn try_from(metadata_json: &str) -> Result<Self, Self::Error> {
@@ -72,10 +150,70 @@ pub enum NodeMetadata {
     Group(GroupMetadata),
 }
 
+impl NodeMetadata {
+    /// Return the Zarr format version of the metadata (`2` or `3`).
+    #[must_use]
+    pub fn zarr_format(&self) -> u8 {
+        match self {
+            Self::Array(metadata) => metadata.zarr_format(),
+            Self::Group(metadata) => metadata.zarr_format(),
+        }
+    }
+
+    /// Return true if the metadata is Zarr V2 metadata.
+    #[must_use]
+    pub fn is_v2(&self) -> bool {
+        self.zarr_format() == 2
+    }
+
+    /// Return true if the metadata is Zarr V3 metadata.
+    #[must_use]
+    pub fn is_v3(&self) -> bool {
+        self.zarr_format() == 3
+    }
+}
+
+/// Serialise a JSON value with object keys sorted and no insignificant whitespace.
+fn canonical_json(value: &serde_json::Value) -> String {
+    fn write(value: &serde_json::Value, output: &mut String) {
+        match value {
+            serde_json::Value::Object(object) => {
+                let mut entries: Vec<_> = object.iter().collect();
+                entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                output.push('{');
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&serde_json::Value::from(key.as_str()).to_string());
+                    output.push(':');
+                    write(value, output);
+                }
+                output.push('}');
+            }
+            serde_json::Value::Array(array) => {
+                output.push('[');
+                for (i, value) in array.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    write(value, output);
+                }
+                output.push(']');
+            }
+            _ => output.push_str(&value.to_string()),
+        }
+    }
+
+    let mut output = String::new();
+    write(value, &mut output);
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use v3::{AdditionalFields, MetadataV3};
+    use v3::{AdditionalFields, AdditionalFieldsExt, MetadataV3};
 
     #[test]
     fn metadata() {
@@ -110,6 +248,99 @@ mod tests {
         assert_eq!(metadata.configuration(), Some(&configuration));
     }
 
+    #[test]
+    fn metadata_zarr_format() {
+        let array_v3 = ArrayMetadata::try_from(
+            r#"{
+            "zarr_format": 3,
+            "node_type": "array",
+            "shape": [10],
+            "data_type": "uint8",
+            "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [5]}},
+            "chunk_key_encoding": {"name": "default"},
+            "fill_value": 0,
+            "codecs": [{"name": "bytes"}]
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(array_v3.zarr_format(), 3);
+        assert!(array_v3.is_v3() && !array_v3.is_v2());
+
+        let array_v2 = ArrayMetadata::try_from(
+            r#"{
+            "zarr_format": 2,
+            "shape": [10],
+            "chunks": [5],
+            "dtype": "|u1",
+            "compressor": null,
+            "fill_value": 0,
+            "order": "C",
+            "filters": null
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(array_v2.zarr_format(), 2);
+        assert!(array_v2.is_v2() && !array_v2.is_v3());
+
+        let group_v3 =
+            GroupMetadata::try_from(r#"{"zarr_format": 3, "node_type": "group"}"#).unwrap();
+        assert_eq!(group_v3.zarr_format(), 3);
+        assert!(group_v3.is_v3() && !group_v3.is_v2());
+        let group_v2 = GroupMetadata::try_from(r#"{"zarr_format": 2}"#).unwrap();
+        assert_eq!(group_v2.zarr_format(), 2);
+        assert!(group_v2.is_v2() && !group_v2.is_v3());
+
+        assert_eq!(NodeMetadata::Array(array_v2).zarr_format(), 2);
+        assert!(NodeMetadata::Group(group_v3).is_v3());
+    }
+
+    #[test]
+    fn metadata_canonical_json() {
+        let json_a = r#"{
+            "zarr_format": 3,
+            "node_type": "array",
+            "shape": [10, 10],
+            "data_type": "float32",
+            "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [5, 5]}},
+            "chunk_key_encoding": {"name": "default", "configuration": {"separator": "/"}},
+            "fill_value": 0.1,
+            "codecs": [{"name": "bytes", "configuration": {"endian": "little"}}],
+            "attributes": {"b": [1, {"y": 2, "x": 1}], "a": "\u00e9"}
+        }"#;
+        let json_b = r#"{"attributes":{"a":"é","b":[1,{"x":1,"y":2}]},"codecs":[{"configuration":{"endian":"little"},"name":"bytes"}],"fill_value":0.1,"data_type":"float32","chunk_key_encoding":{"configuration":{"separator":"/"},"name":"default"},"chunk_grid":{"configuration":{"chunk_shape":[5,5]},"name":"regular"},"shape":[10,10],"node_type":"array","zarr_format":3}"#;
+        let metadata_a = ArrayMetadata::try_from(json_a).unwrap();
+        let metadata_b = ArrayMetadata::try_from(json_b).unwrap();
+        let canonical = metadata_a.to_canonical_json().unwrap();
+        assert_eq!(canonical, metadata_b.to_canonical_json().unwrap());
+        assert!(!canonical.contains(' '));
+        assert!(
+            canonical.starts_with(r#"{"attributes":{"a":"é","b":[1,{"x":1,"y":2}]},"chunk_grid""#)
+        );
+        assert!(canonical.contains(r#""fill_value":0.1,"#));
+
+        // Round trip
+        let metadata_canonical = ArrayMetadata::try_from(canonical.as_str()).unwrap();
+        assert_eq!(metadata_canonical, metadata_a);
+        assert_eq!(metadata_canonical.to_canonical_json().unwrap(), canonical);
+
+        let group_a = GroupMetadata::try_from(
+            r#"{"zarr_format": 3, "node_type": "group", "attributes": {"b": 1, "a": 2}}"#,
+        )
+        .unwrap();
+        let group_b = GroupMetadata::try_from(
+            r#"{"attributes": {"a": 2, "b": 1}, "node_type": "group", "zarr_format": 3}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            group_a.to_canonical_json().unwrap(),
+            group_b.to_canonical_json().unwrap()
+        );
+        assert_eq!(
+            group_a.to_canonical_json().unwrap(),
+            r#"{"attributes":{"a":2,"b":1},"node_type":"group","zarr_format":3}"#
+        );
+    }
+
     #[test]
     fn additional_fields_auto() {
         let mut additional_fields = AdditionalFields::new();
@@ -140,6 +371,13 @@ mod tests {
                 "key": "value"
             }
         }"#;
+        let additional_fields = serde_json::from_str::<AdditionalFields>(json).unwrap();
+        assert!(additional_fields["unknown_field"].must_understand());
+        assert!(additional_fields.validate().is_err());
+
+        let json = r#"{
+            "unknown_field": "value"
+        }"#;
         let additional_fields = serde_json::from_str::<AdditionalFields>(json);
         assert!(additional_fields.is_err());
     }
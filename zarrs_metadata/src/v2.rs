@@ -22,7 +22,7 @@ pub enum NodeMetadataV2 {
 
 #[cfg(test)]
 mod tests {
-    use array::data_type_metadata_v2_to_endianness;
+    use array::{data_type_metadata_v2_to_endianness, DataTypeMetadataV2, FillValueMetadataV2};
 
     use super::*;
 
@@ -100,4 +100,110 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn array_v2_base64_fill_value() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"
+            {
+                "chunks": [10],
+                "compressor": null,
+                "dtype": "|V4",
+                "fill_value": "AAECAw==",
+                "filters": null,
+                "order": "C",
+                "shape": [100],
+                "zarr_format": 2
+            }"#;
+        let array_metadata_v2: crate::v2::ArrayMetadataV2 = serde_json::from_str(json)?;
+        assert_eq!(
+            array_metadata_v2.fill_value,
+            FillValueMetadataV2::Bytes(vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            serde_json::to_value(&array_metadata_v2.fill_value)?,
+            serde_json::json!("AAECAw==")
+        );
+
+        let array_metadata_v3 = array_metadata_v2_to_v3(&array_metadata_v2)?;
+        assert_eq!(array_metadata_v3.data_type.name(), "r32");
+        assert_eq!(
+            array_metadata_v3.fill_value,
+            crate::v3::array::fill_value::FillValueMetadataV3::ByteArray(vec![0, 1, 2, 3])
+        );
+
+        assert!(serde_json::from_str::<FillValueMetadataV2>(r#""not base64!""#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn array_v2_structured_data_type() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"
+            {
+                "chunks": [10],
+                "compressor": null,
+                "dtype": [["a", "<i4"], ["b", ">f8", [2]], ["c", "|S3"], ["d", "<U2"]],
+                "fill_value": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==",
+                "filters": null,
+                "order": "C",
+                "shape": [100],
+                "zarr_format": 2
+            }"#;
+        let array_metadata_v2: crate::v2::ArrayMetadataV2 = serde_json::from_str(json)?;
+        // 4 + 2 * 8 + 3 + 2 * 4 bytes
+        assert_eq!(
+            data_type_metadata_v2_to_v3_data_type(&array_metadata_v2.dtype)?.name(),
+            "r248"
+        );
+        assert_eq!(
+            data_type_metadata_v2_to_endianness(&array_metadata_v2.dtype)?,
+            None
+        );
+
+        let array_metadata_v3 = array_metadata_v2_to_v3(&array_metadata_v2)?;
+        assert_eq!(array_metadata_v3.data_type.name(), "r248");
+        assert_eq!(
+            array_metadata_v3.fill_value,
+            crate::v3::array::fill_value::FillValueMetadataV3::ByteArray(vec![0; 31])
+        );
+
+        // Object fields have no fixed size
+        let data_type: DataTypeMetadataV2 = serde_json::from_str(r#"[["a", "<i4"], ["b", "|O"]]"#)?;
+        assert!(data_type_metadata_v2_to_v3_data_type(&data_type).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn array_v2_bool_fill_value() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"
+            {
+                "chunks": [10],
+                "compressor": null,
+                "dtype": "|b1",
+                "fill_value": false,
+                "filters": null,
+                "order": "C",
+                "shape": [100],
+                "zarr_format": 2
+            }"#;
+        let array_metadata_v2: crate::v2::ArrayMetadataV2 = serde_json::from_str(json)?;
+        assert_eq!(
+            array_metadata_v2.fill_value,
+            FillValueMetadataV2::Bool(false)
+        );
+        assert_eq!(
+            serde_json::to_value(&array_metadata_v2.fill_value)?,
+            serde_json::json!(false)
+        );
+
+        let array_metadata_v3 = array_metadata_v2_to_v3(&array_metadata_v2)?;
+        assert_eq!(array_metadata_v3.data_type.name(), "bool");
+        assert_eq!(
+            array_metadata_v3.fill_value,
+            crate::v3::array::fill_value::FillValueMetadataV3::Bool(false)
+        );
+
+        let fill_value: FillValueMetadataV2 = serde_json::from_str("true")?;
+        assert_eq!(fill_value, FillValueMetadataV2::Bool(true));
+        Ok(())
+    }
 }
@@ -1,3 +1,4 @@
+use base64::Engine;
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -14,6 +15,10 @@ pub mod codec {
     pub mod blosc;
     /// `bz2` codec metadata.
     pub mod bz2;
+    /// `delta` codec metadata.
+    pub mod delta;
+    /// `fixedscaleoffset` codec metadata.
+    pub mod fixedscaleoffset;
     /// `gzip` codec metadata.
     pub mod gzip;
     /// `zfpy` codec metadata.
@@ -51,7 +56,6 @@ pub mod codec {
 /// }
 /// ```
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, Display)]
-#[serde(tag = "node_type", rename = "array")]
 #[display("{}", serde_json::to_string(self).unwrap_or_default())]
 pub struct ArrayMetadataV2 {
     /// An integer defining the version of the storage specification to which the array adheres. Must be `2`.
@@ -84,6 +88,35 @@ pub struct ArrayMetadataV2 {
     pub additional_fields: AdditionalFields,
 }
 
+impl ArrayMetadataV2 {
+    /// Create new Zarr V2 array metadata.
+    ///
+    /// The order is `C`, the dimension separator is `.`, and the attributes and additional fields are empty.
+    #[must_use]
+    pub fn new(
+        shape: ArrayShape,
+        chunks: ChunkShape,
+        dtype: DataTypeMetadataV2,
+        fill_value: FillValueMetadataV2,
+        compressor: Option<MetadataV2>,
+        filters: Option<Vec<MetadataV2>>,
+    ) -> Self {
+        Self {
+            zarr_format: monostate::MustBe!(2u64),
+            shape,
+            chunks,
+            dtype,
+            compressor,
+            fill_value,
+            order: ArrayMetadataV2Order::C,
+            filters,
+            dimension_separator: chunk_key_separator_default_zarr_v2(),
+            attributes: serde_json::Map::default(),
+            additional_fields: AdditionalFields::default(),
+        }
+    }
+}
+
 const fn chunk_key_separator_default_zarr_v2() -> ChunkKeySeparator {
     ChunkKeySeparator::Dot
 }
@@ -107,9 +140,40 @@ pub struct DataTypeMetadataV2Structured {
 struct DataTypeMetadataV2StructuredTuple(
     String,
     String,
-    #[serde(skip_serializing_if = "Option::is_none")] Option<Vec<u64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")] Option<Vec<u64>>,
 );
 
+impl DataTypeMetadataV2Structured {
+    /// Return the size in bytes of the field, or [`None`] if it has an unsupported data type.
+    pub(crate) fn size(&self) -> Option<usize> {
+        let num_elements = self
+            .shape
+            .iter()
+            .flatten()
+            .try_fold(1usize, |num_elements, &dim| {
+                num_elements.checked_mul(usize::try_from(dim).ok()?)
+            })?;
+        data_type_metadata_v2_simple_size(&self.datatype)?.checked_mul(num_elements)
+    }
+}
+
+/// Return the size in bytes of a simple Zarr V2 data type (e.g. `<i4`, `|S10`, `<U5`, `<M8[ns]`), or [`None`] if it has no fixed size.
+fn data_type_metadata_v2_simple_size(data_type: &str) -> Option<usize> {
+    let data_type = data_type.strip_prefix(['|', '<', '>'])?;
+    let mut chars = data_type.chars();
+    let kind = chars.next()?;
+    let size = chars.as_str();
+    // Strip the datetime/timedelta unit (e.g. `[ns]`)
+    let size = size.split_once('[').map_or(size, |(size, _unit)| size);
+    let size = size.parse::<usize>().ok()?;
+    match kind {
+        'b' | 'i' | 'u' | 'f' | 'c' | 'm' | 'M' | 'S' | 'V' => Some(size),
+        // A fixed length sequence of UCS4 code points
+        'U' => size.checked_mul(4),
+        _ => None,
+    }
+}
+
 impl From<DataTypeMetadataV2StructuredTuple> for DataTypeMetadataV2Structured {
     fn from(value: DataTypeMetadataV2StructuredTuple) -> Self {
         let DataTypeMetadataV2StructuredTuple(fieldname, datatype, shape) = value;
@@ -161,9 +225,8 @@ pub fn data_type_metadata_v2_to_endianness(
                 Err(DataTypeMetadataV2InvalidEndiannessError(data_type.clone()))
             }
         }
-        DataTypeMetadataV2::Structured(_) => {
-            Err(DataTypeMetadataV2InvalidEndiannessError(data_type.clone()))
-        }
+        // Structured data types are mapped to raw bits, which have no endianness
+        DataTypeMetadataV2::Structured(_) => Ok(None),
     }
 }
 
@@ -172,6 +235,8 @@ pub fn data_type_metadata_v2_to_endianness(
 pub enum FillValueMetadataV2 {
     /// No fill value.
     Null,
+    /// A boolean (e.g. for the `|b1` data type, as written by `zarr-python`).
+    Bool(bool),
     /// NaN (not-a-number).
     NaN,
     /// Positive infinity.
@@ -180,6 +245,10 @@ pub enum FillValueMetadataV2 {
     NegInfinity,
     /// A number.
     Number(serde_json::Number),
+    /// Raw bytes, encoded as a base64 string.
+    ///
+    /// Zarr V2 uses this encoding for data types without a JSON scalar representation (e.g. `|V` and structured data types).
+    Bytes(Vec<u8>),
 }
 
 impl<'de> serde::Deserialize<'de> for FillValueMetadataV2 {
@@ -189,6 +258,7 @@ impl<'de> serde::Deserialize<'de> for FillValueMetadataV2 {
         enum FillValueMetadataV2Type {
             String(String),
             Number(serde_json::Number),
+            Bool(bool),
             Null,
         }
         let fill_value = FillValueMetadataV2Type::deserialize(d)?;
@@ -197,9 +267,13 @@ impl<'de> serde::Deserialize<'de> for FillValueMetadataV2 {
                 "NaN" => Ok(Self::NaN),
                 "Infinity" => Ok(Self::Infinity),
                 "-Infinity" => Ok(Self::NegInfinity),
-                _ => Err(serde::de::Error::custom("unsupported fill value")),
+                _ => base64::engine::general_purpose::STANDARD
+                    .decode(string)
+                    .map(Self::Bytes)
+                    .map_err(|_| serde::de::Error::custom("unsupported fill value")),
             },
             FillValueMetadataV2Type::Number(number) => Ok(Self::Number(number)),
+            FillValueMetadataV2Type::Bool(bool) => Ok(Self::Bool(bool)),
             FillValueMetadataV2Type::Null => Ok(Self::Null),
         }
     }
@@ -212,10 +286,14 @@ impl Serialize for FillValueMetadataV2 {
     {
         match self {
             Self::Null => serializer.serialize_none(),
+            Self::Bool(bool) => serializer.serialize_bool(*bool),
             Self::NaN => serializer.serialize_str("NaN"),
             Self::Infinity => serializer.serialize_str("Infinity"),
             Self::NegInfinity => serializer.serialize_str("-Infinity"),
             Self::Number(number) => number.serialize(serializer),
+            Self::Bytes(bytes) => {
+                serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+            }
         }
     }
 }
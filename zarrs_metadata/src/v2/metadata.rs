@@ -20,6 +20,29 @@ pub struct MetadataV2 {
 }
 
 impl MetadataV2 {
+    /// Create metadata from an `id` with no configuration.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            configuration: serde_json::Map::default(),
+        }
+    }
+
+    /// Create metadata from an `id` and `configuration`.
+    ///
+    /// The configuration should not include an `"id"` field.
+    #[must_use]
+    pub fn new_with_configuration(
+        id: impl Into<String>,
+        configuration: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            configuration,
+        }
+    }
+
     /// Return the "id" key.
     #[must_use]
     pub fn id(&self) -> &str {
@@ -280,15 +280,31 @@ pub fn data_type_metadata_v2_to_v3_data_type(
                 // TODO "|MX" datetime
                 // TODO "|SX" string (fixed length sequence of char)
                 // TODO "|UX" string (fixed length sequence of Py_UNICODE)
-                // TODO "|VX" other (void * – each item is a fixed-size chunk of memory)
-                _ => Err(DataTypeMetadataV2UnsupportedDataTypeError(
-                    data_type.clone(),
-                )),
+                _ => {
+                    // "|VX" other (void * – each item is a fixed-size chunk of memory)
+                    if let Some(size) = data_type_str
+                        .strip_prefix("|V")
+                        .and_then(|size| size.parse::<usize>().ok())
+                        .filter(|size| *size > 0)
+                    {
+                        Ok(DataTypeMetadataV3::RawBits(size))
+                    } else {
+                        Err(DataTypeMetadataV2UnsupportedDataTypeError(
+                            data_type.clone(),
+                        ))
+                    }
+                }
             }
         }
-        DataTypeMetadataV2::Structured(_) => Err(DataTypeMetadataV2UnsupportedDataTypeError(
-            data_type.clone(),
-        )),
+        DataTypeMetadataV2::Structured(fields) => {
+            // A structured data type is mapped to raw bits of its total item size
+            fields
+                .iter()
+                .try_fold(0usize, |size, field| size.checked_add(field.size()?))
+                .filter(|size| *size > 0)
+                .map(DataTypeMetadataV3::RawBits)
+                .ok_or_else(|| DataTypeMetadataV2UnsupportedDataTypeError(data_type.clone()))
+        }
     }
 }
 
@@ -301,6 +317,7 @@ pub fn array_metadata_fill_value_v2_to_v3(
 ) -> Option<FillValueMetadataV3> {
     match fill_value {
         FillValueMetadataV2::Null => None,
+        FillValueMetadataV2::Bool(bool) => Some(FillValueMetadataV3::Bool(*bool)),
         FillValueMetadataV2::NaN => Some(FillValueMetadataV3::Float(FillValueFloat::NonFinite(
             FillValueFloatStringNonFinite::NaN,
         ))),
@@ -321,5 +338,6 @@ pub fn array_metadata_fill_value_v2_to_v3(
                 unreachable!("number must be convertible to u64, i64 or f64")
             }
         }
+        FillValueMetadataV2::Bytes(bytes) => Some(FillValueMetadataV3::ByteArray(bytes.clone())),
     }
 }
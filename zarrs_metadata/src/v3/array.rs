@@ -11,10 +11,14 @@ pub mod data_type;
 
 /// Zarr V3 codec metadata.
 pub mod codec {
+    /// `adler32` codec metadata.
+    pub mod adler32;
     /// `bitround` codec metadata.
     pub mod bitround;
     /// `blosc` codec metadata.
     pub mod blosc;
+    /// `brotli` codec metadata.
+    pub mod brotli;
     /// `bytes` codec metadata.
     pub mod bytes;
 
@@ -22,10 +26,16 @@ pub mod codec {
     pub mod bz2;
     /// `crc32c` codec metadata.
     pub mod crc32c;
+    /// `delta` codec metadata.
+    pub mod delta;
+    /// `fixedscaleoffset` codec metadata.
+    pub mod fixedscaleoffset;
     /// `gdeflate` codec metadata.
     pub mod gdeflate;
     /// `gzip` codec metadata.
     pub mod gzip;
+    /// `lz4` codec metadata.
+    pub mod lz4;
     /// `pcodec` codec metadata.
     pub mod pcodec;
     /// `sharding` codec metadata.
@@ -48,6 +58,8 @@ pub mod chunk_grid {
     pub mod rectangular;
     /// `regular` chunk grid metadata.
     pub mod regular;
+    /// `zarrs.regular_origin` chunk grid metadata.
+    pub mod regular_origin;
 }
 
 /// Zarr V3 chunk key encoding metadata.
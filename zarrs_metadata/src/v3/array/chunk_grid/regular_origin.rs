@@ -0,0 +1,35 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::ChunkShape;
+
+/// The identifier for the `zarrs.regular_origin` chunk grid.
+pub const IDENTIFIER: &str = "zarrs.regular_origin";
+
+/// Configuration parameters for a `zarrs.regular_origin` chunk grid.
+///
+/// This is a `zarrs` extension of the `regular` chunk grid with a non-zero origin.
+///
+/// ### Example: A 2x3 chunk grid with its first element at array indices `[10, 20]`
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "chunk_shape": [2, 3],
+///     "origin": [10, 20]
+/// }
+/// # "#;
+/// # use zarrs_metadata::v3::array::chunk_grid::regular_origin::RegularOriginChunkGridConfiguration;
+/// # let configuration: RegularOriginChunkGridConfiguration = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display(
+    "zarrs.regular_origin chunk grid {}",
+    serde_json::to_string(self).unwrap_or_default()
+)]
+pub struct RegularOriginChunkGridConfiguration {
+    /// The chunk shape.
+    pub chunk_shape: ChunkShape,
+    /// The array indices of the first element of the grid.
+    pub origin: Vec<u64>,
+}
@@ -0,0 +1,43 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// The identifier for the `adler32` codec.
+// TODO: ZEP for adler32
+pub const IDENTIFIER: &str = "adler32";
+
+/// A wrapper to handle various versions of `adler32` (Adler-32 checksum) codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum Adler32CodecConfiguration {
+    /// Version 1.0 draft.
+    V1(Adler32CodecConfigurationV1),
+}
+
+/// `adler32` (Adler-32 checksum) codec configuration parameters (version 1.0 draft).
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display("{}", serde_json::to_string(self).unwrap_or_default())]
+pub struct Adler32CodecConfigurationV1 {}
+
+#[cfg(test)]
+mod tests {
+    use crate::v3::MetadataV3;
+
+    use super::*;
+
+    #[test]
+    fn codec_adler32_config1() {
+        serde_json::from_str::<Adler32CodecConfiguration>(r#"{}"#).unwrap();
+    }
+
+    #[test]
+    fn codec_adler32_config_outer1() {
+        serde_json::from_str::<MetadataV3>(
+            r#"{
+            "name": "adler32",
+            "configuration": {}
+        }"#,
+        )
+        .unwrap();
+    }
+}
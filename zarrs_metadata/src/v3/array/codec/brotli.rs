@@ -0,0 +1,62 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// The identifier for the `brotli` codec.
+// TODO: ZEP for brotli
+pub const IDENTIFIER: &str = "brotli";
+
+/// A wrapper to handle various versions of `brotli` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum BrotliCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(BrotliCodecConfigurationV1),
+}
+
+/// Configuration parameters for the `brotli` codec (version 1.0 draft).
+///
+/// ### Example: encode with a quality of 9 and a window size of 22
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "quality": 9,
+///     "window_size": 22
+/// }
+/// # "#;
+/// # use zarrs_metadata::v3::array::codec::brotli::BrotliCodecConfigurationV1;
+/// # let configuration: BrotliCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display("{}", serde_json::to_string(self).unwrap_or_default())]
+pub struct BrotliCodecConfigurationV1 {
+    /// The compression quality, from 0 (fastest) to 11 (smallest).
+    pub quality: u32,
+    /// The base 2 logarithm of the sliding window size (`lgwin`), from 10 to 24.
+    pub window_size: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_brotli_valid1() {
+        let json = r#"
+        {
+            "quality": 9,
+            "window_size": 22
+        }"#;
+        assert!(serde_json::from_str::<BrotliCodecConfiguration>(json).is_ok());
+    }
+
+    #[test]
+    fn codec_brotli_invalid_field() {
+        let json = r#"
+        {
+            "level": 9,
+            "window_size": 22
+        }"#;
+        assert!(serde_json::from_str::<BrotliCodecConfiguration>(json).is_err());
+    }
+}
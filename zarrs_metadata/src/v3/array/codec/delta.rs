@@ -0,0 +1,82 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// The identifier for the `delta` codec.
+pub const IDENTIFIER: &str = "delta";
+
+/// A wrapper to handle various versions of `delta` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum DeltaCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(DeltaCodecConfigurationV1),
+}
+
+/// `delta` codec configuration parameters (version 1.0 draft).
+///
+/// The configuration matches the `numcodecs` `Delta` filter, with an additional optional `axis`.
+/// Differences are computed along the last axis if `axis` is unset.
+/// Data types are `NumPy` data type strings (e.g. `"<i4"` or `"u1"`).
+///
+/// ### Example: Delta encode `int32` values along the last axis as `int16`
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "dtype": "<i4",
+///     "astype": "<i2",
+///     "axis": -1
+/// }
+/// # "#;
+/// # use zarrs_metadata::v3::array::codec::delta::DeltaCodecConfigurationV1;
+/// # let configuration: DeltaCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display("{}", serde_json::to_string(self).unwrap_or_default())]
+pub struct DeltaCodecConfigurationV1 {
+    /// The data type of the decoded data.
+    pub dtype: String,
+    /// The data type of the encoded differences. Defaults to `dtype` if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub astype: Option<String>,
+    /// The axis to compute differences along. Negative values count from the last axis.
+    ///
+    /// If unset, differences are computed along the last axis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub axis: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::v3::MetadataV3;
+
+    use super::*;
+
+    #[test]
+    fn codec_delta_metadata() {
+        serde_json::from_str::<MetadataV3>(
+            r#"{
+            "name": "delta",
+            "configuration": {
+                "dtype": "<i4",
+                "astype": "<i2",
+                "axis": -1
+            }
+        }"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_delta_config_numcodecs() {
+        let configuration = serde_json::from_str::<DeltaCodecConfiguration>(
+            r#"{
+                "dtype": "<i8",
+                "astype": "<i8"
+            }"#,
+        )
+        .unwrap();
+        let DeltaCodecConfiguration::V1(configuration) = configuration;
+        assert_eq!(configuration.axis, None);
+    }
+}
@@ -0,0 +1,84 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// The identifier for the `fixedscaleoffset` codec.
+pub const IDENTIFIER: &str = "fixedscaleoffset";
+
+/// A wrapper to handle various versions of `fixedscaleoffset` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum FixedScaleOffsetCodecConfiguration {
+    /// Version 1.0 draft.
+    V1(FixedScaleOffsetCodecConfigurationV1),
+}
+
+/// `fixedscaleoffset` codec configuration parameters (version 1.0 draft).
+///
+/// The configuration matches the `numcodecs` `FixedScaleOffset` filter.
+/// Encodes values as `round((value - offset) * scale)` in the `astype` data type, and decodes them as `value / scale + offset` in the `dtype` data type.
+/// Data types are `NumPy` data type strings (e.g. `"<f8"` or `"u1"`).
+///
+/// ### Example: Quantise `float64` values to `uint8` with a precision of 0.1
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "offset": 1000,
+///     "scale": 10,
+///     "dtype": "<f8",
+///     "astype": "|u1"
+/// }
+/// # "#;
+/// # use zarrs_metadata::v3::array::codec::fixedscaleoffset::FixedScaleOffsetCodecConfigurationV1;
+/// # let configuration: FixedScaleOffsetCodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display("{}", serde_json::to_string(self).unwrap_or_default())]
+pub struct FixedScaleOffsetCodecConfigurationV1 {
+    /// The value subtracted from the data before scaling.
+    pub offset: f64,
+    /// The value the offset data is multiplied by.
+    pub scale: f64,
+    /// The data type of the decoded data.
+    pub dtype: String,
+    /// The data type of the encoded data. Defaults to `dtype` if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub astype: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::v3::MetadataV3;
+
+    use super::*;
+
+    #[test]
+    fn codec_fixedscaleoffset_metadata() {
+        serde_json::from_str::<MetadataV3>(
+            r#"{
+            "name": "fixedscaleoffset",
+            "configuration": {
+                "offset": 1000,
+                "scale": 10,
+                "dtype": "<f8",
+                "astype": "|u1"
+            }
+        }"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn codec_fixedscaleoffset_config_no_astype() {
+        let configuration = serde_json::from_str::<FixedScaleOffsetCodecConfiguration>(
+            r#"{
+                "offset": 0.5,
+                "scale": 2.0,
+                "dtype": "<f4"
+            }"#,
+        )
+        .unwrap();
+        let FixedScaleOffsetCodecConfiguration::V1(configuration) = configuration;
+        assert_eq!(configuration.astype, None);
+    }
+}
@@ -0,0 +1,60 @@
+use derive_more::{Display, From};
+use serde::{Deserialize, Serialize};
+
+/// The identifier for the `lz4` codec.
+// TODO: ZEP for lz4
+pub const IDENTIFIER: &str = "lz4";
+
+/// A wrapper to handle various versions of `lz4` codec configuration parameters.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
+#[serde(untagged)]
+pub enum Lz4CodecConfiguration {
+    /// Version 1.0 draft.
+    V1(Lz4CodecConfigurationV1),
+}
+
+/// Configuration parameters for the `lz4` codec (version 1.0 draft).
+///
+/// ### Example: encode with an acceleration of 1
+/// ```rust
+/// # let JSON = r#"
+/// {
+///     "acceleration": 1
+/// }
+/// # "#;
+/// # use zarrs_metadata::v3::array::codec::lz4::Lz4CodecConfigurationV1;
+/// # let configuration: Lz4CodecConfigurationV1 = serde_json::from_str(JSON).unwrap();
+/// ```
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display)]
+#[serde(deny_unknown_fields)]
+#[display("{}", serde_json::to_string(self).unwrap_or_default())]
+pub struct Lz4CodecConfigurationV1 {
+    /// The acceleration factor.
+    ///
+    /// Higher values compress faster with a lower compression ratio.
+    /// Values less than or equal to zero use the default acceleration of `1`.
+    pub acceleration: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codec_lz4_valid1() {
+        let json = r#"
+        {
+            "acceleration": 1
+        }"#;
+        assert!(serde_json::from_str::<Lz4CodecConfiguration>(json).is_ok());
+    }
+
+    #[test]
+    fn codec_lz4_invalid_field() {
+        let json = r#"
+        {
+            "level": 1
+        }"#;
+        assert!(serde_json::from_str::<Lz4CodecConfiguration>(json).is_err());
+    }
+}
@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 /// The identifier for the `vlen_v2` codec.
 pub const IDENTIFIER: &str = "vlen_v2";
 
+/// The name of the `vlen-utf8` codec used by `zarr-python` for Zarr V3 variable-length string arrays.
+///
+/// It has the same encoding as the `vlen_v2` codec, the `numcodecs` `VLenUTF8` layout.
+pub const IDENTIFIER_VLEN_UTF8: &str = "vlen-utf8";
+
 /// A wrapper to handle various versions of `vlen_v2` codec configuration parameters.
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Display, From)]
 #[serde(untagged)]
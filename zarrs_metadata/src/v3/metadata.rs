@@ -26,12 +26,103 @@ use thiserror::Error;
 ///     }
 /// }
 ///
-#[derive(Clone, Eq, PartialEq, Debug)]
+/// ### Equality and Hashing
+/// Metadata is compared and hashed by its name and normalised configuration.
+/// The order of keys in the configuration (at any depth) does not affect equality or the hash, and an empty configuration is equal to no configuration.
+/// Numbers are compared by value, except that integers and floats are distinct (e.g. `1` and `1.0`).
+#[derive(Clone, Debug)]
 pub struct MetadataV3 {
     name: String,
     configuration: Option<MetadataConfiguration>,
 }
 
+impl MetadataV3 {
+    fn configuration_normalised(&self) -> Option<&MetadataConfiguration> {
+        self.configuration
+            .as_ref()
+            .filter(|configuration| !configuration.is_empty())
+    }
+}
+
+impl PartialEq for MetadataV3 {
+    fn eq(&self, other: &Self) -> bool {
+        // NOTE: serde_json::Map equality is independent of key order, even with `preserve_order`
+        self.name == other.name
+            && self.configuration_normalised() == other.configuration_normalised()
+    }
+}
+
+impl Eq for MetadataV3 {}
+
+impl std::hash::Hash for MetadataV3 {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        match self.configuration_normalised() {
+            Some(configuration) => {
+                state.write_u8(1);
+                hash_json_map(configuration, state);
+            }
+            None => state.write_u8(0),
+        }
+    }
+}
+
+/// Hash a JSON map independently of the order of its keys.
+fn hash_json_map<H: std::hash::Hasher>(
+    map: &serde_json::Map<String, serde_json::Value>,
+    state: &mut H,
+) {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    state.write_usize(entries.len());
+    for (key, value) in entries {
+        std::hash::Hash::hash(key, state);
+        hash_json_value(value, state);
+    }
+}
+
+/// Hash a JSON value consistently with its [`PartialEq`] implementation.
+fn hash_json_value<H: std::hash::Hasher>(value: &serde_json::Value, state: &mut H) {
+    use std::hash::Hash;
+    match value {
+        serde_json::Value::Null => state.write_u8(0),
+        serde_json::Value::Bool(value) => {
+            state.write_u8(1);
+            value.hash(state);
+        }
+        serde_json::Value::Number(number) => {
+            state.write_u8(2);
+            if let Some(number) = number.as_u64() {
+                state.write_u8(0);
+                number.hash(state);
+            } else if let Some(number) = number.as_i64() {
+                state.write_u8(1);
+                number.hash(state);
+            } else if let Some(number) = number.as_f64() {
+                state.write_u8(2);
+                // -0.0 == 0.0
+                let number = if number == 0.0 { 0.0 } else { number };
+                number.to_bits().hash(state);
+            }
+        }
+        serde_json::Value::String(string) => {
+            state.write_u8(3);
+            string.hash(state);
+        }
+        serde_json::Value::Array(values) => {
+            state.write_u8(4);
+            state.write_usize(values.len());
+            for value in values {
+                hash_json_value(value, state);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            state.write_u8(5);
+            hash_json_map(map, state);
+        }
+    }
+}
+
 /// Configuration metadata.
 pub type MetadataConfiguration = serde_json::Map<String, serde_json::Value>;
 
@@ -236,12 +327,18 @@ impl UnsupportedAdditionalFieldError {
     }
 }
 
+const fn default_must_understand() -> bool {
+    true
+}
+
 /// An additional field in array or group metadata.
 ///
-/// Must be an object with a `"must_understand": false` field.
+/// Must be an object.
+/// The field is supported if it has a `"must_understand": false` field, otherwise it must be understood (the default if `must_understand` is absent).
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Debug, Default, From)]
 pub struct AdditionalField {
-    must_understand: monostate::MustBe!(false),
+    #[serde(default = "default_must_understand")]
+    must_understand: bool,
     #[serde(flatten)]
     fields: serde_json::Map<String, serde_json::Value>,
 }
@@ -252,6 +349,33 @@ impl AdditionalField {
     pub const fn as_map(&self) -> &serde_json::Map<String, serde_json::Value> {
         &self.fields
     }
+
+    /// Returns true if the additional field must be understood (i.e. it does not have `"must_understand": false`).
+    #[must_use]
+    pub const fn must_understand(&self) -> bool {
+        self.must_understand
+    }
+
+    /// Validate the additional field named `name`.
+    ///
+    /// # Errors
+    /// Returns an [`UnsupportedAdditionalFieldError`] if the field must be understood, or the underlying map has a `must_understand` key, which would override `"must_understand": false`.
+    pub fn validate(&self, name: &str) -> Result<(), UnsupportedAdditionalFieldError> {
+        if self.must_understand || self.fields.contains_key("must_understand") {
+            let mut value = self.fields.clone();
+            if self.must_understand {
+                value
+                    .entry("must_understand")
+                    .or_insert(serde_json::Value::Bool(true));
+            }
+            Err(UnsupportedAdditionalFieldError {
+                name: name.to_string(),
+                value: serde_json::Value::Object(value),
+            })
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl From<AdditionalField> for serde_json::Map<String, serde_json::Value> {
@@ -263,7 +387,7 @@ impl From<AdditionalField> for serde_json::Map<String, serde_json::Value> {
 impl From<serde_json::Map<String, serde_json::Value>> for AdditionalField {
     fn from(value: serde_json::Map<String, serde_json::Value>) -> Self {
         Self {
-            must_understand: monostate::MustBe!(false),
+            must_understand: false,
             fields: value,
         }
     }
@@ -271,7 +395,8 @@ impl From<serde_json::Map<String, serde_json::Value>> for AdditionalField {
 
 /// Additional fields in array or group metadata.
 ///
-/// Additional fields are a JSON object with a `"must_understand": false` key-value pair.
+/// Additional fields are a JSON object with an optional `"must_understand"` key-value pair.
+/// Fields without `"must_understand": false` must be understood, and are rejected by [`AdditionalFieldsExt::validate`].
 ///
 /// ### Example additional field JSON
 /// ```json
@@ -282,3 +407,145 @@ impl From<serde_json::Map<String, serde_json::Value>> for AdditionalField {
 /// ```
 // NOTE: It would be nice if this was just a serde_json::Map, but it only has implementations for `<String, serde_json::Value>`.
 pub type AdditionalFields = std::collections::BTreeMap<String, AdditionalField>;
+
+/// An additional fields merge error.
+#[derive(Debug, Error)]
+pub enum AdditionalFieldsMergeError {
+    /// An additional field is present in both sources with differing values.
+    #[error("conflicting values for additional field {0}")]
+    Conflict(String),
+    /// An unsupported additional field.
+    #[error(transparent)]
+    Unsupported(#[from] UnsupportedAdditionalFieldError),
+}
+
+/// Merge operations for [`AdditionalFields`].
+pub trait AdditionalFieldsExt {
+    /// Merge the additional fields of `other` into `self`.
+    ///
+    /// Fields present in both with equal values are kept once.
+    /// The merged fields are validated and `self` is left unchanged if an error is returned.
+    ///
+    /// # Errors
+    /// Returns an [`AdditionalFieldsMergeError`] if
+    ///  - a field is present in both with differing values, or
+    ///  - a merged field is not a valid additional field (i.e. it would not serialise with `"must_understand": false`).
+    fn merge(&mut self, other: &AdditionalFields) -> Result<(), AdditionalFieldsMergeError>;
+
+    /// Validate each additional field.
+    ///
+    /// # Errors
+    /// Returns an [`UnsupportedAdditionalFieldError`] for the first field that must be understood.
+    fn validate(&self) -> Result<(), UnsupportedAdditionalFieldError>;
+}
+
+impl AdditionalFieldsExt for AdditionalFields {
+    fn merge(&mut self, other: &AdditionalFields) -> Result<(), AdditionalFieldsMergeError> {
+        let mut merged = self.clone();
+        for (name, field) in other {
+            match merged.get(name) {
+                Some(existing) if existing != field => {
+                    return Err(AdditionalFieldsMergeError::Conflict(name.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    merged.insert(name.clone(), field.clone());
+                }
+            }
+        }
+        merged.validate()?;
+        *self = merged;
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), UnsupportedAdditionalFieldError> {
+        for (name, field) in self {
+            field.validate(name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(metadata: &MetadataV3) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        metadata.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn metadata_v3_eq_hash() {
+        let a: MetadataV3 = serde_json::from_str(
+            r#"{"name": "blosc", "configuration": {"cname": "zstd", "clevel": 5, "nested": {"x": 1, "y": [0.0, true]}}}"#,
+        )
+        .unwrap();
+        let b: MetadataV3 = serde_json::from_str(
+            r#"{"configuration": {"nested": {"y": [-0.0, true], "x": 1}, "clevel": 5, "cname": "zstd"}, "name": "blosc"}"#,
+        )
+        .unwrap();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+
+        let c: MetadataV3 = serde_json::from_str(
+            r#"{"name": "blosc", "configuration": {"cname": "zstd", "clevel": 6, "nested": {"x": 1, "y": [0.0, true]}}}"#,
+        )
+        .unwrap();
+        assert_ne!(a, c);
+        assert_ne!(hash(&a), hash(&c));
+
+        let d: MetadataV3 = serde_json::from_str(r#"{"name": "bytes"}"#).unwrap();
+        let e: MetadataV3 =
+            serde_json::from_str(r#"{"name": "bytes", "configuration": {}}"#).unwrap();
+        assert_eq!(d, e);
+        assert_eq!(hash(&d), hash(&e));
+    }
+
+    fn additional_field(json: &str) -> AdditionalField {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn additional_fields_merge() {
+        let mut fields = AdditionalFields::from([
+            (
+                "a".to_string(),
+                additional_field(r#"{"must_understand": false, "x": 1}"#),
+            ),
+            (
+                "b".to_string(),
+                additional_field(r#"{"must_understand": false, "y": 2}"#),
+            ),
+        ]);
+        let other = AdditionalFields::from([
+            (
+                "b".to_string(),
+                additional_field(r#"{"must_understand": false, "y": 2}"#),
+            ),
+            (
+                "c".to_string(),
+                additional_field(r#"{"must_understand": false, "z": 3}"#),
+            ),
+        ]);
+        fields.merge(&other).unwrap();
+        assert_eq!(fields.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+
+        let conflicting = AdditionalFields::from([(
+            "a".to_string(),
+            additional_field(r#"{"must_understand": false, "x": 2}"#),
+        )]);
+        let err = fields.merge(&conflicting).unwrap_err();
+        assert!(matches!(&err, AdditionalFieldsMergeError::Conflict(name) if name == "a"));
+        assert_eq!(fields.len(), 3);
+
+        let mut map = serde_json::Map::new();
+        map.insert("must_understand".to_string(), serde_json::Value::Bool(true));
+        let invalid = AdditionalFields::from([("d".to_string(), AdditionalField::from(map))]);
+        let err = fields.merge(&invalid).unwrap_err();
+        assert!(matches!(&err, AdditionalFieldsMergeError::Unsupported(err) if err.name() == "d"));
+        assert!(!fields.contains_key("d"));
+    }
+}
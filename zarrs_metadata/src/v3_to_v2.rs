@@ -0,0 +1,416 @@
+use thiserror::Error;
+
+use crate::{
+    v2::{
+        array::{
+            codec::blosc::codec_blosc_v3_to_v2_numcodecs, ArrayMetadataV2Order, DataTypeMetadataV2,
+            FillValueMetadataV2,
+        },
+        ArrayMetadataV2, GroupMetadataV2, MetadataV2,
+    },
+    v3::{
+        array::{
+            chunk_grid::regular::RegularChunkGridConfiguration,
+            chunk_key_encoding::v2::V2ChunkKeyEncodingConfiguration,
+            codec::{
+                blosc::BloscCodecConfiguration, bytes::BytesCodecConfiguration,
+                transpose::TransposeCodecConfiguration,
+            },
+            data_type::DataTypeMetadataV3,
+            fill_value::{FillValueFloat, FillValueFloatStringNonFinite, FillValueMetadataV3},
+        },
+        ArrayMetadataV3, GroupMetadataV3, MetadataV3,
+    },
+    Endianness,
+};
+
+/// Convert Zarr V3 group metadata to V2.
+#[must_use]
+pub fn group_metadata_v3_to_v2(group_metadata_v3: &GroupMetadataV3) -> GroupMetadataV2 {
+    GroupMetadataV2 {
+        zarr_format: monostate::MustBe!(2u64),
+        attributes: group_metadata_v3.attributes.clone(),
+        additional_fields: group_metadata_v3.additional_fields.clone(),
+    }
+}
+
+/// An error converting Zarr V3 array metadata to V2.
+#[derive(Debug, Error)]
+pub enum ArrayMetadataV3ToV2ConversionError {
+    /// Unsupported data type.
+    #[error("unsupported data type {_0}")]
+    UnsupportedDataType(String),
+    /// An unsupported chunk grid.
+    #[error("unsupported chunk grid {_0}")]
+    UnsupportedChunkGrid(MetadataV3),
+    /// An unsupported chunk key encoding.
+    #[error("unsupported chunk key encoding {_0}")]
+    UnsupportedChunkKeyEncoding(MetadataV3),
+    /// An unsupported codec.
+    #[error("unsupported codec {_0}")]
+    UnsupportedCodec(MetadataV3),
+    /// An unsupported fill value.
+    #[error("unsupported fill value {_1} for data type {_0}")]
+    UnsupportedFillValue(String, FillValueMetadataV3),
+    /// Storage transformers are not supported.
+    #[error("storage transformers are not supported")]
+    UnsupportedStorageTransformers,
+    /// Serialization/deserialization error.
+    #[error("JSON serialization or deserialization error: {_0}")]
+    SerdeError(#[from] serde_json::Error),
+    /// Other.
+    #[error("{_0}")]
+    Other(String),
+}
+
+/// Convert Zarr V3 array metadata to V2.
+///
+/// This is the inverse of [`array_metadata_v2_to_v3`](crate::v2_to_v3::array_metadata_v2_to_v3), so V2 metadata converted to V3 converts back to the original V2 metadata.
+/// In particular:
+///  - a `transpose` codec with a reversed order (the first codec) is converted to the `F` order,
+///  - the separator of the `v2` chunk key encoding is converted to the dimension separator,
+///  - `array -> array` codecs are converted to filters, and
+///  - a single `bytes -> bytes` codec is converted to the compressor.
+///
+/// Dimension names are not supported by Zarr V2 and are dropped.
+///
+/// # Errors
+/// Returns a [`ArrayMetadataV3ToV2ConversionError`] if the metadata is invalid or is not compatible with Zarr V2 metadata.
+#[allow(clippy::too_many_lines)]
+pub fn array_metadata_v3_to_v2(
+    array_metadata_v3: &ArrayMetadataV3,
+) -> Result<ArrayMetadataV2, ArrayMetadataV3ToV2ConversionError> {
+    if !array_metadata_v3.storage_transformers.is_empty() {
+        return Err(ArrayMetadataV3ToV2ConversionError::UnsupportedStorageTransformers);
+    }
+
+    // Chunk grid
+    let chunk_grid = &array_metadata_v3.chunk_grid;
+    let chunks = match chunk_grid.name() {
+        crate::v3::array::chunk_grid::regular::IDENTIFIER => {
+            let configuration = chunk_grid
+                .to_configuration::<RegularChunkGridConfiguration>()
+                .map_err(|_| {
+                    ArrayMetadataV3ToV2ConversionError::UnsupportedChunkGrid(chunk_grid.clone())
+                })?;
+            configuration.chunk_shape
+        }
+        _ => {
+            return Err(ArrayMetadataV3ToV2ConversionError::UnsupportedChunkGrid(
+                chunk_grid.clone(),
+            ))
+        }
+    };
+
+    // Chunk key encoding
+    let chunk_key_encoding = &array_metadata_v3.chunk_key_encoding;
+    let dimension_separator = match chunk_key_encoding.name() {
+        crate::v3::array::chunk_key_encoding::v2::IDENTIFIER => {
+            chunk_key_encoding
+                .to_configuration::<V2ChunkKeyEncodingConfiguration>()
+                .map_err(|_| {
+                    ArrayMetadataV3ToV2ConversionError::UnsupportedChunkKeyEncoding(
+                        chunk_key_encoding.clone(),
+                    )
+                })?
+                .separator
+        }
+        _ => {
+            return Err(
+                ArrayMetadataV3ToV2ConversionError::UnsupportedChunkKeyEncoding(
+                    chunk_key_encoding.clone(),
+                ),
+            )
+        }
+    };
+
+    // Codecs
+    let data_type = &array_metadata_v3.data_type;
+    let unsupported_codec =
+        |codec: &MetadataV3| ArrayMetadataV3ToV2ConversionError::UnsupportedCodec(codec.clone());
+    let mut codecs = array_metadata_v3.codecs.iter().peekable();
+
+    // Array-to-array codecs
+    let mut order = ArrayMetadataV2Order::C;
+    if let Some(codec) =
+        codecs.next_if(|codec| codec.name() == crate::v3::array::codec::transpose::IDENTIFIER)
+    {
+        let TransposeCodecConfiguration::V1(configuration) = codec
+            .to_configuration::<TransposeCodecConfiguration>()
+            .map_err(|_| unsupported_codec(codec))?;
+        let f_order: Vec<usize> = (0..array_metadata_v3.shape.len()).rev().collect();
+        if configuration.order.0 != f_order {
+            return Err(unsupported_codec(codec));
+        }
+        order = ArrayMetadataV2Order::F;
+    }
+    let mut filters: Vec<MetadataV2> = vec![];
+    while let Some(codec) = codecs.next_if(|codec| !is_array_to_bytes_codec(codec.name())) {
+        filters.push(MetadataV2::new_with_configuration(
+            codec.name(),
+            codec.configuration().cloned().unwrap_or_default(),
+        ));
+    }
+
+    // Array-to-bytes codec
+    let mut endianness = None;
+    let mut compressor = None;
+    match codecs.next() {
+        Some(codec) if codec.name() == crate::v3::array::codec::bytes::IDENTIFIER => {
+            let BytesCodecConfiguration::V1(configuration) = codec
+                .to_configuration::<BytesCodecConfiguration>()
+                .map_err(|_| unsupported_codec(codec))?;
+            endianness = configuration.endian;
+        }
+        Some(codec)
+            if codec.name() == crate::v3::array::codec::vlen_v2::IDENTIFIER
+                || codec.name() == crate::v3::array::codec::vlen_v2::IDENTIFIER_VLEN_UTF8 =>
+        {
+            let id = match data_type {
+                DataTypeMetadataV3::String => "vlen-utf8",
+                DataTypeMetadataV3::Binary => "vlen-bytes",
+                _ => return Err(unsupported_codec(codec)),
+            };
+            filters.push(MetadataV2::new(id));
+        }
+        Some(codec) if codec.name() == crate::v3::array::codec::pcodec::IDENTIFIER => {
+            // pcodec is v2/v3 compatible
+            compressor = Some(MetadataV2::new_with_configuration(
+                codec.name(),
+                codec.configuration().cloned().unwrap_or_default(),
+            ));
+        }
+        Some(codec) => return Err(unsupported_codec(codec)),
+        None => {
+            return Err(ArrayMetadataV3ToV2ConversionError::Other(
+                "missing array to bytes codec".to_string(),
+            ))
+        }
+    }
+
+    // Bytes-to-bytes codec
+    if let Some(codec) = codecs.next() {
+        if compressor.is_some() {
+            return Err(unsupported_codec(codec));
+        }
+        compressor = Some(match codec.name() {
+            crate::v3::array::codec::blosc::IDENTIFIER => {
+                let BloscCodecConfiguration::V1(configuration) = codec
+                    .to_configuration::<BloscCodecConfiguration>()
+                    .map_err(|_| unsupported_codec(codec))?;
+                let configuration = codec_blosc_v3_to_v2_numcodecs(&configuration);
+                let serde_json::Value::Object(configuration) = serde_json::to_value(configuration)?
+                else {
+                    unreachable!("blosc configuration is an object")
+                };
+                MetadataV2::new_with_configuration(codec.name(), configuration)
+            }
+            _ => MetadataV2::new_with_configuration(
+                codec.name(),
+                codec.configuration().cloned().unwrap_or_default(),
+            ),
+        });
+    }
+    if let Some(codec) = codecs.next() {
+        return Err(unsupported_codec(codec));
+    }
+
+    let dtype = data_type_metadata_v3_to_v2_data_type(data_type, endianness).ok_or_else(|| {
+        ArrayMetadataV3ToV2ConversionError::UnsupportedDataType(data_type.to_string())
+    })?;
+    let fill_value =
+        array_metadata_fill_value_v3_to_v2(&array_metadata_v3.fill_value).ok_or_else(|| {
+            ArrayMetadataV3ToV2ConversionError::UnsupportedFillValue(
+                data_type.to_string(),
+                array_metadata_v3.fill_value.clone(),
+            )
+        })?;
+
+    let mut array_metadata_v2 = ArrayMetadataV2::new(
+        array_metadata_v3.shape.clone(),
+        chunks,
+        dtype,
+        fill_value,
+        compressor,
+        (!filters.is_empty()).then_some(filters),
+    );
+    array_metadata_v2.order = order;
+    array_metadata_v2.dimension_separator = dimension_separator;
+    array_metadata_v2
+        .attributes
+        .clone_from(&array_metadata_v3.attributes);
+    array_metadata_v2
+        .additional_fields
+        .clone_from(&array_metadata_v3.additional_fields);
+    Ok(array_metadata_v2)
+}
+
+/// Returns true if `name` is an `array -> bytes` codec supported by [`array_metadata_v3_to_v2`].
+fn is_array_to_bytes_codec(name: &str) -> bool {
+    name == crate::v3::array::codec::bytes::IDENTIFIER
+        || name == crate::v3::array::codec::vlen_v2::IDENTIFIER
+        || name == crate::v3::array::codec::vlen_v2::IDENTIFIER_VLEN_UTF8
+        || name == crate::v3::array::codec::pcodec::IDENTIFIER
+}
+
+/// Convert a Zarr V3 data type with `endianness` to a compatible V2 data type.
+///
+/// Returns [`None`] if the data type is not supported, or it is larger than one byte and `endianness` is [`None`].
+#[must_use]
+pub fn data_type_metadata_v3_to_v2_data_type(
+    data_type: &DataTypeMetadataV3,
+    endianness: Option<Endianness>,
+) -> Option<DataTypeMetadataV2> {
+    let endianness = match endianness {
+        Some(Endianness::Little) => "<",
+        Some(Endianness::Big) => ">",
+        None => "",
+    };
+    let multi_byte = |kind: &str| (!endianness.is_empty()).then(|| format!("{endianness}{kind}"));
+    let data_type = match data_type {
+        DataTypeMetadataV3::Bool => Some("|b1".to_string()),
+        DataTypeMetadataV3::Int8 => Some("|i1".to_string()),
+        DataTypeMetadataV3::UInt8 => Some("|u1".to_string()),
+        DataTypeMetadataV3::Int16 => multi_byte("i2"),
+        DataTypeMetadataV3::Int32 => multi_byte("i4"),
+        DataTypeMetadataV3::Int64 => multi_byte("i8"),
+        DataTypeMetadataV3::UInt16 => multi_byte("u2"),
+        DataTypeMetadataV3::UInt32 => multi_byte("u4"),
+        DataTypeMetadataV3::UInt64 => multi_byte("u8"),
+        DataTypeMetadataV3::Float16 => multi_byte("f2"),
+        DataTypeMetadataV3::Float32 => multi_byte("f4"),
+        DataTypeMetadataV3::Float64 => multi_byte("f8"),
+        DataTypeMetadataV3::Complex64 => multi_byte("c8"),
+        DataTypeMetadataV3::Complex128 => multi_byte("c16"),
+        DataTypeMetadataV3::RawBits(size) => Some(format!("|V{size}")),
+        DataTypeMetadataV3::String | DataTypeMetadataV3::Binary => Some("|O".to_string()),
+        DataTypeMetadataV3::BFloat16 | DataTypeMetadataV3::Unknown(_) => None,
+    };
+    data_type.map(DataTypeMetadataV2::Simple)
+}
+
+/// Convert [`FillValueMetadataV3`] to Zarr V2 fill value metadata.
+///
+/// Returns [`None`] if the fill value has no Zarr V2 representation.
+#[must_use]
+pub fn array_metadata_fill_value_v3_to_v2(
+    fill_value: &FillValueMetadataV3,
+) -> Option<FillValueMetadataV2> {
+    match fill_value {
+        FillValueMetadataV3::Bool(bool) => Some(FillValueMetadataV2::Bool(*bool)),
+        FillValueMetadataV3::UInt(uint) => Some(FillValueMetadataV2::Number((*uint).into())),
+        FillValueMetadataV3::Int(int) => Some(FillValueMetadataV2::Number((*int).into())),
+        FillValueMetadataV3::Float(FillValueFloat::Float(float)) => {
+            serde_json::Number::from_f64(*float).map(FillValueMetadataV2::Number)
+        }
+        FillValueMetadataV3::Float(FillValueFloat::NonFinite(non_finite)) => {
+            Some(match non_finite {
+                FillValueFloatStringNonFinite::NaN => FillValueMetadataV2::NaN,
+                FillValueFloatStringNonFinite::PosInfinity => FillValueMetadataV2::Infinity,
+                FillValueFloatStringNonFinite::NegInfinity => FillValueMetadataV2::NegInfinity,
+            })
+        }
+        FillValueMetadataV3::ByteArray(bytes) => Some(FillValueMetadataV2::Bytes(bytes.clone())),
+        FillValueMetadataV3::Float(FillValueFloat::HexString(_))
+        | FillValueMetadataV3::Complex(_, _)
+        | FillValueMetadataV3::String(_)
+        | FillValueMetadataV3::Unsupported(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{v2_to_v3::array_metadata_v2_to_v3, ChunkKeySeparator};
+
+    use super::*;
+
+    fn array_metadata_v2_round_trip(json: &str) {
+        let array_metadata_v2: ArrayMetadataV2 = serde_json::from_str(json).unwrap();
+        let array_metadata_v3 = array_metadata_v2_to_v3(&array_metadata_v2).unwrap();
+        assert_eq!(
+            array_metadata_v3_to_v2(&array_metadata_v3).unwrap(),
+            array_metadata_v2
+        );
+    }
+
+    #[test]
+    fn array_metadata_v2_v3_v2_order_separator() {
+        let json = r#"
+            {
+                "zarr_format": 2,
+                "shape": [100, 200, 300],
+                "chunks": [10, 20, 30],
+                "dtype": ">u2",
+                "compressor": null,
+                "fill_value": 0,
+                "order": "F",
+                "filters": null,
+                "dimension_separator": "/"
+            }"#;
+        array_metadata_v2_round_trip(json);
+
+        let array_metadata_v2: ArrayMetadataV2 = serde_json::from_str(json).unwrap();
+        let array_metadata_v3 = array_metadata_v2_to_v3(&array_metadata_v2).unwrap();
+        let array_metadata_v2 = array_metadata_v3_to_v2(&array_metadata_v3).unwrap();
+        assert_eq!(array_metadata_v2.order, ArrayMetadataV2Order::F);
+        assert_eq!(
+            array_metadata_v2.dimension_separator,
+            ChunkKeySeparator::Slash
+        );
+
+        array_metadata_v2_round_trip(
+            r#"
+            {
+                "zarr_format": 2,
+                "shape": [100, 200],
+                "chunks": [10, 20],
+                "dtype": "<f8",
+                "compressor": {"id": "zstd", "level": 5},
+                "fill_value": "NaN",
+                "order": "C",
+                "filters": [{"id": "delta", "dtype": "<f8"}],
+                "dimension_separator": "."
+            }"#,
+        );
+    }
+
+    #[test]
+    fn array_metadata_v2_v3_v2_blosc() {
+        array_metadata_v2_round_trip(
+            r#"
+            {
+                "zarr_format": 2,
+                "shape": [100],
+                "chunks": [10],
+                "dtype": "<i4",
+                "compressor": {"id": "blosc", "cname": "lz4", "clevel": 5, "shuffle": 1, "blocksize": 0},
+                "fill_value": -1,
+                "order": "C",
+                "filters": null,
+                "attributes": {"key": "value"}
+            }"#,
+        );
+    }
+
+    #[test]
+    fn array_metadata_v3_to_v2_unsupported() {
+        let array_metadata_v3: ArrayMetadataV3 = serde_json::from_str(
+            r#"
+            {
+                "zarr_format": 3,
+                "node_type": "array",
+                "shape": [100, 200],
+                "data_type": "uint8",
+                "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": [10, 20]}},
+                "chunk_key_encoding": {"name": "default"},
+                "fill_value": 0,
+                "codecs": [{"name": "bytes"}]
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            array_metadata_v3_to_v2(&array_metadata_v3),
+            Err(ArrayMetadataV3ToV2ConversionError::UnsupportedChunkKeyEncoding(_))
+        ));
+    }
+}
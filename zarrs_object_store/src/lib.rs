@@ -30,9 +30,10 @@ use futures::{StreamExt, TryStreamExt};
 use object_store::path::Path;
 
 use zarrs_storage::{
-    async_store_set_partial_values, byte_range::ByteRange, AsyncBytes, AsyncListableStorageTraits,
-    AsyncReadableStorageTraits, AsyncWritableStorageTraits, MaybeAsyncBytes, StorageError,
-    StoreKey, StoreKeyOffsetValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
+    async_store_set_partial_values, byte_range::ByteRange, paginate_keys, AsyncBytes,
+    AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+    ListContinuationToken, MaybeAsyncBytes, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix,
 };
 
 /// Maps a [`StoreKey`] to an [`object_store`] path.
@@ -60,17 +61,61 @@ fn handle_result<T>(result: Result<T, object_store::Error>) -> Result<T, Storage
     result.map_err(|err| StorageError::Other(err.to_string()))
 }
 
+/// Check that the length of each of `bytes` matches the length of its range in `ranges`.
+///
+/// A store may return fewer bytes than requested (e.g. if an object is truncated, or a proxy truncates a range response).
+/// This is a storage error, rather than passing short data on to be decoded.
+fn validate_range_lengths(
+    key: &StoreKey,
+    ranges: &[std::ops::Range<usize>],
+    bytes: Vec<AsyncBytes>,
+) -> Result<Vec<AsyncBytes>, StorageError> {
+    if let Some((range, bytes)) =
+        std::iter::zip(ranges, &bytes).find(|(range, bytes)| range.len() != bytes.len())
+    {
+        return Err(StorageError::UnexpectedLength(
+            key.clone(),
+            range.len() as u64,
+            bytes.len() as u64,
+        ));
+    }
+    if ranges.len() != bytes.len() {
+        return Err(StorageError::Other(format!(
+            "unexpected number of byte ranges returned for {key}, expected {}, got {}",
+            ranges.len(),
+            bytes.len()
+        )));
+    }
+    Ok(bytes)
+}
+
 /// An asynchronous store backed by an [`object_store::ObjectStore`].
 pub struct AsyncObjectStore<T> {
     object_store: T,
+    list_page_size: usize,
     // locks: AsyncStoreLocks,
 }
 
 impl<T: object_store::ObjectStore> AsyncObjectStore<T> {
+    /// The default maximum number of keys in a page of [`list_prefix_paged`](AsyncListableStorageTraits::list_prefix_paged).
+    pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
     /// Create a new [`AsyncObjectStore`].
     #[must_use]
     pub fn new(object_store: T) -> Self {
-        Self { object_store }
+        Self {
+            object_store,
+            list_page_size: Self::DEFAULT_LIST_PAGE_SIZE,
+        }
+    }
+
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](AsyncListableStorageTraits::list_prefix_paged).
+    ///
+    /// A page size of zero is treated as one.
+    #[must_use]
+    pub fn with_list_page_size(mut self, list_page_size: usize) -> Self {
+        self.list_page_size = list_page_size.max(1);
+        self
     }
 }
 
@@ -103,21 +148,7 @@ impl<T: object_store::ObjectStore> AsyncReadableStorageTraits for AsyncObjectSto
             .get_ranges(&key_to_path(key), &ranges)
             .await;
         match get_ranges {
-            Ok(get_ranges) => Ok(Some(
-                std::iter::zip(ranges, get_ranges)
-                    .map(|(range, bytes)| {
-                        if range.len() == bytes.len() {
-                            Ok(bytes)
-                        } else {
-                            Err(StorageError::Other(format!(
-                                "Unexpected length of bytes returned, expected {}, got {}",
-                                range.len(),
-                                bytes.len()
-                            )))
-                        }
-                    })
-                    .collect::<Result<_, StorageError>>()?,
-            )),
+            Ok(get_ranges) => Ok(Some(validate_range_lengths(key, &ranges, get_ranges)?)),
             Err(err) => {
                 if matches!(err, object_store::Error::NotFound { .. }) {
                     Ok(None)
@@ -170,6 +201,18 @@ impl<T: object_store::ObjectStore> AsyncWritableStorageTraits for AsyncObjectSto
         )?;
         Ok(())
     }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        handle_result(
+            self.object_store
+                .rename(&key_to_path(from), &key_to_path(to))
+                .await,
+        )
+    }
+
+    fn supports_rename(&self) -> bool {
+        true
+    }
 }
 
 #[async_trait::async_trait]
@@ -214,6 +257,19 @@ impl<T: object_store::ObjectStore> AsyncListableStorageTraits for AsyncObjectSto
         Ok(list)
     }
 
+    /// Retrieve a page of at most [`with_list_page_size`](AsyncObjectStore::with_list_page_size) keys with a given prefix.
+    ///
+    /// The continuation token is the last key of the previous page.
+    /// Object stores do not guarantee the order of a listing (e.g. [`object_store::local::LocalFileSystem`]), so each page is taken from the complete sorted listing of [`list_prefix`](AsyncListableStorageTraits::list_prefix).
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let keys = self.list_prefix(prefix).await?;
+        Ok(paginate_keys(keys, continuation_token, self.list_page_size))
+    }
+
     async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
         let path: object_store::path::Path = prefix.as_str().into();
         let list_result = handle_result(self.object_store.list_with_delimiter(Some(&path)).await)?;
@@ -265,15 +321,86 @@ mod tests {
     use super::*;
     use std::error::Error;
 
+    #[test]
+    fn validate_range_lengths_short_read() {
+        let key = StoreKey::new("array/c/0").unwrap();
+        let ranges = [0..4, 4..8];
+        let bytes = vec![
+            AsyncBytes::from_static(&[0, 1, 2, 3]),
+            AsyncBytes::from_static(&[4, 5, 6, 7]),
+        ];
+        assert_eq!(
+            validate_range_lengths(&key, &ranges, bytes.clone()).unwrap(),
+            bytes
+        );
+
+        let bytes = vec![
+            AsyncBytes::from_static(&[0, 1, 2, 3]),
+            AsyncBytes::from_static(&[4, 5]),
+        ];
+        assert!(matches!(
+            validate_range_lengths(&key, &ranges, bytes),
+            Err(StorageError::UnexpectedLength(err_key, 4, 2)) if err_key == key
+        ));
+        assert!(validate_range_lengths(&key, &ranges, vec![]).is_err());
+    }
+
     #[tokio::test]
     async fn memory() -> Result<(), Box<dyn Error>> {
         let store = AsyncObjectStore::new(object_store::memory::InMemory::new());
         zarrs_storage::store_test::async_store_write(&store).await?;
         zarrs_storage::store_test::async_store_read(&store).await?;
         zarrs_storage::store_test::async_store_list(&store).await?;
+        zarrs_storage::store_test::async_store_rename(&store).await?;
+        Ok(())
+    }
+
+    async fn list_prefix_paged<T: object_store::ObjectStore>(
+        store: &AsyncObjectStore<T>,
+    ) -> Result<(), Box<dyn Error>> {
+        for key in ["a/3", "a/0", "a/4", "a/1", "b/0", "a/2"] {
+            store
+                .set(&StoreKey::new(key)?, AsyncBytes::from_static(&[0]))
+                .await?;
+        }
+
+        let prefix = StorePrefix::new("a/")?;
+        let mut pages = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let (keys, next_continuation_token) = store
+                .list_prefix_paged(&prefix, continuation_token.as_ref())
+                .await?;
+            assert!(keys.len() <= 2);
+            pages.push(keys);
+            continuation_token = next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.concat(), store.list_prefix(&prefix).await?);
         Ok(())
     }
 
+    #[tokio::test]
+    async fn memory_list_prefix_paged() -> Result<(), Box<dyn Error>> {
+        let store =
+            AsyncObjectStore::new(object_store::memory::InMemory::new()).with_list_page_size(2);
+        list_prefix_paged(&store).await
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)]
+    async fn filesystem_list_prefix_paged() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let store = AsyncObjectStore::new(object_store::local::LocalFileSystem::new_with_prefix(
+            path.path(),
+        )?)
+        .with_list_page_size(2);
+        list_prefix_paged(&store).await
+    }
+
     #[tokio::test]
     #[cfg_attr(miri, ignore)]
     async fn filesystem() -> Result<(), Box<dyn Error>> {
@@ -284,6 +411,7 @@ mod tests {
         zarrs_storage::store_test::async_store_write(&store).await?;
         zarrs_storage::store_test::async_store_read(&store).await?;
         zarrs_storage::store_test::async_store_list(&store).await?;
+        zarrs_storage::store_test::async_store_rename(&store).await?;
         Ok(())
     }
 }
@@ -1,10 +1,11 @@
+use futures::StreamExt;
 use opendal::Operator;
 
 use zarrs_storage::{
     byte_range::{ByteRange, InvalidByteRangeError},
-    AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
-    MaybeAsyncBytes, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeys, StoreKeysPrefixes,
-    StorePrefix,
+    paginate_keys, AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+    AsyncWritableStorageTraits, ListContinuationToken, MaybeAsyncBytes, StorageError, StoreKey,
+    StoreKeyOffsetValue, StoreKeys, StoreKeysPrefixes, StorePrefix,
 };
 
 use crate::{handle_result, handle_result_notfound};
@@ -12,14 +13,30 @@ use crate::{handle_result, handle_result_notfound};
 /// An asynchronous store backed by an [`opendal::Operator`].
 pub struct AsyncOpendalStore {
     operator: Operator,
+    list_page_size: usize,
     // locks: AsyncStoreLocks,
 }
 
 impl AsyncOpendalStore {
+    /// The default maximum number of keys in a page of [`list_prefix_paged`](AsyncListableStorageTraits::list_prefix_paged).
+    pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
     /// Create a new [`AsyncOpendalStore`].
     #[must_use]
     pub fn new(operator: Operator) -> Self {
-        Self { operator }
+        Self {
+            operator,
+            list_page_size: Self::DEFAULT_LIST_PAGE_SIZE,
+        }
+    }
+
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](AsyncListableStorageTraits::list_prefix_paged).
+    ///
+    /// A page size of zero is treated as one.
+    #[must_use]
+    pub fn with_list_page_size(mut self, list_page_size: usize) -> Self {
+        self.list_page_size = list_page_size.max(1);
+        self
     }
 }
 
@@ -89,6 +106,20 @@ impl AsyncWritableStorageTraits for AsyncOpendalStore {
     async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
         handle_result(self.operator.remove_all(prefix.as_str()).await)
     }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        if self.supports_rename() {
+            handle_result(self.operator.rename(from.as_str(), to.as_str()).await)
+        } else {
+            Err(StorageError::Unsupported(
+                "the opendal service does not support renaming".to_string(),
+            ))
+        }
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.operator.info().full_capability().rename
+    }
 }
 
 #[async_trait::async_trait]
@@ -123,6 +154,46 @@ impl AsyncListableStorageTraits for AsyncOpendalStore {
         )
     }
 
+    /// Retrieve a page of keys with a given prefix.
+    ///
+    /// The listing is resumed after the continuation token if the service supports listing with `start_after`, otherwise the entire prefix is listed and paged.
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        if !self.operator.info().full_capability().list_with_start_after {
+            return Ok(paginate_keys(
+                self.list_prefix(prefix).await?,
+                continuation_token,
+                self.list_page_size,
+            ));
+        }
+        let mut lister = self.operator.lister_with(prefix.as_str()).recursive(true);
+        if let Some(continuation_token) = continuation_token {
+            lister = lister.start_after(continuation_token.as_str());
+        }
+        let Some(mut lister) = handle_result_notfound(lister.await)? else {
+            return Ok((vec![], None));
+        };
+        let mut keys = StoreKeys::new();
+        while let Some(entry) = lister.next().await {
+            let entry = handle_result(entry)?;
+            if entry.metadata().mode() != opendal::EntryMode::FILE {
+                continue;
+            }
+            if keys.len() == self.list_page_size {
+                // There are more keys after this page
+                let continuation_token = keys
+                    .last()
+                    .map(|key| ListContinuationToken::new(key.as_str()));
+                return Ok((keys, continuation_token));
+            }
+            keys.push(StoreKey::try_from(entry.path())?);
+        }
+        Ok((keys, None))
+    }
+
     async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
         handle_result_notfound(
             self.operator
@@ -193,6 +264,36 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn memory_list_prefix_paged() -> Result<(), Box<dyn Error>> {
+        let builder = opendal::services::Memory::default();
+        let op = Operator::new(builder)?.finish();
+        let store = AsyncOpendalStore::new(op).with_list_page_size(2);
+        for key in ["a/0", "a/1", "a/2", "a/3", "a/4", "b/0"] {
+            store
+                .set(&StoreKey::new(key)?, AsyncBytes::from_static(&[0]))
+                .await?;
+        }
+
+        let prefix = StorePrefix::new("a/")?;
+        let mut pages = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let (keys, next_continuation_token) = store
+                .list_prefix_paged(&prefix, continuation_token.as_ref())
+                .await?;
+            assert!(keys.len() <= 2);
+            pages.push(keys);
+            continuation_token = next_continuation_token;
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages.len(), 3);
+        assert_eq!(pages.concat(), store.list_prefix(&prefix).await?);
+        Ok(())
+    }
+
     #[tokio::test]
     #[cfg_attr(miri, ignore)]
     async fn filesystem() -> Result<(), Box<dyn Error>> {
@@ -203,6 +304,7 @@ mod tests {
         zarrs_storage::store_test::async_store_write(&store).await?;
         zarrs_storage::store_test::async_store_read(&store).await?;
         zarrs_storage::store_test::async_store_list(&store).await?;
+        zarrs_storage::store_test::async_store_rename(&store).await?;
         Ok(())
     }
 }
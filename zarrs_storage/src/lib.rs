@@ -12,6 +12,7 @@
 //! - the MIT license [LICENSE-MIT](https://docs.rs/crate/zarrs_storage/latest/source/LICENCE-MIT) or <http://opensource.org/licenses/MIT>, at your option.
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
+pub mod storage_adapter;
 mod storage_handle;
 mod storage_sync;
 mod storage_value_io;
@@ -33,7 +34,7 @@ use std::sync::Arc;
 
 use thiserror::Error;
 
-pub use store_key::{StoreKey, StoreKeyError, StoreKeys};
+pub use store_key::{ListContinuationToken, StoreKey, StoreKeyError, StoreKeys};
 pub use store_prefix::{StorePrefix, StorePrefixError, StorePrefixes};
 
 #[cfg(feature = "async")]
@@ -45,14 +46,14 @@ pub use self::storage_async::{
 };
 
 pub use self::storage_sync::{
-    discover_children, store_set_partial_values, ListableStorageTraits,
+    discover_children, paginate_keys, store_set_partial_values, ListableStorageTraits,
     ReadableListableStorageTraits, ReadableStorageTraits, ReadableWritableListableStorageTraits,
     ReadableWritableStorageTraits, WritableStorageTraits,
 };
 
 pub use self::storage_handle::StorageHandle;
 
-pub use storage_value_io::StorageValueIO;
+pub use storage_value_io::{value_reader, value_writer, StorageValueIO, StorageValueWriter};
 
 /// [`Arc`] wrapped readable storage.
 pub type ReadableStorage = Arc<dyn ReadableStorageTraits>;
@@ -244,6 +245,17 @@ pub enum StorageError {
     /// Unknown key size where the key size must be known.
     #[error("{0}")]
     UnknownKeySize(StoreKey),
+    /// A storage operation exceeded its timeout.
+    #[error("storage operation timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    /// The checksum of a value does not match its stored checksum.
+    #[error("checksum mismatch for {0}")]
+    ChecksumMismatch(StoreKey),
+    /// A store returned an unexpected number of bytes for a byte range of a key.
+    ///
+    /// The fields are the key, the expected length, and the returned length.
+    #[error("unexpected length of bytes returned for {0}, expected {1}, got {2}")]
+    UnexpectedLength(StoreKey, u64, u64),
     /// Any other error.
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,14 @@
+//! Storage adapters.
+//!
+//! A storage adapter wraps a store and alters its behaviour.
+
+#[cfg(feature = "async")]
+pub mod async_timeout;
+#[cfg(feature = "checksumming")]
+pub mod checksumming;
+pub mod logging;
+pub mod metered;
+pub mod normalizing;
+pub mod retry;
+#[cfg(feature = "transport_decompress")]
+pub mod transport_decompress;
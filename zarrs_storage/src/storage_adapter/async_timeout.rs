@@ -0,0 +1,197 @@
+//! A storage adapter which applies a timeout to asynchronous reads.
+
+use std::{future::Future, sync::Arc, time::Duration};
+
+use futures::future::{select, Either};
+
+use crate::{
+    byte_range::ByteRange, AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits,
+    AsyncWritableStorageTraits, ListContinuationToken, MaybeAsyncBytes, StorageError, StoreKey,
+    StoreKeyOffsetValue, StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix,
+};
+
+/// An asynchronous storage adapter which applies a timeout to each read operation.
+///
+/// A [`get`](AsyncReadableStorageTraits::get), [`get_partial_values_key`](AsyncReadableStorageTraits::get_partial_values_key), [`get_partial_values`](AsyncReadableStorageTraits::get_partial_values), or [`size_key`](AsyncReadableStorageTraits::size_key) request that does not complete within the timeout returns a [`StorageError::Timeout`].
+/// Listable and writable operations are passed through to the inner storage unchanged.
+///
+/// The timer is runtime agnostic, so this adapter can be used with any async executor.
+pub struct AsyncTimeoutStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    timeout: Duration,
+}
+
+impl<TStorage: ?Sized> AsyncTimeoutStorageAdapter<TStorage> {
+    /// Create a new async timeout storage adapter with a read `timeout`.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>, timeout: Duration) -> Self {
+        Self { storage, timeout }
+    }
+
+    /// Return the read timeout.
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        future: impl Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        let future = std::pin::pin!(future);
+        match select(future, futures_timer::Delay::new(self.timeout)).await {
+            Either::Left((result, _)) => result,
+            Either::Right(((), _)) => Err(StorageError::Timeout(self.timeout)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for AsyncTimeoutStorageAdapter<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeAsyncBytes, StorageError> {
+        self.with_timeout(self.storage.get(key)).await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+        self.with_timeout(self.storage.get_partial_values_key(key, byte_ranges))
+            .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeAsyncBytes>, StorageError> {
+        self.with_timeout(self.storage.get_partial_values(key_ranges))
+            .await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.with_timeout(self.storage.size_key(key)).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for AsyncTimeoutStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list().await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix).await
+    }
+
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.storage
+            .list_prefix_paged(prefix, continuation_token)
+            .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix).await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size().await
+    }
+}
+
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for AsyncTimeoutStorageAdapter<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        self.storage.set(key, value).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.storage.set_partial_values(key_offset_values).await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.storage.erase(key).await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.storage.erase_values(keys).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.storage.erase_prefix(prefix).await
+    }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to).await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct SlowStorage(Duration);
+
+    #[async_trait::async_trait]
+    impl AsyncReadableStorageTraits for SlowStorage {
+        async fn get_partial_values_key(
+            &self,
+            _key: &StoreKey,
+            byte_ranges: &[ByteRange],
+        ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+            futures_timer::Delay::new(self.0).await;
+            Ok(Some(vec![AsyncBytes::new(); byte_ranges.len()]))
+        }
+
+        async fn size_key(&self, _key: &StoreKey) -> Result<Option<u64>, StorageError> {
+            Ok(Some(0))
+        }
+    }
+
+    #[test]
+    fn async_timeout_storage_adapter() {
+        let key = StoreKey::new("a").unwrap();
+
+        let fast = AsyncTimeoutStorageAdapter::new(
+            Arc::new(SlowStorage(Duration::ZERO)),
+            Duration::from_secs(10),
+        );
+        assert!(futures::executor::block_on(fast.get(&key))
+            .unwrap()
+            .is_some());
+
+        let slow = AsyncTimeoutStorageAdapter::new(
+            Arc::new(SlowStorage(Duration::from_secs(10))),
+            Duration::from_millis(10),
+        );
+        assert!(matches!(
+            futures::executor::block_on(slow.get(&key)),
+            Err(StorageError::Timeout(timeout)) if timeout == Duration::from_millis(10)
+        ));
+        assert_eq!(
+            futures::executor::block_on(slow.size_key(&key)).unwrap(),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! A storage adapter which stores and verifies a checksum of each value.
+
+use std::sync::Arc;
+
+use crate::{
+    byte_range::{extract_byte_ranges, ByteRange},
+    store_set_partial_values, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+/// A storage adapter which stores a `crc32c` checksum of each value in a parallel key namespace, and verifies it on read.
+///
+/// The checksum of the value at `key` is stored at `{namespace}{key}` (e.g. `.checksums/array/zarr.json`) as a little endian `u32`.
+/// Unlike a checksum codec (e.g. `crc32c`), this covers every key written through the adapter, including metadata (`zarr.json`).
+///
+/// Checksums are written on [`set`](WritableStorageTraits::set) and [`set_partial_values`](WritableStorageTraits::set_partial_values), moved on [`rename`](WritableStorageTraits::rename), and removed on erase.
+/// A value and its checksum are renamed separately, so a concurrent verified read of the destination may briefly observe a mismatch.
+/// If verification is enabled (the default), reading a value whose checksum does not match (or is missing) fails with [`StorageError::ChecksumMismatch`].
+/// A partial read of a value must retrieve the entire value to verify it, so verification can be disabled with [`with_verify`](Self::with_verify) for performance.
+///
+/// Checksum keys are excluded from listings.
+pub struct ChecksummingStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    namespace: StorePrefix,
+    verify: bool,
+}
+
+impl<TStorage: ?Sized> ChecksummingStorageAdapter<TStorage> {
+    /// Create a new checksumming storage adapter with the default `.checksums/` namespace and verification enabled.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self {
+            storage,
+            namespace: unsafe { StorePrefix::new_unchecked(".checksums/") },
+            verify: true,
+        }
+    }
+
+    /// Set the namespace (prefix) of checksum keys.
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: StorePrefix) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    /// Enable or disable checksum verification on read.
+    #[must_use]
+    pub fn with_verify(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Return the namespace (prefix) of checksum keys.
+    #[must_use]
+    pub fn namespace(&self) -> &StorePrefix {
+        &self.namespace
+    }
+
+    /// Returns true if checksums are verified on read.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        self.verify
+    }
+
+    fn checksum_key(&self, key: &StoreKey) -> StoreKey {
+        unsafe { StoreKey::new_unchecked(format!("{}{}", self.namespace.as_str(), key.as_str())) }
+    }
+
+    fn checksum_prefix(&self, prefix: &StorePrefix) -> StorePrefix {
+        unsafe {
+            StorePrefix::new_unchecked(format!("{}{}", self.namespace.as_str(), prefix.as_str()))
+        }
+    }
+
+    fn is_checksum_key(&self, key: &StoreKey) -> bool {
+        key.has_prefix(&self.namespace)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ChecksummingStorageAdapter<TStorage> {
+    fn get_verified(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let Some(value) = self.storage.get(key)? else {
+            return Ok(None);
+        };
+        let checksum = self.storage.get(&self.checksum_key(key))?;
+        if checksum.as_deref() == Some(&crc32c::crc32c(&value).to_le_bytes()) {
+            Ok(Some(value))
+        } else {
+            Err(StorageError::ChecksumMismatch(key.clone()))
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for ChecksummingStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        if self.verify {
+            self.get_verified(key)
+        } else {
+            self.storage.get(key)
+        }
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        if self.verify {
+            let Some(value) = self.get_verified(key)? else {
+                return Ok(None);
+            };
+            Ok(Some(
+                extract_byte_ranges(&value, byte_ranges)?
+                    .into_iter()
+                    .map(Bytes::from)
+                    .collect(),
+            ))
+        } else {
+            self.storage.get_partial_values_key(key, byte_ranges)
+        }
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.storage.size_key(key)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for ChecksummingStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.list_prefix(&StorePrefix::root())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        let mut keys = self.storage.list_prefix(prefix)?;
+        keys.retain(|key| !self.is_checksum_key(key));
+        Ok(keys)
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        // A page may contain fewer keys than the underlying page once checksum keys are excluded
+        let (mut keys, continuation_token) =
+            self.storage.list_prefix_paged(prefix, continuation_token)?;
+        keys.retain(|key| !self.is_checksum_key(key));
+        Ok((keys, continuation_token))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let keys_prefixes = self.storage.list_dir(prefix)?;
+        let keys = keys_prefixes
+            .keys()
+            .iter()
+            .filter(|key| !self.is_checksum_key(key))
+            .cloned()
+            .collect();
+        let prefixes = keys_prefixes
+            .prefixes()
+            .iter()
+            .filter(|prefix| *prefix != &self.namespace)
+            .cloned()
+            .collect();
+        Ok(StoreKeysPrefixes::new(keys, prefixes))
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        let size = self.storage.size_prefix(prefix)?;
+        if self.namespace.as_str().starts_with(prefix.as_str()) {
+            // Exclude checksums
+            Ok(size.saturating_sub(self.storage.size_prefix(&self.namespace)?))
+        } else {
+            Ok(size)
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + WritableStorageTraits> WritableStorageTraits
+    for ChecksummingStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        let checksum = crc32c::crc32c(&value).to_le_bytes();
+        self.storage.set(key, value)?;
+        self.storage
+            .set(&self.checksum_key(key), Bytes::copy_from_slice(&checksum))
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        // The checksum of each updated value is recomputed
+        store_set_partial_values(self, key_offset_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.storage.erase(key)?;
+        self.storage.erase(&self.checksum_key(key))
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.storage.erase_prefix(prefix)?;
+        self.storage.erase_prefix(&self.checksum_prefix(prefix))
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to)?;
+        self.storage
+            .rename(&self.checksum_key(from), &self.checksum_key(to))
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn checksumming_storage_adapter() {
+        let store = Arc::new(MemoryStore::new());
+        let adapter = ChecksummingStorageAdapter::new(store.clone());
+
+        let key = StoreKey::new("array/zarr.json").unwrap();
+        adapter.set(&key, vec![0, 1, 2, 3].into()).unwrap();
+        let checksum_key = StoreKey::new(".checksums/array/zarr.json").unwrap();
+        assert!(store.get(&checksum_key).unwrap().is_some());
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(
+            adapter
+                .get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(2))])
+                .unwrap()
+                .unwrap(),
+            vec![Bytes::from(vec![1, 2])]
+        );
+        assert_eq!(adapter.list().unwrap(), &[key.clone()]);
+        assert_eq!(
+            adapter
+                .list_prefix_paged(&StorePrefix::root(), None)
+                .unwrap()
+                .0,
+            &[key.clone()]
+        );
+        assert_eq!(adapter.size().unwrap(), 4);
+
+        // Partial writes update the checksum
+        adapter
+            .set_partial_values(&[StoreKeyOffsetValue::new(key.clone(), 1, &[9])])
+            .unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 9, 2, 3]);
+
+        // Corruption is detected only if verifying
+        store.set(&key, vec![0, 1, 2, 4].into()).unwrap();
+        assert!(matches!(
+            adapter.get(&key),
+            Err(StorageError::ChecksumMismatch(mismatch_key)) if mismatch_key == key
+        ));
+        let adapter_unverified = ChecksummingStorageAdapter::new(store.clone()).with_verify(false);
+        assert_eq!(
+            adapter_unverified.get(&key).unwrap().unwrap(),
+            vec![0, 1, 2, 4]
+        );
+
+        // Renaming moves the checksum
+        store.set(&key, vec![0, 9, 2, 3].into()).unwrap();
+        let key_renamed = StoreKey::new("array/zarr.json.renamed").unwrap();
+        assert!(adapter.supports_rename());
+        adapter.rename(&key, &key_renamed).unwrap();
+        assert_eq!(
+            adapter.get(&key_renamed).unwrap().unwrap(),
+            vec![0, 9, 2, 3]
+        );
+        assert_eq!(adapter.list().unwrap(), &[key_renamed.clone()]);
+
+        // Erasing removes the checksum
+        adapter.erase(&key_renamed).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,642 @@
+//! A storage adapter which logs every storage operation with the [`log`] crate.
+
+use std::{
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    byte_range::ByteRange, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeyRange, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::{
+    AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+    MaybeAsyncBytes,
+};
+
+/// A storage adapter which logs every request to the inner storage at the [`debug`](log::Level::Debug) level.
+///
+/// Each log line includes the operation, the key or prefix, the number of bytes read or written (or keys listed), and the elapsed time.
+/// Failed requests are logged with their error, which is still returned to the caller.
+/// The adapter does not otherwise alter the behaviour of the inner storage, and it logs both synchronous and asynchronous storage.
+///
+/// A prefix can be prepended to each log line with [`new_with_prefix`](LoggingStorageAdapter::new_with_prefix) to distinguish multiple stores.
+pub struct LoggingStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    prefix: String,
+}
+
+impl<TStorage: ?Sized> LoggingStorageAdapter<TStorage> {
+    /// Create a new logging storage adapter.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self::new_with_prefix(storage, "")
+    }
+
+    /// Create a new logging storage adapter which prepends `prefix` to each log line.
+    #[must_use]
+    pub fn new_with_prefix(storage: Arc<TStorage>, prefix: impl Into<String>) -> Self {
+        Self {
+            storage,
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Return the prefix prepended to each log line.
+    #[must_use]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn log<T>(
+        &self,
+        operation: &str,
+        target: &dyn Display,
+        result: &Result<T, StorageError>,
+        elapsed: Duration,
+        detail: impl FnOnce(&T) -> String,
+    ) {
+        if !log::log_enabled!(log::Level::Debug) {
+            return;
+        }
+        match result {
+            Ok(value) => log::debug!(
+                "{}{operation} {target}: {} in {elapsed:?}",
+                self.prefix,
+                detail(value)
+            ),
+            Err(err) => log::debug!(
+                "{}{operation} {target}: failed in {elapsed:?}: {err}",
+                self.prefix
+            ),
+        }
+    }
+
+    fn logged<T>(
+        &self,
+        operation: &str,
+        target: &dyn Display,
+        f: impl FnOnce() -> Result<T, StorageError>,
+        detail: impl FnOnce(&T) -> String,
+    ) -> Result<T, StorageError> {
+        let start = Instant::now();
+        let result = f();
+        self.log(operation, target, &result, start.elapsed(), detail);
+        result
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_logged<T>(
+        &self,
+        operation: &str,
+        target: &(dyn Display + Sync),
+        future: impl std::future::Future<Output = Result<T, StorageError>>,
+        detail: impl FnOnce(&T) -> String,
+    ) -> Result<T, StorageError> {
+        let start = Instant::now();
+        let result = future.await;
+        self.log(operation, target, &result, start.elapsed(), detail);
+        result
+    }
+}
+
+fn bytes_detail(len: usize) -> String {
+    format!("{len} bytes")
+}
+
+fn maybe_bytes_detail<T: AsRef<[u8]>>(value: Option<&T>) -> String {
+    value.map_or_else(
+        || "missing".to_string(),
+        |value| bytes_detail(value.as_ref().len()),
+    )
+}
+
+fn bytes_vec_detail<T: AsRef<[u8]>>(values: &[T]) -> String {
+    bytes_detail(values.iter().map(|value| value.as_ref().len()).sum())
+}
+
+fn ok_detail<T>(_value: &T) -> String {
+    "ok".to_string()
+}
+
+fn keys_detail(keys: &StoreKeys) -> String {
+    format!("{} keys", keys.len())
+}
+
+fn keys_prefixes_detail(keys_prefixes: &StoreKeysPrefixes) -> String {
+    format!(
+        "{} keys, {} prefixes",
+        keys_prefixes.keys().len(),
+        keys_prefixes.prefixes().len()
+    )
+}
+
+fn size_detail(size: u64) -> String {
+    format!("size {size}")
+}
+
+fn key_ranges_target(key_ranges: &[StoreKeyRange]) -> String {
+    key_ranges
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn key_offset_values_target(key_offset_values: &[StoreKeyOffsetValue]) -> String {
+    key_offset_values
+        .iter()
+        .map(|key_offset_value| format!("{}@{}", key_offset_value.key(), key_offset_value.offset()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn key_offset_values_detail(key_offset_values: &[StoreKeyOffsetValue]) -> String {
+    bytes_detail(
+        key_offset_values
+            .iter()
+            .map(|key_offset_value| key_offset_value.value().len())
+            .sum(),
+    )
+}
+
+fn keys_target(keys: &[StoreKey]) -> String {
+    keys.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.logged(
+            "get",
+            key,
+            || self.storage.get(key),
+            |value| maybe_bytes_detail(value.as_ref()),
+        )
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let target = format!(
+            "{key}:[{}]",
+            byte_ranges
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        self.logged(
+            "get_partial_values_key",
+            &target,
+            || self.storage.get_partial_values_key(key, byte_ranges),
+            |values| {
+                values
+                    .as_deref()
+                    .map_or_else(|| "missing".to_string(), bytes_vec_detail)
+            },
+        )
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.logged(
+            "get_partial_values",
+            &key_ranges_target(key_ranges),
+            || self.storage.get_partial_values(key_ranges),
+            |values| bytes_vec_detail(&values.iter().flatten().collect::<Vec<_>>()),
+        )
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.logged(
+            "size_key",
+            key,
+            || self.storage.size_key(key),
+            |size| size.map_or_else(|| "missing".to_string(), size_detail),
+        )
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.logged("list", &"/", || self.storage.list(), keys_detail)
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.logged(
+            "list_prefix",
+            prefix,
+            || self.storage.list_prefix(prefix),
+            keys_detail,
+        )
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.logged(
+            "list_prefix_paged",
+            prefix,
+            || self.storage.list_prefix_paged(prefix, continuation_token),
+            |(keys, _)| keys_detail(keys),
+        )
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.logged(
+            "list_dir",
+            prefix,
+            || self.storage.list_dir(prefix),
+            keys_prefixes_detail,
+        )
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.logged(
+            "size_prefix",
+            prefix,
+            || self.storage.size_prefix(prefix),
+            |size| size_detail(*size),
+        )
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.logged(
+            "size",
+            &"/",
+            || self.storage.size(),
+            |size| size_detail(*size),
+        )
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        let len = value.len();
+        self.logged(
+            "set",
+            key,
+            || self.storage.set(key, value),
+            |()| bytes_detail(len),
+        )
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.logged(
+            "set_partial_values",
+            &key_offset_values_target(key_offset_values),
+            || self.storage.set_partial_values(key_offset_values),
+            |()| key_offset_values_detail(key_offset_values),
+        )
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.logged("erase", key, || self.storage.erase(key), ok_detail)
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.logged(
+            "erase_values",
+            &keys_target(keys),
+            || self.storage.erase_values(keys),
+            ok_detail,
+        )
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.logged(
+            "erase_prefix",
+            prefix,
+            || self.storage.erase_prefix(prefix),
+            ok_detail,
+        )
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.logged(
+            "rename",
+            &format!("{from} -> {to}"),
+            || self.storage.rename(from, to),
+            ok_detail,
+        )
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeAsyncBytes, StorageError> {
+        self.async_logged("get", key, self.storage.get(key), |value| {
+            maybe_bytes_detail(value.as_ref())
+        })
+        .await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+        let target = format!(
+            "{key}:[{}]",
+            byte_ranges
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        self.async_logged(
+            "get_partial_values_key",
+            &target,
+            self.storage.get_partial_values_key(key, byte_ranges),
+            |values| {
+                values
+                    .as_deref()
+                    .map_or_else(|| "missing".to_string(), bytes_vec_detail)
+            },
+        )
+        .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeAsyncBytes>, StorageError> {
+        self.async_logged(
+            "get_partial_values",
+            &key_ranges_target(key_ranges),
+            self.storage.get_partial_values(key_ranges),
+            |values| bytes_vec_detail(&values.iter().flatten().collect::<Vec<_>>()),
+        )
+        .await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.async_logged("size_key", key, self.storage.size_key(key), |size| {
+            size.map_or_else(|| "missing".to_string(), size_detail)
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.async_logged("list", &"/", self.storage.list(), keys_detail)
+            .await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.async_logged(
+            "list_prefix",
+            prefix,
+            self.storage.list_prefix(prefix),
+            keys_detail,
+        )
+        .await
+    }
+
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.async_logged(
+            "list_prefix_paged",
+            prefix,
+            self.storage.list_prefix_paged(prefix, continuation_token),
+            |(keys, _)| keys_detail(keys),
+        )
+        .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.async_logged(
+            "list_dir",
+            prefix,
+            self.storage.list_dir(prefix),
+            keys_prefixes_detail,
+        )
+        .await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.async_logged(
+            "size_prefix",
+            prefix,
+            self.storage.size_prefix(prefix),
+            |size| size_detail(*size),
+        )
+        .await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.async_logged("size", &"/", self.storage.size(), |size| size_detail(*size))
+            .await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for LoggingStorageAdapter<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        let len = value.len();
+        self.async_logged("set", key, self.storage.set(key, value), |()| {
+            bytes_detail(len)
+        })
+        .await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.async_logged(
+            "set_partial_values",
+            &key_offset_values_target(key_offset_values),
+            self.storage.set_partial_values(key_offset_values),
+            |()| key_offset_values_detail(key_offset_values),
+        )
+        .await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.async_logged("erase", key, self.storage.erase(key), ok_detail)
+            .await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.async_logged(
+            "erase_values",
+            &keys_target(keys),
+            self.storage.erase_values(keys),
+            ok_detail,
+        )
+        .await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.async_logged(
+            "erase_prefix",
+            prefix,
+            self.storage.erase_prefix(prefix),
+            ok_detail,
+        )
+        .await
+    }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.async_logged(
+            "rename",
+            &format!("{from} -> {to}"),
+            self.storage.rename(from, to),
+            ok_detail,
+        )
+        .await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    static LOG_LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// A logger which captures every log line so tests can inspect them.
+    struct CapturingLogger;
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Debug
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                LOG_LINES.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Install the capturing logger and return the log lines captured so far with `prefix`, without their elapsed time.
+    fn log_lines(prefix: &str) -> Vec<String> {
+        static LOGGER: CapturingLogger = CapturingLogger;
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(log::LevelFilter::Debug);
+        }
+        LOG_LINES
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|line| line.starts_with(prefix))
+            .map(|line| match line.split_once(": failed in ") {
+                Some((request, elapsed_err)) => {
+                    format!(
+                        "{request}: failed: {}",
+                        elapsed_err.split_once(": ").unwrap().1
+                    )
+                }
+                None => line.rsplit_once(" in ").unwrap().0.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn logging_storage_adapter() {
+        assert!(log_lines("[memory] ").is_empty());
+        let store = Arc::new(MemoryStore::new());
+        let adapter = LoggingStorageAdapter::new_with_prefix(store.clone(), "[memory] ");
+        assert_eq!(adapter.prefix(), "[memory] ");
+
+        let key = StoreKey::new("a/b").unwrap();
+        adapter.set(&key, vec![0, 1, 2, 3].into()).unwrap();
+        adapter
+            .set_partial_values(&[StoreKeyOffsetValue::new(key.clone(), 1, &[9, 9])])
+            .unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 9, 9, 3]);
+        assert_eq!(
+            adapter
+                .get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(2))])
+                .unwrap()
+                .unwrap(),
+            vec![vec![9, 9]]
+        );
+        assert!(adapter
+            .get(&StoreKey::new("missing").unwrap())
+            .unwrap()
+            .is_none());
+        assert_eq!(adapter.size_key(&key).unwrap(), Some(4));
+        assert_eq!(adapter.list().unwrap(), &[key.clone()]);
+        assert_eq!(
+            adapter
+                .list_dir(&StorePrefix::new("a/").unwrap())
+                .unwrap()
+                .keys(),
+            &[key.clone()]
+        );
+        // Failed requests are logged and still return their error
+        let err = adapter
+            .get_partial_values_key(&key, &[ByteRange::FromStart(3, Some(4))])
+            .unwrap_err();
+        adapter.erase(&key).unwrap();
+        assert!(store.get(&key).unwrap().is_none());
+
+        assert_eq!(
+            log_lines("[memory] "),
+            [
+                "[memory] set a/b: 4 bytes".to_string(),
+                "[memory] set_partial_values a/b@1: 2 bytes".to_string(),
+                "[memory] get a/b: 4 bytes".to_string(),
+                "[memory] get_partial_values_key a/b:[1..3]: 2 bytes".to_string(),
+                "[memory] get missing: missing".to_string(),
+                "[memory] size_key a/b: size 4".to_string(),
+                "[memory] list /: 1 keys".to_string(),
+                "[memory] list_dir a/: 1 keys, 0 prefixes".to_string(),
+                format!("[memory] get_partial_values_key a/b:[3..7]: failed: {err}"),
+                "[memory] erase a/b: ok".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,493 @@
+//! A storage adapter which reports storage metrics to a [`StorageMetrics`] sink.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    byte_range::ByteRange, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeyRange, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::{
+    AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+    MaybeAsyncBytes,
+};
+
+/// A storage operation recorded by a [`MeteredStorageAdapter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageOperation {
+    /// A `get` request.
+    Get,
+    /// A `get_partial_values_key` or `get_partial_values` request.
+    GetPartial,
+    /// A `size_key`, `size_prefix`, or `size` request.
+    Size,
+    /// A `list`, `list_prefix`, `list_prefix_paged`, or `list_dir` request.
+    List,
+    /// A `set` request.
+    Set,
+    /// A `set_partial_values` request.
+    SetPartial,
+    /// An `erase`, `erase_values`, or `erase_prefix` request.
+    Erase,
+    /// A `rename` request.
+    Rename,
+}
+
+/// A sink for storage metrics reported by a [`MeteredStorageAdapter`].
+///
+/// Callbacks are invoked from the thread (or task) performing the storage operation, potentially concurrently, so implementations should be cheap and thread-safe (e.g. atomic counters or a metrics library handle).
+/// All callbacks default to doing nothing.
+pub trait StorageMetrics: Send + Sync {
+    /// Record that `bytes` were read from the store.
+    fn record_bytes_read(&self, _bytes: u64) {}
+
+    /// Record that `bytes` were written to the store.
+    fn record_bytes_written(&self, _bytes: u64) {}
+
+    /// Record a completed request with its `latency`.
+    ///
+    /// `success` is false if the request returned an error.
+    fn record_request(&self, _operation: StorageOperation, _latency: Duration, _success: bool) {}
+}
+
+/// A simple in-memory [`StorageMetrics`] implementation.
+///
+/// Latencies are retained for every request, so this is intended for tests and diagnostics rather than long-running processes.
+#[derive(Debug, Default)]
+pub struct InMemoryStorageMetrics {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    errors: AtomicU64,
+    latencies: Mutex<HashMap<StorageOperation, Vec<Duration>>>,
+}
+
+impl InMemoryStorageMetrics {
+    /// Create a new in-memory storage metrics sink.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the total number of bytes read.
+    #[must_use]
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Return the total number of bytes written.
+    #[must_use]
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of requests that returned an error.
+    #[must_use]
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of `operation` requests.
+    #[must_use]
+    pub fn requests(&self, operation: StorageOperation) -> usize {
+        self.latencies(operation).len()
+    }
+
+    /// Return the latencies of `operation` requests in the order they completed.
+    #[must_use]
+    pub fn latencies(&self, operation: StorageOperation) -> Vec<Duration> {
+        self.latencies
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&operation)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl StorageMetrics for InMemoryStorageMetrics {
+    fn record_bytes_read(&self, bytes: u64) {
+        self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn record_bytes_written(&self, bytes: u64) {
+        self.bytes_written.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn record_request(&self, operation: StorageOperation, latency: Duration, success: bool) {
+        if !success {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.latencies
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .entry(operation)
+            .or_default()
+            .push(latency);
+    }
+}
+
+/// A storage adapter which reports the bytes read and written, request counts, and request latencies of the inner storage to a [`StorageMetrics`] sink.
+///
+/// Unlike a logging adapter, this produces structured numeric telemetry, which can be forwarded to a metrics system (e.g. Prometheus or statsd) by implementing [`StorageMetrics`].
+/// Metrics are recorded for both synchronous and asynchronous storage.
+pub struct MeteredStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    metrics: Arc<dyn StorageMetrics>,
+}
+
+impl<TStorage: ?Sized> MeteredStorageAdapter<TStorage> {
+    /// Create a new metered storage adapter reporting to `metrics`.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>, metrics: Arc<dyn StorageMetrics>) -> Self {
+        Self { storage, metrics }
+    }
+
+    /// Return the metrics sink.
+    #[must_use]
+    pub fn metrics(&self) -> &Arc<dyn StorageMetrics> {
+        &self.metrics
+    }
+
+    fn metered<T>(
+        &self,
+        operation: StorageOperation,
+        f: impl FnOnce() -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let start = Instant::now();
+        let result = f();
+        self.metrics
+            .record_request(operation, start.elapsed(), result.is_ok());
+        result
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_metered<T>(
+        &self,
+        operation: StorageOperation,
+        future: impl std::future::Future<Output = Result<T, StorageError>>,
+    ) -> Result<T, StorageError> {
+        let start = Instant::now();
+        let result = future.await;
+        self.metrics
+            .record_request(operation, start.elapsed(), result.is_ok());
+        result
+    }
+
+    fn record_read<'a>(&self, bytes: impl IntoIterator<Item = &'a Bytes>) {
+        let bytes_read = bytes.into_iter().map(|bytes| bytes.len() as u64).sum();
+        self.metrics.record_bytes_read(bytes_read);
+    }
+
+    fn record_written(&self, key_offset_values: &[StoreKeyOffsetValue]) {
+        let bytes_written = key_offset_values
+            .iter()
+            .map(|key_offset_value| key_offset_value.value().len() as u64)
+            .sum();
+        self.metrics.record_bytes_written(bytes_written);
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        let value = self.metered(StorageOperation::Get, || self.storage.get(key))?;
+        self.record_read(&value);
+        Ok(value)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let values = self.metered(StorageOperation::GetPartial, || {
+            self.storage.get_partial_values_key(key, byte_ranges)
+        })?;
+        self.record_read(values.iter().flatten());
+        Ok(values)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        let values = self.metered(StorageOperation::GetPartial, || {
+            self.storage.get_partial_values(key_ranges)
+        })?;
+        self.record_read(values.iter().flatten());
+        Ok(values)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.metered(StorageOperation::Size, || self.storage.size_key(key))
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.metered(StorageOperation::List, || self.storage.list())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.metered(StorageOperation::List, || self.storage.list_prefix(prefix))
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.metered(StorageOperation::List, || {
+            self.storage.list_prefix_paged(prefix, continuation_token)
+        })
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.metered(StorageOperation::List, || self.storage.list_dir(prefix))
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.metered(StorageOperation::Size, || self.storage.size_prefix(prefix))
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.metered(StorageOperation::Size, || self.storage.size())
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        let bytes_written = value.len() as u64;
+        self.metered(StorageOperation::Set, || self.storage.set(key, value))?;
+        self.metrics.record_bytes_written(bytes_written);
+        Ok(())
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.metered(StorageOperation::SetPartial, || {
+            self.storage.set_partial_values(key_offset_values)
+        })?;
+        self.record_written(key_offset_values);
+        Ok(())
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.metered(StorageOperation::Erase, || self.storage.erase(key))
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.metered(StorageOperation::Erase, || self.storage.erase_values(keys))
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.metered(StorageOperation::Erase, || {
+            self.storage.erase_prefix(prefix)
+        })
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.metered(StorageOperation::Rename, || self.storage.rename(from, to))
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeAsyncBytes, StorageError> {
+        let value = self
+            .async_metered(StorageOperation::Get, self.storage.get(key))
+            .await?;
+        self.record_read(&value);
+        Ok(value)
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+        let values = self
+            .async_metered(
+                StorageOperation::GetPartial,
+                self.storage.get_partial_values_key(key, byte_ranges),
+            )
+            .await?;
+        self.record_read(values.iter().flatten());
+        Ok(values)
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeAsyncBytes>, StorageError> {
+        let values = self
+            .async_metered(
+                StorageOperation::GetPartial,
+                self.storage.get_partial_values(key_ranges),
+            )
+            .await?;
+        self.record_read(values.iter().flatten());
+        Ok(values)
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.async_metered(StorageOperation::Size, self.storage.size_key(key))
+            .await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.async_metered(StorageOperation::List, self.storage.list())
+            .await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.async_metered(StorageOperation::List, self.storage.list_prefix(prefix))
+            .await
+    }
+
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.async_metered(
+            StorageOperation::List,
+            self.storage.list_prefix_paged(prefix, continuation_token),
+        )
+        .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.async_metered(StorageOperation::List, self.storage.list_dir(prefix))
+            .await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.async_metered(StorageOperation::Size, self.storage.size_prefix(prefix))
+            .await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.async_metered(StorageOperation::Size, self.storage.size())
+            .await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for MeteredStorageAdapter<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        let bytes_written = value.len() as u64;
+        self.async_metered(StorageOperation::Set, self.storage.set(key, value))
+            .await?;
+        self.metrics.record_bytes_written(bytes_written);
+        Ok(())
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.async_metered(
+            StorageOperation::SetPartial,
+            self.storage.set_partial_values(key_offset_values),
+        )
+        .await?;
+        self.record_written(key_offset_values);
+        Ok(())
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.async_metered(StorageOperation::Erase, self.storage.erase(key))
+            .await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.async_metered(StorageOperation::Erase, self.storage.erase_values(keys))
+            .await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.async_metered(StorageOperation::Erase, self.storage.erase_prefix(prefix))
+            .await
+    }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.async_metered(StorageOperation::Rename, self.storage.rename(from, to))
+            .await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn metered_storage_adapter() {
+        let store = Arc::new(MemoryStore::new());
+        let metrics = Arc::new(InMemoryStorageMetrics::new());
+        let adapter = MeteredStorageAdapter::new(store, metrics.clone());
+
+        let key = StoreKey::new("a/b").unwrap();
+        adapter.set(&key, vec![0, 1, 2, 3].into()).unwrap();
+        adapter
+            .set_partial_values(&[StoreKeyOffsetValue::new(key.clone(), 1, &[9, 9])])
+            .unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 9, 9, 3]);
+        adapter
+            .get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(2))])
+            .unwrap();
+        assert!(adapter
+            .get(&StoreKey::new("missing").unwrap())
+            .unwrap()
+            .is_none());
+        assert_eq!(adapter.list().unwrap(), &[key.clone()]);
+        adapter.erase(&key).unwrap();
+
+        assert_eq!(metrics.bytes_written(), 6);
+        assert_eq!(metrics.bytes_read(), 6);
+        assert_eq!(metrics.requests(StorageOperation::Get), 2);
+        assert_eq!(metrics.latencies(StorageOperation::Get).len(), 2);
+        assert_eq!(metrics.requests(StorageOperation::Set), 1);
+        assert_eq!(metrics.requests(StorageOperation::List), 1);
+        assert_eq!(metrics.requests(StorageOperation::Erase), 1);
+        assert_eq!(metrics.errors(), 0);
+    }
+}
@@ -0,0 +1,230 @@
+//! A storage adapter which normalises store keys.
+
+use std::sync::Arc;
+
+use crate::{
+    byte_range::ByteRange, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeyRange, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+type KeyMapping = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A storage adapter which normalises store keys and prefixes.
+///
+/// This can adapt to backends that are case-insensitive or otherwise normalise keys (e.g. by collapsing duplicate slashes).
+/// The `normalize` function is applied to every key and prefix passed to the inner storage (`get`, `set`, `erase`, `list_prefix`, etc.).
+/// The `denormalize` function is applied to every key and prefix returned by listing, so listed keys match the keys used by callers.
+///
+/// Normalised keys and prefixes must be valid, otherwise an operation fails with [`StorageError::InvalidStoreKey`] or [`StorageError::StorePrefixError`].
+/// The root prefix (an empty string) should map to itself.
+///
+/// ### Collisions
+/// A lossy normalisation maps distinct keys to the same key.
+/// For example, with lowercasing, `a/B` and `a/b` refer to the same value, so writing one overwrites the other and erasing one erases both.
+/// `denormalize` cannot recover the original key in this case, so a listed key may differ from the key it was written with.
+/// Normalisation should only be lossy for keys that the backend itself cannot distinguish.
+pub struct NormalizingStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    normalize: KeyMapping,
+    denormalize: KeyMapping,
+}
+
+impl<TStorage: ?Sized> NormalizingStorageAdapter<TStorage> {
+    /// Create a new normalizing storage adapter.
+    ///
+    /// `normalize` maps a caller key or prefix to the key or prefix of the inner storage, and `denormalize` is its inverse.
+    pub fn new(
+        storage: Arc<TStorage>,
+        normalize: impl Fn(&str) -> String + Send + Sync + 'static,
+        denormalize: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            storage,
+            normalize: Box::new(normalize),
+            denormalize: Box::new(denormalize),
+        }
+    }
+
+    fn normalize_key(&self, key: &StoreKey) -> Result<StoreKey, StorageError> {
+        Ok(StoreKey::new((self.normalize)(key.as_str()))?)
+    }
+
+    fn normalize_prefix(&self, prefix: &StorePrefix) -> Result<StorePrefix, StorageError> {
+        Ok(StorePrefix::new((self.normalize)(prefix.as_str()))?)
+    }
+
+    fn denormalize_keys(&self, keys: StoreKeys) -> Result<StoreKeys, StorageError> {
+        keys.iter()
+            .map(|key| Ok(StoreKey::new((self.denormalize)(key.as_str()))?))
+            .collect()
+    }
+
+    fn denormalize_prefix(&self, prefix: &StorePrefix) -> Result<StorePrefix, StorageError> {
+        Ok(StorePrefix::new((self.denormalize)(prefix.as_str()))?)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for NormalizingStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.storage.get(&self.normalize_key(key)?)
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.storage
+            .get_partial_values_key(&self.normalize_key(key)?, byte_ranges)
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        let key_ranges = key_ranges
+            .iter()
+            .map(|key_range| {
+                Ok(StoreKeyRange::new(
+                    self.normalize_key(&key_range.key)?,
+                    key_range.byte_range,
+                ))
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        self.storage.get_partial_values(&key_ranges)
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.storage.size_key(&self.normalize_key(key)?)
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for NormalizingStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.denormalize_keys(self.storage.list()?)
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.denormalize_keys(self.storage.list_prefix(&self.normalize_prefix(prefix)?)?)
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let (keys, continuation_token) = self
+            .storage
+            .list_prefix_paged(&self.normalize_prefix(prefix)?, continuation_token)?;
+        Ok((self.denormalize_keys(keys)?, continuation_token))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let keys_prefixes = self.storage.list_dir(&self.normalize_prefix(prefix)?)?;
+        let keys = self.denormalize_keys(keys_prefixes.keys().clone())?;
+        let prefixes = keys_prefixes
+            .prefixes()
+            .iter()
+            .map(|prefix| self.denormalize_prefix(prefix))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(StoreKeysPrefixes::new(keys, prefixes))
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(&self.normalize_prefix(prefix)?)
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.storage.size()
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for NormalizingStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        self.storage.set(&self.normalize_key(key)?, value)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        let key_offset_values = key_offset_values
+            .iter()
+            .map(|key_offset_value| {
+                Ok(StoreKeyOffsetValue::new(
+                    self.normalize_key(key_offset_value.key())?,
+                    key_offset_value.offset(),
+                    key_offset_value.value(),
+                ))
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        self.storage.set_partial_values(&key_offset_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.storage.erase(&self.normalize_key(key)?)
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        let keys = keys
+            .iter()
+            .map(|key| self.normalize_key(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.storage.erase_values(&keys)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.storage.erase_prefix(&self.normalize_prefix(prefix)?)
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage
+            .rename(&self.normalize_key(from)?, &self.normalize_key(to)?)
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn normalizing_storage_adapter() {
+        let store = Arc::new(MemoryStore::new());
+        let adapter =
+            NormalizingStorageAdapter::new(store.clone(), str::to_uppercase, str::to_lowercase);
+
+        let key = StoreKey::new("a/b").unwrap();
+        adapter.set(&key, vec![0, 1, 2].into()).unwrap();
+        assert_eq!(store.list().unwrap(), &[StoreKey::new("A/B").unwrap()]);
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 1, 2]);
+        assert_eq!(adapter.list().unwrap(), &[key.clone()]);
+
+        let prefix = StorePrefix::new("a/").unwrap();
+        assert_eq!(adapter.list_prefix(&prefix).unwrap(), &[key.clone()]);
+        let keys_prefixes = adapter.list_dir(&StorePrefix::root()).unwrap();
+        assert!(keys_prefixes.keys().is_empty());
+        assert_eq!(keys_prefixes.prefixes(), &[prefix.clone()]);
+
+        // A lossy normalisation: `a/B` collides with `a/b`
+        let key_collision = StoreKey::new("a/B").unwrap();
+        adapter.set(&key_collision, vec![3].into()).unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![3]);
+        assert_eq!(adapter.list().unwrap(), &[key.clone()]);
+
+        adapter.erase_prefix(&prefix).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+}
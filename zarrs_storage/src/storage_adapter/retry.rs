@@ -0,0 +1,459 @@
+//! A storage adapter which retries storage operations that fail with a transient error.
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    byte_range::ByteRange, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeyRange, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::{
+    AsyncBytes, AsyncListableStorageTraits, AsyncReadableStorageTraits, AsyncWritableStorageTraits,
+    MaybeAsyncBytes,
+};
+
+/// A classifier of retryable storage errors, see [`RetryStorageAdapter::with_is_retryable`].
+pub type RetryClassifier = Arc<dyn Fn(&StorageError) -> bool + Send + Sync>;
+
+/// The default classification of retryable storage errors.
+///
+/// The following errors are retryable:
+///  - [`StorageError::Timeout`],
+///  - [`StorageError::UnexpectedLength`] (e.g. a truncated response), and
+///  - [`StorageError::IOError`] with a [`std::io::ErrorKind`] of `TimedOut`, `Interrupted`, `WouldBlock`, `UnexpectedEof`, `BrokenPipe`, `ConnectionRefused`, `ConnectionReset`, or `ConnectionAborted`.
+///
+/// All other errors are permanent.
+/// In particular, [`StorageError::Other`] is not retryable since its meaning is store specific.
+#[must_use]
+pub fn default_is_retryable(error: &StorageError) -> bool {
+    use std::io::ErrorKind;
+    match error {
+        StorageError::Timeout(_) | StorageError::UnexpectedLength(..) => true,
+        StorageError::IOError(err) => matches!(
+            err.kind(),
+            ErrorKind::TimedOut
+                | ErrorKind::Interrupted
+                | ErrorKind::WouldBlock
+                | ErrorKind::UnexpectedEof
+                | ErrorKind::BrokenPipe
+                | ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+        ),
+        _ => false,
+    }
+}
+
+/// A storage adapter which retries storage operations that fail with a transient error.
+///
+/// An operation is retried up to [`max_retries`](RetryStorageAdapter::max_retries) times if its error is classified as retryable, with an exponential backoff between attempts starting at [`backoff`](RetryStorageAdapter::backoff).
+/// Errors are classified by [`default_is_retryable`] unless a store specific classifier is set with [`with_is_retryable`](RetryStorageAdapter::with_is_retryable).
+///
+/// Reads, lists, sets, and erases are retried, since repeating them is idempotent.
+/// A [`rename`](WritableStorageTraits::rename) is not retried.
+///
+/// Pair with an [`AsyncTimeoutStorageAdapter`](super::async_timeout::AsyncTimeoutStorageAdapter) to retry stalled asynchronous reads.
+pub struct RetryStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    max_retries: usize,
+    backoff: Duration,
+    is_retryable: RetryClassifier,
+}
+
+impl<TStorage: ?Sized> RetryStorageAdapter<TStorage> {
+    /// Create a new retry storage adapter with `3` retries, a `100ms` initial backoff, and the [`default_is_retryable`] classification.
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self {
+            storage,
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+            is_retryable: Arc::new(default_is_retryable),
+        }
+    }
+
+    /// Set the maximum number of retries of an operation.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the backoff before the first retry, which doubles for each subsequent retry.
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the classifier of retryable storage errors.
+    ///
+    /// The classifier returns true if an error is transient and the operation should be retried.
+    /// It can defer to [`default_is_retryable`] for errors that do not need store specific handling.
+    #[must_use]
+    pub fn with_is_retryable(
+        mut self,
+        is_retryable: impl Fn(&StorageError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.is_retryable = Arc::new(is_retryable);
+        self
+    }
+
+    /// Return the maximum number of retries of an operation.
+    #[must_use]
+    pub const fn max_retries(&self) -> usize {
+        self.max_retries
+    }
+
+    /// Return the backoff before the first retry.
+    #[must_use]
+    pub const fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Return the classifier of retryable storage errors.
+    #[must_use]
+    pub fn is_retryable(&self) -> &RetryClassifier {
+        &self.is_retryable
+    }
+
+    /// Return the backoff before retry `retry` (zero-based), or [`None`] if `error` should not be retried.
+    fn retry_backoff(&self, retry: usize, error: &StorageError) -> Option<Duration> {
+        if retry < self.max_retries && (self.is_retryable)(error) {
+            Some(
+                self.backoff
+                    .saturating_mul(2u32.saturating_pow(u32::try_from(retry).unwrap_or(u32::MAX))),
+            )
+        } else {
+            None
+        }
+    }
+
+    fn retry<T>(&self, f: impl Fn() -> Result<T, StorageError>) -> Result<T, StorageError> {
+        let mut retry = 0;
+        loop {
+            match f() {
+                Err(err) => {
+                    let Some(backoff) = self.retry_backoff(retry, &err) else {
+                        return Err(err);
+                    };
+                    std::thread::sleep(backoff);
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn async_retry<T, F: std::future::Future<Output = Result<T, StorageError>>>(
+        &self,
+        f: impl Fn() -> F,
+    ) -> Result<T, StorageError> {
+        let mut retry = 0;
+        loop {
+            match f().await {
+                Err(err) => {
+                    let Some(backoff) = self.retry_backoff(retry, &err) else {
+                        return Err(err);
+                    };
+                    futures_timer::Delay::new(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.retry(|| self.storage.get(key))
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        self.retry(|| self.storage.get_partial_values_key(key, byte_ranges))
+    }
+
+    fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeBytes>, StorageError> {
+        self.retry(|| self.storage.get_partial_values(key_ranges))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.retry(|| self.storage.size_key(key))
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.retry(|| self.storage.list())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.retry(|| self.storage.list_prefix(prefix))
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.retry(|| self.storage.list_prefix_paged(prefix, continuation_token))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.retry(|| self.storage.list_dir(prefix))
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.retry(|| self.storage.size_prefix(prefix))
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        self.retry(|| self.storage.size())
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        self.retry(|| self.storage.set(key, value.clone()))
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.retry(|| self.storage.set_partial_values(key_offset_values))
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.retry(|| self.storage.erase(key))
+    }
+
+    fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.retry(|| self.storage.erase_values(keys))
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.retry(|| self.storage.erase_prefix(prefix))
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to)
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeAsyncBytes, StorageError> {
+        self.async_retry(|| self.storage.get(key)).await
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+        self.async_retry(|| self.storage.get_partial_values_key(key, byte_ranges))
+            .await
+    }
+
+    async fn get_partial_values(
+        &self,
+        key_ranges: &[StoreKeyRange],
+    ) -> Result<Vec<MaybeAsyncBytes>, StorageError> {
+        self.async_retry(|| self.storage.get_partial_values(key_ranges))
+            .await
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        self.async_retry(|| self.storage.size_key(key)).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.async_retry(|| self.storage.list()).await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.async_retry(|| self.storage.list_prefix(prefix)).await
+    }
+
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.async_retry(|| self.storage.list_prefix_paged(prefix, continuation_token))
+            .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.async_retry(|| self.storage.list_dir(prefix)).await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.async_retry(|| self.storage.size_prefix(prefix)).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.async_retry(|| self.storage.size()).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageTraits
+    for RetryStorageAdapter<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        self.async_retry(|| self.storage.set(key, value.clone()))
+            .await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        self.async_retry(|| self.storage.set_partial_values(key_offset_values))
+            .await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.async_retry(|| self.storage.erase(key)).await
+    }
+
+    async fn erase_values(&self, keys: &[StoreKey]) -> Result<(), StorageError> {
+        self.async_retry(|| self.storage.erase_values(keys)).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.async_retry(|| self.storage.erase_prefix(prefix)).await
+    }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to).await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A store which fails `get` with `error` for the first `failures` attempts.
+    struct FlakyStorage {
+        failures: usize,
+        error: fn() -> StorageError,
+        attempts: AtomicUsize,
+    }
+
+    impl FlakyStorage {
+        fn new(failures: usize, error: fn() -> StorageError) -> Self {
+            Self {
+                failures,
+                error,
+                attempts: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl ReadableStorageTraits for FlakyStorage {
+        fn get_partial_values_key(
+            &self,
+            _key: &StoreKey,
+            byte_ranges: &[ByteRange],
+        ) -> Result<Option<Vec<Bytes>>, StorageError> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err((self.error)())
+            } else {
+                Ok(Some(vec![Bytes::new(); byte_ranges.len()]))
+            }
+        }
+
+        fn size_key(&self, _key: &StoreKey) -> Result<Option<u64>, StorageError> {
+            Ok(Some(0))
+        }
+    }
+
+    #[test]
+    fn retry_storage_adapter() {
+        let key = StoreKey::new("a").unwrap();
+        let timeout = || StorageError::Timeout(Duration::from_secs(1));
+        let gateway = || StorageError::Other("502 bad gateway".to_string());
+
+        // Transient errors are retried
+        let storage = Arc::new(FlakyStorage::new(2, timeout));
+        let adapter = RetryStorageAdapter::new(storage.clone()).with_backoff(Duration::ZERO);
+        assert!(adapter.get(&key).unwrap().is_some());
+        assert_eq!(storage.attempts.load(Ordering::SeqCst), 3);
+
+        // Retries are limited
+        let storage = Arc::new(FlakyStorage::new(5, timeout));
+        let adapter = RetryStorageAdapter::new(storage.clone())
+            .with_backoff(Duration::ZERO)
+            .with_max_retries(1);
+        assert!(matches!(adapter.get(&key), Err(StorageError::Timeout(_))));
+        assert_eq!(storage.attempts.load(Ordering::SeqCst), 2);
+
+        // Permanent errors are not retried by default
+        let storage = Arc::new(FlakyStorage::new(1, gateway));
+        let adapter = RetryStorageAdapter::new(storage.clone()).with_backoff(Duration::ZERO);
+        assert!(adapter.get(&key).is_err());
+        assert_eq!(storage.attempts.load(Ordering::SeqCst), 1);
+
+        // A custom classifier
+        let storage = Arc::new(FlakyStorage::new(1, gateway));
+        let adapter = RetryStorageAdapter::new(storage.clone())
+            .with_backoff(Duration::ZERO)
+            .with_is_retryable(|err| {
+                matches!(err, StorageError::Other(msg) if msg.starts_with("502"))
+                    || default_is_retryable(err)
+            });
+        assert!(adapter.get(&key).unwrap().is_some());
+        assert_eq!(storage.attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn retry_default_is_retryable() {
+        assert!(default_is_retryable(&StorageError::IOError(
+            std::io::ErrorKind::ConnectionReset.into()
+        )));
+        assert!(!default_is_retryable(&StorageError::IOError(
+            std::io::ErrorKind::NotFound.into()
+        )));
+        assert!(!default_is_retryable(&StorageError::ReadOnly));
+    }
+}
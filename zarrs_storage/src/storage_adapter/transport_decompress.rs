@@ -0,0 +1,419 @@
+//! A storage adapter which strips a transport compression (`gzip`) layer from stored values.
+
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+};
+
+use flate2::{bufread::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{
+    byte_range::{extract_byte_ranges, ByteRange},
+    store_set_partial_values, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+};
+
+#[cfg(feature = "async")]
+use crate::{
+    async_store_set_partial_values, AsyncBytes, AsyncListableStorageTraits,
+    AsyncReadableStorageTraits, AsyncWritableStorageTraits, MaybeAsyncBytes,
+};
+
+/// The `gzip` magic bytes.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The detection of transport compressed values by a [`TransportDecompressStorageAdapter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransportCompressionDetection {
+    /// Every value is transport compressed, and reading a value that is not `gzip` compressed fails.
+    ///
+    /// This is unambiguous, so values compressed by a codec (e.g. `gzip`) are never decompressed by the adapter.
+    #[default]
+    Always,
+    /// A value is transport compressed if it starts with the `gzip` magic bytes, otherwise it is returned as is.
+    ///
+    /// This suits stores with a mix of wrapped and unwrapped values.
+    /// It must not be used if an unwrapped value can begin with the `gzip` magic bytes (e.g. a chunk encoded with the `gzip` codec and no transport compression), since that value would be decompressed.
+    MagicBytes,
+}
+
+/// A storage adapter which strips a transport compression (`gzip`) layer from stored values.
+///
+/// Some stores wrap each value in an extra `gzip` layer, distinct from any compression by the codecs of an array.
+/// This adapter decompresses values on [`get`](ReadableStorageTraits::get) and compresses them on [`set`](WritableStorageTraits::set), so the codec pipeline sees the unwrapped values.
+/// Exactly one layer is removed, so a chunk encoded with the `gzip` codec is still decoded by the codec.
+/// The detection of transport compressed values is explicit, see [`TransportCompressionDetection`].
+///
+/// Both the synchronous and asynchronous storage traits are implemented.
+///
+/// A partial read or size query of a value must retrieve and decompress the entire value.
+/// The sizes reported by [`size_prefix`](ListableStorageTraits::size_prefix) are the sizes of the transport compressed values.
+pub struct TransportDecompressStorageAdapter<TStorage: ?Sized> {
+    storage: Arc<TStorage>,
+    detection: TransportCompressionDetection,
+    compression_level: u32,
+}
+
+impl<TStorage: ?Sized> TransportDecompressStorageAdapter<TStorage> {
+    /// Create a new transport decompress storage adapter with [`TransportCompressionDetection::Always`] and the default compression level (`6`).
+    #[must_use]
+    pub fn new(storage: Arc<TStorage>) -> Self {
+        Self {
+            storage,
+            detection: TransportCompressionDetection::default(),
+            compression_level: Compression::default().level(),
+        }
+    }
+
+    /// Set the detection of transport compressed values.
+    #[must_use]
+    pub fn with_detection(mut self, detection: TransportCompressionDetection) -> Self {
+        self.detection = detection;
+        self
+    }
+
+    /// Set the `gzip` compression level (`0`-`9`) of values written through the adapter.
+    #[must_use]
+    pub fn with_compression_level(mut self, compression_level: u32) -> Self {
+        self.compression_level = compression_level.min(9);
+        self
+    }
+
+    /// Return the detection of transport compressed values.
+    #[must_use]
+    pub fn detection(&self) -> TransportCompressionDetection {
+        self.detection
+    }
+
+    /// Return the `gzip` compression level of values written through the adapter.
+    #[must_use]
+    pub fn compression_level(&self) -> u32 {
+        self.compression_level
+    }
+
+    fn decompress(&self, value: Bytes) -> Result<Bytes, StorageError> {
+        if self.detection == TransportCompressionDetection::MagicBytes
+            && !value.starts_with(&GZIP_MAGIC)
+        {
+            return Ok(value);
+        }
+        let mut decompressed = Vec::new();
+        GzDecoder::new(value.as_ref()).read_to_end(&mut decompressed)?;
+        Ok(decompressed.into())
+    }
+
+    fn compress(&self, value: &[u8]) -> Result<Bytes, StorageError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(self.compression_level));
+        encoder.write_all(value)?;
+        Ok(encoder.finish()?.into())
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits> ReadableStorageTraits
+    for TransportDecompressStorageAdapter<TStorage>
+{
+    fn get(&self, key: &StoreKey) -> Result<MaybeBytes, StorageError> {
+        self.storage
+            .get(key)?
+            .map(|value| self.decompress(value))
+            .transpose()
+    }
+
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let Some(value) = self.get(key)? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            extract_byte_ranges(&value, byte_ranges)?
+                .into_iter()
+                .map(Bytes::from)
+                .collect(),
+        ))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        Ok(self.get(key)?.map(|value| value.len() as u64))
+    }
+}
+
+impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits
+    for TransportDecompressStorageAdapter<TStorage>
+{
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list()
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix)
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.storage.list_prefix_paged(prefix, continuation_token)
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix)
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix)
+    }
+}
+
+impl<TStorage: ?Sized + ReadableStorageTraits + WritableStorageTraits> WritableStorageTraits
+    for TransportDecompressStorageAdapter<TStorage>
+{
+    fn set(&self, key: &StoreKey, value: Bytes) -> Result<(), StorageError> {
+        self.storage.set(key, self.compress(&value)?)
+    }
+
+    fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        // Each updated value is decompressed and recompressed
+        store_set_partial_values(self, key_offset_values)
+    }
+
+    fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.storage.erase(key)
+    }
+
+    fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.storage.erase_prefix(prefix)
+    }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to)
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits> AsyncReadableStorageTraits
+    for TransportDecompressStorageAdapter<TStorage>
+{
+    async fn get(&self, key: &StoreKey) -> Result<MaybeAsyncBytes, StorageError> {
+        self.storage
+            .get(key)
+            .await?
+            .map(|value| self.decompress(value))
+            .transpose()
+    }
+
+    async fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+        let Some(value) = self.get(key).await? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            extract_byte_ranges(&value, byte_ranges)?
+                .into_iter()
+                .map(AsyncBytes::from)
+                .collect(),
+        ))
+    }
+
+    async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        Ok(self.get(key).await?.map(|value| value.len() as u64))
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncListableStorageTraits> AsyncListableStorageTraits
+    for TransportDecompressStorageAdapter<TStorage>
+{
+    async fn list(&self) -> Result<StoreKeys, StorageError> {
+        self.storage.list().await
+    }
+
+    async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        self.storage.list_prefix(prefix).await
+    }
+
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        self.storage
+            .list_prefix_paged(prefix, continuation_token)
+            .await
+    }
+
+    async fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        self.storage.list_dir(prefix).await
+    }
+
+    async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        self.storage.size_prefix(prefix).await
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<TStorage: ?Sized + AsyncReadableStorageTraits + AsyncWritableStorageTraits>
+    AsyncWritableStorageTraits for TransportDecompressStorageAdapter<TStorage>
+{
+    async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        self.storage.set(key, self.compress(&value)?).await
+    }
+
+    async fn set_partial_values(
+        &self,
+        key_offset_values: &[StoreKeyOffsetValue],
+    ) -> Result<(), StorageError> {
+        // Each updated value is decompressed and recompressed
+        async_store_set_partial_values(self, key_offset_values).await
+    }
+
+    async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+        self.storage.erase(key).await
+    }
+
+    async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+        self.storage.erase_prefix(prefix).await
+    }
+
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        self.storage.rename(from, to).await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.storage.supports_rename()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn transport_decompress_storage_adapter() {
+        let store = Arc::new(MemoryStore::new());
+        let adapter = TransportDecompressStorageAdapter::new(store.clone());
+
+        let key = StoreKey::new("array/c/0").unwrap();
+        adapter.set(&key, vec![0, 1, 2, 3].into()).unwrap();
+        let stored = store.get(&key).unwrap().unwrap();
+        assert!(stored.starts_with(&GZIP_MAGIC));
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(adapter.size_key(&key).unwrap(), Some(4));
+        assert_eq!(
+            adapter
+                .get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(2))])
+                .unwrap()
+                .unwrap(),
+            vec![Bytes::from(vec![1, 2])]
+        );
+        adapter
+            .set_partial_values(&[StoreKeyOffsetValue::new(key.clone(), 1, &[9])])
+            .unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), vec![0, 9, 2, 3]);
+
+        // Only one layer is removed from a double wrapped value
+        adapter.set(&key, stored.clone()).unwrap();
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), stored);
+
+        // An unwrapped value is an error unless detected by magic bytes
+        let key_unwrapped = StoreKey::new("array/zarr.json").unwrap();
+        store.set(&key_unwrapped, vec![4, 5].into()).unwrap();
+        assert!(adapter.get(&key_unwrapped).is_err());
+        let adapter = TransportDecompressStorageAdapter::new(store.clone())
+            .with_detection(TransportCompressionDetection::MagicBytes);
+        assert_eq!(adapter.get(&key_unwrapped).unwrap().unwrap(), vec![4, 5]);
+        assert_eq!(adapter.get(&key).unwrap().unwrap(), stored);
+        assert!(adapter
+            .get(&StoreKey::new("missing").unwrap())
+            .unwrap()
+            .is_none());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn transport_decompress_storage_adapter_async() {
+        // An async store backed by a memory store
+        struct AsyncMemoryStore(MemoryStore);
+
+        #[async_trait::async_trait]
+        impl AsyncReadableStorageTraits for AsyncMemoryStore {
+            async fn get_partial_values_key(
+                &self,
+                key: &StoreKey,
+                byte_ranges: &[ByteRange],
+            ) -> Result<Option<Vec<AsyncBytes>>, StorageError> {
+                self.0.get_partial_values_key(key, byte_ranges)
+            }
+
+            async fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+                self.0.size_key(key)
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl AsyncWritableStorageTraits for AsyncMemoryStore {
+            async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+                self.0.set(key, value)
+            }
+
+            async fn set_partial_values(
+                &self,
+                key_offset_values: &[StoreKeyOffsetValue],
+            ) -> Result<(), StorageError> {
+                self.0.set_partial_values(key_offset_values)
+            }
+
+            async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
+                self.0.erase(key)
+            }
+
+            async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError> {
+                self.0.erase_prefix(prefix)
+            }
+        }
+
+        let store = Arc::new(AsyncMemoryStore(MemoryStore::new()));
+        let adapter = TransportDecompressStorageAdapter::new(store.clone());
+
+        let key = StoreKey::new("array/c/0").unwrap();
+        futures::executor::block_on(async {
+            adapter.set(&key, vec![0, 1, 2, 3].into()).await.unwrap();
+            let stored = store.0.get(&key).unwrap().unwrap();
+            assert!(stored.starts_with(&GZIP_MAGIC));
+            assert_eq!(adapter.get(&key).await.unwrap().unwrap(), vec![0, 1, 2, 3]);
+            assert_eq!(adapter.size_key(&key).await.unwrap(), Some(4));
+            assert_eq!(
+                adapter
+                    .get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(2))])
+                    .await
+                    .unwrap()
+                    .unwrap(),
+                vec![AsyncBytes::from(vec![1, 2])]
+            );
+            adapter
+                .set_partial_values(&[StoreKeyOffsetValue::new(key.clone(), 1, &[9])])
+                .await
+                .unwrap();
+            assert_eq!(adapter.get(&key).await.unwrap().unwrap(), vec![0, 9, 2, 3]);
+        });
+    }
+}
@@ -4,13 +4,14 @@ use futures::{StreamExt, TryStreamExt};
 use itertools::Itertools;
 
 use super::{
-    byte_range::ByteRange, AsyncBytes, MaybeAsyncBytes, StorageError, StoreKey,
-    StoreKeyOffsetValue, StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+    byte_range::ByteRange, AsyncBytes, ListContinuationToken, MaybeAsyncBytes, StorageError,
+    StoreKey, StoreKeyOffsetValue, StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix,
+    StorePrefixes,
 };
 
 /// Async readable storage traits.
-#[cfg_attr(feature = "async", async_trait::async_trait(?Send))]
-pub trait AsyncReadableStorageTraits: Sync {
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+pub trait AsyncReadableStorageTraits: Send + Sync {
     /// Retrieve the value (bytes) associated with a given [`StoreKey`].
     ///
     /// Returns [`None`] if the key is not found.
@@ -118,8 +119,8 @@ pub trait AsyncReadableStorageTraits: Sync {
 }
 
 /// Async listable storage traits.
-#[cfg_attr(feature = "async", async_trait::async_trait(?Send))]
-pub trait AsyncListableStorageTraits: Sync {
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+pub trait AsyncListableStorageTraits: Send + Sync {
     /// Retrieve all [`StoreKeys`] in the store.
     ///
     /// # Errors
@@ -134,6 +135,30 @@ pub trait AsyncListableStorageTraits: Sync {
     /// Returns a [`StorageError`] if the prefix is not a directory or there is an underlying error with the store.
     async fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError>;
 
+    /// Retrieve a page of [`StoreKeys`] with a given [`StorePrefix`].
+    ///
+    /// Pass [`None`] as the `continuation_token` to retrieve the first page, and then the returned token to retrieve the next page.
+    /// The listing is complete when the returned token is [`None`].
+    /// This enables keys to be processed incrementally rather than buffering the entire listing.
+    ///
+    /// The default implementation retrieves all remaining keys in a single page with [`list_prefix`](Self::list_prefix).
+    /// Stores with native pagination (e.g. object stores) should override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StorageError`] if the prefix is not a directory, the continuation token is invalid, or there is an underlying error with the store.
+    async fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let keys = self.list_prefix(prefix).await?;
+        Ok((
+            crate::storage_sync::keys_after_continuation_token(keys, continuation_token),
+            None,
+        ))
+    }
+
     /// Retrieve all [`StoreKeys`] and [`StorePrefix`] which are direct children of [`StorePrefix`].
     ///
     /// # Errors
@@ -223,8 +248,8 @@ pub async fn async_store_set_partial_values<T: AsyncReadableWritableStorageTrait
 }
 
 /// Async writable storage traits.
-#[cfg_attr(feature = "async", async_trait::async_trait(?Send))]
-pub trait AsyncWritableStorageTraits: Sync {
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+pub trait AsyncWritableStorageTraits: Send + Sync {
     /// Store bytes at a [`StoreKey`].
     ///
     /// # Errors
@@ -266,10 +291,30 @@ pub trait AsyncWritableStorageTraits: Sync {
     /// # Errors
     /// Returns a [`StorageError`] if there is an underlying storage error.
     async fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError>;
+
+    /// Rename the value at `from` to `to`, replacing any existing value at `to`.
+    ///
+    /// Stores that support renaming should make the replacement atomic, so readers of `to` observe either the old or the new value and never a partially written value.
+    ///
+    /// # Errors
+    /// Returns [`StorageError::Unsupported`] if the store does not support renaming (the default), or a [`StorageError`] if `from` does not exist or there is an underlying storage error.
+    async fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        let _ = (from, to);
+        Err(StorageError::Unsupported(
+            "the store does not support renaming keys".to_string(),
+        ))
+    }
+
+    /// Returns true if the store supports [`rename`](AsyncWritableStorageTraits::rename).
+    ///
+    /// The default implementation returns false.
+    fn supports_rename(&self) -> bool {
+        false
+    }
 }
 
 /// A supertrait of [`AsyncReadableStorageTraits`] and [`AsyncWritableStorageTraits`].
-#[cfg_attr(feature = "async", async_trait::async_trait(?Send))]
+#[cfg_attr(feature = "async", async_trait::async_trait)]
 pub trait AsyncReadableWritableStorageTraits:
     AsyncReadableStorageTraits + AsyncWritableStorageTraits
 {
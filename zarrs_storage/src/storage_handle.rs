@@ -61,6 +61,14 @@ impl<TStorage: ?Sized + ListableStorageTraits> ListableStorageTraits for Storage
         self.0.list_prefix(prefix)
     }
 
+    fn list_prefix_paged(
+        &self,
+        prefix: &super::StorePrefix,
+        continuation_token: Option<&super::ListContinuationToken>,
+    ) -> Result<(super::StoreKeys, Option<super::ListContinuationToken>), super::StorageError> {
+        self.0.list_prefix_paged(prefix, continuation_token)
+    }
+
     fn list_dir(
         &self,
         prefix: &super::StorePrefix,
@@ -100,10 +108,22 @@ impl<TStorage: ?Sized + WritableStorageTraits> WritableStorageTraits for Storage
     fn erase_prefix(&self, prefix: &super::StorePrefix) -> Result<(), super::StorageError> {
         self.0.erase_prefix(prefix)
     }
+
+    fn rename(
+        &self,
+        from: &super::StoreKey,
+        to: &super::StoreKey,
+    ) -> Result<(), super::StorageError> {
+        self.0.rename(from, to)
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.0.supports_rename()
+    }
 }
 
 #[cfg(feature = "async")]
-#[async_trait::async_trait(?Send)]
+#[async_trait::async_trait]
 impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageTraits
     for StorageHandle<TStorage>
 {
@@ -132,7 +152,7 @@ impl<TStorage: ?Sized + Send + AsyncReadableStorageTraits> AsyncReadableStorageT
 }
 
 #[cfg(feature = "async")]
-#[async_trait::async_trait(?Send)]
+#[async_trait::async_trait]
 impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageTraits
     for StorageHandle<TStorage>
 {
@@ -147,6 +167,14 @@ impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageT
         self.0.list_prefix(prefix).await
     }
 
+    async fn list_prefix_paged(
+        &self,
+        prefix: &super::StorePrefix,
+        continuation_token: Option<&super::ListContinuationToken>,
+    ) -> Result<(super::StoreKeys, Option<super::ListContinuationToken>), super::StorageError> {
+        self.0.list_prefix_paged(prefix, continuation_token).await
+    }
+
     async fn list_dir(
         &self,
         prefix: &super::StorePrefix,
@@ -164,7 +192,7 @@ impl<TStorage: ?Sized + Send + AsyncListableStorageTraits> AsyncListableStorageT
 }
 
 #[cfg(feature = "async")]
-#[async_trait::async_trait(?Send)]
+#[async_trait::async_trait]
 impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageTraits
     for StorageHandle<TStorage>
 {
@@ -190,4 +218,16 @@ impl<TStorage: ?Sized + Send + AsyncWritableStorageTraits> AsyncWritableStorageT
     async fn erase_prefix(&self, prefix: &super::StorePrefix) -> Result<(), super::StorageError> {
         self.0.erase_prefix(prefix).await
     }
+
+    async fn rename(
+        &self,
+        from: &super::StoreKey,
+        to: &super::StoreKey,
+    ) -> Result<(), super::StorageError> {
+        self.0.rename(from, to).await
+    }
+
+    fn supports_rename(&self) -> bool {
+        self.0.supports_rename()
+    }
 }
@@ -3,8 +3,8 @@ use std::sync::Arc;
 use itertools::Itertools;
 
 use super::{
-    byte_range::ByteRange, Bytes, MaybeBytes, StorageError, StoreKey, StoreKeyOffsetValue,
-    StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+    byte_range::ByteRange, Bytes, ListContinuationToken, MaybeBytes, StorageError, StoreKey,
+    StoreKeyOffsetValue, StoreKeyRange, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
 };
 
 /// Readable storage traits.
@@ -120,6 +120,29 @@ pub trait ListableStorageTraits: Send + Sync {
     /// Returns a [`StorageError`] if the prefix is not a directory or there is an underlying error with the store.
     fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError>;
 
+    /// Retrieve a page of [`StoreKeys`] with a given [`StorePrefix`].
+    ///
+    /// Pass [`None`] as the `continuation_token` to retrieve the first page, and then the returned token to retrieve the next page.
+    /// The listing is complete when the returned token is [`None`].
+    /// This enables keys to be processed incrementally rather than buffering the entire listing.
+    ///
+    /// The default implementation retrieves all remaining keys in a single page with [`list_prefix`](Self::list_prefix).
+    /// Stores with native pagination (e.g. object stores) should override it.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if the prefix is not a directory, the continuation token is invalid, or there is an underlying error with the store.
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let keys = self.list_prefix(prefix)?;
+        Ok((
+            keys_after_continuation_token(keys, continuation_token),
+            None,
+        ))
+    }
+
     /// Retrieve all [`StoreKeys`] and [`StorePrefix`] which are direct children of [`StorePrefix`].
     ///
     /// # Errors
@@ -142,6 +165,42 @@ pub trait ListableStorageTraits: Send + Sync {
     }
 }
 
+/// Return the sorted `keys` after the key of a `continuation_token` from the default implementation of `list_prefix_paged`.
+pub(crate) fn keys_after_continuation_token(
+    mut keys: StoreKeys,
+    continuation_token: Option<&ListContinuationToken>,
+) -> StoreKeys {
+    keys.sort();
+    if let Some(continuation_token) = continuation_token {
+        keys.retain(|key| key.as_str() > continuation_token.as_str());
+    }
+    keys
+}
+
+/// Return a page of at most `page_size` of `keys` after the key of a `continuation_token`, and the continuation token of the next page.
+///
+/// This can implement [`ListableStorageTraits::list_prefix_paged`] for stores which list keys in memory.
+/// The continuation token of a page is its last key.
+/// A `page_size` of zero is treated as one.
+#[must_use]
+pub fn paginate_keys(
+    keys: StoreKeys,
+    continuation_token: Option<&ListContinuationToken>,
+    page_size: usize,
+) -> (StoreKeys, Option<ListContinuationToken>) {
+    let mut keys = keys_after_continuation_token(keys, continuation_token);
+    let page_size = page_size.max(1);
+    if keys.len() > page_size {
+        keys.truncate(page_size);
+        let continuation_token = keys
+            .last()
+            .map(|key| ListContinuationToken::new(key.as_str()));
+        (keys, continuation_token)
+    } else {
+        (keys, None)
+    }
+}
+
 /// Set partial values for a store.
 ///
 /// This method reads entire values, updates them, and replaces them.
@@ -240,6 +299,26 @@ pub trait WritableStorageTraits: Send + Sync {
     /// # Errors
     /// Returns a [`StorageError`] is the prefix is not in the store, or the erase otherwise fails.
     fn erase_prefix(&self, prefix: &StorePrefix) -> Result<(), StorageError>;
+
+    /// Rename the value at `from` to `to`, replacing any existing value at `to`.
+    ///
+    /// Stores that support renaming should make the replacement atomic, so readers of `to` observe either the old or the new value and never a partially written value.
+    ///
+    /// # Errors
+    /// Returns [`StorageError::Unsupported`] if the store does not support renaming (the default), or a [`StorageError`] if `from` does not exist or there is an underlying storage error.
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        let _ = (from, to);
+        Err(StorageError::Unsupported(
+            "the store does not support renaming keys".to_string(),
+        ))
+    }
+
+    /// Returns true if the store supports [`rename`](WritableStorageTraits::rename).
+    ///
+    /// The default implementation returns false.
+    fn supports_rename(&self) -> bool {
+        false
+    }
 }
 
 /// A supertrait of [`ReadableStorageTraits`] and [`WritableStorageTraits`].
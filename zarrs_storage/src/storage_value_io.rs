@@ -1,11 +1,17 @@
 use std::{
-    io::{Read, Seek, SeekFrom},
+    io::{Read, Seek, SeekFrom, Write},
     sync::Arc,
 };
 
-use super::{byte_range::ByteRange, ReadableStorageTraits, StoreKey};
+use super::{
+    byte_range::ByteRange, Bytes, ReadableStorageTraits, StorageError, StoreKey,
+    StoreKeyOffsetValue, WritableStorageTraits,
+};
 
-/// Provides a [`Read`] interface to a storage value.
+/// Provides a [`Read`] and [`Seek`] interface to a storage value.
+///
+/// Each read is a partial read (e.g. a range request) of the value at the current position, so seeking does not retrieve any data.
+/// Reads are unbuffered, so wrap it in a [`BufReader`](std::io::BufReader) for many small reads.
 #[derive(Clone)]
 pub struct StorageValueIO<TStorage: ?Sized> {
     storage: Arc<TStorage>,
@@ -17,7 +23,6 @@ pub struct StorageValueIO<TStorage: ?Sized> {
 impl<TStorage: ?Sized + ReadableStorageTraits> StorageValueIO<TStorage> {
     /// Create a new `StorageValueIO` for the `key` in `storage`.
     pub fn new(storage: Arc<TStorage>, key: StoreKey, size: u64) -> Self {
-        debug_assert!(size > 0);
         Self {
             storage,
             key,
@@ -65,14 +70,17 @@ impl<TStorage: ?Sized> Seek for StorageValueIO<TStorage> {
 
 impl<TStorage: ?Sized + ReadableStorageTraits> Read for StorageValueIO<TStorage> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let len = buf.len() as u64;
+        let len = (buf.len() as u64).min(self.size.saturating_sub(self.pos));
+        if len == 0 {
+            return Ok(0);
+        }
         let data = self
             .storage
             .get_partial_values_key(&self.key, &[ByteRange::FromStart(self.pos, Some(len))])
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))?
             .map(|mut v| v.remove(0));
         if let Some(data) = data {
-            buf.copy_from_slice(&data);
+            buf[..data.len()].copy_from_slice(&data);
             self.pos += data.len() as u64;
             Ok(data.len())
         } else {
@@ -86,3 +94,174 @@ impl<TStorage: ?Sized + ReadableStorageTraits> Read for StorageValueIO<TStorage>
 }
 
 // TODO: AsyncRead
+
+/// The default buffer size of a [`StorageValueWriter`] (8 MiB).
+const DEFAULT_WRITE_BUFFER_SIZE: usize = 8 * 1024 * 1024;
+
+/// Provides a [`Write`] interface to a storage value.
+///
+/// Writes are buffered, and each full buffer is written to the store as it fills.
+/// The first buffer replaces any existing value with [`set`](WritableStorageTraits::set), and subsequent buffers are appended with [`set_partial_values`](WritableStorageTraits::set_partial_values).
+/// A value that fits within the buffer is written with a single [`set`](WritableStorageTraits::set).
+///
+/// Call [`finish`](StorageValueWriter::finish) to write any buffered data and observe errors.
+/// Buffered data is also written on drop, but any error is ignored.
+pub struct StorageValueWriter<TStorage: ?Sized + WritableStorageTraits> {
+    storage: Arc<TStorage>,
+    key: StoreKey,
+    buffer: Vec<u8>,
+    buffer_size: usize,
+    offset: u64,
+    finished: bool,
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> StorageValueWriter<TStorage> {
+    /// Create a new `StorageValueWriter` for the `key` in `storage` with the default buffer size (8 MiB).
+    pub fn new(storage: Arc<TStorage>, key: StoreKey) -> Self {
+        Self {
+            storage,
+            key,
+            buffer: Vec::new(),
+            buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+            offset: 0,
+            finished: false,
+        }
+    }
+
+    /// Set the buffer size, the minimum number of bytes written to the store at once.
+    #[must_use]
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Write the buffered data to the store.
+    fn write_buffer(&mut self) -> Result<(), StorageError> {
+        let buffer = std::mem::take(&mut self.buffer);
+        let len = buffer.len() as u64;
+        if self.offset == 0 {
+            self.storage.set(&self.key, Bytes::from(buffer))?;
+        } else if len > 0 {
+            self.storage.set_partial_values(&[StoreKeyOffsetValue::new(
+                self.key.clone(),
+                self.offset,
+                &buffer,
+            )])?;
+        }
+        self.offset += len;
+        Ok(())
+    }
+
+    /// Write any buffered data to the store, completing the value.
+    ///
+    /// An empty value is stored if nothing was written.
+    ///
+    /// # Errors
+    /// Returns a [`StorageError`] if there is an underlying store error.
+    pub fn finish(mut self) -> Result<(), StorageError> {
+        self.finished = true;
+        if self.offset == 0 || !self.buffer.is_empty() {
+            self.write_buffer()?;
+        }
+        Ok(())
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> Write for StorageValueWriter<TStorage> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= self.buffer_size {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.write_buffer()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))
+    }
+}
+
+impl<TStorage: ?Sized + WritableStorageTraits> Drop for StorageValueWriter<TStorage> {
+    fn drop(&mut self) {
+        if !self.finished && (self.offset == 0 || !self.buffer.is_empty()) {
+            let _ = self.write_buffer();
+        }
+    }
+}
+
+/// Return a [`Read`] and [`Seek`] interface to the value at `key` in `storage`, or [`None`] if the key does not exist.
+///
+/// See [`StorageValueIO`].
+///
+/// # Errors
+/// Returns a [`StorageError`] if there is an underlying store error.
+pub fn value_reader<TStorage: ?Sized + ReadableStorageTraits>(
+    storage: Arc<TStorage>,
+    key: StoreKey,
+) -> Result<Option<StorageValueIO<TStorage>>, StorageError> {
+    Ok(storage
+        .size_key(&key)?
+        .map(|size| StorageValueIO::new(storage, key, size)))
+}
+
+/// Return a [`Write`] interface to the value at `key` in `storage`.
+///
+/// See [`StorageValueWriter`].
+pub fn value_writer<TStorage: ?Sized + WritableStorageTraits>(
+    storage: Arc<TStorage>,
+    key: StoreKey,
+) -> StorageValueWriter<TStorage> {
+    StorageValueWriter::new(storage, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::store::MemoryStore;
+
+    use super::*;
+
+    #[test]
+    fn storage_value_io_read_write() {
+        let store = Arc::new(MemoryStore::new());
+        let key = StoreKey::new("zarr.json").unwrap();
+        let value: Vec<u8> = (0..=255).collect();
+
+        let mut writer = value_writer(store.clone(), key.clone()).with_buffer_size(100);
+        for chunk in value.chunks(30) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.finish().unwrap();
+        assert_eq!(store.get(&key).unwrap().unwrap(), value);
+
+        let mut reader = value_reader(store.clone(), key.clone()).unwrap().unwrap();
+        let mut read = Vec::new();
+        BufReader::with_capacity(64, &mut reader)
+            .read_to_end(&mut read)
+            .unwrap();
+        assert_eq!(read, value);
+        reader.seek(SeekFrom::End(-6)).unwrap();
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [250, 251, 252, 253]);
+        let mut buf = [0; 4];
+        assert_eq!(reader.read(&mut buf).unwrap(), 2);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+        // A dropped writer replaces the value
+        let mut writer = value_writer(store.clone(), key.clone());
+        writer.write_all(&[1, 2, 3]).unwrap();
+        drop(writer);
+        assert_eq!(store.get(&key).unwrap().unwrap(), vec![1, 2, 3]);
+        value_writer(store.clone(), key.clone()).finish().unwrap();
+        assert!(store.get(&key).unwrap().unwrap().is_empty());
+
+        let missing = StoreKey::new("missing").unwrap();
+        assert!(value_reader(store, missing).unwrap().is_none());
+    }
+}
@@ -5,12 +5,14 @@ use std::sync::Mutex;
 
 use crate::{
     byte_range::{ByteOffset, ByteRange, InvalidByteRangeError},
-    Bytes, ListableStorageTraits, MaybeBytes, ReadableStorageTraits, StorageError, StoreKey,
-    StoreKeyOffsetValue, StoreKeys, StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
+    paginate_keys, Bytes, ListContinuationToken, ListableStorageTraits, MaybeBytes,
+    ReadableStorageTraits, StorageError, StoreKey, StoreKeyOffsetValue, StoreKeys,
+    StoreKeysPrefixes, StorePrefix, WritableStorageTraits,
 };
 
 use std::{
     collections::{BTreeMap, BTreeSet},
+    ops::Bound,
     sync::Arc,
 };
 
@@ -18,6 +20,7 @@ use std::{
 #[derive(Debug)]
 pub struct MemoryStore {
     data_map: Mutex<BTreeMap<StoreKey, Arc<RwLock<Vec<u8>>>>>,
+    list_page_size: usize,
     // locks: StoreLocks,
 }
 
@@ -28,14 +31,27 @@ impl Default for MemoryStore {
 }
 
 impl MemoryStore {
+    /// The default maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
     /// Create a new memory store.
     #[must_use]
     pub fn new() -> Self {
         Self {
             data_map: Mutex::default(),
+            list_page_size: Self::DEFAULT_LIST_PAGE_SIZE,
         }
     }
 
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    ///
+    /// A page size of zero is treated as one.
+    #[must_use]
+    pub fn with_list_page_size(mut self, list_page_size: usize) -> Self {
+        self.list_page_size = list_page_size.max(1);
+        self
+    }
+
     fn set_impl(&self, key: &StoreKey, value: &[u8], offset: ByteOffset, truncate: bool) {
         let mut data_map = self.data_map.lock().unwrap();
         let data = data_map
@@ -158,6 +174,19 @@ impl WritableStorageTraits for MemoryStore {
         }
         Ok(())
     }
+
+    fn rename(&self, from: &StoreKey, to: &StoreKey) -> Result<(), StorageError> {
+        let mut data_map = self.data_map.lock().unwrap();
+        let value = data_map
+            .remove(from)
+            .ok_or_else(|| StorageError::Other(format!("cannot rename missing key {from}")))?;
+        data_map.insert(to.clone(), value);
+        Ok(())
+    }
+
+    fn supports_rename(&self) -> bool {
+        true
+    }
 }
 
 impl ListableStorageTraits for MemoryStore {
@@ -175,6 +204,32 @@ impl ListableStorageTraits for MemoryStore {
             .collect())
     }
 
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let start = continuation_token
+            .map(|continuation_token| StoreKey::new(continuation_token.as_str()))
+            .transpose()?;
+        let data_map = self.data_map.lock().unwrap();
+        let range = match &start {
+            Some(start) => {
+                data_map.range::<StoreKey, _>((Bound::Excluded(start), Bound::Unbounded))
+            }
+            None => data_map.range::<StoreKey, _>(..),
+        };
+        // Keys with a prefix are contiguous in the sorted map
+        let keys: StoreKeys = range
+            .map(|(key, _)| key)
+            .skip_while(|key| !key.has_prefix(prefix))
+            .take_while(|key| key.has_prefix(prefix))
+            .take(self.list_page_size + 1)
+            .cloned()
+            .collect();
+        Ok(paginate_keys(keys, None, self.list_page_size))
+    }
+
     fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
         let mut keys: StoreKeys = vec![];
         let mut prefixes: BTreeSet<StorePrefix> = BTreeSet::default();
@@ -214,6 +269,7 @@ impl ListableStorageTraits for MemoryStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ListContinuationToken;
     use std::error::Error;
 
     #[test]
@@ -222,6 +278,35 @@ mod tests {
         crate::store_test::store_write(&store)?;
         crate::store_test::store_read(&store)?;
         crate::store_test::store_list(&store)?;
+        crate::store_test::store_rename(&store)?;
+        Ok(())
+    }
+
+    #[test]
+    fn memory_list_prefix_paged() -> Result<(), Box<dyn Error>> {
+        let store = MemoryStore::new();
+        for key in ["a/1", "a/0", "a/2", "b/0"] {
+            store.set(&StoreKey::new(key)?, vec![0].into())?;
+        }
+        let prefix = StorePrefix::new("a/")?;
+        let (keys, continuation_token) = store.list_prefix_paged(&prefix, None)?;
+        assert_eq!(keys, store.list_prefix(&prefix)?);
+        assert!(continuation_token.is_none());
+
+        let (keys, continuation_token) =
+            store.list_prefix_paged(&prefix, Some(&ListContinuationToken::new("a/0")))?;
+        assert_eq!(keys, [StoreKey::new("a/1")?, StoreKey::new("a/2")?]);
+        assert!(continuation_token.is_none());
+
+        // Native paging
+        let store = store.with_list_page_size(2);
+        let (keys, continuation_token) = store.list_prefix_paged(&prefix, None)?;
+        assert_eq!(keys, [StoreKey::new("a/0")?, StoreKey::new("a/1")?]);
+        assert_eq!(continuation_token, Some(ListContinuationToken::new("a/1")));
+        let (keys, continuation_token) =
+            store.list_prefix_paged(&prefix, continuation_token.as_ref())?;
+        assert_eq!(keys, [StoreKey::new("a/2")?]);
+        assert!(continuation_token.is_none());
         Ok(())
     }
 }
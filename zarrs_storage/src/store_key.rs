@@ -17,6 +17,27 @@ pub struct StoreKeyError(String);
 /// A list of [`StoreKey`].
 pub type StoreKeys = Vec<StoreKey>;
 
+/// A continuation token of a paged listing.
+///
+/// See [`ListableStorageTraits::list_prefix_paged`](crate::ListableStorageTraits::list_prefix_paged).
+/// A token is opaque and should only be passed back to the store that returned it.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+pub struct ListContinuationToken(String);
+
+impl ListContinuationToken {
+    /// Create a new continuation token.
+    #[must_use]
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(token.into())
+    }
+
+    /// Return the token as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 impl StoreKey {
     /// Create a new Zarr abstract store key from `key`.
     ///
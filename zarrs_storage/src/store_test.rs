@@ -88,6 +88,29 @@ pub fn store_read<T: ReadableStorageTraits>(store: &T) -> Result<(), Box<dyn Err
     Ok(())
 }
 
+pub fn store_rename<T: ReadableStorageTraits + WritableStorageTraits>(
+    store: &T,
+) -> Result<(), Box<dyn Error>> {
+    assert!(store.supports_rename());
+    store.set(&"x/tmp".try_into()?, vec![1, 2].into())?;
+    store.set(&"x/y/z".try_into()?, vec![0].into())?;
+    store.rename(&"x/tmp".try_into()?, &"x/y/z".try_into()?)?;
+    assert!(store.get(&"x/tmp".try_into()?)?.is_none());
+    assert_eq!(store.get(&"x/y/z".try_into()?)?.unwrap(), vec![1, 2]);
+
+    // Renaming to a new directory
+    store.rename(&"x/y/z".try_into()?, &"x/w/z".try_into()?)?;
+    assert_eq!(store.get(&"x/w/z".try_into()?)?.unwrap(), vec![1, 2]);
+
+    // Renaming a missing key
+    assert!(store
+        .rename(&"x/tmp".try_into()?, &"x/y/z".try_into()?)
+        .is_err());
+
+    store.erase_prefix(&StorePrefix::new("x/")?)?;
+    Ok(())
+}
+
 pub fn store_list<T: ListableStorageTraits>(store: &T) -> Result<(), Box<dyn Error>> {
     assert_eq!(store.size()?, 7);
     assert_eq!(store.size_prefix(&"a/".try_into()?)?, 5);
@@ -205,6 +228,29 @@ pub async fn async_store_write<T: AsyncWritableStorageTraits>(
     Ok(())
 }
 
+#[cfg(feature = "async")]
+pub async fn async_store_rename<T: AsyncReadableStorageTraits + AsyncWritableStorageTraits>(
+    store: &T,
+) -> Result<(), Box<dyn Error>> {
+    assert!(store.supports_rename());
+    store.set(&"x/tmp".try_into()?, vec![1, 2].into()).await?;
+    store.set(&"x/y/z".try_into()?, vec![0].into()).await?;
+    store
+        .rename(&"x/tmp".try_into()?, &"x/y/z".try_into()?)
+        .await?;
+    assert!(store.get(&"x/tmp".try_into()?).await?.is_none());
+    assert_eq!(store.get(&"x/y/z".try_into()?).await?.unwrap(), vec![1, 2]);
+
+    // Renaming a missing key
+    assert!(store
+        .rename(&"x/tmp".try_into()?, &"x/y/z".try_into()?)
+        .await
+        .is_err());
+
+    store.erase_prefix(&StorePrefix::new("x/")?).await?;
+    Ok(())
+}
+
 #[cfg(feature = "async")]
 pub async fn async_store_read<T: AsyncReadableStorageTraits>(
     store: &T,
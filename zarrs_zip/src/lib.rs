@@ -1,5 +1,19 @@
-//! A storage adapter for `zip` files for the [`zarrs`](https://docs.rs/zarrs/latest/zarrs/index.html) crate.
+//! A store and storage adapter for `zip` files for the [`zarrs`](https://docs.rs/zarrs/latest/zarrs/index.html) crate.
 //!
+//! [`ZipStore`] reads a zip archive from a file or any other [`Read`](std::io::Read) + [`Seek`](std::io::Seek) source:
+//! ```
+//! # use std::io::Cursor;
+//! use zarrs_zip::ZipStore;
+//!
+//! # let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+//! # zip.start_file("zarr.json", zip::write::SimpleFileOptions::default())?;
+//! # let bytes = zip.finish()?.into_inner();
+//! let zip_store = ZipStore::new(Cursor::new(bytes))?;
+//! // let zip_store = ZipStore::open("/path/to/zarr.zip")?;
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! [`ZipStorageAdapter`] reads a zip file that is a value in another store:
 //! ```
 //! # use std::path::PathBuf;
 //! # use std::sync::Arc;
@@ -20,10 +34,14 @@
 //! - the Apache License, Version 2.0 [LICENSE-APACHE](https://docs.rs/crate/zarrs_zip/latest/source/LICENCE-APACHE) or <http://www.apache.org/licenses/LICENSE-2.0> or
 //! - the MIT license [LICENSE-MIT](https://docs.rs/crate/zarrs_zip/latest/source/LICENCE-MIT) or <http://opensource.org/licenses/MIT>, at your option.
 
+mod zip_store;
+pub use zip_store::{ZipStore, ZipStoreCreateError};
+
 use zarrs_storage::{
     byte_range::{extract_byte_ranges_read, ByteRange},
-    Bytes, ListableStorageTraits, ReadableStorageTraits, StorageError, StorageValueIO, StoreKey,
-    StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+    paginate_keys, Bytes, ListContinuationToken, ListableStorageTraits, ReadableStorageTraits,
+    StorageError, StorageValueIO, StoreKey, StoreKeys, StoreKeysPrefixes, StorePrefix,
+    StorePrefixes,
 };
 
 use itertools::Itertools;
@@ -38,9 +56,13 @@ pub struct ZipStorageAdapter<TStorage: ?Sized> {
     size: u64,
     zip_archive: Mutex<ZipArchive<StorageValueIO<TStorage>>>,
     zip_path: PathBuf,
+    list_page_size: usize,
 }
 
 impl<TStorage: ?Sized + ReadableStorageTraits> ZipStorageAdapter<TStorage> {
+    /// The default maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
     /// Create a new zip storage adapter.
     ///
     /// # Errors
@@ -78,9 +100,19 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ZipStorageAdapter<TStorage> {
             size,
             zip_archive,
             zip_path,
+            list_page_size: Self::DEFAULT_LIST_PAGE_SIZE,
         })
     }
 
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    ///
+    /// A page size of zero is treated as one.
+    #[must_use]
+    pub fn with_list_page_size(mut self, list_page_size: usize) -> Self {
+        self.list_page_size = list_page_size.max(1);
+        self
+    }
+
     fn key_str_to_zip_path(&self, key: &str) -> String {
         let mut zip_name = self.zip_path.clone();
         zip_name.push(key);
@@ -188,6 +220,19 @@ impl<TStorage: ?Sized + ReadableStorageTraits> ListableStorageTraits
             .collect())
     }
 
+    /// Retrieve a page of keys with a given prefix from the central directory of the zip file, which is held in memory.
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        Ok(paginate_keys(
+            self.list_prefix(prefix)?,
+            continuation_token,
+            self.list_page_size,
+        ))
+    }
+
     fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
         let zip_archive = self.zip_archive.lock().unwrap();
         let mut keys: StoreKeys = vec![];
@@ -377,6 +422,28 @@ mod tests {
             ]
         );
 
+        let store_paged = ZipStorageAdapter::new(
+            FilesystemStore::new(path.parent().unwrap())?.into(),
+            StoreKey::new("test.zip")?,
+        )?
+        .with_list_page_size(3);
+        let (keys, continuation_token) = store_paged.list_prefix_paged(&"a/".try_into()?, None)?;
+        assert_eq!(
+            keys,
+            &[
+                "a/b/zarr.json".try_into()?,
+                "a/c/zarr.json".try_into()?,
+                "a/d/e/zarr.json".try_into()?,
+            ]
+        );
+        let (keys, continuation_token) =
+            store_paged.list_prefix_paged(&"a/".try_into()?, continuation_token.as_ref())?;
+        assert_eq!(
+            keys,
+            &["a/f/g/zarr.json".try_into()?, "a/f/h/zarr.json".try_into()?]
+        );
+        assert!(continuation_token.is_none());
+
         let list = store.list_dir(&"a/".try_into()?)?;
         assert_eq!(list.keys(), &[]);
         assert_eq!(
@@ -0,0 +1,421 @@
+use zarrs_storage::{
+    byte_range::{extract_byte_ranges, ByteRange, InvalidByteRangeError},
+    paginate_keys, Bytes, ListContinuationToken, ListableStorageTraits, ReadableStorageTraits,
+    StorageError, StoreKey, StoreKeys, StoreKeysPrefixes, StorePrefix, StorePrefixes,
+};
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    ops::Bound,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use zip::{CompressionMethod, ZipArchive};
+
+/// A reader with its own position that shares an underlying reader with other [`ZipStoreReader`]s.
+struct ZipStoreReader<R> {
+    reader: Arc<Mutex<R>>,
+    position: u64,
+}
+
+impl<R: Seek> Seek for ZipStoreReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.position = match pos {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::End(_) => self.reader.lock().unwrap().seek(pos)?,
+            SeekFrom::Current(offset) => {
+                self.position.checked_add_signed(offset).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "invalid seek to a negative or overflowing position",
+                    )
+                })?
+            }
+        };
+        Ok(self.position)
+    }
+}
+
+impl<R: Read + Seek> Read for ZipStoreReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut reader = self.reader.lock().unwrap();
+        reader.seek(SeekFrom::Start(self.position))?;
+        let len = reader.read(buf)?;
+        self.position += len as u64;
+        Ok(len)
+    }
+}
+
+/// A file entry in a [`ZipStore`].
+#[derive(Debug, Clone, Copy)]
+struct ZipStoreEntry {
+    /// The offset of the entry data in the zip archive.
+    data_start: u64,
+    /// The uncompressed size of the entry.
+    size: u64,
+    /// True if the entry is stored uncompressed and unencrypted, so its data can be read directly from the archive.
+    stored: bool,
+}
+
+/// A read-only store for a zip archive.
+///
+/// Store keys map directly onto the names of file entries in the archive.
+/// The archive can be file-backed ([`ZipStore::open`]) or any other [`Read`] + [`Seek`] source, such as an in-memory [`Cursor`](std::io::Cursor) ([`ZipStore::new`]).
+///
+/// Partial reads of entries that are stored uncompressed read only the requested byte ranges directly from the archive.
+/// Compressed entries are decompressed in full and then sliced.
+///
+/// To read a zip file that is a value in another store, see [`ZipStorageAdapter`](crate::ZipStorageAdapter).
+pub struct ZipStore<R> {
+    size: u64,
+    entries: BTreeMap<String, ZipStoreEntry>,
+    reader: Arc<Mutex<R>>,
+    zip_archive: Mutex<ZipArchive<ZipStoreReader<R>>>,
+    list_page_size: usize,
+}
+
+impl ZipStore<File> {
+    /// Open a zip store for the zip file at `path`.
+    ///
+    /// # Errors
+    /// Returns a [`ZipStoreCreateError`] if the file cannot be opened or is not a valid zip file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ZipStoreCreateError> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> ZipStore<R> {
+    /// The default maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    pub const DEFAULT_LIST_PAGE_SIZE: usize = 1000;
+
+    /// Create a new zip store reading a zip archive from `reader`.
+    ///
+    /// # Errors
+    /// Returns a [`ZipStoreCreateError`] if `reader` cannot be read or is not a valid zip archive.
+    pub fn new(mut reader: R) -> Result<Self, ZipStoreCreateError> {
+        let size = reader.seek(SeekFrom::End(0))?;
+        let reader = Arc::new(Mutex::new(reader));
+        let mut zip_archive = ZipArchive::new(ZipStoreReader {
+            reader: reader.clone(),
+            position: 0,
+        })
+        .map_err(|err| ZipStoreCreateError::ZipError(err.to_string()))?;
+
+        let mut entries = BTreeMap::new();
+        for index in 0..zip_archive.len() {
+            let file = zip_archive
+                .by_index_raw(index)
+                .map_err(|err| ZipStoreCreateError::ZipError(err.to_string()))?;
+            if file.is_file() {
+                entries.insert(
+                    file.name().to_string(),
+                    ZipStoreEntry {
+                        data_start: file.data_start(),
+                        size: file.size(),
+                        stored: file.compression() == CompressionMethod::Stored
+                            && !file.encrypted(),
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            size,
+            entries,
+            reader,
+            zip_archive: Mutex::new(zip_archive),
+            list_page_size: Self::DEFAULT_LIST_PAGE_SIZE,
+        })
+    }
+
+    /// Set the maximum number of keys in a page of [`list_prefix_paged`](ListableStorageTraits::list_prefix_paged).
+    ///
+    /// A page size of zero is treated as one.
+    #[must_use]
+    pub fn with_list_page_size(mut self, list_page_size: usize) -> Self {
+        self.list_page_size = list_page_size.max(1);
+        self
+    }
+
+    fn get_stored(
+        &self,
+        entry: ZipStoreEntry,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Vec<Bytes>, StorageError> {
+        let mut reader = self.reader.lock().unwrap();
+        byte_ranges
+            .iter()
+            .map(|byte_range| -> Result<Bytes, StorageError> {
+                let invalid_byte_range = || InvalidByteRangeError::new(*byte_range, entry.size);
+                let valid = match byte_range {
+                    ByteRange::FromStart(offset, length) => offset
+                        .checked_add(length.unwrap_or(0))
+                        .is_some_and(|end| end <= entry.size),
+                    ByteRange::Suffix(length) => *length <= entry.size,
+                };
+                if !valid {
+                    return Err(invalid_byte_range().into());
+                }
+                let length = usize::try_from(byte_range.length(entry.size))
+                    .map_err(|_| invalid_byte_range())?;
+                let mut bytes = vec![0; length];
+                reader.seek(SeekFrom::Start(
+                    entry.data_start + byte_range.start(entry.size),
+                ))?;
+                reader.read_exact(&mut bytes)?;
+                Ok(Bytes::from(bytes))
+            })
+            .collect()
+    }
+
+    fn get_compressed(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Vec<Bytes>, StorageError> {
+        let mut zip_archive = self.zip_archive.lock().unwrap();
+        let mut file = zip_archive
+            .by_name(key.as_str())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+        let mut bytes = Vec::with_capacity(usize::try_from(file.size()).unwrap_or_default());
+        file.read_to_end(&mut bytes)?;
+        Ok(extract_byte_ranges(&bytes, byte_ranges)?
+            .into_iter()
+            .map(Bytes::from)
+            .collect())
+    }
+}
+
+impl<R: Read + Seek + Send> ReadableStorageTraits for ZipStore<R> {
+    fn get_partial_values_key(
+        &self,
+        key: &StoreKey,
+        byte_ranges: &[ByteRange],
+    ) -> Result<Option<Vec<Bytes>>, StorageError> {
+        let Some(entry) = self.entries.get(key.as_str()) else {
+            return Ok(None);
+        };
+        let bytes = if entry.stored {
+            self.get_stored(*entry, byte_ranges)?
+        } else {
+            self.get_compressed(key, byte_ranges)?
+        };
+        Ok(Some(bytes))
+    }
+
+    fn size_key(&self, key: &StoreKey) -> Result<Option<u64>, StorageError> {
+        Ok(self.entries.get(key.as_str()).map(|entry| entry.size))
+    }
+}
+
+impl<R: Read + Seek + Send> ListableStorageTraits for ZipStore<R> {
+    fn list(&self) -> Result<StoreKeys, StorageError> {
+        Ok(self
+            .entries
+            .keys()
+            .filter_map(|name| StoreKey::try_from(name.as_str()).ok())
+            .collect())
+    }
+
+    fn list_prefix(&self, prefix: &StorePrefix) -> Result<StoreKeys, StorageError> {
+        Ok(self
+            .entries
+            .keys()
+            .filter(|name| name.starts_with(prefix.as_str()))
+            .filter_map(|name| StoreKey::try_from(name.as_str()).ok())
+            .collect())
+    }
+
+    fn list_prefix_paged(
+        &self,
+        prefix: &StorePrefix,
+        continuation_token: Option<&ListContinuationToken>,
+    ) -> Result<(StoreKeys, Option<ListContinuationToken>), StorageError> {
+        let start = continuation_token.map_or(Bound::Unbounded, |continuation_token| {
+            Bound::Excluded(continuation_token.as_str())
+        });
+        // Entries with a prefix are contiguous in the sorted map
+        let keys: StoreKeys = self
+            .entries
+            .range::<str, _>((start, Bound::Unbounded))
+            .map(|(name, _)| name)
+            .skip_while(|name| !name.starts_with(prefix.as_str()))
+            .take_while(|name| name.starts_with(prefix.as_str()))
+            .filter_map(|name| StoreKey::try_from(name.as_str()).ok())
+            .take(self.list_page_size + 1)
+            .collect();
+        Ok(paginate_keys(keys, None, self.list_page_size))
+    }
+
+    fn list_dir(&self, prefix: &StorePrefix) -> Result<StoreKeysPrefixes, StorageError> {
+        let mut keys: StoreKeys = vec![];
+        let mut prefixes: StorePrefixes = vec![];
+        for name in self.entries.keys() {
+            let Some(child) = name.strip_prefix(prefix.as_str()) else {
+                continue;
+            };
+            if let Some(index) = child.find('/') {
+                let child_prefix = &name[..prefix.as_str().len() + index + 1];
+                if let Ok(child_prefix) = StorePrefix::try_from(child_prefix) {
+                    if prefixes.last() != Some(&child_prefix) {
+                        prefixes.push(child_prefix);
+                    }
+                }
+            } else if let Ok(key) = StoreKey::try_from(name.as_str()) {
+                keys.push(key);
+            }
+        }
+        Ok(StoreKeysPrefixes::new(keys, prefixes))
+    }
+
+    fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        Ok(self
+            .entries
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix.as_str()))
+            .map(|(_, entry)| entry.size)
+            .sum())
+    }
+
+    fn size(&self) -> Result<u64, StorageError> {
+        Ok(self.size)
+    }
+}
+
+/// A zip store creation error.
+#[derive(Debug, Error)]
+pub enum ZipStoreCreateError {
+    /// An IO error.
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    /// A zip error.
+    #[error("{0}")]
+    ZipError(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        error::Error,
+        io::{Cursor, Write},
+    };
+
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    use super::*;
+
+    fn zip_bytes() -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+        let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.add_directory("a/", stored)?;
+        zip.start_file("a/zarr.json", stored)?;
+        zip.write_all(b"{}")?;
+        zip.start_file("a/c/0", stored)?;
+        zip.write_all(&[0, 1, 2, 3, 4, 5, 6, 7])?;
+        zip.start_file("a/c/1", deflated)?;
+        zip.write_all(&[8; 64])?;
+        zip.start_file("b/zarr.json", deflated)?;
+        zip.write_all(b"{}")?;
+        Ok(zip.finish()?.into_inner())
+    }
+
+    #[test]
+    fn zip_store_cursor() -> Result<(), Box<dyn Error>> {
+        let store = ZipStore::new(Cursor::new(zip_bytes()?))?;
+
+        assert_eq!(
+            store.list()?,
+            &[
+                "a/c/0".try_into()?,
+                "a/c/1".try_into()?,
+                "a/zarr.json".try_into()?,
+                "b/zarr.json".try_into()?,
+            ]
+        );
+        assert_eq!(
+            store.list_prefix(&"a/c/".try_into()?)?,
+            &["a/c/0".try_into()?, "a/c/1".try_into()?]
+        );
+        let list = store.list_dir(&"a/".try_into()?)?;
+        assert_eq!(list.keys(), &["a/zarr.json".try_into()?]);
+        assert_eq!(list.prefixes(), &["a/c/".try_into()?]);
+        let list = store.list_dir(&StorePrefix::root())?;
+        assert_eq!(list.keys(), &[]);
+        assert_eq!(list.prefixes(), &["a/".try_into()?, "b/".try_into()?]);
+
+        let store_paged = ZipStore::new(Cursor::new(zip_bytes()?))?.with_list_page_size(2);
+        let (keys, continuation_token) = store_paged.list_prefix_paged(&"a/".try_into()?, None)?;
+        assert_eq!(keys, &["a/c/0".try_into()?, "a/c/1".try_into()?]);
+        let (keys, continuation_token) =
+            store_paged.list_prefix_paged(&"a/".try_into()?, continuation_token.as_ref())?;
+        assert_eq!(keys, &["a/zarr.json".try_into()?]);
+        assert!(continuation_token.is_none());
+
+        // Stored entry
+        let key: StoreKey = "a/c/0".try_into()?;
+        assert_eq!(store.size_key(&key)?, Some(8));
+        assert_eq!(store.get(&key)?.unwrap(), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            store
+                .get_partial_values_key(
+                    &key,
+                    &[ByteRange::FromStart(2, Some(3)), ByteRange::Suffix(2)]
+                )?
+                .unwrap(),
+            vec![vec![2, 3, 4], vec![6, 7]]
+        );
+        assert!(store
+            .get_partial_values_key(&key, &[ByteRange::FromStart(6, Some(3))])
+            .is_err());
+        assert!(matches!(
+            store.get_partial_values_key(&key, &[ByteRange::FromStart(1, Some(u64::MAX))]),
+            Err(StorageError::InvalidByteRangeError(_))
+        ));
+
+        // Compressed entry
+        let key: StoreKey = "a/c/1".try_into()?;
+        assert_eq!(store.size_key(&key)?, Some(64));
+        assert_eq!(store.get(&key)?.unwrap(), vec![8; 64]);
+        assert_eq!(
+            store
+                .get_partial_values_key(&key, &[ByteRange::FromStart(60, None)])?
+                .unwrap(),
+            vec![vec![8; 4]]
+        );
+        assert_eq!(
+            store.get(&"b/zarr.json".try_into()?)?.unwrap(),
+            b"{}".as_slice()
+        );
+
+        assert!(store.get(&"missing".try_into()?)?.is_none());
+        assert_eq!(store.size_prefix(&"a/".try_into()?)?, 74);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn zip_store_file() -> Result<(), Box<dyn Error>> {
+        let path = tempfile::TempDir::new()?;
+        let path = path.path().join("test.zip");
+        std::fs::write(&path, zip_bytes()?)?;
+
+        let store = ZipStore::open(&path)?;
+        assert_eq!(store.size()?, std::fs::metadata(&path)?.len());
+        assert_eq!(
+            store.get(&"a/zarr.json".try_into()?)?.unwrap(),
+            b"{}".as_slice()
+        );
+        assert_eq!(
+            store
+                .get_partial_values_key(&"a/c/0".try_into()?, &[ByteRange::Suffix(1)])?
+                .unwrap(),
+            vec![vec![7]]
+        );
+        Ok(())
+    }
+}